@@ -2,9 +2,10 @@ use pyo3::prelude::*;
 
 use super::qos_policy::{
     DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy,
-    EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy, LatencyBudgetQosPolicy,
-    LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy, OwnershipStrengthQosPolicy,
-    PartitionQosPolicy, PresentationQosPolicy, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
+    DurabilityServiceQosPolicy, EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy,
+    LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy,
+    OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy, PublishModeQosPolicy,
+    ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy, ReliabilityTimingQosPolicy,
     ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
     TransportPriorityQosPolicy, UserDataQosPolicy, WriterDataLifecycleQosPolicy,
     DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
@@ -227,6 +228,7 @@ impl TopicQos {
         lifespan = LifespanQosPolicy::default(),
         ownership = OwnershipQosPolicy::default(),
         representation = DataRepresentationQosPolicy::default(),
+        durability_service = DurabilityServiceQosPolicy::default(),
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -243,10 +245,12 @@ impl TopicQos {
         lifespan: LifespanQosPolicy,
         ownership: OwnershipQosPolicy,
         representation: DataRepresentationQosPolicy,
+        durability_service: DurabilityServiceQosPolicy,
     ) -> Self {
         Self(dust_dds::infrastructure::qos::TopicQos {
             topic_data: topic_data.into(),
             durability: durability.into(),
+            durability_service: durability_service.into(),
             deadline: deadline.into(),
             latency_budget: latency_budget.into(),
             liveliness: liveliness.into(),
@@ -308,6 +312,10 @@ impl TopicQos {
     fn get_ownership(&self) -> OwnershipQosPolicy {
         self.0.ownership.clone().into()
     }
+
+    fn get_durability_service(&self) -> DurabilityServiceQosPolicy {
+        self.0.durability_service.clone().into()
+    }
 }
 
 #[pyclass]
@@ -345,6 +353,9 @@ impl DataWriterQos {
         ownership_strength = OwnershipStrengthQosPolicy::default(),
         writer_data_lifecycle = WriterDataLifecycleQosPolicy::default(),
         representation = DataRepresentationQosPolicy::default(),
+        durability_service = DurabilityServiceQosPolicy::default(),
+        publish_mode = PublishModeQosPolicy::default(),
+        reliability_timing = ReliabilityTimingQosPolicy::default(),
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -363,9 +374,13 @@ impl DataWriterQos {
         ownership_strength: OwnershipStrengthQosPolicy,
         writer_data_lifecycle: WriterDataLifecycleQosPolicy,
         representation: DataRepresentationQosPolicy,
+        durability_service: DurabilityServiceQosPolicy,
+        publish_mode: PublishModeQosPolicy,
+        reliability_timing: ReliabilityTimingQosPolicy,
     ) -> Self {
         Self(dust_dds::infrastructure::qos::DataWriterQos {
             durability: durability.into(),
+            durability_service: durability_service.into(),
             deadline: deadline.into(),
             latency_budget: latency_budget.into(),
             liveliness: liveliness.into(),
@@ -380,6 +395,8 @@ impl DataWriterQos {
             ownership_strength: ownership_strength.into(),
             writer_data_lifecycle: writer_data_lifecycle.into(),
             representation: representation.into(),
+            publish_mode: publish_mode.into(),
+            reliability_timing: reliability_timing.into(),
         })
     }
 
@@ -434,6 +451,18 @@ impl DataWriterQos {
     fn get_writer_data_lifecycle(&self) -> WriterDataLifecycleQosPolicy {
         self.0.writer_data_lifecycle.clone().into()
     }
+
+    fn get_durability_service(&self) -> DurabilityServiceQosPolicy {
+        self.0.durability_service.clone().into()
+    }
+
+    fn get_publish_mode(&self) -> PublishModeQosPolicy {
+        self.0.publish_mode.clone().into()
+    }
+
+    fn get_reliability_timing(&self) -> ReliabilityTimingQosPolicy {
+        self.0.reliability_timing.clone().into()
+    }
 }
 
 #[pyclass]
@@ -469,6 +498,7 @@ impl DataReaderQos {
         time_based_filter = TimeBasedFilterQosPolicy::default(),
         reader_data_lifecycle = ReaderDataLifecycleQosPolicy::default(),
         representation = DataRepresentationQosPolicy::default(),
+        reliability_timing = ReliabilityTimingQosPolicy::default(),
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -485,6 +515,7 @@ impl DataReaderQos {
         time_based_filter: TimeBasedFilterQosPolicy,
         reader_data_lifecycle: ReaderDataLifecycleQosPolicy,
         representation: DataRepresentationQosPolicy,
+        reliability_timing: ReliabilityTimingQosPolicy,
     ) -> Self {
         Self(dust_dds::infrastructure::qos::DataReaderQos {
             durability: durability.into(),
@@ -500,6 +531,7 @@ impl DataReaderQos {
             time_based_filter: time_based_filter.into(),
             reader_data_lifecycle: reader_data_lifecycle.into(),
             representation: representation.into(),
+            reliability_timing: reliability_timing.into(),
         })
     }
 
@@ -550,4 +582,8 @@ impl DataReaderQos {
     fn get_reader_data_lifecycle(&self) -> ReaderDataLifecycleQosPolicy {
         self.0.reader_data_lifecycle.clone().into()
     }
+
+    fn get_reliability_timing(&self) -> ReliabilityTimingQosPolicy {
+        self.0.reliability_timing.clone().into()
+    }
 }
@@ -1,6 +1,6 @@
 use pyo3::prelude::*;
 
-use super::time::DurationKind;
+use super::time::{Duration, DurationKind};
 
 #[pyclass]
 #[derive(Clone)]
@@ -645,6 +645,90 @@ impl ResourceLimitsQosPolicy {
     }
 }
 
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct DurabilityServiceQosPolicy(
+    dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy,
+);
+
+impl From<DurabilityServiceQosPolicy>
+    for dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy
+{
+    fn from(value: DurabilityServiceQosPolicy) -> Self {
+        value.0
+    }
+}
+
+impl From<dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy>
+    for DurabilityServiceQosPolicy
+{
+    fn from(value: dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy) -> Self {
+        Self(value)
+    }
+}
+
+#[pymethods]
+impl DurabilityServiceQosPolicy {
+    #[new]
+    pub fn new(
+        service_cleanup_delay: DurationKind,
+        history_kind: HistoryQosPolicyKind,
+        max_samples: Length,
+        max_instances: Length,
+        max_samples_per_instance: Length,
+    ) -> Self {
+        Self(
+            dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy {
+                service_cleanup_delay: service_cleanup_delay.into(),
+                history_kind: history_kind.into(),
+                max_samples: max_samples.into(),
+                max_instances: max_instances.into(),
+                max_samples_per_instance: max_samples_per_instance.into(),
+            },
+        )
+    }
+
+    pub fn get_service_cleanup_delay(&self) -> DurationKind {
+        self.0.service_cleanup_delay.into()
+    }
+
+    pub fn set_service_cleanup_delay(&mut self, value: DurationKind) {
+        self.0.service_cleanup_delay = value.into()
+    }
+
+    pub fn get_history_kind(&self) -> HistoryQosPolicyKind {
+        self.0.history_kind.into()
+    }
+
+    pub fn set_history_kind(&mut self, value: HistoryQosPolicyKind) {
+        self.0.history_kind = value.into()
+    }
+
+    pub fn get_max_samples(&self) -> Length {
+        self.0.max_samples.into()
+    }
+
+    pub fn set_max_samples(&mut self, value: Length) {
+        self.0.max_samples = value.into()
+    }
+
+    pub fn get_max_instances(&self) -> Length {
+        self.0.max_instances.into()
+    }
+
+    pub fn set_max_instances(&mut self, value: Length) {
+        self.0.max_instances = value.into()
+    }
+
+    pub fn get_max_samples_per_instance(&self) -> Length {
+        self.0.max_samples_per_instance.into()
+    }
+
+    pub fn set_max_samples_per_instance(&mut self, value: Length) {
+        self.0.max_samples_per_instance = value.into()
+    }
+}
+
 #[pyclass]
 #[derive(Clone, Default)]
 pub struct TransportPriorityQosPolicy(
@@ -873,6 +957,7 @@ impl PartitionQosPolicy {
 pub enum PresentationQosPolicyAccessScopeKind {
     Instance,
     Topic,
+    Group,
 }
 
 impl From<PresentationQosPolicyAccessScopeKind>
@@ -886,6 +971,9 @@ impl From<PresentationQosPolicyAccessScopeKind>
             PresentationQosPolicyAccessScopeKind::Topic => {
                 dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Topic
             }
+            PresentationQosPolicyAccessScopeKind::Group => {
+                dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Group
+            }
         }
     }
 }
@@ -899,6 +987,7 @@ impl From<dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScope
         match value {
             dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Instance => PresentationQosPolicyAccessScopeKind::Instance,
             dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Topic => PresentationQosPolicyAccessScopeKind::Topic,
+            dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Group => PresentationQosPolicyAccessScopeKind::Group,
         }
     }
 }
@@ -1137,3 +1226,127 @@ impl From<dust_dds::infrastructure::qos_policy::DataRepresentationQosPolicy>
         Self(value)
     }
 }
+
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct PublishModeQosPolicy(dust_dds::infrastructure::qos_policy::PublishModeQosPolicy);
+
+impl From<PublishModeQosPolicy> for dust_dds::infrastructure::qos_policy::PublishModeQosPolicy {
+    fn from(value: PublishModeQosPolicy) -> Self {
+        value.0
+    }
+}
+
+impl From<dust_dds::infrastructure::qos_policy::PublishModeQosPolicy> for PublishModeQosPolicy {
+    fn from(value: dust_dds::infrastructure::qos_policy::PublishModeQosPolicy) -> Self {
+        Self(value)
+    }
+}
+
+#[pymethods]
+impl PublishModeQosPolicy {
+    #[new]
+    #[pyo3(signature = (flow_controller_name, priority))]
+    pub fn new(flow_controller_name: Option<String>, priority: i32) -> Self {
+        Self(dust_dds::infrastructure::qos_policy::PublishModeQosPolicy {
+            flow_controller_name,
+            priority,
+        })
+    }
+
+    pub fn get_flow_controller_name(&self) -> Option<String> {
+        self.0.flow_controller_name.clone()
+    }
+
+    pub fn set_flow_controller_name(&mut self, value: Option<String>) {
+        self.0.flow_controller_name = value
+    }
+
+    pub fn get_priority(&self) -> i32 {
+        self.0.priority
+    }
+
+    pub fn set_priority(&mut self, value: i32) {
+        self.0.priority = value
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct ReliabilityTimingQosPolicy(
+    dust_dds::infrastructure::qos_policy::ReliabilityTimingQosPolicy,
+);
+
+impl From<ReliabilityTimingQosPolicy>
+    for dust_dds::infrastructure::qos_policy::ReliabilityTimingQosPolicy
+{
+    fn from(value: ReliabilityTimingQosPolicy) -> Self {
+        value.0
+    }
+}
+
+impl From<dust_dds::infrastructure::qos_policy::ReliabilityTimingQosPolicy>
+    for ReliabilityTimingQosPolicy
+{
+    fn from(value: dust_dds::infrastructure::qos_policy::ReliabilityTimingQosPolicy) -> Self {
+        Self(value)
+    }
+}
+
+#[pymethods]
+impl ReliabilityTimingQosPolicy {
+    #[new]
+    #[pyo3(signature = (
+        heartbeat_period = None,
+        nack_response_delay = None,
+        nack_suppression_duration = None,
+        heartbeat_response_delay = None,
+    ))]
+    pub fn new(
+        heartbeat_period: Option<Duration>,
+        nack_response_delay: Option<Duration>,
+        nack_suppression_duration: Option<Duration>,
+        heartbeat_response_delay: Option<Duration>,
+    ) -> Self {
+        Self(
+            dust_dds::infrastructure::qos_policy::ReliabilityTimingQosPolicy {
+                heartbeat_period: heartbeat_period.map(Into::into),
+                nack_response_delay: nack_response_delay.map(Into::into),
+                nack_suppression_duration: nack_suppression_duration.map(Into::into),
+                heartbeat_response_delay: heartbeat_response_delay.map(Into::into),
+            },
+        )
+    }
+
+    pub fn get_heartbeat_period(&self) -> Option<Duration> {
+        self.0.heartbeat_period.map(Into::into)
+    }
+
+    pub fn set_heartbeat_period(&mut self, value: Option<Duration>) {
+        self.0.heartbeat_period = value.map(Into::into)
+    }
+
+    pub fn get_nack_response_delay(&self) -> Option<Duration> {
+        self.0.nack_response_delay.map(Into::into)
+    }
+
+    pub fn set_nack_response_delay(&mut self, value: Option<Duration>) {
+        self.0.nack_response_delay = value.map(Into::into)
+    }
+
+    pub fn get_nack_suppression_duration(&self) -> Option<Duration> {
+        self.0.nack_suppression_duration.map(Into::into)
+    }
+
+    pub fn set_nack_suppression_duration(&mut self, value: Option<Duration>) {
+        self.0.nack_suppression_duration = value.map(Into::into)
+    }
+
+    pub fn get_heartbeat_response_delay(&self) -> Option<Duration> {
+        self.0.heartbeat_response_delay.map(Into::into)
+    }
+
+    pub fn set_heartbeat_response_delay(&mut self, value: Option<Duration>) {
+        self.0.heartbeat_response_delay = value.map(Into::into)
+    }
+}
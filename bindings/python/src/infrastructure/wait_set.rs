@@ -1,11 +1,21 @@
 use pyo3::prelude::*;
 
-use super::{condition::StatusCondition, error::into_pyerr, time::Duration};
+use super::{
+    condition::{GuardCondition, QueryCondition, ReadCondition, StatusCondition},
+    error::into_pyerr,
+    time::Duration,
+};
 
+// The variants are exposed to Python as Condition.StatusCondition, etc. so they
+// keep the names of the variants of the Rust Condition
+#[allow(clippy::enum_variant_names)]
 #[pyclass]
 #[derive(Clone)]
 pub enum Condition {
     StatusCondition { condition: StatusCondition },
+    ReadCondition { condition: ReadCondition },
+    QueryCondition { condition: QueryCondition },
+    GuardCondition { condition: GuardCondition },
 }
 
 impl From<dust_dds::infrastructure::wait_set::Condition> for Condition {
@@ -16,6 +26,21 @@ impl From<dust_dds::infrastructure::wait_set::Condition> for Condition {
                     condition: c.into(),
                 }
             }
+            dust_dds::infrastructure::wait_set::Condition::ReadCondition(c) => {
+                Condition::ReadCondition {
+                    condition: c.into(),
+                }
+            }
+            dust_dds::infrastructure::wait_set::Condition::QueryCondition(c) => {
+                Condition::QueryCondition {
+                    condition: c.into(),
+                }
+            }
+            dust_dds::infrastructure::wait_set::Condition::GuardCondition(c) => {
+                Condition::GuardCondition {
+                    condition: c.into(),
+                }
+            }
         }
     }
 }
@@ -26,6 +51,15 @@ impl From<Condition> for dust_dds::infrastructure::wait_set::Condition {
             Condition::StatusCondition { condition } => {
                 dust_dds::infrastructure::wait_set::Condition::StatusCondition(condition.into())
             }
+            Condition::ReadCondition { condition } => {
+                dust_dds::infrastructure::wait_set::Condition::ReadCondition(condition.into())
+            }
+            Condition::QueryCondition { condition } => {
+                dust_dds::infrastructure::wait_set::Condition::QueryCondition(condition.into())
+            }
+            Condition::GuardCondition { condition } => {
+                dust_dds::infrastructure::wait_set::Condition::GuardCondition(condition.into())
+            }
         }
     }
 }
@@ -55,7 +89,7 @@ impl WaitSet {
         self.0.attach_condition(cond.into()).map_err(into_pyerr)
     }
 
-    pub fn detach_condition(&self, cond: Condition) -> PyResult<()> {
+    pub fn detach_condition(&mut self, cond: Condition) -> PyResult<()> {
         self.0.detach_condition(cond.into()).map_err(into_pyerr)
     }
 
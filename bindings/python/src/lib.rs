@@ -30,6 +30,7 @@ fn dust_dds(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<infrastructure::time::Duration>()?;
     m.add_class::<infrastructure::time::DurationKind>()?;
     m.add_class::<infrastructure::status::StatusKind>()?;
+    m.add_class::<infrastructure::condition::GuardCondition>()?;
     m.add_class::<infrastructure::wait_set::Condition>()?;
     m.add_class::<infrastructure::wait_set::WaitSet>()?;
 
@@ -51,10 +52,13 @@ fn dust_dds(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<infrastructure::qos_policy::OwnershipQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::OwnershipQosPolicyKind>()?;
     m.add_class::<infrastructure::qos_policy::PartitionQosPolicy>()?;
+    m.add_class::<infrastructure::qos_policy::PublishModeQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::ReaderDataLifecycleQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::ReliabilityQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::ReliabilityQosPolicyKind>()?;
+    m.add_class::<infrastructure::qos_policy::ReliabilityTimingQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::ResourceLimitsQosPolicy>()?;
+    m.add_class::<infrastructure::qos_policy::DurabilityServiceQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::TimeBasedFilterQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::TopicDataQosPolicy>()?;
     m.add_class::<infrastructure::qos_policy::TransportPriorityQosPolicy>()?;
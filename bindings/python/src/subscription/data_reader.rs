@@ -7,7 +7,7 @@ use crate::{
     builtin_topics::PublicationBuiltinTopicData,
     domain::domain_participant::DomainParticipant,
     infrastructure::{
-        condition::StatusCondition,
+        condition::{AnyReadCondition, QueryCondition, ReadCondition, StatusCondition},
         error::into_pyerr,
         instance::InstanceHandle,
         qos::DataReaderQos,
@@ -125,6 +125,46 @@ impl DataReader {
         }
     }
 
+    pub fn read_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: AnyReadCondition,
+    ) -> PyResult<Vec<Sample>> {
+        let type_ = DomainParticipant::get_type(&self.0.get_topicdescription().get_type_name())
+            .ok_or(PyTypeError::new_err("Type information not found"))?;
+        match self.0.read_w_condition(max_samples, &a_condition) {
+            Ok(s) => Ok(s
+                .into_iter()
+                .map(|s| Sample {
+                    sample: s,
+                    type_: type_.clone(),
+                })
+                .collect()),
+            Err(dust_dds::infrastructure::error::DdsError::NoData) => Ok(Vec::new()),
+            Err(e) => Err(PyTypeError::new_err(format!("{:?}", e))),
+        }
+    }
+
+    pub fn take_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: AnyReadCondition,
+    ) -> PyResult<Vec<Sample>> {
+        let type_ = DomainParticipant::get_type(&self.0.get_topicdescription().get_type_name())
+            .ok_or(PyTypeError::new_err("Type information not found"))?;
+        match self.0.take_w_condition(max_samples, &a_condition) {
+            Ok(s) => Ok(s
+                .into_iter()
+                .map(|s| Sample {
+                    sample: s,
+                    type_: type_.clone(),
+                })
+                .collect()),
+            Err(dust_dds::infrastructure::error::DdsError::NoData) => Ok(Vec::new()),
+            Err(e) => Err(PyTypeError::new_err(format!("{:?}", e))),
+        }
+    }
+
     pub fn read_next_sample(&self) -> PyResult<Sample> {
         let type_ = DomainParticipant::get_type(&self.0.get_topicdescription().get_type_name())
             .ok_or(PyTypeError::new_err("Type information not found"))?;
@@ -349,6 +389,75 @@ impl DataReader {
         unimplemented!()
     }
 
+    #[pyo3(signature = (
+        sample_states=ANY_SAMPLE_STATE.to_vec(),
+        view_states=ANY_VIEW_STATE.to_vec(),
+        instance_states=ANY_INSTANCE_STATE.to_vec(),
+    ))]
+    pub fn create_readcondition(
+        &self,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+    ) -> PyResult<ReadCondition> {
+        let sample_states: Vec<_> = sample_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::SampleStateKind::from)
+            .collect();
+        let view_states: Vec<_> = view_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::ViewStateKind::from)
+            .collect();
+        let instance_states: Vec<_> = instance_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::InstanceStateKind::from)
+            .collect();
+        Ok(self
+            .0
+            .create_readcondition(&sample_states, &view_states, &instance_states)
+            .map_err(into_pyerr)?
+            .into())
+    }
+
+    pub fn create_querycondition(
+        &self,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+        query_expression: String,
+        query_parameters: Vec<String>,
+    ) -> PyResult<QueryCondition> {
+        let sample_states: Vec<_> = sample_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::SampleStateKind::from)
+            .collect();
+        let view_states: Vec<_> = view_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::ViewStateKind::from)
+            .collect();
+        let instance_states: Vec<_> = instance_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::InstanceStateKind::from)
+            .collect();
+        Ok(self
+            .0
+            .create_querycondition(
+                &sample_states,
+                &view_states,
+                &instance_states,
+                &query_expression,
+                &query_parameters,
+            )
+            .map_err(into_pyerr)?
+            .into())
+    }
+
+    pub fn delete_readcondition(&self, a_condition: AnyReadCondition) -> PyResult<()> {
+        self.0
+            .delete_readcondition(&a_condition)
+            .map_err(into_pyerr)
+    }
+
     pub fn get_liveliness_changed_status(&self) -> PyResult<LivelinessChangedStatus> {
         Ok(self
             .0
@@ -8,6 +8,8 @@ use crate::infrastructure::error::DdsResult;
 pub struct DustDdsConfiguration {
     domain_tag: String,
     participant_announcement_interval: Duration,
+    participant_lease_duration: Duration,
+    intraprocess_delivery: bool,
 }
 
 impl DustDdsConfiguration {
@@ -20,6 +22,18 @@ impl DustDdsConfiguration {
     pub fn participant_announcement_interval(&self) -> Duration {
         self.participant_announcement_interval
     }
+
+    /// Lease duration announced by the participant. Remote participants consider this participant
+    /// gone if no announcement is received from it within this time.
+    pub fn participant_lease_duration(&self) -> Duration {
+        self.participant_lease_duration
+    }
+
+    /// Whether the samples are delivered directly to the readers of the participants of this
+    /// application instead of through the transport.
+    pub fn intraprocess_delivery(&self) -> bool {
+        self.intraprocess_delivery
+    }
 }
 
 impl Default for DustDdsConfiguration {
@@ -27,6 +41,8 @@ impl Default for DustDdsConfiguration {
         Self {
             domain_tag: "".to_string(),
             participant_announcement_interval: Duration::from_secs(5),
+            participant_lease_duration: Duration::from_secs(100),
+            intraprocess_delivery: false,
         }
     }
 }
@@ -65,4 +81,21 @@ impl DustDdsConfigurationBuilder {
         self.configuration.participant_announcement_interval = participant_announcement_interval;
         self
     }
+
+    /// Set the lease duration announced by the participant. This should be larger than the
+    /// participant announcement interval, otherwise remote participants may consider this
+    /// participant gone between two announcements.
+    pub fn participant_lease_duration(mut self, participant_lease_duration: Duration) -> Self {
+        self.configuration.participant_lease_duration = participant_lease_duration;
+        self
+    }
+
+    /// Set whether the samples are delivered directly to the readers of the participants of this
+    /// application instead of through the transport. This is disabled by default so that all the
+    /// samples go through the transport. When enabled, the values written with `write_shared`
+    /// are handed to these readers without being deserialized again.
+    pub fn intraprocess_delivery(mut self, intraprocess_delivery: bool) -> Self {
+        self.configuration.intraprocess_delivery = intraprocess_delivery;
+        self
+    }
 }
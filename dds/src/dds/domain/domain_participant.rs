@@ -17,7 +17,10 @@ use crate::{
     publication::{publisher::Publisher, publisher_listener::PublisherListener},
     runtime::executor::block_on,
     subscription::{subscriber::Subscriber, subscriber_listener::SubscriberListener},
-    topic_definition::{topic::Topic, topic_listener::TopicListener, type_support::TypeSupport},
+    topic_definition::{
+        content_filtered_topic::ContentFilteredTopic, topic::Topic, topic_listener::TopicListener,
+        type_support::TypeSupport,
+    },
     xtypes::dynamic_type::DynamicType,
 };
 
@@ -195,6 +198,52 @@ impl DomainParticipant {
         block_on(self.participant_async.delete_topic(a_topic.topic_async()))
     }
 
+    /// This operation creates a [`ContentFilteredTopic`]. As described in [`ContentFilteredTopic`], the [`ContentFilteredTopic`] can
+    /// be used to do content-based subscriptions.
+    /// The related [`Topic`] being subscribed to is specified by means of the `related_topic` parameter. The [`ContentFilteredTopic`]
+    /// only relates to samples published under that [`Topic`], filtered according to their content. The filtering is done by means
+    /// of evaluating a logical expression that involves the values of some of the data-fields in the sample. The logical expression
+    /// is derived from the `filter_expression` and `expression_parameters` arguments.
+    /// The syntax of the filter expression and parameters is the SQL subset described in Annex B of the DDS specification. If the
+    /// expression is not valid, refers to members which are not part of the type of the related [`Topic`] or more parameters are
+    /// required than the ones provided the operation fails and returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    /// In case of failure, the operation will return an error and no [`ContentFilteredTopic`] will be created.
+    #[tracing::instrument(skip(self, related_topic))]
+    pub fn create_contentfilteredtopic(
+        &self,
+        name: &str,
+        related_topic: &Topic,
+        filter_expression: &str,
+        expression_parameters: &[String],
+    ) -> DdsResult<ContentFilteredTopic> {
+        block_on(self.participant_async.create_contentfilteredtopic(
+            name,
+            related_topic.topic_async(),
+            filter_expression,
+            expression_parameters,
+        ))
+        .map(ContentFilteredTopic::new)
+    }
+
+    /// This operation deletes a [`ContentFilteredTopic`].
+    /// The deletion of a [`ContentFilteredTopic`] is not allowed if there are any existing [`DataReader`](crate::subscription::data_reader::DataReader)
+    /// objects that are using the [`ContentFilteredTopic`]. If the [`DomainParticipant::delete_contentfilteredtopic()`] operation is called on a
+    /// [`ContentFilteredTopic`] with existing [`DataReader`](crate::subscription::data_reader::DataReader) objects attached to it, it will return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// The [`DomainParticipant::delete_contentfilteredtopic()`] operation must be called on the same [`DomainParticipant`] object used to create
+    /// the [`ContentFilteredTopic`]. If [`DomainParticipant::delete_contentfilteredtopic()`] is called on a different [`DomainParticipant`], the
+    /// operation will have no effect and it will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self, a_contentfilteredtopic))]
+    pub fn delete_contentfilteredtopic(
+        &self,
+        a_contentfilteredtopic: &ContentFilteredTopic,
+    ) -> DdsResult<()> {
+        block_on(
+            self.participant_async
+                .delete_contentfilteredtopic(a_contentfilteredtopic.content_filtered_topic_async()),
+        )
+    }
+
     /// This operation gives access to an existing (or ready to exist) enabled [`Topic`], based on its name. The operation takes
     /// as arguments the name of the [`Topic`], a timeout and the type as a generic type argument `Foo`.
     /// If a [`Topic`] of the same name and type already exists, it gives access to it, otherwise it waits (blocks the caller) until another mechanism
@@ -6,6 +6,7 @@ use crate::{
         domain_participant_listener::DomainParticipantListenerAsync,
     },
     domain::domain_participant_listener::DomainParticipantListener,
+    durability::DurabilityStore,
     infrastructure::{
         error::DdsResult,
         qos::{DomainParticipantFactoryQos, DomainParticipantQos, QosKind},
@@ -144,4 +145,32 @@ impl DomainParticipantFactory {
     pub fn set_transport(&self, transport: Box<dyn TransportParticipantFactory>) -> DdsResult<()> {
         block_on(self.participant_factory_async.set_transport(transport))
     }
+
+    /// Set the store used by the [`DomainParticipant`] entities created by the [`DomainParticipantFactory`]
+    /// singleton to keep the samples of the writers with a
+    /// [`DurabilityQosPolicyKind::Transient`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Transient) durability.
+    /// By default the samples are kept in memory by an [`InMemoryDurabilityStore`](crate::durability::InMemoryDurabilityStore).
+    pub fn set_transient_durability_store(&self, store: Box<dyn DurabilityStore>) -> DdsResult<()> {
+        block_on(
+            self.participant_factory_async
+                .set_transient_durability_store(store),
+        )
+    }
+
+    /// Set the store used by the [`DomainParticipant`] entities created by the [`DomainParticipantFactory`]
+    /// singleton to keep the samples of the writers with a
+    /// [`DurabilityQosPolicyKind::Persistent`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Persistent) durability.
+    /// By default the samples are stored on disk by a [`FileDurabilityStore`](crate::durability::FileDurabilityStore)
+    /// in the [`user_data_directory`](crate::durability::FileDurabilityStore::user_data_directory). If this directory
+    /// can't be determined, no store exists and creating a persistent [`DataWriter`](crate::publication::data_writer::DataWriter)
+    /// fails until one is set.
+    pub fn set_persistent_durability_store(
+        &self,
+        store: Box<dyn DurabilityStore>,
+    ) -> DdsResult<()> {
+        block_on(
+            self.participant_factory_async
+                .set_persistent_durability_store(store),
+        )
+    }
 }
@@ -0,0 +1,998 @@
+use crate::{
+    domain::domain_participant_factory::DomainId,
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
+        qos_policy::{DurabilityServiceQosPolicy, HistoryQosPolicyKind, Length},
+        time::Time,
+    },
+};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{mpsc, Mutex},
+    thread::JoinHandle,
+};
+use tracing::warn;
+
+/// Identifies the data kept by a [`DurabilityStore`]. Topics with the same name are only the same topic when they belong to
+/// the same domain and have the same type.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct DurabilityTopicKey {
+    domain_id: DomainId,
+    topic_name: String,
+    type_name: String,
+}
+
+impl DurabilityTopicKey {
+    /// Create a new [`DurabilityTopicKey`] for the topic `topic_name` of type `type_name` in the domain `domain_id`.
+    pub fn new(domain_id: DomainId, topic_name: String, type_name: String) -> Self {
+        Self {
+            domain_id,
+            topic_name,
+            type_name,
+        }
+    }
+
+    /// Domain to which the topic belongs.
+    pub fn domain_id(&self) -> DomainId {
+        self.domain_id
+    }
+
+    /// Name of the topic.
+    pub fn topic_name(&self) -> &str {
+        &self.topic_name
+    }
+
+    /// Name of the type of the topic.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+/// Sample kept by a [`DurabilityStore`] on behalf of the durability service.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DurabilitySample {
+    writer_handle: InstanceHandle,
+    sequence_number: i64,
+    instance_handle: InstanceHandle,
+    source_timestamp: Time,
+    serialized_data: Vec<u8>,
+}
+
+impl DurabilitySample {
+    /// Create a new [`DurabilitySample`] written with `sequence_number` by the writer identified by `writer_handle` for
+    /// the instance identified by `instance_handle`.
+    pub fn new(
+        writer_handle: InstanceHandle,
+        sequence_number: i64,
+        instance_handle: InstanceHandle,
+        source_timestamp: Time,
+        serialized_data: Vec<u8>,
+    ) -> Self {
+        Self {
+            writer_handle,
+            sequence_number,
+            instance_handle,
+            source_timestamp,
+            serialized_data,
+        }
+    }
+
+    /// Handle of the writer which wrote the sample.
+    pub fn writer_handle(&self) -> InstanceHandle {
+        self.writer_handle
+    }
+
+    /// Sequence number with which the writer wrote the sample. Together with the [`DurabilitySample::writer_handle`] it
+    /// identifies the sample.
+    pub fn sequence_number(&self) -> i64 {
+        self.sequence_number
+    }
+
+    /// Handle of the instance the sample belongs to.
+    pub fn instance_handle(&self) -> InstanceHandle {
+        self.instance_handle
+    }
+
+    /// Source timestamp with which the sample was originally written.
+    pub fn source_timestamp(&self) -> Time {
+        self.source_timestamp
+    }
+
+    /// Serialized data of the sample.
+    pub fn serialized_data(&self) -> &[u8] {
+        &self.serialized_data
+    }
+}
+
+/// Storage used by the durability service to keep the samples written by the
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) objects with a
+/// [`DurabilityQosPolicyKind::Transient`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Transient) or
+/// [`DurabilityQosPolicyKind::Persistent`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Persistent) durability.
+///
+/// The samples are kept per topic, beyond the lifetime of the writers that wrote them. Once the writer of a sample is deleted,
+/// the durability service of a participant of the application with the same topic serves the sample to the late-joining
+/// readers.
+/// A custom store can be installed with
+/// [`DomainParticipantFactory::set_transient_durability_store`](crate::domain::domain_participant_factory::DomainParticipantFactory::set_transient_durability_store)
+/// and
+/// [`DomainParticipantFactory::set_persistent_durability_store`](crate::domain::domain_participant_factory::DomainParticipantFactory::set_persistent_durability_store).
+pub trait DurabilityStore: Send + Sync {
+    /// Add a sample written on the topic. The store must keep at most the samples allowed by the history and resource
+    /// limits of the `durability_service` policy.
+    fn add_sample(
+        &self,
+        topic: &DurabilityTopicKey,
+        sample: DurabilitySample,
+        durability_service: &DurabilityServiceQosPolicy,
+    ) -> DdsResult<()>;
+
+    /// Remove the samples kept for a disposed instance of the topic whose source timestamp is not later than
+    /// `disposed_timestamp`.
+    fn dispose_instance(
+        &self,
+        topic: &DurabilityTopicKey,
+        instance_handle: InstanceHandle,
+        disposed_timestamp: Time,
+    ) -> DdsResult<()>;
+
+    /// Get all the samples kept for the topic, ordered from oldest to newest.
+    fn get_samples(&self, topic: &DurabilityTopicKey) -> DdsResult<Vec<DurabilitySample>>;
+}
+
+#[derive(Default)]
+struct TopicHistory {
+    samples: VecDeque<DurabilitySample>,
+}
+
+impl TopicHistory {
+    fn add_sample(
+        &mut self,
+        sample: DurabilitySample,
+        durability_service: &DurabilityServiceQosPolicy,
+    ) -> DdsResult<()> {
+        let instance_handle = sample.instance_handle;
+        let instance_samples = self
+            .samples
+            .iter()
+            .filter(|s| s.instance_handle == instance_handle)
+            .count();
+
+        if instance_samples == 0 {
+            let mut instances: Vec<_> = self.samples.iter().map(|s| s.instance_handle).collect();
+            instances.sort();
+            instances.dedup();
+            if instances.len() >= durability_service.max_instances {
+                return Err(DdsError::OutOfResources);
+            }
+        }
+
+        // Older samples are discarded to make room for the new one so that
+        // the store always holds the last known state of each instance
+        let max_instance_samples = match durability_service.history_kind {
+            HistoryQosPolicyKind::KeepLast(depth) => {
+                match durability_service.max_samples_per_instance {
+                    Length::Limited(max_samples_per_instance) => {
+                        depth.min(max_samples_per_instance) as usize
+                    }
+                    Length::Unlimited => depth as usize,
+                }
+            }
+            HistoryQosPolicyKind::KeepAll => match durability_service.max_samples_per_instance {
+                Length::Limited(max_samples_per_instance) => max_samples_per_instance as usize,
+                Length::Unlimited => usize::MAX,
+            },
+        };
+        if max_instance_samples == 0 {
+            return Ok(());
+        }
+        if instance_samples >= max_instance_samples {
+            if let Some(index) = self
+                .samples
+                .iter()
+                .position(|s| s.instance_handle == instance_handle)
+            {
+                self.samples.remove(index);
+            }
+        }
+        if let Length::Limited(max_samples) = durability_service.max_samples {
+            if max_samples == 0 {
+                return Ok(());
+            }
+            while self.samples.len() >= max_samples as usize {
+                self.samples.pop_front();
+            }
+        }
+
+        self.samples.push_back(sample);
+        Ok(())
+    }
+
+    fn dispose_instance(&mut self, instance_handle: InstanceHandle, disposed_timestamp: Time) {
+        self.samples.retain(|s| {
+            s.instance_handle != instance_handle || s.source_timestamp > disposed_timestamp
+        });
+    }
+
+    fn apply(&mut self, record: TopicLogRecord) -> DdsResult<()> {
+        match record {
+            TopicLogRecord::AddSample(sample, durability_service) => {
+                self.add_sample(sample, &durability_service)
+            }
+            TopicLogRecord::DisposeInstance(instance_handle, disposed_timestamp) => {
+                self.dispose_instance(instance_handle, disposed_timestamp);
+                Ok(())
+            }
+        }
+    }
+}
+
+// Change of the samples of a topic appended to the file of the topic by the FileDurabilityStore.
+// A sample is stored with the limits it was added with so that replaying the records rebuilds
+// the same history.
+enum TopicLogRecord {
+    AddSample(DurabilitySample, DurabilityServiceQosPolicy),
+    DisposeInstance(InstanceHandle, Time),
+}
+
+impl TopicLogRecord {
+    const ADD_SAMPLE: u8 = 0;
+    const DISPOSE_INSTANCE: u8 = 1;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        fn limit_to_bytes(bytes: &mut Vec<u8>, limit: Option<u32>) {
+            bytes.push(limit.is_some() as u8);
+            bytes.extend_from_slice(&limit.unwrap_or_default().to_le_bytes());
+        }
+        fn length_to_bytes(bytes: &mut Vec<u8>, length: Length) {
+            match length {
+                Length::Unlimited => limit_to_bytes(bytes, None),
+                Length::Limited(length) => limit_to_bytes(bytes, Some(length)),
+            }
+        }
+
+        let mut bytes = Vec::new();
+        match self {
+            TopicLogRecord::AddSample(sample, durability_service) => {
+                bytes.push(Self::ADD_SAMPLE);
+                match durability_service.history_kind {
+                    HistoryQosPolicyKind::KeepAll => limit_to_bytes(&mut bytes, None),
+                    HistoryQosPolicyKind::KeepLast(depth) => {
+                        limit_to_bytes(&mut bytes, Some(depth))
+                    }
+                }
+                length_to_bytes(&mut bytes, durability_service.max_samples);
+                length_to_bytes(&mut bytes, durability_service.max_instances);
+                length_to_bytes(&mut bytes, durability_service.max_samples_per_instance);
+                bytes.extend_from_slice(&<[u8; 16]>::from(sample.writer_handle));
+                bytes.extend_from_slice(&sample.sequence_number.to_le_bytes());
+                bytes.extend_from_slice(&<[u8; 16]>::from(sample.instance_handle));
+                bytes.extend_from_slice(&sample.source_timestamp.sec().to_le_bytes());
+                bytes.extend_from_slice(&sample.source_timestamp.nanosec().to_le_bytes());
+                bytes.extend_from_slice(&(sample.serialized_data.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(&sample.serialized_data);
+            }
+            TopicLogRecord::DisposeInstance(instance_handle, disposed_timestamp) => {
+                bytes.push(Self::DISPOSE_INSTANCE);
+                bytes.extend_from_slice(&<[u8; 16]>::from(*instance_handle));
+                bytes.extend_from_slice(&disposed_timestamp.sec().to_le_bytes());
+                bytes.extend_from_slice(&disposed_timestamp.nanosec().to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    // Read the next record, None if the bytes end before the record does
+    fn from_bytes(bytes: &mut &[u8]) -> DdsResult<Option<Self>> {
+        let mut remaining = *bytes;
+        let record = match take(&mut remaining, 1).map(|kind| kind[0]) {
+            None => None,
+            Some(Self::ADD_SAMPLE) => Self::add_sample_from_bytes(&mut remaining),
+            Some(Self::DISPOSE_INSTANCE) => Self::dispose_instance_from_bytes(&mut remaining),
+            Some(_) => {
+                return Err(DdsError::Error(
+                    "Invalid record in durability store file".to_string(),
+                ))
+            }
+        };
+        if record.is_some() {
+            *bytes = remaining;
+        }
+        Ok(record)
+    }
+
+    fn add_sample_from_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        let history_kind = match limit_from_bytes(bytes)? {
+            Some(depth) => HistoryQosPolicyKind::KeepLast(depth),
+            None => HistoryQosPolicyKind::KeepAll,
+        };
+        let durability_service = DurabilityServiceQosPolicy {
+            history_kind,
+            max_samples: length_from_bytes(bytes)?,
+            max_instances: length_from_bytes(bytes)?,
+            max_samples_per_instance: length_from_bytes(bytes)?,
+            ..Default::default()
+        };
+        let writer_handle = InstanceHandle::new(take(bytes, 16)?.try_into().unwrap());
+        let sequence_number = i64::from_le_bytes(take(bytes, 8)?.try_into().unwrap());
+        let instance_handle = InstanceHandle::new(take(bytes, 16)?.try_into().unwrap());
+        let source_timestamp = time_from_bytes(bytes)?;
+        let length = u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap());
+        let serialized_data = take(bytes, length as usize)?.to_vec();
+        let sample = DurabilitySample::new(
+            writer_handle,
+            sequence_number,
+            instance_handle,
+            source_timestamp,
+            serialized_data,
+        );
+        Some(TopicLogRecord::AddSample(sample, durability_service))
+    }
+
+    fn dispose_instance_from_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        let instance_handle = InstanceHandle::new(take(bytes, 16)?.try_into().unwrap());
+        let disposed_timestamp = time_from_bytes(bytes)?;
+        Some(TopicLogRecord::DisposeInstance(
+            instance_handle,
+            disposed_timestamp,
+        ))
+    }
+}
+
+fn take<'a>(bytes: &mut &'a [u8], length: usize) -> Option<&'a [u8]> {
+    if bytes.len() < length {
+        return None;
+    }
+    let (value, remaining) = bytes.split_at(length);
+    *bytes = remaining;
+    Some(value)
+}
+
+fn limit_from_bytes(bytes: &mut &[u8]) -> Option<Option<u32>> {
+    let is_limited = take(bytes, 1)?[0] != 0;
+    let limit = u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap());
+    Some(is_limited.then_some(limit))
+}
+
+fn length_from_bytes(bytes: &mut &[u8]) -> Option<Length> {
+    Some(match limit_from_bytes(bytes)? {
+        Some(length) => Length::Limited(length),
+        None => Length::Unlimited,
+    })
+}
+
+fn time_from_bytes(bytes: &mut &[u8]) -> Option<Time> {
+    let sec = i32::from_le_bytes(take(bytes, 4)?.try_into().unwrap());
+    let nanosec = u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap());
+    Some(Time::new(sec, nanosec))
+}
+
+/// [`DurabilityStore`] keeping the samples in memory. The samples are kept for the lifetime of the
+/// application. This is the default store used for the
+/// [`DurabilityQosPolicyKind::Transient`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Transient) durability.
+#[derive(Default)]
+pub struct InMemoryDurabilityStore {
+    topics: Mutex<HashMap<DurabilityTopicKey, TopicHistory>>,
+}
+
+impl InMemoryDurabilityStore {
+    /// Create a new empty [`InMemoryDurabilityStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DurabilityStore for InMemoryDurabilityStore {
+    fn add_sample(
+        &self,
+        topic: &DurabilityTopicKey,
+        sample: DurabilitySample,
+        durability_service: &DurabilityServiceQosPolicy,
+    ) -> DdsResult<()> {
+        self.topics
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .entry(topic.clone())
+            .or_default()
+            .add_sample(sample, durability_service)
+    }
+
+    fn dispose_instance(
+        &self,
+        topic: &DurabilityTopicKey,
+        instance_handle: InstanceHandle,
+        disposed_timestamp: Time,
+    ) -> DdsResult<()> {
+        if let Some(topic) = self
+            .topics
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .get_mut(topic)
+        {
+            topic.dispose_instance(instance_handle, disposed_timestamp);
+        }
+        Ok(())
+    }
+
+    fn get_samples(&self, topic: &DurabilityTopicKey) -> DdsResult<Vec<DurabilitySample>> {
+        Ok(self
+            .topics
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .get(topic)
+            .map(|t| t.samples.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// [`DurabilityStore`] keeping the samples in files of a directory so that they survive the restart of the
+/// application. Each topic is stored in its own file to which every change of the samples of the topic is appended. The
+/// file is named after a hash of the names of the topic and of its type and starts with these names. The files are written
+/// and synchronized to the disk in batches by a thread of the store so that storing a sample doesn't wait for the disk.
+/// The file is compacted to the samples kept by the store once most of its content is outdated.
+/// This is the default store used for the
+/// [`DurabilityQosPolicyKind::Persistent`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Persistent) durability,
+/// in which case the samples are stored in the [`FileDurabilityStore::user_data_directory`].
+pub struct FileDurabilityStore {
+    directory: PathBuf,
+    topics: Mutex<HashMap<DurabilityTopicKey, TopicLog>>,
+    file_writer: Mutex<Option<TopicFileWriter>>,
+}
+
+// Samples of a topic together with the number of records of the file they are stored in
+#[derive(Default)]
+struct TopicLog {
+    history: TopicHistory,
+    record_count: usize,
+}
+
+impl FileDurabilityStore {
+    /// Create a new [`FileDurabilityStore`] storing its files in `directory`. The directory is created when the
+    /// first sample is stored.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            topics: Mutex::new(HashMap::new()),
+            file_writer: Mutex::new(None),
+        }
+    }
+
+    /// Directory in which the samples are stored by default, which is the `dust_dds/persistence` directory inside the data
+    /// directory of the user: `$XDG_DATA_HOME` or `$HOME/.local/share` on Linux, `$HOME/Library/Application Support` on
+    /// macOS and `%LOCALAPPDATA%` on Windows. [`None`] if the data directory of the user is unknown.
+    pub fn user_data_directory() -> Option<PathBuf> {
+        fn env_directory(variable: &str) -> Option<PathBuf> {
+            std::env::var_os(variable)
+                .map(PathBuf::from)
+                .filter(|d| d.is_absolute())
+        }
+
+        let data_directory = if cfg!(windows) {
+            env_directory("LOCALAPPDATA")
+        } else if cfg!(target_os = "macos") {
+            env_directory("HOME").map(|h| h.join("Library").join("Application Support"))
+        } else {
+            env_directory("XDG_DATA_HOME")
+                .or_else(|| env_directory("HOME").map(|h| h.join(".local").join("share")))
+        };
+        data_directory.map(|d| d.join("dust_dds").join("persistence"))
+    }
+
+    fn topic_file(&self, topic: &DurabilityTopicKey) -> PathBuf {
+        // The names may be too long or contain characters which are not valid in a file name
+        // so the file is named after their 64-bit FNV-1a hash
+        const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+        let hash = topic_file_header(topic)
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, &b| {
+                (hash ^ b as u64).wrapping_mul(FNV_PRIME)
+            });
+        self.directory
+            .join(format!("{}_{:016x}.dat", topic.domain_id, hash))
+    }
+
+    fn with_topic<T>(
+        &self,
+        topic: &DurabilityTopicKey,
+        f: impl FnOnce(&mut TopicLog) -> DdsResult<T>,
+    ) -> DdsResult<T> {
+        let mut topics = self.topics.lock().expect("Mutex shouldn't be poisoned");
+        if !topics.contains_key(topic) {
+            let path = self.topic_file(topic);
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(e) => return Err(e.into()),
+            };
+            let header = topic_file_header(topic);
+            let (mut remaining, is_header_partial) = if bytes.starts_with(&header) {
+                (&bytes[header.len()..], false)
+            } else if header.starts_with(&bytes) {
+                // The creation of the file was interrupted
+                (&[][..], !bytes.is_empty())
+            } else {
+                return Err(DdsError::Error(format!(
+                    "Durability store file {} holds the samples of another topic",
+                    path.display()
+                )));
+            };
+            let mut log = TopicLog::default();
+            while let Some(record) = TopicLogRecord::from_bytes(&mut remaining)? {
+                log.history.apply(record)?;
+                log.record_count += 1;
+            }
+            // A record only partially written because of a crash is discarded so
+            // that the following records are appended after the complete ones
+            if is_header_partial || !remaining.is_empty() {
+                self.compact_topic(topic, &mut log)?;
+            }
+            topics.insert(topic.clone(), log);
+        }
+        f(topics.get_mut(topic).expect("Topic log is loaded"))
+    }
+
+    fn apply_record(
+        &self,
+        topic: &DurabilityTopicKey,
+        log: &mut TopicLog,
+        record: TopicLogRecord,
+    ) -> DdsResult<()> {
+        const MIN_COMPACTED_RECORD_COUNT: usize = 64;
+
+        let bytes = record.to_bytes();
+        log.history.apply(record)?;
+        if log.record_count >= MIN_COMPACTED_RECORD_COUNT
+            && log.record_count >= 2 * log.history.samples.len()
+        {
+            return self.compact_topic(topic, log);
+        }
+
+        self.write_topic_file(TopicFileOperation::Append {
+            path: self.topic_file(topic),
+            header: topic_file_header(topic),
+            records: bytes,
+        })?;
+        log.record_count += 1;
+        Ok(())
+    }
+
+    fn compact_topic(&self, topic: &DurabilityTopicKey, log: &mut TopicLog) -> DdsResult<()> {
+        // The samples are already limited by the history so they are stored without limits
+        let unlimited = DurabilityServiceQosPolicy {
+            history_kind: HistoryQosPolicyKind::KeepAll,
+            max_samples: Length::Unlimited,
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Unlimited,
+            ..Default::default()
+        };
+        let mut bytes = topic_file_header(topic);
+        for sample in &log.history.samples {
+            bytes.extend(TopicLogRecord::AddSample(sample.clone(), unlimited.clone()).to_bytes());
+        }
+
+        self.write_topic_file(TopicFileOperation::Replace {
+            path: self.topic_file(topic),
+            content: bytes,
+        })?;
+        log.record_count = log.history.samples.len();
+        Ok(())
+    }
+
+    fn write_topic_file(&self, operation: TopicFileOperation) -> DdsResult<()> {
+        self.file_writer
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .get_or_insert_with(|| TopicFileWriter::new(self.directory.clone()))
+            .write(operation)
+    }
+}
+
+impl DurabilityStore for FileDurabilityStore {
+    fn add_sample(
+        &self,
+        topic: &DurabilityTopicKey,
+        sample: DurabilitySample,
+        durability_service: &DurabilityServiceQosPolicy,
+    ) -> DdsResult<()> {
+        self.with_topic(topic, |log| {
+            let record = TopicLogRecord::AddSample(sample, durability_service.clone());
+            self.apply_record(topic, log, record)
+        })
+    }
+
+    fn dispose_instance(
+        &self,
+        topic: &DurabilityTopicKey,
+        instance_handle: InstanceHandle,
+        disposed_timestamp: Time,
+    ) -> DdsResult<()> {
+        self.with_topic(topic, |log| {
+            let record = TopicLogRecord::DisposeInstance(instance_handle, disposed_timestamp);
+            self.apply_record(topic, log, record)
+        })
+    }
+
+    fn get_samples(&self, topic: &DurabilityTopicKey) -> DdsResult<Vec<DurabilitySample>> {
+        self.with_topic(topic, |log| {
+            Ok(log.history.samples.iter().cloned().collect())
+        })
+    }
+}
+
+// The file of a topic starts with the names of the topic and of its type so that the file
+// of another topic with the same hash is not taken for the file of the topic
+fn topic_file_header(topic: &DurabilityTopicKey) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for name in [&topic.topic_name, &topic.type_name] {
+        bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(name.as_bytes());
+    }
+    bytes
+}
+
+// Change of the file of a topic written by the thread of the TopicFileWriter
+enum TopicFileOperation {
+    // Append records to the file, which is created starting with the header if it doesn't exist
+    Append {
+        path: PathBuf,
+        header: Vec<u8>,
+        records: Vec<u8>,
+    },
+    // Replace the whole content of the file
+    Replace { path: PathBuf, content: Vec<u8> },
+}
+
+// Thread writing the changes of the files of the topics in the order in which they are made.
+// The changes made while the previous ones are written are written together and synchronized
+// to the disk once. Dropping the writer waits until all the changes are written.
+struct TopicFileWriter {
+    sender: Option<mpsc::Sender<TopicFileOperation>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl TopicFileWriter {
+    fn new(directory: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel::<TopicFileOperation>();
+        let join_handle = std::thread::Builder::new()
+            .name("Dust DDS durability store".to_string())
+            .spawn(move || {
+                let mut topic_files = TopicFiles {
+                    directory,
+                    files: HashMap::new(),
+                    unsynchronized_files: HashSet::new(),
+                };
+                while let Ok(operation) = receiver.recv() {
+                    for operation in std::iter::once(operation).chain(receiver.try_iter()) {
+                        if let Err(e) = topic_files.write(operation) {
+                            warn!("Failed to write durability store file: {:?}", e);
+                        }
+                    }
+                    if let Err(e) = topic_files.synchronize() {
+                        warn!("Failed to synchronize durability store file: {:?}", e);
+                    }
+                }
+            })
+            .expect("failed to spawn thread");
+        Self {
+            sender: Some(sender),
+            join_handle: Some(join_handle),
+        }
+    }
+
+    fn write(&self, operation: TopicFileOperation) -> DdsResult<()> {
+        self.sender
+            .as_ref()
+            .expect("Sender exists until the writer is dropped")
+            .send(operation)
+            .map_err(|_| DdsError::Error("Durability store writer thread stopped".to_string()))
+    }
+}
+
+impl Drop for TopicFileWriter {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle.join().ok();
+        }
+    }
+}
+
+// Files of the topics opened by the thread of the TopicFileWriter
+struct TopicFiles {
+    directory: PathBuf,
+    files: HashMap<PathBuf, fs::File>,
+    unsynchronized_files: HashSet<PathBuf>,
+}
+
+impl TopicFiles {
+    fn write(&mut self, operation: TopicFileOperation) -> std::io::Result<()> {
+        match operation {
+            TopicFileOperation::Append {
+                path,
+                header,
+                records,
+            } => {
+                let file = match self.files.entry(path.clone()) {
+                    Entry::Occupied(e) => e.into_mut(),
+                    Entry::Vacant(e) => {
+                        fs::create_dir_all(&self.directory)?;
+                        let mut file = fs::OpenOptions::new()
+                            .create(true)
+                            .append(true)
+                            .open(&path)?;
+                        if file.metadata()?.len() == 0 {
+                            file.write_all(&header)?;
+                            file.sync_all()?;
+                            sync_directory(&self.directory)?;
+                        }
+                        e.insert(file)
+                    }
+                };
+                file.write_all(&records)?;
+                self.unsynchronized_files.insert(path);
+            }
+            TopicFileOperation::Replace { path, content } => {
+                self.files.remove(&path);
+                self.unsynchronized_files.remove(&path);
+                fs::create_dir_all(&self.directory)?;
+                // Write to a temporary file first so that a crash while writing doesn't corrupt the stored samples
+                let temporary_path = path.with_extension("tmp");
+                let mut temporary = fs::File::create(&temporary_path)?;
+                temporary.write_all(&content)?;
+                temporary.sync_all()?;
+                fs::rename(temporary_path, path)?;
+                sync_directory(&self.directory)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn synchronize(&mut self) -> std::io::Result<()> {
+        for path in self.unsynchronized_files.drain() {
+            if let Some(file) = self.files.get(&path) {
+                file.sync_data()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Make the creation and renaming of the files of the directory durable
+fn sync_directory(directory: &Path) -> std::io::Result<()> {
+    // Directories can only be opened to be synchronized on unix systems
+    #[cfg(unix)]
+    fs::File::open(directory)?.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(topic_name: &str) -> DurabilityTopicKey {
+        DurabilityTopicKey::new(0, topic_name.to_owned(), "Type".to_owned())
+    }
+
+    fn sample(instance: u8, sec: i32) -> DurabilitySample {
+        DurabilitySample::new(
+            InstanceHandle::new([9; 16]),
+            sec as i64,
+            InstanceHandle::new([instance; 16]),
+            Time::new(sec, 0),
+            vec![instance, sec as u8],
+        )
+    }
+
+    #[test]
+    fn keep_last_history_per_instance() {
+        let store = InMemoryDurabilityStore::new();
+        let durability_service = DurabilityServiceQosPolicy {
+            history_kind: HistoryQosPolicyKind::KeepLast(2),
+            ..Default::default()
+        };
+        for sec in 0..3 {
+            store
+                .add_sample(&topic("Topic"), sample(1, sec), &durability_service)
+                .unwrap();
+            store
+                .add_sample(&topic("Topic"), sample(2, sec), &durability_service)
+                .unwrap();
+        }
+
+        assert_eq!(
+            store.get_samples(&topic("Topic")).unwrap(),
+            vec![sample(1, 1), sample(2, 1), sample(1, 2), sample(2, 2)]
+        );
+        assert_eq!(store.get_samples(&topic("OtherTopic")).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn samples_are_kept_per_domain_and_type() {
+        let store = InMemoryDurabilityStore::new();
+        let durability_service = DurabilityServiceQosPolicy::default();
+        store
+            .add_sample(&topic("Topic"), sample(1, 0), &durability_service)
+            .unwrap();
+
+        let other_domain = DurabilityTopicKey::new(1, "Topic".to_owned(), "Type".to_owned());
+        let other_type = DurabilityTopicKey::new(0, "Topic".to_owned(), "OtherType".to_owned());
+        assert_eq!(store.get_samples(&other_domain).unwrap(), vec![]);
+        assert_eq!(store.get_samples(&other_type).unwrap(), vec![]);
+        assert_eq!(
+            store.get_samples(&topic("Topic")).unwrap(),
+            vec![sample(1, 0)]
+        );
+    }
+
+    #[test]
+    fn resource_limits_are_honored() {
+        let store = InMemoryDurabilityStore::new();
+        let durability_service = DurabilityServiceQosPolicy {
+            history_kind: HistoryQosPolicyKind::KeepAll,
+            max_samples: Length::Limited(3),
+            max_instances: Length::Limited(2),
+            max_samples_per_instance: Length::Limited(2),
+            ..Default::default()
+        };
+        for sec in 0..3 {
+            store
+                .add_sample(&topic("Topic"), sample(1, sec), &durability_service)
+                .unwrap();
+        }
+        store
+            .add_sample(&topic("Topic"), sample(2, 3), &durability_service)
+            .unwrap();
+        store
+            .add_sample(&topic("Topic"), sample(2, 4), &durability_service)
+            .unwrap();
+
+        assert_eq!(
+            store.add_sample(&topic("Topic"), sample(3, 5), &durability_service),
+            Err(DdsError::OutOfResources)
+        );
+        assert_eq!(
+            store.get_samples(&topic("Topic")).unwrap(),
+            vec![sample(1, 2), sample(2, 3), sample(2, 4)]
+        );
+    }
+
+    #[test]
+    fn disposed_instance_is_removed() {
+        let store = InMemoryDurabilityStore::new();
+        let durability_service = DurabilityServiceQosPolicy {
+            history_kind: HistoryQosPolicyKind::KeepAll,
+            ..Default::default()
+        };
+        store
+            .add_sample(&topic("Topic"), sample(1, 0), &durability_service)
+            .unwrap();
+        store
+            .add_sample(&topic("Topic"), sample(2, 0), &durability_service)
+            .unwrap();
+        store
+            .add_sample(&topic("Topic"), sample(1, 2), &durability_service)
+            .unwrap();
+
+        // Samples written after the instance was disposed are kept
+        store
+            .dispose_instance(
+                &topic("Topic"),
+                InstanceHandle::new([1; 16]),
+                Time::new(1, 0),
+            )
+            .unwrap();
+
+        assert_eq!(
+            store.get_samples(&topic("Topic")).unwrap(),
+            vec![sample(2, 0), sample(1, 2)]
+        );
+    }
+
+    #[test]
+    fn file_store_samples_survive_a_new_store() {
+        let directory =
+            std::env::temp_dir().join(format!("dust_dds_file_store_test_{}", std::process::id()));
+        let durability_service = DurabilityServiceQosPolicy {
+            history_kind: HistoryQosPolicyKind::KeepLast(2),
+            ..Default::default()
+        };
+        {
+            let store = FileDurabilityStore::new(&directory);
+            for sec in 0..3 {
+                store
+                    .add_sample(
+                        &topic("Topic/With:Symbols"),
+                        sample(1, sec),
+                        &durability_service,
+                    )
+                    .unwrap();
+            }
+        }
+
+        let store = FileDurabilityStore::new(&directory);
+        let samples = store.get_samples(&topic("Topic/With:Symbols")).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(samples, vec![sample(1, 1), sample(1, 2)]);
+    }
+
+    #[test]
+    fn file_store_names_its_files_after_a_hash_of_the_names() {
+        let directory = std::env::temp_dir().join(format!(
+            "dust_dds_file_store_name_test_{}",
+            std::process::id()
+        ));
+        let durability_service = DurabilityServiceQosPolicy::default();
+        let long_topic = DurabilityTopicKey::new(0, "Topic".repeat(100), "Type".repeat(100));
+        {
+            let store = FileDurabilityStore::new(&directory);
+            store
+                .add_sample(&long_topic, sample(1, 0), &durability_service)
+                .unwrap();
+        }
+
+        let store = FileDurabilityStore::new(&directory);
+        let file_name_length = store.topic_file(&long_topic).file_name().unwrap().len();
+        let samples = store.get_samples(&long_topic).unwrap();
+        // A file holding the samples of another topic, e.g. one with the same hash, is not loaded
+        fs::copy(
+            store.topic_file(&long_topic),
+            store.topic_file(&topic("Topic")),
+        )
+        .unwrap();
+        let other_topic_samples = store.get_samples(&topic("Topic"));
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(file_name_length < 32);
+        assert_eq!(samples, vec![sample(1, 0)]);
+        assert!(matches!(other_topic_samples, Err(DdsError::Error(_))));
+    }
+
+    #[test]
+    fn file_store_compacts_its_log_and_discards_a_partial_record() {
+        let directory = std::env::temp_dir().join(format!(
+            "dust_dds_file_store_compaction_test_{}",
+            std::process::id()
+        ));
+        let durability_service = DurabilityServiceQosPolicy {
+            history_kind: HistoryQosPolicyKind::KeepLast(1),
+            ..Default::default()
+        };
+        let file = {
+            let store = FileDurabilityStore::new(&directory);
+            for sec in 0..200 {
+                store
+                    .add_sample(&topic("Topic"), sample(1, sec), &durability_service)
+                    .unwrap();
+            }
+            store.topic_file(&topic("Topic"))
+        };
+        let compacted_length = fs::metadata(&file).unwrap().len();
+        // Simulate a crash while a record was being appended
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&file)
+            .unwrap()
+            .write_all(&[0, 1, 2])
+            .unwrap();
+
+        let store = FileDurabilityStore::new(&directory);
+        store
+            .add_sample(&topic("Topic"), sample(1, 200), &durability_service)
+            .unwrap();
+        // Dropping the store waits until its changes are written
+        drop(store);
+        let samples = FileDurabilityStore::new(&directory)
+            .get_samples(&topic("Topic"))
+            .unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let record = TopicLogRecord::AddSample(sample(1, 0), durability_service.clone()).to_bytes();
+        assert!(compacted_length < 100 * record.len() as u64);
+        assert_eq!(samples, vec![sample(1, 200)]);
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    dds_async::condition::StatusConditionAsync, infrastructure::error::DdsResult,
+    dds_async::condition::{
+        GuardConditionAsync, QueryConditionAsync, ReadConditionAsync, StatusConditionAsync,
+    },
+    infrastructure::error::DdsResult,
     runtime::executor::block_on,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
 };
 
 use super::status::StatusKind;
@@ -8,7 +12,7 @@ use super::status::StatusKind;
 /// A [`StatusCondition`] object is a specific Condition that is associated with each Entity.
 /// The *trigger_value* of the [`StatusCondition`] depends on the communication status of that entity (e.g., arrival of data, loss of
 /// information, etc.), 'filtered' by the set of *enabled_statuses* on the [`StatusCondition`].
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct StatusCondition {
     condition_async: StatusConditionAsync,
 }
@@ -54,3 +58,191 @@ impl StatusCondition {
         block_on(self.condition_async.get_trigger_value())
     }
 }
+
+/// A [`GuardCondition`] object is a specific Condition whose *trigger_value* is completely under the control of the application.
+/// The [`GuardCondition`] has no factory and must be created by the application. When first created the *trigger_value* is set to [`false`].
+/// The purpose of the [`GuardCondition`] is to provide the means for the application to manually wake up a
+/// [`WaitSet`](crate::infrastructure::wait_set::WaitSet). This is accomplished by attaching the [`GuardCondition`] to the
+/// [`WaitSet`](crate::infrastructure::wait_set::WaitSet) and then setting the *trigger_value* by means of the [`GuardCondition::set_trigger_value`] operation.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct GuardCondition {
+    condition_async: GuardConditionAsync,
+}
+
+impl GuardCondition {
+    /// Create a new [`GuardCondition`] with a *trigger_value* of [`false`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn from_async(condition_async: GuardConditionAsync) -> Self {
+        Self { condition_async }
+    }
+
+    pub(crate) fn condition_async(&self) -> &GuardConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl GuardCondition {
+    /// This operation sets the *trigger_value* of the [`GuardCondition`].
+    /// [`WaitSet`](crate::infrastructure::wait_set::WaitSet) objects behavior depend on the changes of the *trigger_value* of their
+    /// attached conditions. Therefore, any [`WaitSet`](crate::infrastructure::wait_set::WaitSet) to which the [`GuardCondition`] is attached is potentially affected by this operation.
+    pub fn set_trigger_value(&self, value: bool) -> DdsResult<()> {
+        block_on(self.condition_async.set_trigger_value(value))
+    }
+
+    /// This operation retrieves the *trigger_value* of the [`GuardCondition`].
+    pub fn get_trigger_value(&self) -> DdsResult<bool> {
+        block_on(self.condition_async.get_trigger_value())
+    }
+}
+
+/// [`ReadCondition`] objects are conditions specifically dedicated to read operations and attached to one
+/// [`DataReader`](crate::subscription::data_reader::DataReader).
+/// [`ReadCondition`] objects allow an application to specify the data samples it is interested in by specifying the desired
+/// sample states, view states, and instance states. This allows the middleware to enable the condition only when suitable
+/// information is available. They are to be used in conjunction with a [`WaitSet`](crate::infrastructure::wait_set::WaitSet)
+/// as normal conditions. More than one [`ReadCondition`] may be attached to the same
+/// [`DataReader`](crate::subscription::data_reader::DataReader).
+#[derive(Clone, PartialEq, Eq)]
+pub struct ReadCondition {
+    condition_async: ReadConditionAsync,
+}
+
+impl ReadCondition {
+    pub(crate) fn new(condition_async: ReadConditionAsync) -> Self {
+        Self { condition_async }
+    }
+
+    pub(crate) fn condition_async(&self) -> &ReadConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl ReadCondition {
+    /// This operation returns the set of sample states that are taken into account to determine the *trigger_value* of the
+    /// [`ReadCondition`]. These are the sample states specified when the [`ReadCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.condition_async.get_sample_state_mask()
+    }
+
+    /// This operation returns the set of view states that are taken into account to determine the *trigger_value* of the
+    /// [`ReadCondition`]. These are the view states specified when the [`ReadCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.condition_async.get_view_state_mask()
+    }
+
+    /// This operation returns the set of instance states that are taken into account to determine the *trigger_value* of the
+    /// [`ReadCondition`]. These are the instance states specified when the [`ReadCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.condition_async.get_instance_state_mask()
+    }
+}
+
+/// This implementation block contains the Condition operations for the [`ReadCondition`].
+impl ReadCondition {
+    /// This operation retrieves the *trigger_value* of the [`ReadCondition`]. The *trigger_value* is [`true`] if
+    /// there is at least one sample in the [`DataReader`](crate::subscription::data_reader::DataReader) whose sample state,
+    /// view state and instance state match the masks of the [`ReadCondition`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_trigger_value(&self) -> DdsResult<bool> {
+        block_on(self.condition_async.get_trigger_value())
+    }
+}
+
+impl AsRef<ReadCondition> for ReadCondition {
+    fn as_ref(&self) -> &ReadCondition {
+        self
+    }
+}
+
+/// [`QueryCondition`] objects are specialized [`ReadCondition`] objects that allow the application to also specify a filter
+/// on the locally available data.
+/// The query (`query_expression`) is similar to an SQL WHERE clause, e.g. `"id = %0 AND value > 10.5"`, and uses the same
+/// syntax as the `filter_expression` of a [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic).
+/// The `query_parameters` give values to the "%n" tokens in the `query_expression`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct QueryCondition {
+    read_condition: ReadCondition,
+    condition_async: QueryConditionAsync,
+}
+
+impl QueryCondition {
+    pub(crate) fn new(condition_async: QueryConditionAsync) -> Self {
+        Self {
+            read_condition: ReadCondition::new(condition_async.as_ref().clone()),
+            condition_async,
+        }
+    }
+
+    pub(crate) fn condition_async(&self) -> &QueryConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl QueryCondition {
+    /// This operation returns the `query_expression` associated with the [`QueryCondition`]. That is, the expression specified
+    /// when the [`QueryCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_expression(&self) -> String {
+        self.condition_async.get_query_expression()
+    }
+
+    /// This operation returns the `query_parameters` associated with the [`QueryCondition`]. That is, the parameters specified
+    /// on the last successful call to [`QueryCondition::set_query_parameters`], or if [`QueryCondition::set_query_parameters`]
+    /// was never called, the parameters specified when the [`QueryCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_parameters(&self) -> DdsResult<Vec<String>> {
+        block_on(self.condition_async.get_query_parameters())
+    }
+
+    /// This operation changes the `query_parameters` associated with the [`QueryCondition`].
+    /// If the number of parameters is not enough for the `query_expression` the operation fails and returns
+    /// [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn set_query_parameters(&self, query_parameters: &[String]) -> DdsResult<()> {
+        block_on(self.condition_async.set_query_parameters(query_parameters))
+    }
+}
+
+/// This implementation block contains the ReadCondition operations for the [`QueryCondition`].
+impl QueryCondition {
+    /// This operation returns the set of sample states that are taken into account to determine the *trigger_value* of the
+    /// [`QueryCondition`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.condition_async.get_sample_state_mask()
+    }
+
+    /// This operation returns the set of view states that are taken into account to determine the *trigger_value* of the
+    /// [`QueryCondition`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.condition_async.get_view_state_mask()
+    }
+
+    /// This operation returns the set of instance states that are taken into account to determine the *trigger_value* of the
+    /// [`QueryCondition`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.condition_async.get_instance_state_mask()
+    }
+
+    /// This operation retrieves the *trigger_value* of the [`QueryCondition`]. The *trigger_value* is [`true`] if
+    /// there is at least one sample in the [`DataReader`](crate::subscription::data_reader::DataReader) which matches the masks
+    /// and the query of the [`QueryCondition`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_trigger_value(&self) -> DdsResult<bool> {
+        block_on(self.condition_async.get_trigger_value())
+    }
+}
+
+impl AsRef<ReadCondition> for QueryCondition {
+    fn as_ref(&self) -> &ReadCondition {
+        &self.read_condition
+    }
+}
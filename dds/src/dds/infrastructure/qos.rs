@@ -6,11 +6,12 @@ use crate::infrastructure::{
 use super::{
     qos_policy::{
         DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
-        DurabilityQosPolicy, EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy,
-        HistoryQosPolicyKind, LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy,
-        OwnershipQosPolicy, OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
+        DurabilityQosPolicy, DurabilityServiceQosPolicy, EntityFactoryQosPolicy,
+        GroupDataQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, LatencyBudgetQosPolicy,
+        LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy, OwnershipStrengthQosPolicy,
+        PartitionQosPolicy, PresentationQosPolicy, PublishModeQosPolicy,
         ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
-        ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
+        ReliabilityTimingQosPolicy, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
         TransportPriorityQosPolicy, UserDataQosPolicy, WriterDataLifecycleQosPolicy,
     },
     time::DurationKind,
@@ -59,6 +60,8 @@ pub struct PublisherQos {
 pub struct DataWriterQos {
     /// Value of the durability QoS policy.
     pub durability: DurabilityQosPolicy,
+    /// Value of the durability service QoS policy.
+    pub durability_service: DurabilityServiceQosPolicy,
     /// Value of the deadline QoS policy.
     pub deadline: DeadlineQosPolicy,
     /// Value of the latency budget QoS policy.
@@ -87,6 +90,10 @@ pub struct DataWriterQos {
     pub writer_data_lifecycle: WriterDataLifecycleQosPolicy,
     /// Value of the data representation QoS policy.
     pub representation: DataRepresentationQosPolicy,
+    /// Value of the publish mode QoS policy.
+    pub publish_mode: PublishModeQosPolicy,
+    /// Value of the reliability timing QoS policy.
+    pub reliability_timing: ReliabilityTimingQosPolicy,
 }
 
 impl Default for DataWriterQos {
@@ -112,7 +119,10 @@ impl Default for DataWriterQos {
             lifespan: LifespanQosPolicy::default(),
             transport_priority: TransportPriorityQosPolicy::default(),
             writer_data_lifecycle: WriterDataLifecycleQosPolicy::default(),
+            durability_service: DurabilityServiceQosPolicy::default(),
             representation: DataRepresentationQosPolicy::default(),
+            publish_mode: PublishModeQosPolicy::default(),
+            reliability_timing: ReliabilityTimingQosPolicy::default(),
         }
     }
 }
@@ -124,6 +134,17 @@ impl DataWriterQos {
             return Err(DdsError::InconsistentPolicy);
         }
 
+        // The resource limits and history of the durability service follow the same
+        // consistency rules as the ones of the writer
+        if self.durability_service.max_samples < self.durability_service.max_samples_per_instance {
+            return Err(DdsError::InconsistentPolicy);
+        }
+        if let HistoryQosPolicyKind::KeepLast(depth) = self.durability_service.history_kind {
+            if depth as usize > self.durability_service.max_samples_per_instance {
+                return Err(DdsError::InconsistentPolicy);
+            }
+        }
+
         // The setting of RESOURCE_LIMITS max_samples must be consistent with the max_samples_per_instance. For these two
         // values to be consistent they must verify that *max_samples >= max_samples_per_instanc
         if self.resource_limits.max_samples < self.resource_limits.max_samples_per_instance {
@@ -146,12 +167,15 @@ impl DataWriterQos {
 
     pub(crate) fn check_immutability(&self, other: &Self) -> DdsResult<()> {
         if self.durability != other.durability
+            || self.durability_service != other.durability_service
             || self.liveliness != other.liveliness
             || self.reliability != other.reliability
             || self.destination_order != other.destination_order
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.publish_mode != other.publish_mode
+            || self.reliability_timing != other.reliability_timing
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -212,6 +236,8 @@ pub struct DataReaderQos {
     pub reader_data_lifecycle: ReaderDataLifecycleQosPolicy,
     /// Value of the data representation QoS policy.
     pub representation: DataRepresentationQosPolicy,
+    /// Value of the reliability timing QoS policy.
+    pub reliability_timing: ReliabilityTimingQosPolicy,
 }
 
 impl Default for DataReaderQos {
@@ -236,6 +262,7 @@ impl Default for DataReaderQos {
             time_based_filter: TimeBasedFilterQosPolicy::default(),
             reader_data_lifecycle: ReaderDataLifecycleQosPolicy::default(),
             representation: DataRepresentationQosPolicy::default(),
+            reliability_timing: ReliabilityTimingQosPolicy::default(),
         }
     }
 }
@@ -276,6 +303,7 @@ impl DataReaderQos {
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.reliability_timing != other.reliability_timing
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -291,6 +319,8 @@ pub struct TopicQos {
     pub topic_data: TopicDataQosPolicy,
     /// Value of the durability QoS policy.
     pub durability: DurabilityQosPolicy,
+    /// Value of the durability service QoS policy.
+    pub durability_service: DurabilityServiceQosPolicy,
     /// Value of the deadline QoS policy.
     pub deadline: DeadlineQosPolicy,
     /// Value of the latency budget QoS policy.
@@ -327,6 +357,7 @@ impl Default for TopicQos {
             },
             topic_data: TopicDataQosPolicy::default(),
             durability: DurabilityQosPolicy::default(),
+            durability_service: DurabilityServiceQosPolicy::default(),
             deadline: DeadlineQosPolicy::default(),
             latency_budget: LatencyBudgetQosPolicy::default(),
             liveliness: LivelinessQosPolicy::default(),
@@ -362,7 +393,6 @@ impl TopicQos {
             HistoryQosPolicyKind::KeepAll => Ok(()),
         }
     }
-
 }
 
 #[cfg(test)]
@@ -400,6 +430,18 @@ mod tests {
             .is_consistent(),
             Err(DdsError::InconsistentPolicy)
         );
+        assert_eq!(
+            DataWriterQos {
+                durability_service: DurabilityServiceQosPolicy {
+                    history_kind: HistoryQosPolicyKind::KeepLast(3),
+                    max_samples_per_instance: Length::Limited(2),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+            .is_consistent(),
+            Err(DdsError::InconsistentPolicy)
+        );
     }
 
     #[test]
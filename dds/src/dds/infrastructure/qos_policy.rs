@@ -138,7 +138,10 @@ const TOPICDATA_QOS_POLICY_NAME: &str = "TopicData";
 const TRANSPORTPRIORITY_QOS_POLICY_NAME: &str = "TransportPriority";
 const GROUPDATA_QOS_POLICY_NAME: &str = "GroupData";
 const LIFESPAN_QOS_POLICY_NAME: &str = "Lifespan";
+const DURABILITYSERVICE_QOS_POLICY_NAME: &str = "DurabilityService";
 const DATA_REPRESENTATION_QOS_POLICY_NAME: &str = "DataRepresentation";
+const PUBLISH_MODE_QOS_POLICY_NAME: &str = "PublishMode";
+const RELIABILITY_TIMING_QOS_POLICY_NAME: &str = "ReliabilityTiming";
 
 /// QosPolicy Id representing an invalid QoS policy
 pub const INVALID_QOS_POLICY_ID: QosPolicyId = 0;
@@ -328,7 +331,7 @@ impl Default for LifespanQosPolicy {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, XTypesSerialize, XTypesDeserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, XTypesSerialize, XTypesDeserialize)]
 /// Enumeration representing the different types of Durability QoS policies.
 pub enum DurabilityQosPolicyKind {
     /// Volatile durability QoS policy
@@ -412,20 +415,20 @@ pub enum PresentationQosPolicyAccessScopeKind {
     Instance,
     /// Access scope per topic
     Topic,
+    /// Access scope per group of topics written by the same publisher
+    Group,
 }
 
 impl PartialOrd for PresentationQosPolicyAccessScopeKind {
     fn partial_cmp(&self, other: &PresentationQosPolicyAccessScopeKind) -> Option<Ordering> {
-        match self {
-            PresentationQosPolicyAccessScopeKind::Instance => match other {
-                PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Equal),
-                PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Less),
-            },
-            PresentationQosPolicyAccessScopeKind::Topic => match other {
-                PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Greater),
-                PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Equal),
-            },
+        fn rank(kind: &PresentationQosPolicyAccessScopeKind) -> u8 {
+            match kind {
+                PresentationQosPolicyAccessScopeKind::Instance => 0,
+                PresentationQosPolicyAccessScopeKind::Topic => 1,
+                PresentationQosPolicyAccessScopeKind::Group => 2,
+            }
         }
+        rank(self).partial_cmp(&rank(other))
     }
 }
 
@@ -446,6 +449,8 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
 ///   independently. That is, changes made to instances within each individual DataWriter will be available as coherent with
 ///   respect to other changes to instances in that same DataWriter, but will not be grouped with changes made to instances
 ///   belonging to a different DataWriter.
+/// - If access_scope is set to GROUP, then coherent changes made to instances through DataWriter entities attached to a
+///   common Publisher are made available as a unit to remote subscribers.
 ///   If ordered_access is set, then the access_scope controls the maximum extent for which order will be preserved by the Service.
 /// - If access_scope is set to INSTANCE (the lowest level), then changes to each instance are considered unordered relative
 ///   to changes to any other instance. That means that changes (creations, deletions, modifications) made to two instances
@@ -1070,6 +1075,52 @@ impl Default for ResourceLimitsQosPolicy {
     }
 }
 
+/// This policy is used to configure the [`HistoryQosPolicy`] and [`ResourceLimitsQosPolicy`] used by the fictitious
+/// [`DataReader`](crate::subscription::data_reader::DataReader) and [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// used by the "persistence service".
+///
+/// The "persistence service" is the one responsible for implementing the [`DurabilityQosPolicyKind::Transient`] and
+/// [`DurabilityQosPolicyKind::Persistent`] kinds of [`DurabilityQosPolicy`]. The samples written by a
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) with one of those kinds are kept by the service according to
+/// [`DurabilityServiceQosPolicy::history_kind`] and the resource limits of this policy, independently of the history and
+/// resource limits of the [`DataWriter`](crate::publication::data_writer::DataWriter) itself.
+/// The [`DurabilityServiceQosPolicy::service_cleanup_delay`] controls when the service is able to remove all information regarding
+/// a data-instance. The samples of a disposed instance are removed once this delay has elapsed since the instance was disposed.
+#[derive(Debug, PartialEq, Eq, Clone, XTypesSerialize, XTypesDeserialize)]
+pub struct DurabilityServiceQosPolicy {
+    /// Delay after which the information of a disposed instance can be removed.
+    pub service_cleanup_delay: DurationKind,
+    /// Kind of history kept by the durability service.
+    pub history_kind: HistoryQosPolicyKind,
+    /// Maximum number of samples limit.
+    pub max_samples: Length,
+    /// Maximum number of instances limit.
+    pub max_instances: Length,
+    /// Maximum number of samples per instance limit.
+    pub max_samples_per_instance: Length,
+}
+
+impl QosPolicy for DurabilityServiceQosPolicy {
+    fn name(&self) -> &str {
+        DURABILITYSERVICE_QOS_POLICY_NAME
+    }
+}
+
+impl Default for DurabilityServiceQosPolicy {
+    fn default() -> Self {
+        Self {
+            service_cleanup_delay: DurationKind::Finite(Duration::new(
+                DURATION_ZERO_SEC,
+                DURATION_ZERO_NSEC,
+            )),
+            history_kind: HistoryQosPolicyKind::KeepLast(1),
+            max_samples: Length::Unlimited,
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Unlimited,
+        }
+    }
+}
+
 /// This policy controls the behavior of the Entity as a factory for other entities.
 ///
 /// This policy concerns only DomainParticipant (as factory for Publisher, Subscriber, and Topic), Publisher (as factory for
@@ -1206,6 +1257,60 @@ impl QosPolicy for DataRepresentationQosPolicy {
     }
 }
 
+/*******  Dust DDS Extension **********/
+
+/// This policy is a Dust DDS extension which controls how the [`DataWriter`](crate::publication::data_writer::DataWriter) sends its data.
+///
+/// By default the data and repair messages of the [`DataWriter`](crate::publication::data_writer::DataWriter) are sent as soon as they are written.
+/// When [`PublishModeQosPolicy::flow_controller_name`] names one of the flow controllers configured on the transport of the
+/// [`DomainParticipant`](crate::domain::domain_participant::DomainParticipant), the messages are instead queued and released at the rate allowed
+/// by the token bucket of the flow controller. The [`PublishModeQosPolicy::priority`] orders the messages of the writers sharing a
+/// flow controller which schedules by priority, higher values being sent first.
+/// Only the samples still in the history of the writer stay queued: a sample replaced in a
+/// [`HistoryQosPolicyKind::KeepLast`] history before the flow controller sent it is not sent anymore.
+/// Creating a [`DataWriter`](crate::publication::data_writer::DataWriter) attached to a flow controller which does not exist fails with
+/// [`DdsError::BadParameter`](crate::infrastructure::error::DdsError). This policy can not be changed once the
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) is created.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct PublishModeQosPolicy {
+    /// Name of the flow controller releasing the messages of the writer. [`None`] sends the messages immediately.
+    pub flow_controller_name: Option<String>,
+    /// Scheduling priority of the messages of the writer in its flow controller
+    pub priority: i32,
+}
+
+impl QosPolicy for PublishModeQosPolicy {
+    fn name(&self) -> &str {
+        PUBLISH_MODE_QOS_POLICY_NAME
+    }
+}
+
+/// This policy is a Dust DDS extension which overrides the timing of the reliable protocol for a single
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) or [`DataReader`](crate::subscription::data_reader::DataReader).
+///
+/// Each value left to [`None`] keeps the timing configured on the transport of the
+/// [`DomainParticipant`](crate::domain::domain_participant::DomainParticipant). The heartbeat period, nack response delay and
+/// nack suppression duration are used by the [`DataWriter`](crate::publication::data_writer::DataWriter) and the heartbeat
+/// response delay by the [`DataReader`](crate::subscription::data_reader::DataReader). This policy only applies to reliable
+/// entities and can not be changed once the entity is created.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ReliabilityTimingQosPolicy {
+    /// Period at which the writer with unacknowledged changes announces its available changes
+    pub heartbeat_period: Option<Duration>,
+    /// Delay before the writer sends the changes requested by a reader
+    pub nack_response_delay: Option<Duration>,
+    /// Time after sending a change during which the requests of a reader for it are ignored by the writer
+    pub nack_suppression_duration: Option<Duration>,
+    /// Delay before the reader answers a heartbeat of a writer
+    pub heartbeat_response_delay: Option<Duration>,
+}
+
+impl QosPolicy for ReliabilityTimingQosPolicy {
+    fn name(&self) -> &str {
+        RELIABILITY_TIMING_QOS_POLICY_NAME
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +192,16 @@ impl Time {
     pub const fn nanosec(&self) -> u32 {
         self.nanosec
     }
+
+    /// Time after the given duration, or `None` if it is beyond the range of [`Time`]
+    pub(crate) fn checked_add(&self, duration: Duration) -> Option<Time> {
+        let nanosec = self.nanosec as u64 + duration.nanosec() as u64;
+        let sec = self
+            .sec
+            .checked_add(duration.sec())?
+            .checked_add((nanosec / 1_000_000_000) as i32)?;
+        Some(Self::new(sec, (nanosec % 1_000_000_000) as u32))
+    }
 }
 
 impl From<crate::transport::types::Time> for Time {
@@ -259,4 +269,18 @@ mod tests {
 
         assert_eq!(dds_time, dds_time_from_rtps_time)
     }
+
+    #[test]
+    fn time_checked_add() {
+        let time = Time::new(10, 600_000_000);
+
+        assert_eq!(
+            time.checked_add(Duration::new(1, 500_000_000)),
+            Some(Time::new(12, 100_000_000))
+        );
+        assert_eq!(
+            Time::new(i32::MAX, 0).checked_add(Duration::new(1, 0)),
+            None
+        );
+    }
 }
@@ -4,13 +4,19 @@ use crate::{
     runtime::executor::block_on,
 };
 
-use super::condition::StatusCondition;
+use super::condition::{GuardCondition, QueryCondition, ReadCondition, StatusCondition};
 
 /// Enumeration of the different Condition objects that can be associated with a [`WaitSet`].
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum Condition {
     /// Status condition variant
     StatusCondition(StatusCondition),
+    /// Read condition variant
+    ReadCondition(ReadCondition),
+    /// Query condition variant
+    QueryCondition(QueryCondition),
+    /// Guard condition variant
+    GuardCondition(GuardCondition),
 }
 impl Condition {
     #[tracing::instrument(skip(self))]
@@ -18,6 +24,28 @@ impl Condition {
     pub fn get_trigger_value(&self) -> DdsResult<bool> {
         match self {
             Condition::StatusCondition(c) => c.get_trigger_value(),
+            Condition::ReadCondition(c) => c.get_trigger_value(),
+            Condition::QueryCondition(c) => c.get_trigger_value(),
+            Condition::GuardCondition(c) => c.get_trigger_value(),
+        }
+    }
+}
+
+impl From<Condition> for ConditionAsync {
+    fn from(value: Condition) -> Self {
+        match value {
+            Condition::StatusCondition(sc) => {
+                ConditionAsync::StatusCondition(sc.condition_async().clone())
+            }
+            Condition::ReadCondition(rc) => {
+                ConditionAsync::ReadCondition(rc.condition_async().clone())
+            }
+            Condition::QueryCondition(qc) => {
+                ConditionAsync::QueryCondition(qc.condition_async().clone())
+            }
+            Condition::GuardCondition(gc) => {
+                ConditionAsync::GuardCondition(gc.condition_async().clone())
+            }
         }
     }
 }
@@ -54,6 +82,15 @@ impl WaitSet {
                 ConditionAsync::StatusCondition(sc) => {
                     Condition::StatusCondition(StatusCondition::new(sc))
                 }
+                ConditionAsync::ReadCondition(rc) => {
+                    Condition::ReadCondition(ReadCondition::new(rc))
+                }
+                ConditionAsync::QueryCondition(qc) => {
+                    Condition::QueryCondition(QueryCondition::new(qc))
+                }
+                ConditionAsync::GuardCondition(gc) => {
+                    Condition::GuardCondition(GuardCondition::from_async(gc))
+                }
             })
             .collect())
     }
@@ -64,18 +101,14 @@ impl WaitSet {
     /// Adding a [`Condition`] that is already attached to the [`WaitSet`] has no effect.
     #[tracing::instrument(skip(self, cond))]
     pub fn attach_condition(&mut self, cond: Condition) -> DdsResult<()> {
-        match cond {
-            Condition::StatusCondition(sc) => block_on(self.waitset_async.attach_condition(
-                ConditionAsync::StatusCondition(sc.condition_async().clone()),
-            )),
-        }
+        block_on(self.waitset_async.attach_condition(cond.into()))
     }
 
     /// Detaches a [`Condition`] from the [`WaitSet`].
     /// If the [`Condition`] was not attached to the [`WaitSet`], the operation will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError::PreconditionNotMet).
-    #[tracing::instrument(skip(self, _cond))]
-    pub fn detach_condition(&self, _cond: Condition) -> DdsResult<()> {
-        todo!()
+    #[tracing::instrument(skip(self, cond))]
+    pub fn detach_condition(&mut self, cond: Condition) -> DdsResult<()> {
+        block_on(self.waitset_async.detach_condition(cond.into()))
     }
 
     /// This operation retrieves the list of attached conditions.
@@ -87,6 +120,15 @@ impl WaitSet {
                 ConditionAsync::StatusCondition(sc) => {
                     Condition::StatusCondition(StatusCondition::new(sc))
                 }
+                ConditionAsync::ReadCondition(rc) => {
+                    Condition::ReadCondition(ReadCondition::new(rc))
+                }
+                ConditionAsync::QueryCondition(qc) => {
+                    Condition::QueryCondition(QueryCondition::new(qc))
+                }
+                ConditionAsync::GuardCondition(gc) => {
+                    Condition::GuardCondition(GuardCondition::from_async(gc))
+                }
             })
             .collect())
     }
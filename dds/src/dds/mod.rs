@@ -21,6 +21,10 @@ pub mod subscription;
 /// by the application to define topics and attach qos policies.
 pub mod topic_definition;
 
+/// Contains the [`DurabilityStore`](crate::durability::DurabilityStore) trait and the stores used by the durability service to
+/// keep the data of the writers with a transient or persistent durability.
+pub mod durability;
+
 /// Contains the [`DustDdsConfiguration`](crate::configuration::DustDdsConfiguration) struct that allow configuring the runtime options
 /// of the Dust DDS systems
 pub mod configuration;
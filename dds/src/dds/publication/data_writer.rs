@@ -16,6 +16,7 @@ use crate::{
     runtime::executor::block_on,
     topic_definition::{topic::Topic, type_support::DdsSerialize},
 };
+use std::sync::Arc;
 
 /// The [`DataWriter`] allows the application to set the value of the
 /// data to be published under a given [`Topic`].
@@ -242,6 +243,22 @@ where
     }
 }
 
+impl<Foo> DataWriter<Foo>
+where
+    Foo: DdsSerialize + Send + Sync + 'static,
+{
+    /// This operation performs the same function as [`DataWriter::write`] and additionally hands the value itself to the
+    /// readers of the participants of this application when intraprocess delivery is enabled in the
+    /// [`DustDdsConfiguration`](crate::configuration::DustDdsConfiguration). Those readers get the value with
+    /// [`Sample::shared_data`](crate::subscription::data_reader::Sample::shared_data) without deserializing it.
+    /// The value is still serialized since the instance is identified by its serialized key and the history of the
+    /// [`DataWriter`] serves the remote and late-joining readers.
+    #[tracing::instrument(skip(self, data))]
+    pub fn write_shared(&self, data: Arc<Foo>, handle: Option<InstanceHandle>) -> DdsResult<()> {
+        block_on(self.writer_async.write_shared(data, handle))
+    }
+}
+
 impl<Foo> DataWriter<Foo> {
     /// This operation blocks the calling thread until either all data written by the [`DataWriter`] is acknowledged by all
     /// matched [`DataReader`](crate::subscription::data_reader::DataReader) entities that have
@@ -1,8 +1,11 @@
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
     dds_async::{data_reader::DataReaderAsync, data_reader_listener::DataReaderListenerAsync},
+    implementation::domain_participant_backend::entities::{
+        data_reader::SampleData, data_writer::SharedValue,
+    },
     infrastructure::{
-        condition::StatusCondition,
+        condition::{QueryCondition, ReadCondition, StatusCondition},
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind},
@@ -17,7 +20,7 @@ use crate::{
     topic_definition::{topic::Topic, type_support::DdsDeserialize},
 };
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
 use super::{
     sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
@@ -25,25 +28,49 @@ use super::{
 };
 
 /// A [`Sample`] contains the data and [`SampleInfo`] read by the [`DataReader`].
-#[derive(Debug, PartialEq, Eq)]
 pub struct Sample<Foo> {
     /// Data received by the [`DataReader`]. A sample might contain no valid data in which case this field is [`None`].
     data: Option<Arc<[u8]>>,
+    /// Value shared by the writer of this application which wrote the data, if any.
+    shared_value: Option<SharedValue>,
     /// Information of the sample received by the [`DataReader`].
     sample_info: SampleInfo,
     phantom: PhantomData<Foo>,
 }
 
 impl<Foo> Sample<Foo> {
-    pub(crate) fn new(data: Option<Arc<[u8]>>, sample_info: SampleInfo) -> Self {
+    pub(crate) fn new(data: Option<SampleData>, sample_info: SampleInfo) -> Self {
+        let (data, shared_value) = match data {
+            Some(data) => (Some(data.serialized_data), data.shared_value),
+            None => (None, None),
+        };
         Self {
             data,
+            shared_value,
             sample_info,
             phantom: PhantomData,
         }
     }
 }
 
+// The shared value is the same data as the serialized one so it is left out
+impl<Foo> Debug for Sample<Foo> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sample")
+            .field("data", &self.data)
+            .field("sample_info", &self.sample_info)
+            .finish()
+    }
+}
+
+impl<Foo> PartialEq for Sample<Foo> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.sample_info == other.sample_info
+    }
+}
+
+impl<Foo> Eq for Sample<Foo> {}
+
 impl<'de, Foo> Sample<Foo>
 where
     Foo: DdsDeserialize<'de>,
@@ -57,6 +84,28 @@ where
     }
 }
 
+impl<Foo> Sample<Foo>
+where
+    Foo: for<'de> DdsDeserialize<'de> + Send + Sync + 'static,
+{
+    /// Get the Foo value associated with this sample. The value written with
+    /// [`DataWriter::write_shared`](crate::publication::data_writer::DataWriter::write_shared) by a writer of
+    /// this application is returned as is instead of being deserialized.
+    pub fn shared_data(&self) -> DdsResult<Arc<Foo>> {
+        if let Some(value) = self
+            .shared_value
+            .clone()
+            .and_then(|v| v.downcast::<Foo>().ok())
+        {
+            return Ok(value);
+        }
+        match self.data.as_ref() {
+            Some(data) => Ok(Arc::new(Foo::deserialize_data(data.as_ref())?)),
+            None => Err(DdsError::NoData),
+        }
+    }
+}
+
 impl<Foo> Sample<Foo> {
     /// Get the sample info associated with this sample.
     pub fn sample_info(&self) -> SampleInfo {
@@ -163,6 +212,39 @@ impl<Foo> DataReader<Foo> {
         )
     }
 
+    /// This operation accesses a collection of [`Sample`] from the [`DataReader`]. The behavior is identical to
+    /// [`DataReader::read`] except that all returned samples satisfy the specified condition. In other words, on success all returned
+    /// samples belong to the instances with instance states, view states and sample states matching the masks of the
+    /// [`ReadCondition`] and, if the condition is a [`QueryCondition`], the samples also satisfy its query.
+    /// If the [`ReadCondition`] is not attached to this [`DataReader`] the operation will fail and return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn read_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &impl AsRef<ReadCondition>,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(
+            self.reader_async
+                .read_w_condition(max_samples, a_condition.as_ref().condition_async()),
+        )
+    }
+
+    /// This operation accesses a collection of [`Sample`] from the [`DataReader`] and removes them from the [`DataReader`].
+    /// The behavior is identical to [`DataReader::read_w_condition`] except that the samples are 'taken' from the [`DataReader`]
+    /// such that they are no longer accessible via subsequent 'read' or 'take' operations.
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn take_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &impl AsRef<ReadCondition>,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(
+            self.reader_async
+                .take_w_condition(max_samples, a_condition.as_ref().condition_async()),
+        )
+    }
+
     /// This operation reads the next, non-previously accessed [`Sample`] value from the [`DataReader`].
     /// The implied order among the samples stored in the [`DataReader`] is the same as for the [`DataReader::read`]
     /// operation. This operation is semantically equivalent to the read operation where the input Data sequence has
@@ -320,6 +402,60 @@ impl<Foo> DataReader<Foo> {
     pub fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
         block_on(self.reader_async.lookup_instance(instance))
     }
+
+    /// This operation creates a [`ReadCondition`]. The returned [`ReadCondition`] will be attached and belong to the [`DataReader`].
+    /// The *trigger_value* of the [`ReadCondition`] is [`true`] when the [`DataReader`] contains at least one sample matching the
+    /// `sample_states`, `view_states` and `instance_states`.
+    #[tracing::instrument(skip(self))]
+    pub fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadCondition> {
+        block_on(self.reader_async.create_readcondition(
+            sample_states,
+            view_states,
+            instance_states,
+        ))
+        .map(ReadCondition::new)
+    }
+
+    /// This operation creates a [`QueryCondition`]. The returned [`QueryCondition`] will be attached and belong to the [`DataReader`].
+    /// The `query_expression` uses the same syntax as the `filter_expression` of a
+    /// [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic) and `query_parameters`
+    /// give values to its "%n" tokens. If the `query_expression` is not valid for the type of the [`DataReader`] or the number of
+    /// parameters is not enough, the operation fails and returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn create_querycondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query_expression: &str,
+        query_parameters: &[String],
+    ) -> DdsResult<QueryCondition> {
+        block_on(self.reader_async.create_querycondition(
+            sample_states,
+            view_states,
+            instance_states,
+            query_expression,
+            query_parameters,
+        ))
+        .map(QueryCondition::new)
+    }
+
+    /// This operation deletes a [`ReadCondition`] or [`QueryCondition`] attached to the [`DataReader`].
+    /// The deletion of a condition that was not created by this [`DataReader`] fails and returns
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// A [`DataReader`] can not be deleted while it still has attached conditions.
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn delete_readcondition(&self, a_condition: &impl AsRef<ReadCondition>) -> DdsResult<()> {
+        block_on(
+            self.reader_async
+                .delete_readcondition(a_condition.as_ref().condition_async()),
+        )
+    }
 }
 
 impl<Foo> DataReader<Foo> {
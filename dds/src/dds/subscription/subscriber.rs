@@ -12,11 +12,13 @@ use crate::{
         status::{SampleLostStatus, StatusKind},
     },
     runtime::executor::block_on,
-    topic_definition::topic::Topic,
+    topic_definition::topic::TopicDescription,
 };
 
 use super::{
-    data_reader::DataReader, data_reader_listener::DataReaderListener,
+    data_reader::DataReader,
+    data_reader_listener::DataReaderListener,
+    sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
     subscriber_listener::SubscriberListener,
 };
 
@@ -41,9 +43,9 @@ impl Subscriber {
 
 impl Subscriber {
     /// This operation creates a [`DataReader`]. The returned [`DataReader`] will be attached and belong to the [`Subscriber`].
-    /// The [`DataReader`] returned by this operation has an associated [`Topic`] and a type `Foo`.
-    /// The [`Topic`] passed to this operation must have been created from the same [`DomainParticipant`] that was used to create this
-    /// [`Subscriber`]. If the [`Topic`] was created from a different [`DomainParticipant`], the operation will fail and
+    /// The [`DataReader`] returned by this operation has an associated [`Topic`](crate::topic_definition::topic::Topic) and a type `Foo`.
+    /// The [`Topic`](crate::topic_definition::topic::Topic) passed to this operation must have been created from the same [`DomainParticipant`] that was used to create this
+    /// [`Subscriber`]. If the [`Topic`](crate::topic_definition::topic::Topic) was created from a different [`DomainParticipant`], the operation will fail and
     /// return a [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError). In case of failure, the operation
     /// will return an error and no writer will be created.
     ///
@@ -52,14 +54,17 @@ impl Subscriber {
     /// [`DataReaderQos`] by means of the operation [`Subscriber::get_default_datareader_qos`] and using the resulting qos
     /// to create the [`DataReader`]. A common application pattern to construct the [`DataReaderQos`] to ensure consistency with the
     /// associated [`TopicQos`] is to:
-    /// 1. Retrieve the QoS policies on the associated [`Topic`] by means of the [`Topic::get_qos`] operation.
+    /// 1. Retrieve the QoS policies on the associated [`Topic`](crate::topic_definition::topic::Topic) by means of the [`Topic::get_qos`](crate::topic_definition::topic::Topic::get_qos) operation.
     /// 2. Retrieve the default [`DataReaderQos`] qos by means of the [`Subscriber::get_default_datareader_qos`] operation.
     /// 3. Combine those two qos policies using the [`Subscriber::copy_from_topic_qos`] and selectively modify policies as desired and
     /// use the resulting [`DataReaderQos`] to construct the [`DataReader`].
+    ///
+    /// The [`DataReader`] can also be created on a [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic),
+    /// in which case it is associated with the related [`Topic`](crate::topic_definition::topic::Topic) and only receives the samples which pass the filter.
     #[tracing::instrument(skip(self, a_topic, a_listener))]
     pub fn create_datareader<'a, Foo>(
         &self,
-        a_topic: &Topic,
+        a_topic: &dyn TopicDescription,
         qos: QosKind<DataReaderQos>,
         a_listener: Option<Box<dyn DataReaderListener<'a, Foo = Foo> + Send + 'a>>,
         mask: &[StatusKind],
@@ -69,7 +74,7 @@ impl Subscriber {
     {
         block_on(
             self.subscriber_async.create_datareader::<Foo>(
-                a_topic.topic_async(),
+                a_topic.topic_description_async(),
                 qos,
                 a_listener.map::<Box<dyn DataReaderListenerAsync<Foo = Foo> + Send + 'a>, _>(|b| {
                     Box::new(b)
@@ -91,7 +96,7 @@ impl Subscriber {
         )
     }
 
-    /// This operation retrieves a previously created [`DataReader`] belonging to the [`Subscriber`] that is attached to a [`Topic`].
+    /// This operation retrieves a previously created [`DataReader`] belonging to the [`Subscriber`] that is attached to a [`Topic`](crate::topic_definition::topic::Topic).
     /// If no such [`DataReader`] exists, the operation will succeed but return [`None`].
     /// If multiple [`DataReader`] attached to the [`Subscriber`] satisfy this condition, then the operation will return one of them. It is not
     /// specified which one.
@@ -104,6 +109,51 @@ impl Subscriber {
         )
     }
 
+    /// This operation indicates that the application is about to access the data samples in any of the [`DataReader`] objects attached to
+    /// the [`Subscriber`].
+    /// The application is required to use this operation only if [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy)
+    /// of the [`Subscriber`] to which the [`DataReader`] belongs has the `access_scope` set to
+    /// [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind). In the
+    /// aforementioned case, the operation [`Subscriber::begin_access`] must be called prior to calling any of the sample-accessing operations,
+    /// namely: [`Subscriber::get_datareaders`], [`DataReader::read`] and [`DataReader::take`]. Otherwise the sample-accessing operations
+    /// may not observe a consistent view of the data: while the access is in progress, the samples received by the [`Subscriber`] are
+    /// held back and only added to its [`DataReader`] objects after [`Subscriber::end_access`].
+    /// Calls to [`Subscriber::begin_access`] may be nested and must be matched by the same number of calls to [`Subscriber::end_access`].
+    #[tracing::instrument(skip(self))]
+    pub fn begin_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.begin_access())
+    }
+
+    /// This operation indicates that the application has finished accessing the data samples in [`DataReader`] objects managed by the
+    /// [`Subscriber`].
+    /// This operation must be used to 'close' a corresponding [`Subscriber::begin_access`]. If there is no matching call to
+    /// [`Subscriber::begin_access`] the operation will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn end_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.end_access())
+    }
+
+    /// This operation returns the instance handles of the [`DataReader`] objects that contain samples with the specified `sample_states`,
+    /// `view_states`, and `instance_states`. The handles can be compared with [`DataReader::get_instance_handle`] to find the corresponding
+    /// [`DataReader`].
+    /// If the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) of the [`Subscriber`] has `ordered_access`
+    /// set to [`true`] and `access_scope` set to [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind),
+    /// the returned list contains the handle of a [`DataReader`] once for every sample it holds, in the order in which the samples were
+    /// written by their [`Publisher`](crate::publication::publisher::Publisher). The application should then access one sample from each
+    /// [`DataReader`] in the order of the list. Otherwise each [`DataReader`] appears only once and the order of the list is not specified.
+    #[tracing::instrument(skip(self))]
+    pub fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<InstanceHandle>> {
+        block_on(
+            self.subscriber_async
+                .get_datareaders(sample_states, view_states, instance_states),
+        )
+    }
+
     /// This operation invokes the operation [`DataReaderListener::on_data_available`] on the listener objects attached to contained [`DataReader`]
     /// entities with a [`StatusKind::DataAvailable`] that is considered changed.
     /// This operation is typically invoked from the [`SubscriberListener::on_data_on_readers`] operation. That way the
@@ -158,8 +208,8 @@ impl Subscriber {
 
     /// This operation copies the policies in the `a_topic_qos` to the corresponding policies in the `a_datareader_qos`.
     /// This is a *convenience* operation most useful in combination with the operations [`Subscriber::get_default_datareader_qos`] and
-    /// [`Topic::get_qos`]. This operation can be used to merge the [`DataReader`] default qos policies with the
-    /// corresponding ones on the [`Topic`]. The resulting qos can then be used to create a new [`DataReader`], or set its qos.
+    /// [`Topic::get_qos`](crate::topic_definition::topic::Topic::get_qos). This operation can be used to merge the [`DataReader`] default qos policies with the
+    /// corresponding ones on the [`Topic`](crate::topic_definition::topic::Topic). The resulting qos can then be used to create a new [`DataReader`], or set its qos.
     /// This operation does not check the resulting `a_datareader_qos` for consistency. This is because the merged `a_datareader_qos`
     /// may not be the final one, as the application can still modify some policies prior to applying the policies to the [`DataReader`].
     #[tracing::instrument]
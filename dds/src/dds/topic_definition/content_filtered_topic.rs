@@ -0,0 +1,115 @@
+use crate::{
+    dds_async::{content_filtered_topic::ContentFilteredTopicAsync, topic::TopicDescriptionAsync},
+    domain::domain_participant::DomainParticipant,
+    infrastructure::error::DdsResult,
+    runtime::executor::block_on,
+};
+
+use super::topic::{Topic, TopicDescription};
+
+/// [`ContentFilteredTopic`] is a specialization of [`TopicDescription`] that allows for content-based subscriptions.
+/// It describes a more sophisticated subscription that indicates the subscriber does not want to necessarily see all values of each
+/// instance published under the [`Topic`]. Rather, it wants to see only the values whose contents satisfy certain criteria. This class
+/// therefore can be used to request content-based subscriptions.
+/// The selection of the content is done using the `filter_expression` with parameters `expression_parameters`.
+/// - The `filter_expression` attribute is a string that specifies the criteria to select the data samples of interest. It is similar to
+///   the WHERE part of an SQL clause, e.g. `"id = %0 AND value > 10.5"`, and the members of the type are referred to by their name
+///   (nested members as `member.submember`).
+/// - The `expression_parameters` attribute is a sequence of strings that give values to the 'parameters' (i.e., "%n" tokens) in
+///   the `filter_expression`. The number of supplied parameters must fit with the requested values in the `filter_expression` (i.e.,
+///   the number of %n tokens).
+pub struct ContentFilteredTopic {
+    content_filtered_topic_async: ContentFilteredTopicAsync,
+}
+
+impl ContentFilteredTopic {
+    pub(crate) fn new(content_filtered_topic_async: ContentFilteredTopicAsync) -> Self {
+        Self {
+            content_filtered_topic_async,
+        }
+    }
+
+    pub(crate) fn content_filtered_topic_async(&self) -> &ContentFilteredTopicAsync {
+        &self.content_filtered_topic_async
+    }
+}
+
+impl ContentFilteredTopic {
+    /// This operation returns the [`Topic`] associated with the [`ContentFilteredTopic`]. That is, the [`Topic`] specified when the
+    /// [`ContentFilteredTopic`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_related_topic(&self) -> Topic {
+        Topic::new(self.content_filtered_topic_async.get_related_topic())
+    }
+
+    /// This operation returns the `filter_expression` associated with the [`ContentFilteredTopic`]. That is, the expression specified
+    /// when the [`ContentFilteredTopic`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_filter_expression(&self) -> String {
+        self.content_filtered_topic_async.get_filter_expression()
+    }
+
+    /// This operation returns the `expression_parameters` associated with the [`ContentFilteredTopic`]. That is, the parameters
+    /// specified on the last successful call to [`ContentFilteredTopic::set_expression_parameters`], or if
+    /// [`ContentFilteredTopic::set_expression_parameters`] was never called, the parameters specified when the [`ContentFilteredTopic`]
+    /// was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_expression_parameters(&self) -> DdsResult<Vec<String>> {
+        block_on(
+            self.content_filtered_topic_async
+                .get_expression_parameters(),
+        )
+    }
+
+    /// This operation changes the `expression_parameters` associated with the [`ContentFilteredTopic`]. The new parameters apply
+    /// to the samples received after the operation by the [`DataReader`](crate::subscription::data_reader::DataReader) objects
+    /// created on this [`ContentFilteredTopic`].
+    /// If the number of parameters is not enough for the `filter_expression` the operation fails and returns
+    /// [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn set_expression_parameters(&self, expression_parameters: &[String]) -> DdsResult<()> {
+        block_on(
+            self.content_filtered_topic_async
+                .set_expression_parameters(expression_parameters),
+        )
+    }
+}
+
+/// This implementation block represents the TopicDescription operations for the [`ContentFilteredTopic`].
+impl ContentFilteredTopic {
+    /// This operation returns the [`DomainParticipant`] to which the [`ContentFilteredTopic`] belongs.
+    #[tracing::instrument(skip(self))]
+    pub fn get_participant(&self) -> DomainParticipant {
+        DomainParticipant::new(self.content_filtered_topic_async.get_participant())
+    }
+
+    /// The name of the type of the related [`Topic`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_type_name(&self) -> String {
+        self.content_filtered_topic_async.get_type_name()
+    }
+
+    /// The name used to create the [`ContentFilteredTopic`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_name(&self) -> String {
+        self.content_filtered_topic_async.get_name()
+    }
+}
+
+impl TopicDescription for ContentFilteredTopic {
+    fn get_participant(&self) -> DomainParticipant {
+        self.get_participant()
+    }
+
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn topic_description_async(&self) -> &dyn TopicDescriptionAsync {
+        &self.content_filtered_topic_async
+    }
+}
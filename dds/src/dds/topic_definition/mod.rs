@@ -1,3 +1,6 @@
+/// Contains the [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic).
+pub mod content_filtered_topic;
+
 /// Contains the [`Topic`](crate::topic_definition::topic::Topic) and any related objects.
 pub mod topic;
 
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    dds_async::topic::TopicAsync,
+    dds_async::topic::{TopicAsync, TopicDescriptionAsync},
     domain::domain_participant::DomainParticipant,
     infrastructure::{
         condition::StatusCondition,
@@ -16,6 +16,23 @@ use crate::{
 
 use super::topic_listener::TopicListener;
 
+/// [`TopicDescription`] represents the fact that both publications and subscriptions are tied to a single data-type. It is
+/// the common interface of [`Topic`] and [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic)
+/// which allows either of them to be used to create a [`DataReader`](crate::subscription::data_reader::DataReader).
+pub trait TopicDescription {
+    /// This operation returns the [`DomainParticipant`] to which the topic description belongs.
+    fn get_participant(&self) -> DomainParticipant;
+
+    /// The name of the type used to create the topic description.
+    fn get_type_name(&self) -> String;
+
+    /// The name used to create the topic description.
+    fn get_name(&self) -> String;
+
+    #[doc(hidden)]
+    fn topic_description_async(&self) -> &dyn TopicDescriptionAsync;
+}
+
 /// The [`Topic`] represents the fact that both publications and subscriptions are tied to a single data-type. Its attributes
 /// `type_name` defines a unique resulting type for the publication or the subscription. It has also a `name` that allows it to
 /// be retrieved locally.
@@ -159,6 +176,24 @@ impl Topic {
     }
 }
 
+impl TopicDescription for Topic {
+    fn get_participant(&self) -> DomainParticipant {
+        self.get_participant()
+    }
+
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn topic_description_async(&self) -> &dyn TopicDescriptionAsync {
+        &self.topic_async
+    }
+}
+
 impl Topic {
     #[doc(hidden)]
     #[tracing::instrument(skip(self))]
@@ -1,11 +1,25 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::Waker,
+};
+
 use crate::{
-    implementation::status_condition::status_condition_actor::{self, StatusConditionActor},
-    infrastructure::{error::DdsResult, status::StatusKind},
+    implementation::{
+        domain_participant_backend::{
+            domain_participant_actor::DomainParticipantActor, services::data_reader_service,
+        },
+        status_condition::status_condition_actor::{self, StatusConditionActor},
+    },
+    infrastructure::{error::DdsResult, instance::InstanceHandle, status::StatusKind},
     runtime::actor::ActorAddress,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
 };
 
 /// Async version of [`StatusCondition`](crate::infrastructure::condition::StatusCondition).
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct StatusConditionAsync {
     address: ActorAddress<StatusConditionActor>,
 }
@@ -57,4 +71,258 @@ impl StatusConditionAsync {
             .receive_reply()
             .await)
     }
+
+    pub(crate) async fn register_waiter(&self, waker: Waker) -> DdsResult<()> {
+        register_status_condition_waiter(&self.address, waker).await
+    }
+}
+
+async fn register_status_condition_waiter(
+    address: &ActorAddress<StatusConditionActor>,
+    waker: Waker,
+) -> DdsResult<()> {
+    address
+        .send_actor_mail(status_condition_actor::RegisterStatusConditionWaiter { waker })?
+        .receive_reply()
+        .await;
+    Ok(())
+}
+
+/// Async version of [`GuardCondition`](crate::infrastructure::condition::GuardCondition).
+#[derive(Clone, Default)]
+pub struct GuardConditionAsync {
+    trigger_value: Arc<AtomicBool>,
+    waiter_wakers: Arc<Mutex<Vec<Waker>>>,
+}
+
+impl GuardConditionAsync {
+    /// Create a new [`GuardConditionAsync`]
+    #[tracing::instrument]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for GuardConditionAsync {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.trigger_value, &other.trigger_value)
+    }
+}
+
+impl Eq for GuardConditionAsync {}
+
+impl GuardConditionAsync {
+    /// Async version of [`set_trigger_value`](crate::infrastructure::condition::GuardCondition::set_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn set_trigger_value(&self, value: bool) -> DdsResult<()> {
+        self.trigger_value.store(value, Ordering::Release);
+        let waiter_wakers = std::mem::take(
+            &mut *self
+                .waiter_wakers
+                .lock()
+                .expect("Mutex should not be poisoned"),
+        );
+        for waker in waiter_wakers {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    /// Async version of [`get_trigger_value`](crate::infrastructure::condition::GuardCondition::get_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trigger_value(&self) -> DdsResult<bool> {
+        Ok(self.trigger_value.load(Ordering::Acquire))
+    }
+
+    pub(crate) fn register_waiter(&self, waker: Waker) {
+        let mut waiter_wakers = self
+            .waiter_wakers
+            .lock()
+            .expect("Mutex should not be poisoned");
+        if !waiter_wakers.iter().any(|w| w.will_wake(&waker)) {
+            waiter_wakers.push(waker);
+        }
+    }
+}
+
+/// Async version of [`ReadCondition`](crate::infrastructure::condition::ReadCondition).
+#[derive(Clone, PartialEq, Eq)]
+pub struct ReadConditionAsync {
+    handle: InstanceHandle,
+    participant_address: ActorAddress<DomainParticipantActor>,
+    subscriber_handle: InstanceHandle,
+    data_reader_handle: InstanceHandle,
+    data_reader_status_condition_address: ActorAddress<StatusConditionActor>,
+    sample_state_mask: Vec<SampleStateKind>,
+    view_state_mask: Vec<ViewStateKind>,
+    instance_state_mask: Vec<InstanceStateKind>,
+}
+
+impl ReadConditionAsync {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        handle: InstanceHandle,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+        data_reader_status_condition_address: ActorAddress<StatusConditionActor>,
+        sample_state_mask: Vec<SampleStateKind>,
+        view_state_mask: Vec<ViewStateKind>,
+        instance_state_mask: Vec<InstanceStateKind>,
+    ) -> Self {
+        Self {
+            handle,
+            participant_address,
+            subscriber_handle,
+            data_reader_handle,
+            data_reader_status_condition_address,
+            sample_state_mask,
+            view_state_mask,
+            instance_state_mask,
+        }
+    }
+
+    pub(crate) fn handle(&self) -> InstanceHandle {
+        self.handle
+    }
+
+    pub(crate) fn data_reader_handle(&self) -> InstanceHandle {
+        self.data_reader_handle
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_sample_state_mask`](crate::infrastructure::condition::ReadCondition::get_sample_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.sample_state_mask.clone()
+    }
+
+    /// Async version of [`get_view_state_mask`](crate::infrastructure::condition::ReadCondition::get_view_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.view_state_mask.clone()
+    }
+
+    /// Async version of [`get_instance_state_mask`](crate::infrastructure::condition::ReadCondition::get_instance_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.instance_state_mask.clone()
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_trigger_value`](crate::infrastructure::condition::ReadCondition::get_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trigger_value(&self) -> DdsResult<bool> {
+        self.participant_address
+            .send_actor_mail(data_reader_service::GetReadConditionTriggerValue {
+                subscriber_handle: self.subscriber_handle,
+                data_reader_handle: self.data_reader_handle,
+                condition_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    // The samples and instances of the reader only change together with the
+    // communication states of its status condition
+    pub(crate) async fn register_waiter(&self, waker: Waker) -> DdsResult<()> {
+        register_status_condition_waiter(&self.data_reader_status_condition_address, waker).await
+    }
+}
+
+impl AsRef<ReadConditionAsync> for ReadConditionAsync {
+    fn as_ref(&self) -> &ReadConditionAsync {
+        self
+    }
+}
+
+/// Async version of [`QueryCondition`](crate::infrastructure::condition::QueryCondition).
+#[derive(Clone, PartialEq, Eq)]
+pub struct QueryConditionAsync {
+    read_condition: ReadConditionAsync,
+    query_expression: String,
+}
+
+impl QueryConditionAsync {
+    pub(crate) fn new(read_condition: ReadConditionAsync, query_expression: String) -> Self {
+        Self {
+            read_condition,
+            query_expression,
+        }
+    }
+}
+
+impl QueryConditionAsync {
+    /// Async version of [`get_query_expression`](crate::infrastructure::condition::QueryCondition::get_query_expression).
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_expression(&self) -> String {
+        self.query_expression.clone()
+    }
+
+    /// Async version of [`get_query_parameters`](crate::infrastructure::condition::QueryCondition::get_query_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_query_parameters(&self) -> DdsResult<Vec<String>> {
+        self.read_condition
+            .participant_address
+            .send_actor_mail(data_reader_service::GetQueryParameters {
+                subscriber_handle: self.read_condition.subscriber_handle,
+                data_reader_handle: self.read_condition.data_reader_handle,
+                condition_handle: self.read_condition.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`set_query_parameters`](crate::infrastructure::condition::QueryCondition::set_query_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn set_query_parameters(&self, query_parameters: &[String]) -> DdsResult<()> {
+        self.read_condition
+            .participant_address
+            .send_actor_mail(data_reader_service::SetQueryParameters {
+                subscriber_handle: self.read_condition.subscriber_handle,
+                data_reader_handle: self.read_condition.data_reader_handle,
+                condition_handle: self.read_condition.handle,
+                query_parameters: query_parameters.to_vec(),
+            })?
+            .receive_reply()
+            .await
+    }
+}
+
+impl QueryConditionAsync {
+    /// Async version of [`get_sample_state_mask`](crate::infrastructure::condition::QueryCondition::get_sample_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.read_condition.get_sample_state_mask()
+    }
+
+    /// Async version of [`get_view_state_mask`](crate::infrastructure::condition::QueryCondition::get_view_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.read_condition.get_view_state_mask()
+    }
+
+    /// Async version of [`get_instance_state_mask`](crate::infrastructure::condition::QueryCondition::get_instance_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.read_condition.get_instance_state_mask()
+    }
+
+    /// Async version of [`get_trigger_value`](crate::infrastructure::condition::QueryCondition::get_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trigger_value(&self) -> DdsResult<bool> {
+        self.read_condition.get_trigger_value().await
+    }
+
+    pub(crate) async fn register_waiter(&self, waker: Waker) -> DdsResult<()> {
+        self.read_condition.register_waiter(waker).await
+    }
+}
+
+impl AsRef<ReadConditionAsync> for QueryConditionAsync {
+    fn as_ref(&self) -> &ReadConditionAsync {
+        &self.read_condition
+    }
 }
@@ -0,0 +1,116 @@
+use super::{
+    domain_participant::DomainParticipantAsync,
+    topic::{TopicAsync, TopicDescriptionAsync},
+};
+use crate::{
+    implementation::domain_participant_backend::services::content_filtered_topic_service,
+    infrastructure::error::DdsResult,
+};
+
+/// Async version of [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic).
+#[derive(Clone)]
+pub struct ContentFilteredTopicAsync {
+    topic_name: String,
+    filter_expression: String,
+    related_topic: TopicAsync,
+}
+
+impl ContentFilteredTopicAsync {
+    pub(crate) fn new(
+        topic_name: String,
+        filter_expression: String,
+        related_topic: TopicAsync,
+    ) -> Self {
+        Self {
+            topic_name,
+            filter_expression,
+            related_topic,
+        }
+    }
+}
+
+impl ContentFilteredTopicAsync {
+    /// Async version of [`get_related_topic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_related_topic).
+    #[tracing::instrument(skip(self))]
+    pub fn get_related_topic(&self) -> TopicAsync {
+        self.related_topic.clone()
+    }
+
+    /// Async version of [`get_filter_expression`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_filter_expression).
+    #[tracing::instrument(skip(self))]
+    pub fn get_filter_expression(&self) -> String {
+        self.filter_expression.clone()
+    }
+
+    /// Async version of [`get_expression_parameters`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_expression_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_expression_parameters(&self) -> DdsResult<Vec<String>> {
+        self.related_topic
+            .get_participant()
+            .participant_address()
+            .send_actor_mail(content_filtered_topic_service::GetExpressionParameters {
+                topic_name: self.topic_name.clone(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`set_expression_parameters`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::set_expression_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn set_expression_parameters(
+        &self,
+        expression_parameters: &[String],
+    ) -> DdsResult<()> {
+        let participant_address = self
+            .related_topic
+            .get_participant()
+            .participant_address()
+            .clone();
+        participant_address
+            .send_actor_mail(content_filtered_topic_service::SetExpressionParameters {
+                topic_name: self.topic_name.clone(),
+                expression_parameters: expression_parameters.to_vec(),
+                participant_address: participant_address.clone(),
+            })?
+            .receive_reply()
+            .await
+    }
+}
+
+impl ContentFilteredTopicAsync {
+    /// Async version of [`get_participant`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_participant).
+    #[tracing::instrument(skip(self))]
+    pub fn get_participant(&self) -> DomainParticipantAsync {
+        self.related_topic.get_participant()
+    }
+
+    /// Async version of [`get_type_name`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_type_name).
+    #[tracing::instrument(skip(self))]
+    pub fn get_type_name(&self) -> String {
+        self.related_topic.get_type_name()
+    }
+
+    /// Async version of [`get_name`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_name).
+    #[tracing::instrument(skip(self))]
+    pub fn get_name(&self) -> String {
+        self.topic_name.clone()
+    }
+}
+
+impl TopicDescriptionAsync for ContentFilteredTopicAsync {
+    fn get_participant(&self) -> DomainParticipantAsync {
+        self.get_participant()
+    }
+
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn topic(&self) -> TopicAsync {
+        self.related_topic.clone()
+    }
+}
@@ -1,8 +1,10 @@
 use tracing::warn;
 
 use super::{
-    condition::StatusConditionAsync, data_reader_listener::DataReaderListenerAsync,
-    subscriber::SubscriberAsync, topic::TopicAsync,
+    condition::{QueryConditionAsync, ReadConditionAsync, StatusConditionAsync},
+    data_reader_listener::DataReaderListenerAsync,
+    subscriber::SubscriberAsync,
+    topic::TopicAsync,
 };
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
@@ -14,7 +16,7 @@ use crate::{
         status_condition::status_condition_actor::StatusConditionActor,
     },
     infrastructure::{
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind},
         status::{
@@ -144,6 +146,66 @@ impl<Foo> DataReaderAsync<Foo> {
             .collect())
     }
 
+    /// Async version of [`read_w_condition`](crate::subscription::data_reader::DataReader::read_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn read_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &impl AsRef<ReadConditionAsync>,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        let a_condition = a_condition.as_ref();
+        if a_condition.data_reader_handle() != self.handle {
+            return Err(DdsError::PreconditionNotMet(
+                "Condition not created by this data reader".to_string(),
+            ));
+        }
+        let samples = self
+            .participant_address()
+            .send_actor_mail(data_reader_service::ReadWCondition {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+                max_samples,
+                condition_handle: a_condition.handle(),
+            })?
+            .receive_reply()
+            .await?;
+
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
+    /// Async version of [`take_w_condition`](crate::subscription::data_reader::DataReader::take_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn take_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &impl AsRef<ReadConditionAsync>,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        let a_condition = a_condition.as_ref();
+        if a_condition.data_reader_handle() != self.handle {
+            return Err(DdsError::PreconditionNotMet(
+                "Condition not created by this data reader".to_string(),
+            ));
+        }
+        let samples = self
+            .participant_address()
+            .send_actor_mail(data_reader_service::TakeWCondition {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+                max_samples,
+                condition_handle: a_condition.handle(),
+            })?
+            .receive_reply()
+            .await?;
+
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
     /// Async version of [`read_next_sample`](crate::subscription::data_reader::DataReader::read_next_sample).
     #[tracing::instrument(skip(self))]
     pub async fn read_next_sample(&self) -> DdsResult<Sample<Foo>> {
@@ -316,13 +378,109 @@ impl<Foo> DataReaderAsync<Foo> {
     pub async fn lookup_instance(&self, _instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
         todo!()
     }
+
+    /// Async version of [`create_readcondition`](crate::subscription::data_reader::DataReader::create_readcondition).
+    #[tracing::instrument(skip(self))]
+    pub async fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadConditionAsync> {
+        self.create_read_condition(sample_states, view_states, instance_states, None)
+            .await
+    }
+
+    /// Async version of [`create_querycondition`](crate::subscription::data_reader::DataReader::create_querycondition).
+    #[tracing::instrument(skip(self))]
+    pub async fn create_querycondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query_expression: &str,
+        query_parameters: &[String],
+    ) -> DdsResult<QueryConditionAsync> {
+        let read_condition = self
+            .create_read_condition(
+                sample_states,
+                view_states,
+                instance_states,
+                Some((query_expression.to_string(), query_parameters.to_vec())),
+            )
+            .await?;
+        Ok(QueryConditionAsync::new(
+            read_condition,
+            query_expression.to_string(),
+        ))
+    }
+
+    /// Async version of [`delete_readcondition`](crate::subscription::data_reader::DataReader::delete_readcondition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn delete_readcondition(
+        &self,
+        a_condition: &impl AsRef<ReadConditionAsync>,
+    ) -> DdsResult<()> {
+        let a_condition = a_condition.as_ref();
+        if a_condition.data_reader_handle() != self.handle {
+            return Err(DdsError::PreconditionNotMet(
+                "Condition not created by this data reader".to_string(),
+            ));
+        }
+        self.participant_address()
+            .send_actor_mail(data_reader_service::DeleteReadCondition {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+                condition_handle: a_condition.handle(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    async fn create_read_condition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query: Option<(String, Vec<String>)>,
+    ) -> DdsResult<ReadConditionAsync> {
+        let subscriber_handle = self.subscriber.get_instance_handle().await;
+        let handle = self
+            .participant_address()
+            .send_actor_mail(data_reader_service::CreateReadCondition {
+                subscriber_handle,
+                data_reader_handle: self.handle,
+                sample_states: sample_states.to_vec(),
+                view_states: view_states.to_vec(),
+                instance_states: instance_states.to_vec(),
+                query,
+            })?
+            .receive_reply()
+            .await?;
+        Ok(ReadConditionAsync::new(
+            handle,
+            self.participant_address().clone(),
+            subscriber_handle,
+            self.handle,
+            self.status_condition_address.clone(),
+            sample_states.to_vec(),
+            view_states.to_vec(),
+            instance_states.to_vec(),
+        ))
+    }
 }
 
 impl<Foo> DataReaderAsync<Foo> {
     /// Async version of [`get_liveliness_changed_status`](crate::subscription::data_reader::DataReader::get_liveliness_changed_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_liveliness_changed_status(&self) -> DdsResult<LivelinessChangedStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_reader_service::GetLivelinessChangedStatus {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_requested_deadline_missed_status`](crate::subscription::data_reader::DataReader::get_requested_deadline_missed_status).
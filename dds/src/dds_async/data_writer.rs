@@ -26,7 +26,7 @@ use crate::{
     runtime::actor::ActorAddress,
     topic_definition::type_support::DdsSerialize,
 };
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 /// Async version of [`DataWriter`](crate::publication::data_writer::DataWriter).
 pub struct DataWriterAsync<Foo> {
@@ -133,6 +133,7 @@ where
         let serialized_data = instance.serialize_data()?;
         self.participant_address()
             .send_actor_mail(data_writer_service::UnregisterInstance {
+                participant_address: self.participant_address().clone(),
                 publisher_handle: self.publisher.get_instance_handle().await,
                 data_writer_handle: self.handle,
                 serialized_data,
@@ -192,6 +193,7 @@ where
                 publisher_handle: self.publisher.get_instance_handle().await,
                 data_writer_handle: self.handle,
                 serialized_data,
+                shared_value: None,
                 timestamp,
             })?
             .receive_reply()
@@ -220,9 +222,37 @@ where
         let serialized_data = data.serialize_data()?;
         self.participant_address()
             .send_actor_mail(data_writer_service::DisposeWTimestamp {
+                participant_address: self.participant_address().clone(),
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+                serialized_data,
+                timestamp,
+            })?
+            .receive_reply()
+            .await
+    }
+}
+
+impl<Foo> DataWriterAsync<Foo>
+where
+    Foo: DdsSerialize + Send + Sync + 'static,
+{
+    /// Async version of [`write_shared`](crate::publication::data_writer::DataWriter::write_shared).
+    #[tracing::instrument(skip(self, data))]
+    pub async fn write_shared(&self, data: Arc<Foo>, handle: Option<InstanceHandle>) -> DdsResult<()> {
+        let timestamp = self
+            .get_publisher()
+            .get_participant()
+            .get_current_time()
+            .await?;
+        let serialized_data = data.serialize_data()?;
+        self.participant_address()
+            .send_actor_mail(data_writer_service::WriteWTimestamp {
+                participant_address: self.participant_address().clone(),
                 publisher_handle: self.publisher.get_instance_handle().await,
                 data_writer_handle: self.handle,
                 serialized_data,
+                shared_value: Some(data),
                 timestamp,
             })?
             .receive_reply()
@@ -249,7 +279,13 @@ impl<Foo> DataWriterAsync<Foo> {
     /// Async version of [`get_liveliness_lost_status`](crate::publication::data_writer::DataWriter::get_liveliness_lost_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_liveliness_lost_status(&self) -> DdsResult<LivelinessLostStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_writer_service::GetLivelinessLostStatus {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_offered_deadline_missed_status`](crate::publication::data_writer::DataWriter::get_offered_deadline_missed_status).
@@ -301,7 +337,14 @@ impl<Foo> DataWriterAsync<Foo> {
     /// Async version of [`assert_liveliness`](crate::publication::data_writer::DataWriter::assert_liveliness).
     #[tracing::instrument(skip(self))]
     pub async fn assert_liveliness(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_writer_service::AssertLiveliness {
+                participant_address: self.participant_address().clone(),
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_matched_subscription_data`](crate::publication::data_writer::DataWriter::get_matched_subscription_data).
@@ -1,7 +1,8 @@
 use super::{
-    condition::StatusConditionAsync, domain_participant_listener::DomainParticipantListenerAsync,
-    publisher::PublisherAsync, publisher_listener::PublisherListenerAsync,
-    subscriber::SubscriberAsync, subscriber_listener::SubscriberListenerAsync, topic::TopicAsync,
+    condition::StatusConditionAsync, content_filtered_topic::ContentFilteredTopicAsync,
+    domain_participant_listener::DomainParticipantListenerAsync, publisher::PublisherAsync,
+    publisher_listener::PublisherListenerAsync, subscriber::SubscriberAsync,
+    subscriber_listener::SubscriberListenerAsync, topic::TopicAsync,
     topic_listener::TopicListenerAsync,
 };
 use crate::{
@@ -191,6 +192,48 @@ impl DomainParticipantAsync {
             .send_actor_mail(domain_participant_service::DeleteUserDefinedTopic {
                 participant_handle: a_topic.get_participant().handle,
                 topic_name: a_topic.get_name(),
+                participant_address: self.participant_address.clone(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`create_contentfilteredtopic`](crate::domain::domain_participant::DomainParticipant::create_contentfilteredtopic).
+    #[tracing::instrument(skip(self, related_topic))]
+    pub async fn create_contentfilteredtopic(
+        &self,
+        name: &str,
+        related_topic: &TopicAsync,
+        filter_expression: &str,
+        expression_parameters: &[String],
+    ) -> DdsResult<ContentFilteredTopicAsync> {
+        self.participant_address
+            .send_actor_mail(domain_participant_service::CreateContentFilteredTopic {
+                name: name.to_string(),
+                related_topic_name: related_topic.get_name(),
+                filter_expression: filter_expression.to_string(),
+                expression_parameters: expression_parameters.to_vec(),
+            })?
+            .receive_reply()
+            .await?;
+
+        Ok(ContentFilteredTopicAsync::new(
+            name.to_string(),
+            filter_expression.to_string(),
+            related_topic.clone(),
+        ))
+    }
+
+    /// Async version of [`delete_contentfilteredtopic`](crate::domain::domain_participant::DomainParticipant::delete_contentfilteredtopic).
+    #[tracing::instrument(skip(self, a_contentfilteredtopic))]
+    pub async fn delete_contentfilteredtopic(
+        &self,
+        a_contentfilteredtopic: &ContentFilteredTopicAsync,
+    ) -> DdsResult<()> {
+        self.participant_address
+            .send_actor_mail(domain_participant_service::DeleteContentFilteredTopic {
+                participant_handle: a_contentfilteredtopic.get_participant().handle,
+                topic_name: a_contentfilteredtopic.get_name(),
             })?
             .receive_reply()
             .await
@@ -325,7 +368,12 @@ impl DomainParticipantAsync {
     /// Async version of [`assert_liveliness`](crate::domain::domain_participant::DomainParticipant::assert_liveliness).
     #[tracing::instrument(skip(self))]
     pub async fn assert_liveliness(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address
+            .send_actor_mail(domain_participant_service::AssertLiveliness {
+                participant_address: self.participant_address.clone(),
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`set_default_publisher_qos`](crate::domain::domain_participant::DomainParticipant::set_default_publisher_qos).
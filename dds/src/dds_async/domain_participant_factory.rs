@@ -7,6 +7,7 @@ use super::{
 use crate::{
     configuration::DustDdsConfiguration,
     domain::domain_participant_factory::DomainId,
+    durability::DurabilityStore,
     implementation::{
         domain_participant_backend::services::{discovery_service, domain_participant_service},
         domain_participant_factory::domain_participant_factory_actor::{
@@ -18,7 +19,11 @@ use crate::{
         qos::{DomainParticipantFactoryQos, DomainParticipantQos, QosKind},
         status::StatusKind,
     },
-    runtime::{actor::Actor, executor::Executor, timer::TimerDriver},
+    runtime::{
+        actor::Actor,
+        executor::Executor,
+        timer::{TimerDriver, TimerHandle},
+    },
     transport::factory::TransportParticipantFactory,
 };
 
@@ -116,6 +121,10 @@ impl DomainParticipantFactoryAsync {
         })
     }
 
+    pub(crate) fn timer_handle(&self) -> TimerHandle {
+        self.timer_driver.handle()
+    }
+
     /// Async version of [`lookup_participant`](crate::domain::domain_participant_factory::DomainParticipantFactory::lookup_participant).
     pub async fn lookup_participant(
         &self,
@@ -190,4 +199,32 @@ impl DomainParticipantFactoryAsync {
             .await;
         Ok(())
     }
+
+    /// Async version of [`set_transient_durability_store`](crate::domain::domain_participant_factory::DomainParticipantFactory::set_transient_durability_store).
+    pub async fn set_transient_durability_store(
+        &self,
+        store: Box<dyn DurabilityStore>,
+    ) -> DdsResult<()> {
+        self.domain_participant_factory_actor
+            .send_actor_mail(
+                domain_participant_factory_actor::SetTransientDurabilityStore { store },
+            )
+            .receive_reply()
+            .await;
+        Ok(())
+    }
+
+    /// Async version of [`set_persistent_durability_store`](crate::domain::domain_participant_factory::DomainParticipantFactory::set_persistent_durability_store).
+    pub async fn set_persistent_durability_store(
+        &self,
+        store: Box<dyn DurabilityStore>,
+    ) -> DdsResult<()> {
+        self.domain_participant_factory_actor
+            .send_actor_mail(
+                domain_participant_factory_actor::SetPersistentDurabilityStore { store },
+            )
+            .receive_reply()
+            .await;
+        Ok(())
+    }
 }
@@ -111,25 +111,45 @@ impl PublisherAsync {
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::suspend_publications).
     #[tracing::instrument(skip(self))]
     pub async fn suspend_publications(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(publisher_service::SuspendPublications {
+                publisher_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::resume_publications).
     #[tracing::instrument(skip(self))]
     pub async fn resume_publications(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(publisher_service::ResumePublications {
+                publisher_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::begin_coherent_changes).
     #[tracing::instrument(skip(self))]
     pub async fn begin_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(publisher_service::BeginCoherentChanges {
+                publisher_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::end_coherent_changes).
     #[tracing::instrument(skip(self))]
     pub async fn end_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(publisher_service::EndCoherentChanges {
+                publisher_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::wait_for_acknowledgments).
@@ -1,7 +1,7 @@
 use super::{
     condition::StatusConditionAsync, data_reader::DataReaderAsync,
     data_reader_listener::DataReaderListenerAsync, domain_participant::DomainParticipantAsync,
-    subscriber_listener::SubscriberListenerAsync, topic::TopicAsync,
+    subscriber_listener::SubscriberListenerAsync, topic::TopicDescriptionAsync,
 };
 use crate::{
     implementation::{
@@ -18,6 +18,7 @@ use crate::{
         status::{SampleLostStatus, StatusKind},
     },
     runtime::actor::ActorAddress,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
 };
 
 /// Async version of [`Subscriber`](crate::subscription::subscriber::Subscriber).
@@ -51,7 +52,7 @@ impl SubscriberAsync {
     #[tracing::instrument(skip(self, a_topic, a_listener))]
     pub async fn create_datareader<'a, 'b, Foo>(
         &'a self,
-        a_topic: &'a TopicAsync,
+        a_topic: &'a dyn TopicDescriptionAsync,
         qos: QosKind<DataReaderQos>,
         a_listener: Option<Box<(dyn DataReaderListenerAsync<'b, Foo = Foo> + Send + 'b)>>,
         mask: &'a [StatusKind],
@@ -77,7 +78,7 @@ impl SubscriberAsync {
             guid,
             reader_status_condition_address,
             self.clone(),
-            a_topic.clone(),
+            a_topic.topic(),
         ))
     }
 
@@ -127,10 +128,58 @@ impl SubscriberAsync {
         }
     }
 
+    /// Async version of [`begin_access`](crate::subscription::subscriber::Subscriber::begin_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn begin_access(&self) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(subscriber_service::BeginAccess {
+                subscriber_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`end_access`](crate::subscription::subscriber::Subscriber::end_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn end_access(&self) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(subscriber_service::EndAccess {
+                participant_address: self.participant_address().clone(),
+                subscriber_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`get_datareaders`](crate::subscription::subscriber::Subscriber::get_datareaders).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<InstanceHandle>> {
+        self.participant_address()
+            .send_actor_mail(subscriber_service::GetDataReaders {
+                subscriber_handle: self.handle,
+                sample_states: sample_states.to_vec(),
+                view_states: view_states.to_vec(),
+                instance_states: instance_states.to_vec(),
+            })?
+            .receive_reply()
+            .await
+    }
+
     /// Async version of [`notify_datareaders`](crate::subscription::subscriber::Subscriber::notify_datareaders).
     #[tracing::instrument(skip(self))]
     pub async fn notify_datareaders(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(subscriber_service::NotifyDataReaders {
+                participant_address: self.participant_address().clone(),
+                subscriber_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_participant`](crate::subscription::subscriber::Subscriber::get_participant).
@@ -18,6 +18,21 @@ use crate::{
 };
 use std::sync::Arc;
 
+/// Async version of [`TopicDescription`](crate::topic_definition::topic::TopicDescription).
+pub trait TopicDescriptionAsync: Send + Sync {
+    /// Async version of [`get_participant`](crate::topic_definition::topic::TopicDescription::get_participant).
+    fn get_participant(&self) -> DomainParticipantAsync;
+
+    /// Async version of [`get_type_name`](crate::topic_definition::topic::TopicDescription::get_type_name).
+    fn get_type_name(&self) -> String;
+
+    /// Async version of [`get_name`](crate::topic_definition::topic::TopicDescription::get_name).
+    fn get_name(&self) -> String;
+
+    #[doc(hidden)]
+    fn topic(&self) -> TopicAsync;
+}
+
 /// Async version of [`Topic`](crate::topic_definition::topic::Topic).
 #[derive(Clone)]
 pub struct TopicAsync {
@@ -148,6 +163,24 @@ impl TopicAsync {
     }
 }
 
+impl TopicDescriptionAsync for TopicAsync {
+    fn get_participant(&self) -> DomainParticipantAsync {
+        self.get_participant()
+    }
+
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn topic(&self) -> TopicAsync {
+        self.clone()
+    }
+}
+
 impl TopicAsync {
     #[doc(hidden)]
     #[tracing::instrument(skip(self))]
@@ -1,15 +1,32 @@
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::{Poll, Waker},
+};
+
 use crate::infrastructure::{
     error::{DdsError, DdsResult},
     time::Duration,
 };
 
-use super::condition::StatusConditionAsync;
+use super::{
+    condition::{
+        GuardConditionAsync, QueryConditionAsync, ReadConditionAsync, StatusConditionAsync,
+    },
+    domain_participant_factory::DomainParticipantFactoryAsync,
+};
 
 /// Async version of [`Condition`](crate::infrastructure::wait_set::Condition).
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum ConditionAsync {
     /// Status condition variant
     StatusCondition(StatusConditionAsync),
+    /// Read condition variant
+    ReadCondition(ReadConditionAsync),
+    /// Query condition variant
+    QueryCondition(QueryConditionAsync),
+    /// Guard condition variant
+    GuardCondition(GuardConditionAsync),
 }
 
 impl ConditionAsync {
@@ -18,6 +35,21 @@ impl ConditionAsync {
     pub async fn get_trigger_value(&self) -> DdsResult<bool> {
         match self {
             ConditionAsync::StatusCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::ReadCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::QueryCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::GuardCondition(c) => c.get_trigger_value().await,
+        }
+    }
+
+    async fn register_waiter(&self, waker: Waker) -> DdsResult<()> {
+        match self {
+            ConditionAsync::StatusCondition(c) => c.register_waiter(waker).await,
+            ConditionAsync::ReadCondition(c) => c.register_waiter(waker).await,
+            ConditionAsync::QueryCondition(c) => c.register_waiter(waker).await,
+            ConditionAsync::GuardCondition(c) => {
+                c.register_waiter(waker);
+                Ok(())
+            }
         }
     }
 }
@@ -44,40 +76,67 @@ impl WaitSetAsync {
             ));
         };
 
-        // let timer_handle = self.conditions[0].timer_handle().clone();
-        let start = std::time::Instant::now();
-        while std::time::Instant::now().duration_since(start) < timeout.into() {
-            let mut finished = false;
+        let mut sleep = DomainParticipantFactoryAsync::get_instance()
+            .timer_handle()
+            .sleep(timeout.into());
+        loop {
+            // The waker is registered before reading the trigger values so that no
+            // change happening in between is missed
+            let waker = poll_fn(|cx| Poll::Ready(cx.waker().clone())).await;
+            for condition in &self.conditions {
+                condition.register_waiter(waker.clone()).await?;
+            }
+
             let mut trigger_conditions = Vec::new();
             for condition in &self.conditions {
                 if condition.get_trigger_value().await? {
                     trigger_conditions.push(condition.clone());
-                    finished = true;
                 }
             }
-
-            if finished {
+            if !trigger_conditions.is_empty() {
                 return Ok(trigger_conditions);
             }
-            // timer_handle
-            //     .sleep(std::time::Duration::from_millis(20))
-            //     .await;
-        }
 
-        Err(DdsError::Timeout)
+            // Suspend until one of the conditions notifies a change or the timeout elapses
+            let mut notified = false;
+            let timed_out = poll_fn(|cx| {
+                if Pin::new(&mut sleep).poll(cx).is_ready() {
+                    Poll::Ready(true)
+                } else if notified {
+                    Poll::Ready(false)
+                } else {
+                    notified = true;
+                    Poll::Pending
+                }
+            })
+            .await;
+            if timed_out {
+                return Err(DdsError::Timeout);
+            }
+        }
     }
 
     /// Async version of [`attach_condition`](crate::infrastructure::wait_set::WaitSet::attach_condition).
     #[tracing::instrument(skip(self, cond))]
     pub async fn attach_condition(&mut self, cond: ConditionAsync) -> DdsResult<()> {
-        self.conditions.push(cond);
+        if !self.conditions.contains(&cond) {
+            self.conditions.push(cond);
+        }
         Ok(())
     }
 
     /// Async version of [`detach_condition`](crate::infrastructure::wait_set::WaitSet::detach_condition).
-    #[tracing::instrument(skip(self, _cond))]
-    pub async fn detach_condition(&self, _cond: ConditionAsync) -> DdsResult<()> {
-        todo!()
+    #[tracing::instrument(skip(self, cond))]
+    pub async fn detach_condition(&mut self, cond: ConditionAsync) -> DdsResult<()> {
+        let index = self
+            .conditions
+            .iter()
+            .position(|c| c == &cond)
+            .ok_or_else(|| {
+                DdsError::PreconditionNotMet("Condition not attached to WaitSet".to_string())
+            })?;
+        self.conditions.remove(index);
+        Ok(())
     }
 
     /// Async version of [`get_conditions`](crate::infrastructure::wait_set::WaitSet::get_conditions).
@@ -1,8 +1,8 @@
 use crate::{
     dds_async::{data_reader::DataReaderAsync, data_reader_listener::DataReaderListenerAsync},
     infrastructure::status::{
-        RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-        SubscriptionMatchedStatus,
+        LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+        SampleRejectedStatus, SubscriptionMatchedStatus,
     },
     runtime::executor::block_on,
 };
@@ -31,6 +31,12 @@ pub trait AnyDataReaderListener: Send {
         the_reader: DataReaderAsync<()>,
         status: RequestedIncompatibleQosStatus,
     );
+
+    fn trigger_on_liveliness_changed(
+        &mut self,
+        the_reader: DataReaderAsync<()>,
+        status: LivelinessChangedStatus,
+    );
 }
 
 impl<'a, Foo> AnyDataReaderListener for Box<dyn DataReaderListenerAsync<'a, Foo = Foo> + Send + 'a>
@@ -71,4 +77,12 @@ where
     ) {
         block_on(self.on_requested_incompatible_qos(the_reader.change_foo_type(), status))
     }
+
+    fn trigger_on_liveliness_changed(
+        &mut self,
+        the_reader: DataReaderAsync<()>,
+        status: LivelinessChangedStatus,
+    ) {
+        block_on(self.on_liveliness_changed(the_reader.change_foo_type(), status))
+    }
 }
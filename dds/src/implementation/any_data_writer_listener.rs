@@ -1,7 +1,8 @@
 use crate::{
     dds_async::{data_writer::DataWriterAsync, data_writer_listener::DataWriterListenerAsync},
     infrastructure::status::{
-        OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus, PublicationMatchedStatus,
+        LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+        PublicationMatchedStatus,
     },
     runtime::executor::block_on,
 };
@@ -22,6 +23,11 @@ pub trait AnyDataWriterListener: Send {
         the_writer: DataWriterAsync<()>,
         status: OfferedDeadlineMissedStatus,
     );
+    fn trigger_on_liveliness_lost(
+        &mut self,
+        the_writer: DataWriterAsync<()>,
+        status: LivelinessLostStatus,
+    );
 }
 
 impl<'a, Foo> AnyDataWriterListener for Box<dyn DataWriterListenerAsync<'a, Foo = Foo> + Send + 'a>
@@ -51,4 +57,12 @@ where
     ) {
         block_on(self.on_offered_deadline_missed(the_writer.change_foo_type(), status))
     }
+
+    fn trigger_on_liveliness_lost(
+        &mut self,
+        the_writer: DataWriterAsync<()>,
+        status: LivelinessLostStatus,
+    ) {
+        block_on(self.on_liveliness_lost(the_writer.change_foo_type(), status))
+    }
 }
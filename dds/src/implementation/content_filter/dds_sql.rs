@@ -0,0 +1,689 @@
+use core::cmp::Ordering;
+
+// Implementation of the SQL subset defined in Annex B of the DDS specification
+// which is used for the filter expressions of the ContentFilteredTopic.
+
+// As of Annex B of the DDS specification the number of parameters is limited to 100
+pub const MAX_EXPRESSION_PARAMETERS: usize = 100;
+
+// Deepest nesting of parentheses and NOT operators accepted. The parser is recursive
+// so this bounds the stack it uses.
+const MAX_NESTING_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Boolean(bool),
+    Integer(i128),
+    Float(f64),
+    Char(char),
+    String(String),
+}
+
+impl Value {
+    fn as_text(&self) -> Option<String> {
+        match self {
+            Value::Char(c) => Some(c.to_string()),
+            Value::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+            (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+            (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (a, b) => Some(a.as_text()?.cmp(&b.as_text()?)),
+        }
+    }
+
+    /// Interpret an expression parameter. Parameters which are a single literal
+    /// (e.g. `5`, `3.2` or `'Red'`) take that value, anything else is taken as a string.
+    pub fn from_parameter(parameter: &str) -> Value {
+        match tokenize(parameter).as_deref() {
+            Ok([Token::Literal(v)]) => v.clone(),
+            Ok([Token::True]) => Value::Boolean(true),
+            Ok([Token::False]) => Value::Boolean(false),
+            _ => Value::String(parameter.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Literal(Value),
+    Parameter(usize),
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    LeftParenthesis,
+    RightParenthesis,
+    And,
+    Or,
+    Not,
+    Between,
+    Like,
+    True,
+    False,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LeftParenthesis);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RightParenthesis);
+            }
+            '=' => {
+                chars.next();
+                tokens.push(Token::Equal);
+            }
+            '!' => {
+                chars.next();
+                if chars.next_if_eq(&'=').is_none() {
+                    return Err("Expected '=' after '!'".to_string());
+                }
+                tokens.push(Token::NotEqual);
+            }
+            '<' => {
+                chars.next();
+                if chars.next_if_eq(&'=').is_some() {
+                    tokens.push(Token::LessEqual);
+                } else if chars.next_if_eq(&'>').is_some() {
+                    tokens.push(Token::NotEqual);
+                } else {
+                    tokens.push(Token::Less);
+                }
+            }
+            '>' => {
+                chars.next();
+                if chars.next_if_eq(&'=').is_some() {
+                    tokens.push(Token::GreaterEqual);
+                } else {
+                    tokens.push(Token::Greater);
+                }
+            }
+            '%' => {
+                chars.next();
+                let mut index = String::new();
+                while let Some(d) = chars.next_if(|c| c.is_ascii_digit()) {
+                    index.push(d);
+                }
+                let index = index
+                    .parse()
+                    .map_err(|_| "Expected parameter index after '%'".to_string())?;
+                if index >= MAX_EXPRESSION_PARAMETERS {
+                    return Err(format!(
+                        "Parameter index {} out of range. Value must be below {}",
+                        index, MAX_EXPRESSION_PARAMETERS
+                    ));
+                }
+                tokens.push(Token::Parameter(index));
+            }
+            '\'' | '’' | '‘' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\'' | '’' | '‘') => break,
+                        Some(c) => text.push(c),
+                        None => return Err("Unterminated string literal".to_string()),
+                    }
+                }
+                let mut text_chars = text.chars();
+                match (text_chars.next(), text_chars.next()) {
+                    (Some(c), None) => tokens.push(Token::Literal(Value::Char(c))),
+                    _ => tokens.push(Token::Literal(Value::String(text))),
+                }
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let mut number = String::new();
+                number.push(c);
+                chars.next();
+                while let Some(d) = chars
+                    .next_if(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '-' || *c == '+')
+                {
+                    number.push(d);
+                }
+                tokens.push(Token::Literal(parse_number(&number)?));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut identifier = String::new();
+                while let Some(d) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    identifier.push(d);
+                }
+                let token = match identifier.to_ascii_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    "BETWEEN" => Token::Between,
+                    "LIKE" => Token::Like,
+                    "TRUE" => Token::True,
+                    "FALSE" => Token::False,
+                    _ => Token::Identifier(identifier),
+                };
+                tokens.push(token);
+            }
+            c => return Err(format!("Unexpected character '{}'", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_number(number: &str) -> Result<Value, String> {
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(d) => (-1, d),
+        None => (1, number.strip_prefix('+').unwrap_or(number)),
+    };
+    if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        i128::from_str_radix(hex, 16)
+            .map(|v| Value::Integer(sign * v))
+            .map_err(|_| format!("Invalid hexadecimal value {}", number))
+    } else if let Ok(v) = digits.parse::<i128>() {
+        Ok(Value::Integer(sign * v))
+    } else {
+        number
+            .parse::<f64>()
+            .map(Value::Float)
+            .map_err(|_| format!("Invalid numeric value {}", number))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Field(String),
+    Literal(Value),
+    Parameter(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelationalOperator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Like,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Comparison {
+        left: Operand,
+        operator: RelationalOperator,
+        right: Operand,
+    },
+    Between {
+        operand: Operand,
+        low: Operand,
+        high: Operand,
+        negated: bool,
+    },
+    // AND and OR chains are kept flat so that a long chain of operators does
+    // not build a deep tree that would be walked recursively
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn consume_if(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Condition, String> {
+        let mut conditions = vec![self.parse_and()?];
+        while self.consume_if(&Token::Or) {
+            conditions.push(self.parse_and()?);
+        }
+        Ok(if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            Condition::Or(conditions)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, String> {
+        let mut conditions = vec![self.parse_not()?];
+        while self.consume_if(&Token::And) {
+            conditions.push(self.parse_not()?);
+        }
+        Ok(if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            Condition::And(conditions)
+        })
+    }
+
+    fn parse_not(&mut self) -> Result<Condition, String> {
+        if self.consume_if(&Token::Not) {
+            let condition = self.nested(Self::parse_not)?;
+            Ok(Condition::Not(Box::new(condition)))
+        } else if self.consume_if(&Token::LeftParenthesis) {
+            let condition = self.nested(Self::parse_or)?;
+            if !self.consume_if(&Token::RightParenthesis) {
+                return Err("Expected ')'".to_string());
+            }
+            Ok(condition)
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Condition, String>,
+    ) -> Result<Condition, String> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(format!(
+                "Expression nested deeper than {} levels",
+                MAX_NESTING_DEPTH
+            ));
+        }
+        self.depth += 1;
+        let condition = parse(self);
+        self.depth -= 1;
+        condition
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Identifier(name)) => Ok(Operand::Field(name)),
+            Some(Token::Literal(value)) => Ok(Operand::Literal(value)),
+            Some(Token::True) => Ok(Operand::Literal(Value::Boolean(true))),
+            Some(Token::False) => Ok(Operand::Literal(Value::Boolean(false))),
+            Some(Token::Parameter(index)) => Ok(Operand::Parameter(index)),
+            Some(t) => Err(format!("Unexpected token {:?}", t)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+
+    fn parse_predicate(&mut self) -> Result<Condition, String> {
+        let left = self.parse_operand()?;
+        let negated = self.consume_if(&Token::Not);
+        let operator = match self.next() {
+            Some(Token::Between) => {
+                let low = self.parse_operand()?;
+                if !self.consume_if(&Token::And) {
+                    return Err("Expected AND in BETWEEN predicate".to_string());
+                }
+                let high = self.parse_operand()?;
+                return Ok(Condition::Between {
+                    operand: left,
+                    low,
+                    high,
+                    negated,
+                });
+            }
+            _ if negated => return Err("Expected BETWEEN after NOT".to_string()),
+            Some(Token::Equal) => RelationalOperator::Equal,
+            Some(Token::NotEqual) => RelationalOperator::NotEqual,
+            Some(Token::Greater) => RelationalOperator::Greater,
+            Some(Token::GreaterEqual) => RelationalOperator::GreaterEqual,
+            Some(Token::Less) => RelationalOperator::Less,
+            Some(Token::LessEqual) => RelationalOperator::LessEqual,
+            Some(Token::Like) => RelationalOperator::Like,
+            Some(t) => return Err(format!("Expected relational operator, found {:?}", t)),
+            None => return Err("Unexpected end of expression".to_string()),
+        };
+        let right = self.parse_operand()?;
+        Ok(Condition::Comparison {
+            left,
+            operator,
+            right,
+        })
+    }
+}
+
+// Greedy wildcard matching. On a mismatch only the last '%' is retried one character
+// further, since what the earlier ones matched can not change the result. This takes
+// at most text length times pattern length steps.
+fn like(text: &[char], pattern: &[char]) -> bool {
+    let mut t = 0;
+    let mut p = 0;
+    let mut last_percent: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                p += 1;
+                last_percent = Some((p, t));
+            }
+            Some(&c) if c == '_' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match last_percent {
+                Some((pattern_after_percent, text_matched_by_percent)) => {
+                    p = pattern_after_percent;
+                    t = text_matched_by_percent + 1;
+                    last_percent = Some((p, t));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '%')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpression {
+    condition: Condition,
+}
+
+impl FilterExpression {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            position: 0,
+            depth: 0,
+        };
+        let condition = parser.parse_or()?;
+        if let Some(t) = parser.peek() {
+            return Err(format!("Unexpected token {:?}", t));
+        }
+        Ok(Self { condition })
+    }
+
+    pub fn field_names(&self) -> Vec<&str> {
+        fn collect<'a>(condition: &'a Condition, names: &mut Vec<&'a str>) {
+            let mut push = |operand: &'a Operand| {
+                if let Operand::Field(name) = operand {
+                    names.push(name.as_str());
+                }
+            };
+            match condition {
+                Condition::Comparison { left, right, .. } => {
+                    push(left);
+                    push(right);
+                }
+                Condition::Between {
+                    operand, low, high, ..
+                } => {
+                    push(operand);
+                    push(low);
+                    push(high);
+                }
+                Condition::And(conditions) | Condition::Or(conditions) => {
+                    for c in conditions {
+                        collect(c, names);
+                    }
+                }
+                Condition::Not(c) => collect(c, names),
+            }
+        }
+        let mut names = Vec::new();
+        collect(&self.condition, &mut names);
+        names
+    }
+
+    /// Number of expression parameters needed to evaluate the expression,
+    /// i.e. the highest parameter index used plus one.
+    pub fn parameter_count(&self) -> usize {
+        fn max_index(condition: &Condition) -> usize {
+            let index = |operand: &Operand| match operand {
+                Operand::Parameter(i) => i + 1,
+                _ => 0,
+            };
+            match condition {
+                Condition::Comparison { left, right, .. } => index(left).max(index(right)),
+                Condition::Between {
+                    operand, low, high, ..
+                } => index(operand).max(index(low)).max(index(high)),
+                Condition::And(conditions) | Condition::Or(conditions) => {
+                    conditions.iter().map(max_index).max().unwrap_or(0)
+                }
+                Condition::Not(c) => max_index(c),
+            }
+        }
+        max_index(&self.condition)
+    }
+
+    pub fn evaluate<'a>(
+        &self,
+        field_value: &impl Fn(&str) -> Option<&'a Value>,
+        parameters: &[Value],
+    ) -> bool {
+        Self::evaluate_condition(&self.condition, field_value, parameters)
+    }
+
+    fn evaluate_condition<'a>(
+        condition: &Condition,
+        field_value: &impl Fn(&str) -> Option<&'a Value>,
+        parameters: &[Value],
+    ) -> bool {
+        let value = |operand: &Operand| -> Option<Value> {
+            match operand {
+                Operand::Field(name) => field_value(name).cloned(),
+                Operand::Literal(v) => Some(v.clone()),
+                Operand::Parameter(i) => parameters.get(*i).cloned(),
+            }
+        };
+        match condition {
+            Condition::Comparison {
+                left,
+                operator,
+                right,
+            } => {
+                let (Some(left), Some(right)) = (value(left), value(right)) else {
+                    return false;
+                };
+                if *operator == RelationalOperator::Like {
+                    return match (left.as_text(), right.as_text()) {
+                        (Some(text), Some(pattern)) => like(
+                            &text.chars().collect::<Vec<_>>(),
+                            &pattern.chars().collect::<Vec<_>>(),
+                        ),
+                        _ => false,
+                    };
+                }
+                let Some(ordering) = left.compare(&right) else {
+                    return false;
+                };
+                match operator {
+                    RelationalOperator::Equal => ordering == Ordering::Equal,
+                    RelationalOperator::NotEqual => ordering != Ordering::Equal,
+                    RelationalOperator::Greater => ordering == Ordering::Greater,
+                    RelationalOperator::GreaterEqual => ordering != Ordering::Less,
+                    RelationalOperator::Less => ordering == Ordering::Less,
+                    RelationalOperator::LessEqual => ordering != Ordering::Greater,
+                    RelationalOperator::Like => unreachable!("Handled above"),
+                }
+            }
+            Condition::Between {
+                operand,
+                low,
+                high,
+                negated,
+            } => {
+                let (Some(v), Some(low), Some(high)) = (value(operand), value(low), value(high))
+                else {
+                    return false;
+                };
+                let in_range = matches!(v.compare(&low), Some(Ordering::Greater | Ordering::Equal))
+                    && matches!(v.compare(&high), Some(Ordering::Less | Ordering::Equal));
+                in_range != *negated
+            }
+            Condition::And(conditions) => conditions
+                .iter()
+                .all(|c| Self::evaluate_condition(c, field_value, parameters)),
+            Condition::Or(conditions) => conditions
+                .iter()
+                .any(|c| Self::evaluate_condition(c, field_value, parameters)),
+            Condition::Not(c) => !Self::evaluate_condition(c, field_value, parameters),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str, fields: &[(&str, Value)], parameters: &[&str]) -> bool {
+        let parameters: Vec<Value> = parameters
+            .iter()
+            .map(|p| Value::from_parameter(p))
+            .collect();
+        FilterExpression::parse(expression).unwrap().evaluate(
+            &|name| fields.iter().find(|(n, _)| *n == name).map(|(_, v)| v),
+            &parameters,
+        )
+    }
+
+    #[test]
+    fn comparison_operators() {
+        let fields = [("id", Value::Integer(5))];
+        assert!(evaluate("id = 5", &fields, &[]));
+        assert!(evaluate("id <> 4", &fields, &[]));
+        assert!(evaluate("id != 4", &fields, &[]));
+        assert!(evaluate("id > 4", &fields, &[]));
+        assert!(evaluate("id >= 5", &fields, &[]));
+        assert!(evaluate("id < 5.5", &fields, &[]));
+        assert!(evaluate("id <= 5", &fields, &[]));
+        assert!(evaluate("4 < id", &fields, &[]));
+        assert!(!evaluate("id = -5", &fields, &[]));
+        assert!(evaluate("id = 0x05", &fields, &[]));
+    }
+
+    #[test]
+    fn logical_operators_and_precedence() {
+        let fields = [("a", Value::Integer(1)), ("b", Value::Integer(2))];
+        assert!(evaluate("a = 1 AND b = 2", &fields, &[]));
+        assert!(!evaluate("a = 1 AND b = 3", &fields, &[]));
+        assert!(evaluate("a = 0 OR b = 2", &fields, &[]));
+        assert!(evaluate("a = 1 OR a = 0 AND b = 3", &fields, &[]));
+        assert!(!evaluate("(a = 1 OR a = 0) AND b = 3", &fields, &[]));
+        assert!(evaluate("NOT a = 2", &fields, &[]));
+        assert!(evaluate("not (a = 2) and b = 2", &fields, &[]));
+    }
+
+    #[test]
+    fn between_predicate() {
+        let fields = [("x", Value::Float(2.5))];
+        assert!(evaluate("x BETWEEN 1 AND 3", &fields, &[]));
+        assert!(!evaluate("x NOT BETWEEN 1 AND 3", &fields, &[]));
+        assert!(evaluate(
+            "x BETWEEN %0 AND %1 AND x > 2",
+            &fields,
+            &["2", "2.5"]
+        ));
+    }
+
+    #[test]
+    fn string_and_like() {
+        let fields = [
+            ("color", Value::String("BLUE".to_string())),
+            ("c", Value::Char('x')),
+        ];
+        assert!(evaluate("color = 'BLUE'", &fields, &[]));
+        assert!(evaluate("color LIKE 'B%'", &fields, &[]));
+        assert!(evaluate("color LIKE '_LU_'", &fields, &[]));
+        assert!(!evaluate("color LIKE 'R%'", &fields, &[]));
+        assert!(evaluate("c = 'x'", &fields, &[]));
+        assert!(evaluate("color = %0", &fields, &["'BLUE'"]));
+        assert!(evaluate("color = %0", &fields, &["BLUE"]));
+    }
+
+    #[test]
+    fn parameters_and_field_names() {
+        let expression = FilterExpression::parse("a.b > %1 OR c = %0").unwrap();
+        assert_eq!(expression.parameter_count(), 2);
+        assert_eq!(expression.field_names(), vec!["a.b", "c"]);
+
+        let fields = [("a.b", Value::Integer(10)), ("c", Value::Boolean(true))];
+        assert!(evaluate("a.b > %1 OR c = %0", &fields, &["FALSE", "9"]));
+        assert!(evaluate("c = TRUE", &fields, &[]));
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert!(FilterExpression::parse("").is_err());
+        assert!(FilterExpression::parse("a =").is_err());
+        assert!(FilterExpression::parse("a = 1 AND").is_err());
+        assert!(FilterExpression::parse("(a = 1").is_err());
+        assert!(FilterExpression::parse("a = 1)").is_err());
+        assert!(FilterExpression::parse("a NOT = 1").is_err());
+        assert!(FilterExpression::parse("a = 'abc").is_err());
+        assert!(FilterExpression::parse("a = %").is_err());
+        assert!(FilterExpression::parse("a = %100").is_err());
+        assert!(FilterExpression::parse("a = %18446744073709551615").is_err());
+        assert!(FilterExpression::parse("a = %99").is_ok());
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth| format!("{}a = 1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(FilterExpression::parse(&nested(MAX_NESTING_DEPTH)).is_ok());
+        assert!(FilterExpression::parse(&nested(MAX_NESTING_DEPTH + 1)).is_err());
+        assert!(FilterExpression::parse(&nested(3000)).is_err());
+        assert!(FilterExpression::parse(&format!("{}a = 1", "NOT ".repeat(3000))).is_err());
+    }
+
+    #[test]
+    fn long_operator_chains() {
+        let chain = |operator| vec!["a = 1"; 100_000].join(operator);
+        assert!(evaluate(&chain(" AND "), &[("a", Value::Integer(1))], &[]));
+        assert!(!evaluate(&chain(" AND "), &[("a", Value::Integer(2))], &[]));
+        assert!(evaluate(&chain(" OR "), &[("a", Value::Integer(1))], &[]));
+        assert!(!evaluate(&chain(" OR "), &[("a", Value::Integer(2))], &[]));
+    }
+
+    #[test]
+    fn like_patterns() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert!(like(&chars(""), &chars("%")));
+        assert!(like(&chars("abc"), &chars("a%c")));
+        assert!(like(&chars("abcbc"), &chars("%bc")));
+        assert!(like(&chars("aXbYc"), &chars("a_b%c")));
+        assert!(!like(&chars("abcb"), &chars("%bc")));
+        assert!(!like(&chars("ab"), &chars("a_b")));
+        assert!(like(&chars("a%b"), &chars("a%%b")));
+
+        let text = chars(&"a".repeat(40));
+        let start = std::time::Instant::now();
+        assert!(!like(&text, &chars("%a%a%a%a%a%a%b")));
+        assert!(start.elapsed() < std::time::Duration::from_millis(50));
+    }
+}
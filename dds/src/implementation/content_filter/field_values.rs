@@ -0,0 +1,414 @@
+use std::io::BufRead;
+
+use crate::{
+    implementation::xtypes_glue::key_and_instance_handle::{
+        go_to_pid_be, go_to_pid_le, RepresentationIdentifier, CDR2_BE, CDR2_LE, CDR_BE, CDR_LE,
+        PL_CDR_BE, PL_CDR_LE,
+    },
+    xtypes::{
+        deserializer::{DeserializeSequence, XTypesDeserializer},
+        dynamic_type::{DynamicType, ExtensibilityKind},
+        error::XTypesError,
+        type_object::{TypeIdentifier, TK_STRUCTURE},
+        xcdr_deserializer::{
+            Xcdr1BeDeserializer, Xcdr1LeDeserializer, Xcdr2BeDeserializer, Xcdr2LeDeserializer,
+        },
+    },
+};
+
+use super::dds_sql::Value;
+
+const PL_CDR2_BE: RepresentationIdentifier = [0x00, 0x0a];
+const PL_CDR2_LE: RepresentationIdentifier = [0x00, 0x0b];
+
+/// List of the values of the primitive and string members of a sample. Members
+/// of nested structures are named with their full path, e.g. `position.x`.
+pub type FieldValues = Vec<(String, Value)>;
+
+fn deserialize_field_value<'a, T>(
+    type_identifier: &TypeIdentifier,
+    name: &str,
+    de: &mut T,
+    values: &mut FieldValues,
+) -> Result<(), XTypesError>
+where
+    for<'b> &'b mut T: XTypesDeserializer<'a>,
+{
+    let value = match type_identifier {
+        TypeIdentifier::TkBoolean => Value::Boolean(de.deserialize_boolean()?),
+        TypeIdentifier::TkByteType | TypeIdentifier::TkUint8Type => {
+            Value::Integer(de.deserialize_uint8()?.into())
+        }
+        TypeIdentifier::TkInt8Type => Value::Integer(de.deserialize_int8()?.into()),
+        TypeIdentifier::TkInt16Type => Value::Integer(de.deserialize_int16()?.into()),
+        TypeIdentifier::TkInt32Type => Value::Integer(de.deserialize_int32()?.into()),
+        TypeIdentifier::TkInt64Type => Value::Integer(de.deserialize_int64()?.into()),
+        TypeIdentifier::TkUint16Type => Value::Integer(de.deserialize_uint16()?.into()),
+        TypeIdentifier::TkUint32Type => Value::Integer(de.deserialize_uint32()?.into()),
+        TypeIdentifier::TkUint64Type => Value::Integer(de.deserialize_uint64()?.into()),
+        TypeIdentifier::TkFloat32Type => Value::Float(de.deserialize_float32()?.into()),
+        TypeIdentifier::TkFloat64Type => Value::Float(de.deserialize_float64()?),
+        TypeIdentifier::TkChar8Type => Value::Char(de.deserialize_char8()?),
+        TypeIdentifier::TiString8Small { .. } | TypeIdentifier::TiString8Large { .. } => {
+            Value::String(de.deserialize_string()?.to_string())
+        }
+        // Collections are not addressable in filter expressions but their
+        // elements must still be consumed to reach the following members.
+        TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => {
+            let len = de.deserialize_sequence()?.len();
+            return skip_elements(&seq_sdefn.element_identifier, len, de);
+        }
+        TypeIdentifier::TiPlainSequenceLarge { seq_ldefn } => {
+            let len = de.deserialize_sequence()?.len();
+            return skip_elements(&seq_ldefn.element_identifier, len, de);
+        }
+        // Multi-dimensional arrays are serialized as a single array with
+        // the product of all the bounds as length
+        TypeIdentifier::TiPlainArraySmall { array_sdefn } => {
+            let len = array_sdefn
+                .array_bound_seq
+                .iter()
+                .map(|&bound| bound as usize)
+                .product();
+            return skip_elements(&array_sdefn.element_identifier, len, de);
+        }
+        TypeIdentifier::TiPlainArrayLarge { array_ldefn } => {
+            let len = array_ldefn
+                .array_bound_seq
+                .iter()
+                .map(|&bound| bound as usize)
+                .product();
+            return skip_elements(&array_ldefn.element_identifier, len, de);
+        }
+        TypeIdentifier::EkComplete { complete } => {
+            return push_field_values(complete.as_ref(), &format!("{}.", name), de, values);
+        }
+        _ => return Err(XTypesError::InvalidData),
+    };
+    values.push((name.to_string(), value));
+    Ok(())
+}
+
+fn skip_elements<'a, T>(
+    element_identifier: &TypeIdentifier,
+    len: usize,
+    de: &mut T,
+) -> Result<(), XTypesError>
+where
+    for<'b> &'b mut T: XTypesDeserializer<'a>,
+{
+    for _ in 0..len {
+        deserialize_field_value(element_identifier, "", de, &mut Vec::new())?;
+    }
+    Ok(())
+}
+
+fn push_field_values<'a, T>(
+    dynamic_type: &dyn DynamicType,
+    prefix: &str,
+    de: &mut T,
+    values: &mut FieldValues,
+) -> Result<(), XTypesError>
+where
+    for<'b> &'b mut T: XTypesDeserializer<'a>,
+{
+    for member_descriptor in dynamic_type.into_iter() {
+        let member_descriptor = member_descriptor?;
+        deserialize_field_value(
+            member_descriptor.type_,
+            &format!("{}{}", prefix, member_descriptor.name),
+            de,
+            values,
+        )?;
+    }
+    Ok(())
+}
+
+fn push_field_values_parameter_list<'a, T>(
+    dynamic_type: &dyn DynamicType,
+    data: &'a [u8],
+    go_to_pid: fn(&'a [u8], u32) -> Result<&'a [u8], XTypesError>,
+    new_deserializer: fn(&'a [u8]) -> T,
+    values: &mut FieldValues,
+) -> Result<(), XTypesError>
+where
+    for<'b> &'b mut T: XTypesDeserializer<'a>,
+{
+    for descriptor in dynamic_type.into_iter() {
+        let descriptor = descriptor?;
+        // Optional members which are not present in the sample have no value
+        if let Ok(buffer) = go_to_pid(data, descriptor.id) {
+            let mut de = new_deserializer(buffer);
+            deserialize_field_value(descriptor.type_, &descriptor.name, &mut de, values)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn get_field_values_from_serialized_foo(
+    mut data: &[u8],
+    dynamic_type: &dyn DynamicType,
+) -> Result<FieldValues, XTypesError> {
+    if data.len() < 4 {
+        return Err(XTypesError::InvalidData);
+    }
+    let mut values = Vec::new();
+    let representation_identifier = [data[0], data[1]];
+    data.consume(4);
+    match representation_identifier {
+        CDR_BE => push_field_values(
+            dynamic_type,
+            "",
+            &mut Xcdr1BeDeserializer::new(data),
+            &mut values,
+        )?,
+        CDR_LE => push_field_values(
+            dynamic_type,
+            "",
+            &mut Xcdr1LeDeserializer::new(data),
+            &mut values,
+        )?,
+        CDR2_BE => push_field_values(
+            dynamic_type,
+            "",
+            &mut Xcdr2BeDeserializer::new(data),
+            &mut values,
+        )?,
+        CDR2_LE => push_field_values(
+            dynamic_type,
+            "",
+            &mut Xcdr2LeDeserializer::new(data),
+            &mut values,
+        )?,
+        PL_CDR_BE => push_field_values_parameter_list(
+            dynamic_type,
+            data,
+            go_to_pid_be,
+            Xcdr1BeDeserializer::new,
+            &mut values,
+        )?,
+        PL_CDR_LE => push_field_values_parameter_list(
+            dynamic_type,
+            data,
+            go_to_pid_le,
+            Xcdr1LeDeserializer::new,
+            &mut values,
+        )?,
+        PL_CDR2_BE => push_field_values_parameter_list(
+            dynamic_type,
+            data,
+            go_to_pid_be,
+            Xcdr2BeDeserializer::new,
+            &mut values,
+        )?,
+        PL_CDR2_LE => push_field_values_parameter_list(
+            dynamic_type,
+            data,
+            go_to_pid_le,
+            Xcdr2LeDeserializer::new,
+            &mut values,
+        )?,
+        _ => return Err(XTypesError::InvalidData),
+    }
+    Ok(values)
+}
+
+/// Whether the samples of the type can be read by a filter. Appendable
+/// types, nested structures which are not final, optional members of final
+/// structures and members which are not primitives, strings, structures or
+/// plain collections of those are not supported.
+pub fn is_filterable_type(dynamic_type: &dyn DynamicType) -> Result<bool, XTypesError> {
+    fn is_filterable_member(type_identifier: &TypeIdentifier) -> Result<bool, XTypesError> {
+        Ok(match type_identifier {
+            TypeIdentifier::TkBoolean
+            | TypeIdentifier::TkByteType
+            | TypeIdentifier::TkInt8Type
+            | TypeIdentifier::TkInt16Type
+            | TypeIdentifier::TkInt32Type
+            | TypeIdentifier::TkInt64Type
+            | TypeIdentifier::TkUint8Type
+            | TypeIdentifier::TkUint16Type
+            | TypeIdentifier::TkUint32Type
+            | TypeIdentifier::TkUint64Type
+            | TypeIdentifier::TkFloat32Type
+            | TypeIdentifier::TkFloat64Type
+            | TypeIdentifier::TkChar8Type
+            | TypeIdentifier::TiString8Small { .. }
+            | TypeIdentifier::TiString8Large { .. } => true,
+            TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => {
+                is_filterable_member(&seq_sdefn.element_identifier)?
+            }
+            TypeIdentifier::TiPlainSequenceLarge { seq_ldefn } => {
+                is_filterable_member(&seq_ldefn.element_identifier)?
+            }
+            TypeIdentifier::TiPlainArraySmall { array_sdefn } => {
+                is_filterable_member(&array_sdefn.element_identifier)?
+            }
+            TypeIdentifier::TiPlainArrayLarge { array_ldefn } => {
+                is_filterable_member(&array_ldefn.element_identifier)?
+            }
+            TypeIdentifier::EkComplete { complete } => {
+                matches!(
+                    complete.get_descriptor()?.extensibility_kind,
+                    ExtensibilityKind::Final
+                ) && are_filterable_members(complete.as_ref(), false)?
+            }
+            _ => false,
+        })
+    }
+
+    fn are_filterable_members(
+        dynamic_type: &dyn DynamicType,
+        is_parameter_list: bool,
+    ) -> Result<bool, XTypesError> {
+        if dynamic_type.get_kind() != TK_STRUCTURE {
+            return Ok(false);
+        }
+        for member_descriptor in dynamic_type.into_iter() {
+            let member_descriptor = member_descriptor?;
+            // Only the members of a parameter list can be left out of a sample
+            if (member_descriptor.is_optional && !is_parameter_list)
+                || !is_filterable_member(member_descriptor.type_)?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    match dynamic_type.get_descriptor()?.extensibility_kind {
+        ExtensibilityKind::Final => are_filterable_members(dynamic_type, false),
+        ExtensibilityKind::Mutable => are_filterable_members(dynamic_type, true),
+        ExtensibilityKind::Appendable => Ok(false),
+    }
+}
+
+/// Names of the members of the type which can be used in a filter expression.
+pub fn get_field_names(dynamic_type: &dyn DynamicType) -> Result<Vec<String>, XTypesError> {
+    fn push_field_names(
+        dynamic_type: &dyn DynamicType,
+        prefix: &str,
+        names: &mut Vec<String>,
+    ) -> Result<(), XTypesError> {
+        for member_descriptor in dynamic_type.into_iter() {
+            let member_descriptor = member_descriptor?;
+            let name = format!("{}{}", prefix, member_descriptor.name);
+            match member_descriptor.type_ {
+                TypeIdentifier::EkComplete { complete } => {
+                    push_field_names(complete.as_ref(), &format!("{}.", name), names)?
+                }
+                TypeIdentifier::TiPlainSequenceSmall { .. }
+                | TypeIdentifier::TiPlainSequenceLarge { .. }
+                | TypeIdentifier::TiPlainArraySmall { .. }
+                | TypeIdentifier::TiPlainArrayLarge { .. } => (),
+                _ => names.push(name),
+            }
+        }
+        Ok(())
+    }
+
+    let mut names = Vec::new();
+    push_field_names(dynamic_type, "", &mut names)?;
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::{DdsSerialize, TypeSupport};
+    use dust_dds_derive::DdsType;
+
+    #[derive(DdsType)]
+    struct Position {
+        x: i32,
+        y: f64,
+    }
+
+    #[derive(DdsType)]
+    struct Sample {
+        #[dust_dds(key)]
+        id: u8,
+        name: String,
+        list: Vec<u16>,
+        position: Position,
+        active: bool,
+    }
+
+    #[test]
+    fn field_values_of_final_struct() {
+        let sample = Sample {
+            id: 7,
+            name: String::from("sensor"),
+            list: vec![1, 2, 3],
+            position: Position { x: -3, y: 1.5 },
+            active: true,
+        };
+        let data = sample.serialize_data().unwrap();
+        let values = get_field_values_from_serialized_foo(&data, &Sample::get_type()).unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                ("id".to_string(), Value::Integer(7)),
+                ("name".to_string(), Value::String("sensor".to_string())),
+                ("position.x".to_string(), Value::Integer(-3)),
+                ("position.y".to_string(), Value::Float(1.5)),
+                ("active".to_string(), Value::Boolean(true)),
+            ]
+        );
+    }
+
+    #[derive(DdsType)]
+    struct Matrix {
+        cells: [[u16; 2]; 3],
+        trace: u16,
+    }
+
+    #[test]
+    fn field_values_after_nested_arrays() {
+        let sample = Matrix {
+            cells: [[1, 2], [3, 4], [5, 6]],
+            trace: 10,
+        };
+        let data = sample.serialize_data().unwrap();
+        let values = get_field_values_from_serialized_foo(&data, &Matrix::get_type()).unwrap();
+
+        assert_eq!(values, vec![("trace".to_string(), Value::Integer(10))]);
+    }
+
+    #[derive(DdsType)]
+    #[dust_dds(extensibility = "Appendable")]
+    struct AppendableSample {
+        id: u8,
+    }
+
+    #[derive(DdsType)]
+    struct NestedMutableSample {
+        id: u8,
+        nested: MutablePosition,
+    }
+
+    #[derive(DdsType)]
+    #[dust_dds(extensibility = "Mutable")]
+    struct MutablePosition {
+        #[dust_dds(id = 1)]
+        x: i32,
+    }
+
+    #[test]
+    fn filterable_types() {
+        assert!(is_filterable_type(&Sample::get_type()).unwrap());
+        assert!(is_filterable_type(&Matrix::get_type()).unwrap());
+        assert!(is_filterable_type(&MutablePosition::get_type()).unwrap());
+        assert!(!is_filterable_type(&AppendableSample::get_type()).unwrap());
+        assert!(!is_filterable_type(&NestedMutableSample::get_type()).unwrap());
+    }
+
+    #[test]
+    fn field_names_of_struct() {
+        assert_eq!(
+            get_field_names(&Sample::get_type()).unwrap(),
+            vec!["id", "name", "position.x", "position.y", "active"]
+        );
+    }
+}
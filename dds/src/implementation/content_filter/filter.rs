@@ -0,0 +1,225 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use tracing::warn;
+
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    transport::{history_cache::CacheChange, types::ChangeKind, writer::ReaderContentFilter},
+    xtypes::dynamic_type::DynamicType,
+};
+
+use super::{
+    dds_sql::{FilterExpression, Value, MAX_EXPRESSION_PARAMETERS},
+    field_values::{get_field_names, get_field_values_from_serialized_foo, is_filterable_type},
+};
+
+/// DDS-SQL filter expression together with the values of its parameters.
+/// Used both as the filter of a content filtered topic and as the query of a
+/// query condition.
+#[derive(Clone)]
+pub struct SqlFilter {
+    filter_expression: String,
+    expression_parameters: Vec<String>,
+    expression: FilterExpression,
+    parameter_values: Vec<Value>,
+}
+
+impl SqlFilter {
+    pub fn new(
+        filter_expression: String,
+        expression_parameters: Vec<String>,
+        type_support: &dyn DynamicType,
+    ) -> DdsResult<Self> {
+        let expression = FilterExpression::parse(&filter_expression).map_err(|e| {
+            warn!("Invalid filter expression {:?}: {}", filter_expression, e);
+            DdsError::BadParameter
+        })?;
+
+        if !is_filterable_type(type_support).map_err(|_| DdsError::BadParameter)? {
+            warn!(
+                "Samples of type {} can not be filtered",
+                type_support.get_name()
+            );
+            return Err(DdsError::Unsupported);
+        }
+
+        let field_names = get_field_names(type_support).map_err(|_| DdsError::BadParameter)?;
+        if let Some(unknown_field) = expression
+            .field_names()
+            .into_iter()
+            .find(|f| !field_names.iter().any(|n| n == f))
+        {
+            warn!(
+                "Field {} in filter expression is not a member of type {}",
+                unknown_field,
+                type_support.get_name()
+            );
+            return Err(DdsError::BadParameter);
+        }
+
+        let mut sql_filter = Self {
+            filter_expression,
+            expression_parameters: Vec::new(),
+            expression,
+            parameter_values: Vec::new(),
+        };
+        sql_filter.set_expression_parameters(expression_parameters)?;
+        Ok(sql_filter)
+    }
+
+    pub fn filter_expression(&self) -> &str {
+        &self.filter_expression
+    }
+
+    pub fn expression_parameters(&self) -> &[String] {
+        &self.expression_parameters
+    }
+
+    pub fn set_expression_parameters(
+        &mut self,
+        expression_parameters: Vec<String>,
+    ) -> DdsResult<()> {
+        if expression_parameters.len() > MAX_EXPRESSION_PARAMETERS
+            || expression_parameters.len() < self.expression.parameter_count()
+        {
+            return Err(DdsError::BadParameter);
+        }
+        self.parameter_values = expression_parameters
+            .iter()
+            .map(|p| Value::from_parameter(p))
+            .collect();
+        self.expression_parameters = expression_parameters;
+        Ok(())
+    }
+
+    pub fn is_sample_accepted(
+        &self,
+        serialized_data: &[u8],
+        type_support: &dyn DynamicType,
+    ) -> bool {
+        match get_field_values_from_serialized_foo(serialized_data, type_support) {
+            Ok(field_values) => self.expression.evaluate(
+                &|name| {
+                    field_values
+                        .iter()
+                        .find(|(field_name, _)| field_name == name)
+                        .map(|(_, value)| value)
+                },
+                &self.parameter_values,
+            ),
+            Err(_) => false,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ContentFilter {
+    content_filtered_topic_name: String,
+    related_topic_name: String,
+    sql_filter: SqlFilter,
+}
+
+impl ContentFilter {
+    pub fn new(
+        content_filtered_topic_name: String,
+        related_topic_name: String,
+        filter_expression: String,
+        expression_parameters: Vec<String>,
+        type_support: &dyn DynamicType,
+    ) -> DdsResult<Self> {
+        Ok(Self {
+            content_filtered_topic_name,
+            related_topic_name,
+            sql_filter: SqlFilter::new(filter_expression, expression_parameters, type_support)?,
+        })
+    }
+
+    pub fn content_filtered_topic_name(&self) -> &str {
+        &self.content_filtered_topic_name
+    }
+
+    pub fn related_topic_name(&self) -> &str {
+        &self.related_topic_name
+    }
+
+    pub fn filter_expression(&self) -> &str {
+        self.sql_filter.filter_expression()
+    }
+
+    pub fn expression_parameters(&self) -> &[String] {
+        self.sql_filter.expression_parameters()
+    }
+
+    pub fn set_expression_parameters(
+        &mut self,
+        expression_parameters: Vec<String>,
+    ) -> DdsResult<()> {
+        self.sql_filter
+            .set_expression_parameters(expression_parameters)
+    }
+
+    pub fn is_sample_accepted(
+        &self,
+        serialized_data: &[u8],
+        type_support: &dyn DynamicType,
+    ) -> bool {
+        self.sql_filter
+            .is_sample_accepted(serialized_data, type_support)
+    }
+}
+
+// Number of changes whose filter result is kept by a writer side content filter
+const MAX_EVALUATED_CHANGES: usize = 1024;
+
+/// Content filter of a discovered reader which is evaluated by a matched
+/// writer so that samples the reader would discard are not sent at all.
+pub struct WriterSideContentFilter {
+    content_filter: ContentFilter,
+    type_support: Arc<dyn DynamicType + Send + Sync>,
+    // The filter is asked again for each send and resend of a change. The result of
+    // the most recent changes is kept by sequence number so their samples are only
+    // deserialized once.
+    evaluated_changes: Mutex<BTreeMap<i64, bool>>,
+}
+
+impl WriterSideContentFilter {
+    pub fn new(
+        content_filter: ContentFilter,
+        type_support: Arc<dyn DynamicType + Send + Sync>,
+    ) -> Self {
+        Self {
+            content_filter,
+            type_support,
+            evaluated_changes: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl ReaderContentFilter for WriterSideContentFilter {
+    fn is_relevant(&self, cache_change: &CacheChange) -> bool {
+        // Dispose and unregister notifications only carry the key and are
+        // always delivered, as are the changes of a coherent set since the
+        // reader discards any set with missing sequence numbers
+        if cache_change.kind != ChangeKind::Alive || cache_change.coherent_set.is_some() {
+            return true;
+        }
+        let mut evaluated_changes = self
+            .evaluated_changes
+            .lock()
+            .expect("Mutex should not be poisoned");
+        if let Some(&is_relevant) = evaluated_changes.get(&cache_change.sequence_number) {
+            return is_relevant;
+        }
+        let is_relevant = self
+            .content_filter
+            .is_sample_accepted(&cache_change.data_value, self.type_support.as_ref());
+        evaluated_changes.insert(cache_change.sequence_number, is_relevant);
+        if evaluated_changes.len() > MAX_EVALUATED_CHANGES {
+            evaluated_changes.pop_first();
+        }
+        is_relevant
+    }
+}
@@ -0,0 +1,3 @@
+pub mod dds_sql;
+pub mod field_values;
+pub mod filter;
@@ -1,11 +1,11 @@
 use super::{
     parameter_id_values::{
-        DEFAULT_EXPECTS_INLINE_QOS, PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER,
-        PID_DURABILITY, PID_ENDPOINT_GUID, PID_EXPECTS_INLINE_QOS, PID_GROUP_DATA,
-        PID_GROUP_ENTITYID, PID_LATENCY_BUDGET, PID_LIVELINESS, PID_MULTICAST_LOCATOR,
-        PID_OWNERSHIP, PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION, PID_RELIABILITY,
-        PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME, PID_TYPE_NAME, PID_UNICAST_LOCATOR,
-        PID_USER_DATA,
+        DEFAULT_EXPECTS_INLINE_QOS, PID_CONTENT_FILTER_PROPERTY, PID_DATA_REPRESENTATION,
+        PID_DEADLINE, PID_DESTINATION_ORDER, PID_DURABILITY, PID_ENDPOINT_GUID,
+        PID_EXPECTS_INLINE_QOS, PID_GROUP_DATA, PID_GROUP_ENTITYID, PID_LATENCY_BUDGET,
+        PID_LIVELINESS, PID_MULTICAST_LOCATOR, PID_OWNERSHIP, PID_PARTICIPANT_GUID, PID_PARTITION,
+        PID_PRESENTATION, PID_RELIABILITY, PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME,
+        PID_TYPE_NAME, PID_UNICAST_LOCATOR, PID_USER_DATA,
     },
     payload_serializer_deserializer::{
         parameter_list_deserializer::ParameterListCdrDeserializer,
@@ -19,8 +19,11 @@ use crate::{
     },
     topic_definition::type_support::{DdsDeserialize, DdsSerialize, TypeSupport},
     transport::types::{EntityId, Guid, Locator},
+    xtypes::{deserialize::XTypesDeserialize, serialize::XTypesSerialize},
 };
 
+pub const DDSSQL_FILTER_CLASS_NAME: &str = "DDSSQL";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ReaderProxy {
     pub remote_reader_guid: Guid,
@@ -30,10 +33,20 @@ pub struct ReaderProxy {
     pub expects_inline_qos: bool,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, XTypesSerialize, XTypesDeserialize)]
+pub struct ContentFilterProperty {
+    pub content_filtered_topic_name: String,
+    pub related_topic_name: String,
+    pub filter_class_name: String,
+    pub filter_expression: String,
+    pub expression_parameters: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DiscoveredReaderData {
     pub(crate) dds_subscription_data: SubscriptionBuiltinTopicData,
     pub(crate) reader_proxy: ReaderProxy,
+    pub(crate) content_filter: Option<ContentFilterProperty>,
 }
 impl TypeSupport for DiscoveredReaderData {
     fn get_type_name() -> &'static str {
@@ -202,6 +215,10 @@ impl DdsSerialize for DiscoveredReaderData {
             &DEFAULT_EXPECTS_INLINE_QOS,
         )?;
 
+        if let Some(content_filter) = &self.content_filter {
+            serializer.write(PID_CONTENT_FILTER_PROPERTY, content_filter)?;
+        }
+
         serializer.write_sentinel()?;
         Ok(serializer.writer)
     }
@@ -259,6 +276,12 @@ impl<'de> DdsDeserialize<'de> for DiscoveredReaderData {
                 expects_inline_qos: pl_deserializer
                     .read_with_default(PID_EXPECTS_INLINE_QOS, DEFAULT_EXPECTS_INLINE_QOS)?,
             },
+            // A content filter which can not be read only disables the filtering on the
+            // writer side. The reader is still discovered and filters on reception.
+            content_filter: pl_deserializer
+                .read_optional(PID_CONTENT_FILTER_PROPERTY)
+                .ok()
+                .flatten(),
         })
     }
 }
@@ -311,6 +334,7 @@ mod tests {
                 multicast_locator_list: vec![],
                 expects_inline_qos: false,
             },
+            content_filter: None,
         };
 
         let expected = vec![
@@ -378,6 +402,7 @@ mod tests {
                 group_data: Default::default(),
                 representation: Default::default(),
             },
+            content_filter: None,
         };
 
         let mut data = &[
@@ -405,4 +430,94 @@ mod tests {
         let result = DiscoveredReaderData::deserialize_data(&mut data).unwrap();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn serialize_deserialize_content_filter_property() {
+        let data = DiscoveredReaderData {
+            dds_subscription_data: SubscriptionBuiltinTopicData {
+                key: BuiltInTopicKey {
+                    value: [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0],
+                },
+                participant_key: BuiltInTopicKey {
+                    value: [6, 0, 0, 0, 7, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0],
+                },
+                topic_name: "ab".to_string(),
+                type_name: "cd".to_string(),
+                durability: Default::default(),
+                deadline: Default::default(),
+                latency_budget: Default::default(),
+                liveliness: Default::default(),
+                reliability: DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
+                ownership: Default::default(),
+                destination_order: Default::default(),
+                user_data: Default::default(),
+                time_based_filter: Default::default(),
+                presentation: Default::default(),
+                partition: Default::default(),
+                topic_data: Default::default(),
+                group_data: Default::default(),
+                representation: Default::default(),
+            },
+            reader_proxy: ReaderProxy {
+                remote_reader_guid: Guid::new(
+                    [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0],
+                    EntityId::new([4, 0, 0], USER_DEFINED_UNKNOWN),
+                ),
+                remote_group_entity_id: EntityId::new([21, 22, 23], BUILT_IN_WRITER_WITH_KEY),
+                unicast_locator_list: vec![],
+                multicast_locator_list: vec![],
+                expects_inline_qos: false,
+            },
+            content_filter: Some(ContentFilterProperty {
+                content_filtered_topic_name: "ef".to_string(),
+                related_topic_name: "ab".to_string(),
+                filter_class_name: DDSSQL_FILTER_CLASS_NAME.to_string(),
+                filter_expression: "x>%0".to_string(),
+                expression_parameters: vec!["1".to_string()],
+            }),
+        };
+
+        let serialized_data = data.serialize_data().unwrap();
+        let content_filter_property = [
+            0x35, 0x00, 52, 0, // PID_CONTENT_FILTER_PROPERTY, length
+            3, 0, 0, 0, // content_filtered_topic_name length (incl. terminator)
+            b'e', b'f', 0, 0, // string + padding (1 byte)
+            3, 0, 0, 0, // related_topic_name length (incl. terminator)
+            b'a', b'b', 0, 0, // string + padding (1 byte)
+            7, 0, 0, 0, // filter_class_name length (incl. terminator)
+            b'D', b'D', b'S', b'S', // string
+            b'Q', b'L', 0, 0, // string + padding (1 byte)
+            5, 0, 0, 0, // filter_expression length (incl. terminator)
+            b'x', b'>', b'%', b'0', // string
+            0, 0, 0, 0, // string terminator + padding (3 bytes)
+            1, 0, 0, 0, // expression_parameters length
+            2, 0, 0, 0, // string length (incl. terminator)
+            b'1', 0, 0, 0, // string + padding (2 bytes)
+        ];
+        assert!(serialized_data
+            .windows(content_filter_property.len())
+            .any(|w| w == content_filter_property));
+        assert_eq!(
+            DiscoveredReaderData::deserialize_data(&serialized_data).unwrap(),
+            data
+        );
+
+        // A content filter which can not be read does not prevent the reader from
+        // being discovered
+        let mut malformed_data = serialized_data.to_vec();
+        let filter_expression_length_position = malformed_data
+            .windows(content_filter_property.len())
+            .position(|w| w == content_filter_property)
+            .unwrap()
+            + 32;
+        malformed_data[filter_expression_length_position..filter_expression_length_position + 4]
+            .copy_from_slice(&[0xff, 0xff, 0, 0]);
+        assert_eq!(
+            DiscoveredReaderData::deserialize_data(&malformed_data).unwrap(),
+            DiscoveredReaderData {
+                content_filter: None,
+                ..data
+            }
+        );
+    }
 }
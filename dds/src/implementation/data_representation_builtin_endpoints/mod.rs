@@ -2,5 +2,6 @@ pub mod discovered_reader_data;
 pub mod discovered_topic_data;
 pub mod discovered_writer_data;
 pub mod parameter_id_values;
+pub mod participant_message_data;
 pub mod payload_serializer_deserializer;
 pub mod spdp_discovered_participant_data;
@@ -42,7 +42,7 @@ pub const PID_METATRAFFIC_MULTICAST_LOCATOR: ParameterId = 0x0033;
 pub const PID_EXPECTS_INLINE_QOS: ParameterId = 0x0043;
 pub const PID_PARTICIPANT_MANUAL_LIVELINESS_COUNT: ParameterId = 0x0034;
 pub const PID_PARTICIPANT_LEASE_DURATION: ParameterId = 0x0002;
-pub const _PID_CONTENT_FILTER_PROPERTY: ParameterId = 0x0035;
+pub const PID_CONTENT_FILTER_PROPERTY: ParameterId = 0x0035;
 pub const PID_PARTICIPANT_GUID: ParameterId = 0x0050;
 pub const _PID_GROUP_GUID: ParameterId = 0x0052;
 pub const PID_BUILTIN_ENDPOINT_SET: ParameterId = 0x0058;
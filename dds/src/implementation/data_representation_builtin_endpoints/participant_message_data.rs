@@ -0,0 +1,66 @@
+use dust_dds_derive::DdsType;
+
+pub const DCPS_PARTICIPANT_MESSAGE: &str = "DCPSParticipantMessage";
+
+pub type ParticipantMessageKind = [u8; 4];
+
+pub const PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE: ParticipantMessageKind =
+    [0x00, 0x00, 0x00, 0x01];
+pub const PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE: ParticipantMessageKind =
+    [0x00, 0x00, 0x00, 0x02];
+
+// Data type used by the Writer Liveliness Protocol (8.4.13.3 RTPS standard)
+#[derive(Debug, PartialEq, Eq, Clone, DdsType)]
+pub struct ParticipantMessageData {
+    #[dust_dds(key)]
+    pub participant_guid_prefix: [u8; 12],
+    #[dust_dds(key)]
+    pub kind: [u8; 4],
+    pub data: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::{DdsDeserialize, DdsSerialize};
+
+    #[test]
+    fn serialize_participant_message_data() {
+        let data = ParticipantMessageData {
+            participant_guid_prefix: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+            kind: PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
+            data: vec![],
+        };
+        let expected = [
+            0x00, 0x01, 0x00, 0x00, // CDR_LE
+            1, 2, 3, 4, // participant_guid_prefix
+            5, 6, 7, 8, //
+            9, 10, 11, 12, //
+            0, 0, 0, 2, // kind
+            0, 0, 0, 0, // data length
+        ];
+        assert_eq!(data.serialize_data().unwrap(), expected);
+    }
+
+    #[test]
+    fn deserialize_participant_message_data() {
+        let expected = ParticipantMessageData {
+            participant_guid_prefix: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+            kind: PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE,
+            data: vec![7],
+        };
+        let data = [
+            0x00, 0x01, 0x00, 0x00, // CDR_LE
+            1, 2, 3, 4, // participant_guid_prefix
+            5, 6, 7, 8, //
+            9, 10, 11, 12, //
+            0, 0, 0, 1, // kind
+            1, 0, 0, 0, // data length
+            7, 0, 0, 0, // data
+        ];
+        assert_eq!(
+            ParticipantMessageData::deserialize_data(&data).unwrap(),
+            expected
+        );
+    }
+}
@@ -141,6 +141,19 @@ impl<'de> ParameterListCdrDeserializer<'de> {
         Ok(collection)
     }
 
+    pub fn read_optional<T>(&self, pid: ParameterId) -> Result<Option<T>, RtpsError>
+    where
+        T: XTypesDeserialize<'de>,
+    {
+        let mut iterator = self.iter();
+        while let Some(parameter) = iterator.next()? {
+            if parameter.pid == pid {
+                return parameter.deserialize(self.endianness).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
     pub fn read_with_default<T>(&self, pid: ParameterId, default: T) -> Result<T, RtpsError>
     where
         T: XTypesDeserialize<'de>,
@@ -1,6 +1,6 @@
 use dust_dds_derive::XTypesDeserialize;
 
-use crate::xtypes::serialize::XTypesSerialize;
+use crate::{transport::types::Guid, xtypes::serialize::XTypesSerialize};
 
 #[derive(Clone, Copy, PartialEq, Eq, XTypesSerialize, XTypesDeserialize, Debug)]
 pub struct KeyHash(pub [u8; 16]);
@@ -11,3 +11,17 @@ pub const STATUS_INFO_DISPOSED: StatusInfo = StatusInfo([0, 0, 0, 0b00000001]);
 pub const STATUS_INFO_UNREGISTERED: StatusInfo = StatusInfo([0, 0, 0, 0b0000010]);
 pub const STATUS_INFO_DISPOSED_UNREGISTERED: StatusInfo = StatusInfo([0, 0, 0, 0b00000011]);
 pub const STATUS_INFO_FILTERED: StatusInfo = StatusInfo([0, 0, 0, 0b0000100]);
+
+/// Digest identifying the set of writers which took part in a group coherent set.
+/// It is computed over the entity ids of the writers so that it does not depend
+/// on the order in which they are given.
+pub fn writer_group_digest(writer_guids: impl IntoIterator<Item = Guid>) -> [u8; 4] {
+    let mut guids: Vec<[u8; 16]> = writer_guids.into_iter().map(<[u8; 16]>::from).collect();
+    guids.sort();
+    let mut context = md5::Context::new();
+    for guid in &guids {
+        context.consume(&guid[12..]);
+    }
+    let digest = context.compute();
+    [digest[0], digest[1], digest[2], digest[3]]
+}
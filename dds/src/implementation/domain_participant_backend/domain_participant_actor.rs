@@ -1,18 +1,191 @@
-use super::{entities::domain_participant::DomainParticipantEntity, handle::InstanceHandleCounter};
+use super::{
+    entities::domain_participant::DomainParticipantEntity, handle::InstanceHandleCounter,
+    services::durability_service::DurabilityServiceTopic,
+};
 use crate::{
     dds_async::{
         data_reader::DataReaderAsync, data_writer::DataWriterAsync,
         domain_participant::DomainParticipantAsync, publisher::PublisherAsync,
         subscriber::SubscriberAsync, topic::TopicAsync,
     },
+    durability::{
+        DurabilityStore, DurabilityTopicKey, FileDurabilityStore, InMemoryDurabilityStore,
+    },
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
+        qos_policy::DurabilityQosPolicyKind,
     },
     runtime::{actor::ActorAddress, executor::Executor, timer::TimerDriver},
-    transport::participant::TransportParticipant,
+    transport::{participant::TransportParticipant, types::GuidPrefix},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
 };
 
+/// Stores used by the durability service to keep the samples of the
+/// writers with a transient or persistent durability. They are shared by all
+/// the participants of the application.
+#[derive(Clone)]
+pub struct DurabilityStores {
+    pub transient: Arc<dyn DurabilityStore>,
+    pub persistent: Option<Arc<dyn DurabilityStore>>,
+    services: Arc<Mutex<DurabilityServices>>,
+}
+
+#[derive(Default)]
+struct DurabilityServices {
+    // Writers whose samples are still served by the writers themselves
+    live_writers: HashSet<InstanceHandle>,
+    // Participants with a durability service for each topic. Only the first one
+    // serves the samples, the next one takes over when it stops.
+    providers: HashMap<
+        (DurabilityQosPolicyKind, DurabilityTopicKey),
+        Vec<ActorAddress<DomainParticipantActor>>,
+    >,
+}
+
+impl DurabilityStores {
+    pub fn get(&self, kind: DurabilityQosPolicyKind) -> Option<&dyn DurabilityStore> {
+        match kind {
+            DurabilityQosPolicyKind::Volatile | DurabilityQosPolicyKind::TransientLocal => None,
+            DurabilityQosPolicyKind::Transient => Some(self.transient.as_ref()),
+            DurabilityQosPolicyKind::Persistent => self.persistent.as_deref(),
+        }
+    }
+
+    pub fn add_live_writer(&self, writer_handle: InstanceHandle) {
+        self.services
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .live_writers
+            .insert(writer_handle);
+    }
+
+    pub fn remove_live_writer(&self, writer_handle: &InstanceHandle) {
+        self.services
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .live_writers
+            .remove(writer_handle);
+    }
+
+    pub fn is_live_writer(&self, writer_handle: &InstanceHandle) -> bool {
+        self.services
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .live_writers
+            .contains(writer_handle)
+    }
+
+    /// Add the participant to the providers of the topic and return the one serving its samples
+    pub fn add_provider(
+        &self,
+        kind: DurabilityQosPolicyKind,
+        topic: &DurabilityTopicKey,
+        participant_address: &ActorAddress<DomainParticipantActor>,
+    ) -> ActorAddress<DomainParticipantActor> {
+        let mut services = self.services.lock().expect("Mutex shouldn't be poisoned");
+        let providers = services.providers.entry((kind, topic.clone())).or_default();
+        providers.retain(|p| !p.is_closed());
+        if !providers.contains(participant_address) {
+            providers.push(participant_address.clone());
+        }
+        providers[0].clone()
+    }
+
+    /// Remove the participant from the providers of the topic and return the one serving its samples, if any
+    pub fn remove_provider(
+        &self,
+        kind: DurabilityQosPolicyKind,
+        topic: &DurabilityTopicKey,
+        participant_address: &ActorAddress<DomainParticipantActor>,
+    ) -> Option<ActorAddress<DomainParticipantActor>> {
+        let mut services = self.services.lock().expect("Mutex shouldn't be poisoned");
+        let key = (kind, topic.clone());
+        let providers = services.providers.get_mut(&key)?;
+        providers.retain(|p| !p.is_closed() && p != participant_address);
+        let provider = providers.first().cloned();
+        if provider.is_none() {
+            services.providers.remove(&key);
+        }
+        provider
+    }
+
+    /// Participant serving the samples of the topic, if any
+    pub fn provider(
+        &self,
+        kind: DurabilityQosPolicyKind,
+        topic: &DurabilityTopicKey,
+    ) -> Option<ActorAddress<DomainParticipantActor>> {
+        self.services
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .providers
+            .get(&(kind, topic.clone()))?
+            .iter()
+            .find(|p| !p.is_closed())
+            .cloned()
+    }
+}
+
+impl Default for DurabilityStores {
+    fn default() -> Self {
+        Self {
+            transient: Arc::new(InMemoryDurabilityStore::new()),
+            persistent: FileDurabilityStore::user_data_directory().map(|directory| {
+                Arc::new(FileDurabilityStore::new(directory)) as Arc<dyn DurabilityStore>
+            }),
+            services: Default::default(),
+        }
+    }
+}
+
+/// Participants of the application by their GUID prefix. Their endpoints
+/// exchange the changes directly instead of through the transport.
+#[derive(Clone, Default)]
+pub struct LocalParticipants {
+    participants: Arc<Mutex<HashMap<GuidPrefix, ActorAddress<DomainParticipantActor>>>>,
+}
+
+impl LocalParticipants {
+    pub fn add(
+        &self,
+        guid_prefix: GuidPrefix,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) {
+        self.participants
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .insert(guid_prefix, participant_address);
+    }
+
+    pub fn remove(&self, guid_prefix: &GuidPrefix) {
+        self.participants
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .remove(guid_prefix);
+    }
+
+    /// Address of the other participant if both of them exchange the changes directly
+    pub fn get(
+        &self,
+        guid_prefix: &GuidPrefix,
+        other_guid_prefix: &GuidPrefix,
+    ) -> Option<ActorAddress<DomainParticipantActor>> {
+        let participants = self
+            .participants
+            .lock()
+            .expect("Mutex shouldn't be poisoned");
+        participants.get(guid_prefix)?;
+        participants
+            .get(other_guid_prefix)
+            .filter(|p| !p.is_closed())
+            .cloned()
+    }
+}
+
 pub struct DomainParticipantActor {
     pub transport: Box<dyn TransportParticipant>,
     pub instance_handle_counter: InstanceHandleCounter,
@@ -21,9 +194,14 @@ pub struct DomainParticipantActor {
     pub backend_executor: Executor,
     pub listener_executor: Executor,
     pub timer_driver: TimerDriver,
+    pub durability_stores: DurabilityStores,
+    pub local_participants: LocalParticipants,
+    pub durability_service_topics:
+        HashMap<(DurabilityQosPolicyKind, DurabilityTopicKey), DurabilityServiceTopic>,
 }
 
 impl DomainParticipantActor {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         domain_participant: DomainParticipantEntity,
         transport: Box<dyn TransportParticipant>,
@@ -31,6 +209,8 @@ impl DomainParticipantActor {
         listener_executor: Executor,
         timer_driver: TimerDriver,
         instance_handle_counter: InstanceHandleCounter,
+        durability_stores: DurabilityStores,
+        local_participants: LocalParticipants,
     ) -> Self {
         Self {
             transport,
@@ -40,6 +220,9 @@ impl DomainParticipantActor {
             backend_executor,
             listener_executor,
             timer_driver,
+            durability_stores,
+            local_participants,
+            durability_service_topics: HashMap::new(),
         }
     }
 
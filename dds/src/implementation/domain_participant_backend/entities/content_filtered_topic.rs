@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{
+    implementation::content_filter::filter::ContentFilter, xtypes::dynamic_type::DynamicType,
+};
+
+pub struct ContentFilteredTopicEntity {
+    content_filter: ContentFilter,
+    // Keeps the related topic from being deleted while the content filtered topic exists
+    _type_support: Arc<dyn DynamicType + Send + Sync>,
+}
+
+impl ContentFilteredTopicEntity {
+    pub fn new(
+        content_filter: ContentFilter,
+        type_support: Arc<dyn DynamicType + Send + Sync>,
+    ) -> Self {
+        Self {
+            content_filter,
+            _type_support: type_support,
+        }
+    }
+
+    pub fn topic_name(&self) -> &str {
+        self.content_filter.content_filtered_topic_name()
+    }
+
+    pub fn content_filter(&self) -> &ContentFilter {
+        &self.content_filter
+    }
+
+    pub fn content_filter_mut(&mut self) -> &mut ContentFilter {
+        &mut self.content_filter
+    }
+}
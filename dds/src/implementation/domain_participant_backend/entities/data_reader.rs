@@ -6,6 +6,7 @@ use std::{
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
     implementation::{
+        content_filter::filter::{ContentFilter, SqlFilter},
         listeners::data_reader_listener::DataReaderListenerActor,
         status_condition::status_condition_actor::{self, StatusConditionActor},
         xtypes_glue::key_and_instance_handle::{
@@ -25,7 +26,7 @@ use crate::{
             RequestedIncompatibleQosStatus, SampleLostStatus, SampleRejectedStatus,
             SampleRejectedStatusKind, StatusKind, SubscriptionMatchedStatus,
         },
-        time::{DurationKind, Time},
+        time::{Duration, DurationKind, Time},
     },
     runtime::{actor::Actor, executor::TaskHandle},
     subscription::sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
@@ -37,7 +38,33 @@ use crate::{
     xtypes::dynamic_type::DynamicType,
 };
 
-type SampleList = Vec<(Option<Arc<[u8]>>, SampleInfo)>;
+use super::{data_writer::SharedValue, read_condition::ReadConditionEntity};
+
+/// Data of a sample, together with the value itself when it was written by a
+/// writer of this application which shared it
+#[derive(Clone)]
+pub struct SampleData {
+    pub serialized_data: Arc<[u8]>,
+    pub shared_value: Option<SharedValue>,
+}
+
+type SampleList = Vec<(Option<SampleData>, SampleInfo)>;
+
+fn is_sample_accepted_by_query(
+    sample: &ReaderSample,
+    query: Option<&SqlFilter>,
+    type_support: &dyn DynamicType,
+) -> bool {
+    // Dispose and unregister notifications only carry the key and are not
+    // evaluated against the query
+    sample.kind != ChangeKind::Alive
+        || query.is_none_or(|q| q.is_sample_accepted(&sample.data_value, type_support))
+}
+
+struct WriterLiveliness {
+    alive: bool,
+    last_assertion: Time,
+}
 
 pub enum AddChangeResult {
     Added(InstanceHandle),
@@ -50,6 +77,8 @@ struct InstanceState {
     instance_state: InstanceStateKind,
     most_recent_disposed_generation_count: i32,
     most_recent_no_writers_generation_count: i32,
+    // Writers which wrote the instance and haven't unregistered it
+    writers: HashSet<[u8; 16]>,
 }
 
 impl InstanceState {
@@ -59,17 +88,30 @@ impl InstanceState {
             instance_state: InstanceStateKind::Alive,
             most_recent_disposed_generation_count: 0,
             most_recent_no_writers_generation_count: 0,
+            writers: HashSet::new(),
         }
     }
 
-    fn update_state(&mut self, change_kind: ChangeKind) {
+    fn update_state(&mut self, change_kind: ChangeKind, writer_guid: [u8; 16]) {
+        match change_kind {
+            ChangeKind::Alive | ChangeKind::AliveFiltered => {
+                self.writers.insert(writer_guid);
+            }
+            ChangeKind::NotAliveUnregistered | ChangeKind::NotAliveDisposedUnregistered => {
+                self.writers.remove(&writer_guid);
+            }
+            ChangeKind::NotAliveDisposed => (),
+        }
+
         match self.instance_state {
             InstanceStateKind::Alive => {
                 if change_kind == ChangeKind::NotAliveDisposed
                     || change_kind == ChangeKind::NotAliveDisposedUnregistered
                 {
                     self.instance_state = InstanceStateKind::NotAliveDisposed;
-                } else if change_kind == ChangeKind::NotAliveUnregistered {
+                } else if change_kind == ChangeKind::NotAliveUnregistered && self.writers.is_empty()
+                {
+                    // The instance stays alive as long as any other writer writes it
                     self.instance_state = InstanceStateKind::NotAliveNoWriters;
                 }
             }
@@ -111,17 +153,59 @@ pub struct ReaderSample {
     pub instance_handle: InstanceHandle,
     pub source_timestamp: Option<Time>,
     pub data_value: Arc<[u8]>,
+    pub shared_value: Option<SharedValue>,
     pub sample_state: SampleStateKind,
     pub disposed_generation_count: i32,
     pub no_writers_generation_count: i32,
     pub reception_timestamp: Time,
+    pub group_sequence_number: Option<i64>,
+    pub lifespan: DurationKind,
+    // Query conditions whose query accepts the sample. The queries are evaluated
+    // once when the sample is added instead of every time a condition is checked.
+    pub accepted_by_queries: Vec<InstanceHandle>,
+}
+
+impl ReaderSample {
+    /// The lifespan of a sample is the one offered by the writer which sent it and
+    /// starts at its source timestamp, or at its reception if it has none.
+    fn is_expired(&self, now: Time) -> bool {
+        match self.lifespan {
+            DurationKind::Finite(lifespan) => {
+                now - self.source_timestamp.unwrap_or(self.reception_timestamp) >= lifespan
+            }
+            DurationKind::Infinite => false,
+        }
+    }
+}
+
+/// Position of a sample among the samples of all the readers of a subscriber with
+/// ordered access and group scope. The samples of a publisher are ordered by the
+/// group sequence number it assigned to them and otherwise by reception time.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub struct GroupSampleOrder {
+    group_sequence_number: Option<([u8; 12], i64)>,
+    reception_timestamp: Time,
 }
 
 pub struct IndexedSample {
     pub index: usize,
-    pub sample: (Option<Arc<[u8]>>, SampleInfo),
+    pub sample: (Option<SampleData>, SampleInfo),
+}
+
+struct CoherentSet {
+    writer_guid: Guid,
+    coherent_set: i64,
+    group_coherent_set: Option<i64>,
+    complete: bool,
+    // False once a change of the set is known to be lost
+    intact: bool,
+    changes: Vec<CacheChange>,
 }
 
+/// Maximum number of coherent sets held back by a reader. The oldest sets are
+/// discarded beyond this limit.
+const MAX_HELD_COHERENT_SETS: usize = 64;
+
 pub enum TransportReaderKind {
     Stateful(Box<dyn TransportStatefulReader>),
     Stateless(Box<dyn TransportStatelessReader>),
@@ -143,7 +227,8 @@ pub struct DataReaderEntity {
     topic_name: String,
     type_name: String,
     type_support: Arc<dyn DynamicType + Send + Sync>,
-    _liveliness_changed_status: LivelinessChangedStatus,
+    liveliness_changed_status: LivelinessChangedStatus,
+    writer_liveliness: HashMap<InstanceHandle, WriterLiveliness>,
     requested_deadline_missed_status: RequestedDeadlineMissedStatus,
     requested_incompatible_qos_status: RequestedIncompatibleQosStatus,
     _sample_lost_status: SampleLostStatus,
@@ -158,8 +243,19 @@ pub struct DataReaderEntity {
     listener_mask: Vec<StatusKind>,
     instances: HashMap<InstanceHandle, InstanceState>,
     instance_deadline_missed_task: HashMap<InstanceHandle, TaskHandle>,
+    instance_autopurge_task: HashMap<InstanceHandle, TaskHandle>,
+    lifespan_timer: Option<(Time, TaskHandle)>,
     instance_ownership: HashMap<InstanceHandle, [u8; 16]>,
     transport_reader: TransportReaderKind,
+    content_filter: Option<ContentFilter>,
+    read_conditions: Vec<ReadConditionEntity>,
+    coherent_sets: Vec<CoherentSet>,
+    // Changes reported lost by the transport since the last change received from
+    // each writer
+    lost_changes: HashMap<InstanceHandle, (i64, i64)>,
+    // Values shared by the writers of this application, by writer and sequence
+    // number, until their change is added
+    shared_values: HashMap<(InstanceHandle, i64), SharedValue>,
 }
 
 impl DataReaderEntity {
@@ -174,6 +270,7 @@ impl DataReaderEntity {
         listener: Option<Actor<DataReaderListenerActor>>,
         listener_mask: Vec<StatusKind>,
         transport_reader: TransportReaderKind,
+        content_filter: Option<ContentFilter>,
     ) -> Self {
         Self {
             instance_handle,
@@ -182,7 +279,8 @@ impl DataReaderEntity {
             topic_name,
             type_name,
             type_support,
-            _liveliness_changed_status: LivelinessChangedStatus::default(),
+            liveliness_changed_status: LivelinessChangedStatus::default(),
+            writer_liveliness: HashMap::new(),
             requested_deadline_missed_status: RequestedDeadlineMissedStatus::default(),
             requested_incompatible_qos_status: RequestedIncompatibleQosStatus::default(),
             _sample_lost_status: SampleLostStatus::default(),
@@ -197,8 +295,15 @@ impl DataReaderEntity {
             listener_mask,
             instances: HashMap::new(),
             instance_deadline_missed_task: HashMap::new(),
+            instance_autopurge_task: HashMap::new(),
+            lifespan_timer: None,
             instance_ownership: HashMap::new(),
             transport_reader,
+            content_filter,
+            read_conditions: Vec::new(),
+            coherent_sets: Vec::new(),
+            lost_changes: HashMap::new(),
+            shared_values: HashMap::new(),
         }
     }
 
@@ -209,6 +314,83 @@ impl DataReaderEntity {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: Option<InstanceHandle>,
+    ) -> DdsResult<SampleList> {
+        self.read_samples(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            specific_instance_handle,
+            None,
+        )
+    }
+
+    pub fn take(
+        &mut self,
+        max_samples: i32,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+        specific_instance_handle: Option<InstanceHandle>,
+    ) -> DdsResult<SampleList> {
+        self.take_samples(
+            max_samples,
+            &sample_states,
+            &view_states,
+            &instance_states,
+            specific_instance_handle,
+            None,
+        )
+    }
+
+    pub fn read_w_condition(
+        &mut self,
+        max_samples: i32,
+        condition_handle: InstanceHandle,
+    ) -> DdsResult<SampleList> {
+        let read_condition = self.get_read_condition(condition_handle)?;
+        let sample_states = read_condition.sample_state_mask().to_vec();
+        let view_states = read_condition.view_state_mask().to_vec();
+        let instance_states = read_condition.instance_state_mask().to_vec();
+        let query = read_condition.query().cloned();
+        self.read_samples(
+            max_samples,
+            &sample_states,
+            &view_states,
+            &instance_states,
+            None,
+            query.as_ref(),
+        )
+    }
+
+    pub fn take_w_condition(
+        &mut self,
+        max_samples: i32,
+        condition_handle: InstanceHandle,
+    ) -> DdsResult<SampleList> {
+        let read_condition = self.get_read_condition(condition_handle)?;
+        let sample_states = read_condition.sample_state_mask().to_vec();
+        let view_states = read_condition.view_state_mask().to_vec();
+        let instance_states = read_condition.instance_state_mask().to_vec();
+        let query = read_condition.query().cloned();
+        self.take_samples(
+            max_samples,
+            &sample_states,
+            &view_states,
+            &instance_states,
+            None,
+            query.as_ref(),
+        )
+    }
+
+    fn read_samples(
+        &mut self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        specific_instance_handle: Option<InstanceHandle>,
+        query: Option<&SqlFilter>,
     ) -> DdsResult<SampleList> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -218,6 +400,7 @@ impl DataReaderEntity {
             .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::DataAvailable,
             });
+        self.data_available_status_changed_flag = false;
 
         let indexed_sample_list = self.create_indexed_sample_collection(
             max_samples,
@@ -225,6 +408,7 @@ impl DataReaderEntity {
             view_states,
             instance_states,
             specific_instance_handle,
+            query,
         )?;
 
         let change_index_list: Vec<usize>;
@@ -242,13 +426,14 @@ impl DataReaderEntity {
         Ok(samples)
     }
 
-    pub fn take(
+    fn take_samples(
         &mut self,
         max_samples: i32,
-        sample_states: Vec<SampleStateKind>,
-        view_states: Vec<ViewStateKind>,
-        instance_states: Vec<InstanceStateKind>,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
         specific_instance_handle: Option<InstanceHandle>,
+        query: Option<&SqlFilter>,
     ) -> DdsResult<SampleList> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -256,16 +441,18 @@ impl DataReaderEntity {
 
         let indexed_sample_list = self.create_indexed_sample_collection(
             max_samples,
-            &sample_states,
-            &view_states,
-            &instance_states,
+            sample_states,
+            view_states,
+            instance_states,
             specific_instance_handle,
+            query,
         )?;
 
         self.status_condition
             .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::DataAvailable,
             });
+        self.data_available_status_changed_flag = false;
 
         let mut change_index_list: Vec<usize>;
         let samples;
@@ -289,6 +476,7 @@ impl DataReaderEntity {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: Option<InstanceHandle>,
+        query: Option<&SqlFilter>,
     ) -> DdsResult<Vec<IndexedSample>> {
         if let Some(h) = specific_instance_handle {
             if !self.instances.contains_key(&h) {
@@ -313,6 +501,7 @@ impl DataReaderEntity {
                     } else {
                         true
                     }
+                    && is_sample_accepted_by_query(cc, query, self.type_support.as_ref())
             })
             .take(max_samples as usize)
         {
@@ -323,7 +512,7 @@ impl DataReaderEntity {
             instances_in_collection
                 .get_mut(&cache_change.instance_handle)
                 .unwrap()
-                .update_state(cache_change.kind);
+                .update_state(cache_change.kind, cache_change.writer_guid);
             let sample_state = cache_change.sample_state;
             let view_state = self.instances[&cache_change.instance_handle].view_state;
             let instance_state = self.instances[&cache_change.instance_handle].instance_state;
@@ -338,9 +527,13 @@ impl DataReaderEntity {
                         .most_recent_no_writers_generation_count);
 
             let (data, valid_data) = match cache_change.kind {
-                ChangeKind::Alive | ChangeKind::AliveFiltered => {
-                    (Some(cache_change.data_value.clone()), true)
-                }
+                ChangeKind::Alive | ChangeKind::AliveFiltered => (
+                    Some(SampleData {
+                        serialized_data: cache_change.data_value.clone(),
+                        shared_value: cache_change.shared_value.clone(),
+                    }),
+                    true,
+                ),
                 ChangeKind::NotAliveDisposed
                 | ChangeKind::NotAliveUnregistered
                 | ChangeKind::NotAliveDisposedUnregistered => (None, false),
@@ -481,6 +674,7 @@ impl DataReaderEntity {
     fn convert_cache_change_to_sample(
         &mut self,
         cache_change: CacheChange,
+        shared_value: Option<SharedValue>,
         reception_timestamp: Time,
     ) -> DdsResult<ReaderSample> {
         let instance_handle = {
@@ -510,7 +704,7 @@ impl DataReaderEntity {
                 self.instances
                     .entry(instance_handle)
                     .or_insert_with(InstanceState::new)
-                    .update_state(cache_change.kind);
+                    .update_state(cache_change.kind, cache_change.writer_guid.into());
                 Ok(())
             }
             ChangeKind::NotAliveDisposed
@@ -518,7 +712,7 @@ impl DataReaderEntity {
             | ChangeKind::NotAliveDisposedUnregistered => {
                 match self.instances.get_mut(&instance_handle) {
                     Some(instance) => {
-                        instance.update_state(cache_change.kind);
+                        instance.update_state(cache_change.kind, cache_change.writer_guid.into());
                         Ok(())
                     }
                     None => Err(DdsError::Error(
@@ -528,28 +722,222 @@ impl DataReaderEntity {
             }
         }?;
 
+        let lifespan = self
+            .matched_publication_list
+            .get(&InstanceHandle::new(cache_change.writer_guid.into()))
+            .map_or(DurationKind::Infinite, |p| p.lifespan().duration);
+
         Ok(ReaderSample {
             kind: cache_change.kind,
             writer_guid: cache_change.writer_guid.into(),
             instance_handle,
             source_timestamp: cache_change.source_timestamp.map(Into::into),
             data_value: cache_change.data_value.clone(),
+            shared_value,
             sample_state: SampleStateKind::NotRead,
             disposed_generation_count: self.instances[&instance_handle]
                 .most_recent_disposed_generation_count,
             no_writers_generation_count: self.instances[&instance_handle]
                 .most_recent_no_writers_generation_count,
             reception_timestamp,
+            group_sequence_number: cache_change.group_sequence_number,
+            lifespan,
+            accepted_by_queries: Vec::new(),
         })
     }
 
+    pub fn get_group_sample_order(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> Vec<GroupSampleOrder> {
+        self.sample_list
+            .iter()
+            .filter(|s| {
+                sample_states.contains(&s.sample_state)
+                    && view_states.contains(&self.instances[&s.instance_handle].view_state)
+                    && instance_states.contains(&self.instances[&s.instance_handle].instance_state)
+            })
+            .map(|s| {
+                let mut writer_guid_prefix = [0; 12];
+                writer_guid_prefix.copy_from_slice(&s.writer_guid[..12]);
+                GroupSampleOrder {
+                    group_sequence_number: s.group_sequence_number.map(|n| (writer_guid_prefix, n)),
+                    reception_timestamp: s.reception_timestamp,
+                }
+            })
+            .collect()
+    }
+
+    /// Holds back the changes which belong to a coherent set until the writer signals
+    /// the end of the set. Returns the changes which can be made available to the
+    /// application, in the order in which they must be added. With group access the
+    /// completed sets which are part of a group coherent set are only returned by
+    /// [`Self::take_group_coherent_changes`]. Sets with lost changes are
+    /// discarded so that the application never observes a partial set. Sequence
+    /// numbers skipped by the writer, for example because the change was replaced
+    /// in its history, are not lost.
+    pub fn add_coherent_change(
+        &mut self,
+        cache_change: CacheChange,
+        group_access: bool,
+    ) -> Vec<CacheChange> {
+        let sequence_number = cache_change.sequence_number;
+        let lost_changes = self
+            .lost_changes
+            .remove(&InstanceHandle::new(cache_change.writer_guid.into()));
+        let is_any_lost = |first: i64, last: i64| {
+            lost_changes.is_some_and(|(lost_first, lost_last)| {
+                lost_first <= last && first <= lost_last
+            })
+        };
+
+        // Receiving a change which is not part of the set in progress also ends it.
+        // Only the change signalling the end of the set may be lost in that case.
+        for coherent_set in self.coherent_sets.iter_mut().filter(|s| {
+            s.writer_guid == cache_change.writer_guid
+                && Some(s.coherent_set) != cache_change.coherent_set
+                && !s.complete
+        }) {
+            coherent_set.intact &= !is_any_lost(coherent_set.coherent_set, sequence_number - 2);
+            coherent_set.complete = true;
+        }
+
+        let mut remaining_change = None;
+        if let Some(coherent_set) = cache_change.coherent_set {
+            let position = self.coherent_sets.iter().position(|s| {
+                s.writer_guid == cache_change.writer_guid && s.coherent_set == coherent_set
+            });
+            let intact = !is_any_lost(coherent_set, sequence_number - 1);
+            if cache_change.is_end_of_coherent_set() {
+                if let Some(i) = position {
+                    self.coherent_sets[i].intact &= intact;
+                    self.coherent_sets[i].complete = true;
+                }
+            } else if let Some(i) = position {
+                self.coherent_sets[i].intact &= intact;
+                self.coherent_sets[i].changes.push(cache_change);
+            } else {
+                // The set is identified by the sequence number of its first change
+                // so its start is known even when that change is not received
+                self.coherent_sets.push(CoherentSet {
+                    writer_guid: cache_change.writer_guid,
+                    coherent_set,
+                    group_coherent_set: cache_change.group_coherent_set,
+                    complete: false,
+                    intact,
+                    changes: vec![cache_change],
+                });
+                if self.coherent_sets.len() > MAX_HELD_COHERENT_SETS {
+                    let discarded_set = self.coherent_sets.remove(0);
+                    self.remove_shared_values(&discarded_set.changes);
+                }
+            }
+        } else {
+            remaining_change = Some(cache_change);
+        }
+
+        let mut changes = Vec::new();
+        let mut i = 0;
+        while i < self.coherent_sets.len() {
+            let coherent_set = &self.coherent_sets[i];
+            if coherent_set.complete && !coherent_set.intact {
+                let discarded_set = self.coherent_sets.remove(i);
+                self.remove_shared_values(&discarded_set.changes);
+            } else if coherent_set.complete
+                && !(group_access && coherent_set.group_coherent_set.is_some())
+            {
+                changes.append(&mut self.coherent_sets.remove(i).changes);
+            } else {
+                i += 1;
+            }
+        }
+        changes.extend(remaining_change);
+        changes
+    }
+
+    /// Keeps the value shared by a writer of this application until its change is
+    /// added. The values of writers which aren't matched are discarded like their
+    /// changes.
+    pub fn add_shared_value(&mut self, cache_change: &CacheChange, shared_value: SharedValue) {
+        let writer_handle = InstanceHandle::new(cache_change.writer_guid.into());
+        if self.matched_publication_list.contains_key(&writer_handle) {
+            self.shared_values
+                .insert((writer_handle, cache_change.sequence_number), shared_value);
+        }
+    }
+
+    fn remove_shared_values(&mut self, cache_changes: &[CacheChange]) {
+        for cache_change in cache_changes {
+            self.shared_values.remove(&(
+                InstanceHandle::new(cache_change.writer_guid.into()),
+                cache_change.sequence_number,
+            ));
+        }
+    }
+
+    /// Records changes of a writer which were lost so that the coherent sets they
+    /// belong to are discarded once the next change of the writer is received.
+    pub fn add_lost_changes(
+        &mut self,
+        writer_guid: Guid,
+        first_sequence_number: i64,
+        last_sequence_number: i64,
+    ) {
+        let lost_changes = self
+            .lost_changes
+            .entry(InstanceHandle::new(writer_guid.into()))
+            .or_insert((first_sequence_number, last_sequence_number));
+        lost_changes.0 = lost_changes.0.min(first_sequence_number);
+        lost_changes.1 = lost_changes.1.max(last_sequence_number);
+    }
+
+    pub fn take_group_coherent_changes(
+        &mut self,
+        writer_guids: &[Guid],
+        group_coherent_set: i64,
+    ) -> Vec<CacheChange> {
+        let mut changes = Vec::new();
+        let mut i = 0;
+        while i < self.coherent_sets.len() {
+            let coherent_set = &self.coherent_sets[i];
+            if coherent_set.complete
+                && coherent_set.group_coherent_set == Some(group_coherent_set)
+                && writer_guids.contains(&coherent_set.writer_guid)
+            {
+                changes.append(&mut self.coherent_sets.remove(i).changes);
+            } else {
+                i += 1;
+            }
+        }
+        changes
+    }
+
     pub fn add_reader_change(
         &mut self,
         cache_change: CacheChange,
         reception_timestamp: Time,
     ) -> DdsResult<AddChangeResult> {
-        let sample = self.convert_cache_change_to_sample(cache_change, reception_timestamp)?;
+        let shared_value = self.shared_values.remove(&(
+            InstanceHandle::new(cache_change.writer_guid.into()),
+            cache_change.sequence_number,
+        ));
+        if let Some(content_filter) = &self.content_filter {
+            if cache_change.kind == ChangeKind::Alive
+                && !content_filter
+                    .is_sample_accepted(&cache_change.data_value, self.type_support.as_ref())
+            {
+                return Ok(AddChangeResult::NotAdded);
+            }
+        }
+
+        let mut sample =
+            self.convert_cache_change_to_sample(cache_change, shared_value, reception_timestamp)?;
         let change_instance_handle = sample.instance_handle;
+        if sample.is_expired(reception_timestamp) {
+            return Ok(AddChangeResult::NotAdded);
+        }
         // data_reader exclusive access if the writer is not the allowed to write the sample do an early return
         if self.qos.ownership.kind == OwnershipQosPolicyKind::Exclusive {
             // Get the InstanceHandle of the data writer owning this instance
@@ -682,7 +1070,7 @@ impl DataReaderEntity {
                 self.instances
                     .entry(sample.instance_handle)
                     .or_insert_with(InstanceState::new)
-                    .update_state(sample.kind);
+                    .update_state(sample.kind, sample.writer_guid);
                 Ok(())
             }
             ChangeKind::NotAliveDisposed
@@ -690,7 +1078,7 @@ impl DataReaderEntity {
             | ChangeKind::NotAliveDisposedUnregistered => {
                 match self.instances.get_mut(&sample.instance_handle) {
                     Some(instance) => {
-                        instance.update_state(sample.kind);
+                        instance.update_state(sample.kind, sample.writer_guid);
                         Ok(())
                     }
                     None => Err(DdsError::Error(
@@ -700,6 +1088,15 @@ impl DataReaderEntity {
             }
         }?;
 
+        sample.accepted_by_queries = self
+            .read_conditions
+            .iter()
+            .filter(|c| {
+                c.query().is_some()
+                    && is_sample_accepted_by_query(&sample, c.query(), self.type_support.as_ref())
+            })
+            .map(|c| c.instance_handle())
+            .collect();
         tracing::debug!(cache_change = ?sample, "Adding change to data reader history cache");
         self.sample_list.push(sample);
         self.data_available_status_changed_flag = true;
@@ -729,13 +1126,105 @@ impl DataReaderEntity {
             t.abort();
         }
 
+        if self.instances[&change_instance_handle].instance_state == InstanceStateKind::Alive {
+            if let Some(t) = self.instance_autopurge_task.remove(&change_instance_handle) {
+                t.abort();
+            }
+        }
+
         Ok(AddChangeResult::Added(change_instance_handle))
     }
 
+    /// Remove the samples whose lifespan has elapsed. This is done when the lifespan
+    /// timer of the reader fires, which is then no longer armed.
+    pub fn remove_expired_samples(&mut self, now: Time) {
+        self.lifespan_timer = None;
+        self.sample_list.retain(|s| !s.is_expired(now));
+    }
+
+    /// Earliest time at which the lifespan of one of the samples elapses. Samples whose
+    /// lifespan ends beyond the range of [`Time`] never expire.
+    pub fn next_sample_expiration(&self) -> Option<Time> {
+        self.sample_list
+            .iter()
+            .filter_map(|s| match s.lifespan {
+                DurationKind::Finite(lifespan) => s
+                    .source_timestamp
+                    .unwrap_or(s.reception_timestamp)
+                    .checked_add(lifespan),
+                DurationKind::Infinite => None,
+            })
+            .min()
+    }
+
+    /// Time at which the armed lifespan timer of the reader fires
+    pub fn lifespan_timer_expiration(&self) -> Option<Time> {
+        self.lifespan_timer
+            .as_ref()
+            .map(|(expiration, _)| *expiration)
+    }
+
+    /// Arm the lifespan timer of the reader, replacing the one previously armed. The
+    /// timer is disarmed if there is no task.
+    pub fn set_lifespan_timer(&mut self, lifespan_timer: Option<(Time, TaskHandle)>) {
+        if let Some((_, task)) = std::mem::replace(&mut self.lifespan_timer, lifespan_timer) {
+            task.abort();
+        }
+    }
+
+    /// Delay after which the samples of a not alive instance must be purged according
+    /// to the reader data lifecycle QoS. Returns [`None`] if the instance is alive, the
+    /// delay is infinite or the purge of the instance is already scheduled.
+    pub fn instance_autopurge_delay(&self, instance_handle: &InstanceHandle) -> Option<Duration> {
+        if self.instance_autopurge_task.contains_key(instance_handle) {
+            return None;
+        }
+        let delay = match self.instances.get(instance_handle)?.instance_state {
+            InstanceStateKind::Alive => DurationKind::Infinite,
+            InstanceStateKind::NotAliveDisposed => {
+                self.qos
+                    .reader_data_lifecycle
+                    .autopurge_disposed_samples_delay
+            }
+            InstanceStateKind::NotAliveNoWriters => {
+                self.qos
+                    .reader_data_lifecycle
+                    .autopurge_nowriter_samples_delay
+            }
+        };
+        match delay {
+            DurationKind::Finite(d) => Some(d),
+            DurationKind::Infinite => None,
+        }
+    }
+
+    /// Remove all the samples and the state of an instance which is not alive. An
+    /// instance which becomes alive again afterwards is seen as a new instance.
+    pub fn purge_instance(&mut self, instance_handle: &InstanceHandle) {
+        self.instance_autopurge_task.remove(instance_handle);
+        if self
+            .instances
+            .get(instance_handle)
+            .is_some_and(|i| i.instance_state != InstanceStateKind::Alive)
+        {
+            self.sample_list
+                .retain(|s| &s.instance_handle != instance_handle);
+            self.instances.remove(instance_handle);
+            self.instance_ownership.remove(instance_handle);
+            if let Some(t) = self.instance_deadline_missed_task.remove(instance_handle) {
+                t.abort();
+            }
+        }
+    }
+
     pub fn instance_handle(&self) -> InstanceHandle {
         self.instance_handle
     }
 
+    pub fn data_available_status_changed(&self) -> bool {
+        self.data_available_status_changed_flag
+    }
+
     pub fn enable(&mut self) {
         self.enabled = true;
     }
@@ -756,6 +1245,128 @@ impl DataReaderEntity {
         &self.topic_name
     }
 
+    pub fn content_filter(&self) -> Option<&ContentFilter> {
+        self.content_filter.as_ref()
+    }
+
+    pub fn content_filter_mut(&mut self) -> Option<&mut ContentFilter> {
+        self.content_filter.as_mut()
+    }
+
+    pub fn create_read_condition(
+        &mut self,
+        instance_handle: InstanceHandle,
+        sample_state_mask: Vec<SampleStateKind>,
+        view_state_mask: Vec<ViewStateKind>,
+        instance_state_mask: Vec<InstanceStateKind>,
+        query: Option<(String, Vec<String>)>,
+    ) -> DdsResult<()> {
+        let query = match query {
+            Some((query_expression, query_parameters)) => Some(SqlFilter::new(
+                query_expression,
+                query_parameters,
+                self.type_support.as_ref(),
+            )?),
+            None => None,
+        };
+        let has_query = query.is_some();
+        self.read_conditions.push(ReadConditionEntity::new(
+            instance_handle,
+            sample_state_mask,
+            view_state_mask,
+            instance_state_mask,
+            query,
+        ));
+        if has_query {
+            self.evaluate_query(instance_handle);
+        }
+        Ok(())
+    }
+
+    pub fn set_query_parameters(
+        &mut self,
+        handle: InstanceHandle,
+        query_parameters: Vec<String>,
+    ) -> DdsResult<()> {
+        self.get_mut_read_condition(handle)?
+            .query_mut()
+            .ok_or(DdsError::IllegalOperation)?
+            .set_expression_parameters(query_parameters)?;
+        self.evaluate_query(handle);
+        Ok(())
+    }
+
+    // Update which samples are accepted by the query of the condition
+    fn evaluate_query(&mut self, handle: InstanceHandle) {
+        let Some(query) = self
+            .read_conditions
+            .iter()
+            .find(|c| c.instance_handle() == handle)
+            .and_then(|c| c.query())
+        else {
+            return;
+        };
+        for sample in &mut self.sample_list {
+            sample.accepted_by_queries.retain(|&h| h != handle);
+            if is_sample_accepted_by_query(sample, Some(query), self.type_support.as_ref()) {
+                sample.accepted_by_queries.push(handle);
+            }
+        }
+    }
+
+    pub fn delete_read_condition(&mut self, handle: InstanceHandle) -> DdsResult<()> {
+        let index = self
+            .read_conditions
+            .iter()
+            .position(|c| c.instance_handle() == handle)
+            .ok_or_else(|| {
+                DdsError::PreconditionNotMet(
+                    "Read condition not created by this data reader".to_string(),
+                )
+            })?;
+        self.read_conditions.remove(index);
+        for sample in &mut self.sample_list {
+            sample.accepted_by_queries.retain(|&h| h != handle);
+        }
+        Ok(())
+    }
+
+    pub fn has_read_conditions(&self) -> bool {
+        !self.read_conditions.is_empty()
+    }
+
+    pub fn get_read_condition(&self, handle: InstanceHandle) -> DdsResult<&ReadConditionEntity> {
+        self.read_conditions
+            .iter()
+            .find(|c| c.instance_handle() == handle)
+            .ok_or(DdsError::AlreadyDeleted)
+    }
+
+    pub fn get_mut_read_condition(
+        &mut self,
+        handle: InstanceHandle,
+    ) -> DdsResult<&mut ReadConditionEntity> {
+        self.read_conditions
+            .iter_mut()
+            .find(|c| c.instance_handle() == handle)
+            .ok_or(DdsError::AlreadyDeleted)
+    }
+
+    pub fn get_read_condition_trigger_value(&self, handle: InstanceHandle) -> DdsResult<bool> {
+        let read_condition = self.get_read_condition(handle)?;
+        Ok(self.sample_list.iter().any(|s| {
+            let instance = &self.instances[&s.instance_handle];
+            read_condition.sample_state_mask().contains(&s.sample_state)
+                && read_condition
+                    .view_state_mask()
+                    .contains(&instance.view_state)
+                && read_condition
+                    .instance_state_mask()
+                    .contains(&instance.instance_state)
+                && (read_condition.query().is_none() || s.accepted_by_queries.contains(&handle))
+        }))
+    }
+
     pub fn set_qos(&mut self, qos: DataReaderQos) -> DdsResult<()> {
         qos.is_consistent()?;
         if self.enabled {
@@ -782,8 +1393,49 @@ impl DataReaderEntity {
         self.subscription_matched_status.total_count_change += 1;
     }
 
+    /// Unregistration notifications for the instances which a matched writer leaves
+    /// without any writer. They stand for the unregistrations which the writer can't
+    /// send anymore once it is gone.
+    pub fn writer_unregistration_changes(
+        &self,
+        publication_handle: &InstanceHandle,
+        timestamp: Time,
+    ) -> Vec<CacheChange> {
+        let writer_guid = <[u8; 16]>::from(*publication_handle);
+        self.instances
+            .iter()
+            .filter(|(_, i)| {
+                i.instance_state == InstanceStateKind::Alive
+                    && i.writers.len() == 1
+                    && i.writers.contains(&writer_guid)
+            })
+            .map(|(&instance_handle, _)| CacheChange {
+                kind: ChangeKind::NotAliveUnregistered,
+                writer_guid: writer_guid.into(),
+                sequence_number: 0,
+                source_timestamp: Some(timestamp.into()),
+                instance_handle: Some(instance_handle.into()),
+                data_value: Arc::new([]),
+                coherent_set: None,
+                group_coherent_set: None,
+                writer_group_info: None,
+                group_sequence_number: None,
+            })
+            .collect()
+    }
+
     pub fn remove_matched_publication(&mut self, publication_handle: &InstanceHandle) {
         self.matched_publication_list.remove(publication_handle);
+        let writer_guid = <[u8; 16]>::from(*publication_handle);
+        for instance in self.instances.values_mut() {
+            instance.writers.remove(&writer_guid);
+        }
+        // A coherent set which can no longer be completed must not be made available
+        let writer_guid = Guid::from(<[u8; 16]>::from(*publication_handle));
+        self.coherent_sets.retain(|s| s.writer_guid != writer_guid);
+        self.lost_changes.remove(publication_handle);
+        self.shared_values
+            .retain(|(writer_handle, _), _| writer_handle != publication_handle);
         self.subscription_matched_status.current_count = self.matched_publication_list.len() as i32;
         self.subscription_matched_status.current_count_change -= 1;
         self.status_condition
@@ -804,6 +1456,93 @@ impl DataReaderEntity {
         status
     }
 
+    /// Record a liveliness assertion of a matched writer. Returns true if the writer
+    /// became alive, in which case the liveliness changed status has been updated.
+    pub fn assert_writer_liveliness(
+        &mut self,
+        publication_handle: InstanceHandle,
+        now: Time,
+    ) -> bool {
+        let status = &mut self.liveliness_changed_status;
+        match self.writer_liveliness.get_mut(&publication_handle) {
+            Some(writer_liveliness) if writer_liveliness.alive => {
+                writer_liveliness.last_assertion = now;
+                return false;
+            }
+            Some(writer_liveliness) => {
+                writer_liveliness.alive = true;
+                writer_liveliness.last_assertion = now;
+                status.not_alive_count -= 1;
+                status.not_alive_count_change -= 1;
+            }
+            None => {
+                self.writer_liveliness.insert(
+                    publication_handle,
+                    WriterLiveliness {
+                        alive: true,
+                        last_assertion: now,
+                    },
+                );
+            }
+        }
+        status.alive_count += 1;
+        status.alive_count_change += 1;
+        status.last_publication_handle = publication_handle;
+        true
+    }
+
+    /// Time of the last liveliness assertion of a matched writer which is still alive
+    pub fn writer_last_liveliness_assertion(
+        &self,
+        publication_handle: &InstanceHandle,
+    ) -> Option<Time> {
+        self.writer_liveliness
+            .get(publication_handle)
+            .filter(|w| w.alive)
+            .map(|w| w.last_assertion)
+    }
+
+    pub fn writer_liveliness_lost(&mut self, publication_handle: InstanceHandle) -> bool {
+        match self.writer_liveliness.get_mut(&publication_handle) {
+            Some(writer_liveliness) if writer_liveliness.alive => {
+                writer_liveliness.alive = false;
+                let status = &mut self.liveliness_changed_status;
+                status.alive_count -= 1;
+                status.alive_count_change -= 1;
+                status.not_alive_count += 1;
+                status.not_alive_count_change += 1;
+                status.last_publication_handle = publication_handle;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn remove_writer_liveliness(&mut self, publication_handle: InstanceHandle) -> bool {
+        match self.writer_liveliness.remove(&publication_handle) {
+            Some(writer_liveliness) => {
+                let status = &mut self.liveliness_changed_status;
+                if writer_liveliness.alive {
+                    status.alive_count -= 1;
+                    status.alive_count_change -= 1;
+                } else {
+                    status.not_alive_count -= 1;
+                    status.not_alive_count_change -= 1;
+                }
+                status.last_publication_handle = publication_handle;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_liveliness_changed_status(&mut self) -> LivelinessChangedStatus {
+        let status = self.liveliness_changed_status.clone();
+        self.liveliness_changed_status.alive_count_change = 0;
+        self.liveliness_changed_status.not_alive_count_change = 0;
+        status
+    }
+
     pub fn remove_instance_ownership(&mut self, instance_handle: &InstanceHandle) {
         self.instance_ownership.remove(instance_handle);
     }
@@ -903,6 +1642,14 @@ impl DataReaderEntity {
             .insert(instance_handle, task);
     }
 
+    pub fn insert_instance_autopurge_task(
+        &mut self,
+        instance_handle: InstanceHandle,
+        task: TaskHandle,
+    ) {
+        self.instance_autopurge_task.insert(instance_handle, task);
+    }
+
     pub fn listener(&self) -> Option<&Actor<DataReaderListenerActor>> {
         self.listener.as_ref()
     }
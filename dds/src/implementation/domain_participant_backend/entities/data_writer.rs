@@ -1,24 +1,35 @@
 use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
     implementation::{
+        domain_participant_backend::{
+            domain_participant_actor::DomainParticipantActor,
+            services::message_service::{AddLocalCacheChange, AssertRemoteWriterLiveliness},
+        },
         listeners::data_writer_listener::DataWriterListenerActor,
         status_condition::status_condition_actor::{self, StatusConditionActor},
         xtypes_glue::key_and_instance_handle::{
             get_instance_handle_from_serialized_foo, get_instance_handle_from_serialized_key,
+            get_serialized_key_from_serialized_foo,
         },
     },
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::DataWriterQos,
-        qos_policy::{HistoryQosPolicyKind, Length, QosPolicyId, ReliabilityQosPolicyKind},
+        qos_policy::{
+            DurabilityQosPolicyKind, HistoryQosPolicyKind, Length, QosPolicyId,
+            ReliabilityQosPolicyKind,
+        },
         status::{
-            OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus, PublicationMatchedStatus,
-            QosPolicyCount, StatusKind,
+            LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+            PublicationMatchedStatus, QosPolicyCount, StatusKind,
         },
         time::{DurationKind, Time},
     },
-    runtime::{actor::Actor, executor::TaskHandle},
+    runtime::{
+        actor::{Actor, ActorAddress},
+        executor::TaskHandle,
+    },
     transport::{
         history_cache::{CacheChange, HistoryCache},
         types::{ChangeKind, Guid},
@@ -27,6 +38,7 @@ use crate::{
     xtypes::dynamic_type::DynamicType,
 };
 use std::{
+    any::Any,
     collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
 };
@@ -51,6 +63,32 @@ impl TransportWriterKind {
         }
     }
 }
+
+/// Value written by the application which is handed as is to the readers of this
+/// application so that they don't need to deserialize it
+pub type SharedValue = Arc<dyn Any + Send + Sync>;
+
+/// A reader of a participant of this application to which the changes are
+/// delivered directly instead of through the transport
+pub struct LocalDataReader {
+    pub guid: Guid,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+
+struct LocalReaderProxy {
+    reader: LocalDataReader,
+    receive_history: bool,
+    // First change written after the reader was discovered
+    first_sequence_number: i64,
+    // The reader discards the changes until it has matched this writer as well
+    is_reader_matched: bool,
+}
+
+struct WriterCoherentSet {
+    first_sequence_number: Option<i64>,
+    group_coherent_set: Option<i64>,
+}
+
 pub struct DataWriterEntity {
     instance_handle: InstanceHandle,
     transport_writer: TransportWriterKind,
@@ -68,10 +106,18 @@ pub struct DataWriterEntity {
     max_seq_num: Option<i64>,
     last_change_sequence_number: i64,
     qos: DataWriterQos,
-    registered_instance_list: HashSet<InstanceHandle>,
+    registered_instance_list: HashMap<InstanceHandle, Arc<[u8]>>,
     offered_deadline_missed_status: OfferedDeadlineMissedStatus,
     instance_deadline_missed_task: HashMap<InstanceHandle, TaskHandle>,
+    liveliness_lost_status: LivelinessLostStatus,
+    last_liveliness_assertion: Option<Time>,
     instance_samples: HashMap<InstanceHandle, VecDeque<i64>>,
+    coherent_set: Option<WriterCoherentSet>,
+    // First sequence number of the coherent set and sequence number of the
+    // change signalling its end
+    coherent_set_end_changes: Vec<(i64, i64)>,
+    suspended_changes: Option<Vec<CacheChange>>,
+    local_reader_list: Vec<LocalReaderProxy>,
 }
 
 impl DataWriterEntity {
@@ -104,10 +150,16 @@ impl DataWriterEntity {
             max_seq_num: None,
             last_change_sequence_number: 0,
             qos,
-            registered_instance_list: HashSet::new(),
+            registered_instance_list: HashMap::new(),
             offered_deadline_missed_status: OfferedDeadlineMissedStatus::default(),
             instance_deadline_missed_task: HashMap::new(),
+            liveliness_lost_status: LivelinessLostStatus::default(),
+            last_liveliness_assertion: None,
             instance_samples: HashMap::new(),
+            coherent_set: None,
+            coherent_set_end_changes: Vec::new(),
+            suspended_changes: None,
+            local_reader_list: Vec::new(),
         }
     }
 
@@ -152,27 +204,282 @@ impl DataWriterEntity {
         Ok(())
     }
 
+    pub fn begin_coherent_set(&mut self, group_coherent_set: Option<i64>) {
+        self.coherent_set = Some(WriterCoherentSet {
+            first_sequence_number: None,
+            group_coherent_set,
+        });
+    }
+
+    pub fn has_coherent_changes(&self) -> bool {
+        self.coherent_set
+            .as_ref()
+            .is_some_and(|c| c.first_sequence_number.is_some())
+    }
+
+    pub fn end_coherent_set(&mut self, writer_group_info: Option<[u8; 4]>, timestamp: Time) {
+        if let Some(WriterCoherentSet {
+            first_sequence_number: Some(first_sequence_number),
+            group_coherent_set,
+        }) = self.coherent_set.take()
+        {
+            // Readers only make the coherent set available once they receive
+            // this change without payload signalling its end
+            self.last_change_sequence_number += 1;
+            let cache_change = CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid: self.transport_writer().guid(),
+                sequence_number: self.last_change_sequence_number,
+                source_timestamp: Some(timestamp.into()),
+                instance_handle: None,
+                data_value: Arc::from([]),
+                coherent_set: Some(first_sequence_number),
+                group_coherent_set,
+                writer_group_info,
+                group_sequence_number: None,
+            };
+            self.add_change(cache_change, None);
+            self.coherent_set_end_changes
+                .push((first_sequence_number, self.last_change_sequence_number));
+        }
+    }
+
+    /// Remove the changes signalling the end of a coherent set once all the readers
+    /// have acknowledged them. Durable writers keep them for the readers matched
+    /// later as long as samples of the coherent set are in the history.
+    fn remove_acknowledged_coherent_set_ends(&mut self) {
+        let removable_changes: Vec<i64> = self
+            .coherent_set_end_changes
+            .iter()
+            .filter(|&&(first, end)| self.is_coherent_set_end_removable(first, end))
+            .map(|&(_, end)| end)
+            .collect();
+        for sequence_number in removable_changes {
+            self.coherent_set_end_changes
+                .retain(|&(_, end)| end != sequence_number);
+            self.remove_change(sequence_number);
+        }
+    }
+
+    fn is_coherent_set_end_removable(
+        &self,
+        first_sequence_number: i64,
+        end_sequence_number: i64,
+    ) -> bool {
+        let is_suspended = self
+            .suspended_changes
+            .iter()
+            .flatten()
+            .any(|cc| cc.sequence_number == end_sequence_number);
+        // Local readers which haven't matched yet are served from the history later
+        let is_pending_for_local_reader = self.local_reader_list.iter().any(|r| {
+            !r.is_reader_matched
+                && (r.receive_history || r.first_sequence_number <= end_sequence_number)
+        });
+        if is_suspended || is_pending_for_local_reader {
+            return false;
+        }
+        if let TransportWriterKind::Stateful(w) = &self.transport_writer {
+            if !w.is_change_acknowledged(end_sequence_number) {
+                return false;
+            }
+        }
+        self.qos.durability.kind == DurabilityQosPolicyKind::Volatile
+            || !self
+                .instance_samples
+                .values()
+                .flatten()
+                .any(|s| (first_sequence_number..end_sequence_number).contains(s))
+    }
+
+    pub fn suspend_publications(&mut self) {
+        if self.suspended_changes.is_none() {
+            self.suspended_changes = Some(Vec::new());
+        }
+    }
+
+    pub fn resume_publications(&mut self) {
+        if let Some(suspended_changes) = self.suspended_changes.take() {
+            for change in suspended_changes {
+                self.publish_change(change, None);
+            }
+        }
+    }
+
+    /// The shared value is only handed to the readers of this application when the
+    /// change is published right away. The changes published later are taken from
+    /// the serialized data.
+    fn add_change(&mut self, change: CacheChange, shared_value: Option<SharedValue>) {
+        self.remove_acknowledged_coherent_set_ends();
+        match &mut self.suspended_changes {
+            Some(suspended_changes) => suspended_changes.push(change),
+            None => self.publish_change(change, shared_value),
+        }
+    }
+
+    fn publish_change(&mut self, change: CacheChange, shared_value: Option<SharedValue>) {
+        for local_reader in self
+            .local_reader_list
+            .iter()
+            .filter(|r| r.is_reader_matched)
+        {
+            Self::deliver_to_local_reader(
+                &local_reader.reader,
+                change.clone(),
+                shared_value.clone(),
+            );
+        }
+        self.transport_writer.history_cache().add_change(change);
+    }
+
+    fn deliver_to_local_reader(
+        local_reader: &LocalDataReader,
+        cache_change: CacheChange,
+        shared_value: Option<SharedValue>,
+    ) {
+        local_reader
+            .participant_address
+            .send_actor_mail(AddLocalCacheChange {
+                participant_address: local_reader.participant_address.clone(),
+                data_reader_guid: local_reader.guid,
+                cache_change,
+                shared_value,
+            })
+            .ok();
+    }
+
+    /// Add a reader of this application when either of them matches the other.
+    /// The changes are only delivered once the reader has matched this writer since
+    /// it would discard them otherwise. Those written in the meantime, and the
+    /// historical ones if requested, are then taken from the writer history.
+    pub fn add_local_reader(
+        &mut self,
+        local_reader: LocalDataReader,
+        receive_history: bool,
+        is_reader_matched: bool,
+    ) {
+        let index = match self
+            .local_reader_list
+            .iter()
+            .position(|r| r.reader.guid == local_reader.guid)
+        {
+            Some(index) => index,
+            None => {
+                self.local_reader_list.push(LocalReaderProxy {
+                    reader: local_reader,
+                    receive_history,
+                    first_sequence_number: self.last_change_sequence_number + 1,
+                    is_reader_matched: false,
+                });
+                self.local_reader_list.len() - 1
+            }
+        };
+        let local_reader = &mut self.local_reader_list[index];
+        if is_reader_matched && !local_reader.is_reader_matched {
+            local_reader.is_reader_matched = true;
+            if let TransportWriterKind::Stateful(w) = &self.transport_writer {
+                for change in w.changes().into_iter().filter(|cc| {
+                    local_reader.receive_history
+                        || cc.sequence_number >= local_reader.first_sequence_number
+                }) {
+                    Self::deliver_to_local_reader(&local_reader.reader, change, None);
+                }
+            }
+        }
+    }
+
+    /// Forward the liveliness assertion of this writer to the readers of this
+    /// application since they don't receive the HEARTBEAT of the transport
+    pub fn assert_local_readers_liveliness(&self) {
+        let writer_guid = self.transport_writer.guid();
+        for local_reader in &self.local_reader_list {
+            local_reader
+                .reader
+                .participant_address
+                .send_actor_mail(AssertRemoteWriterLiveliness {
+                    participant_address: local_reader.reader.participant_address.clone(),
+                    writer_guid,
+                })
+                .ok();
+        }
+    }
+
+    pub fn remove_local_reader(&mut self, reader_guid: Guid) {
+        self.local_reader_list
+            .retain(|r| r.reader.guid != reader_guid);
+    }
+
+    fn next_coherent_set(&mut self) -> (Option<i64>, Option<i64>) {
+        let sequence_number = self.last_change_sequence_number;
+        match &mut self.coherent_set {
+            Some(c) => (
+                Some(*c.first_sequence_number.get_or_insert(sequence_number)),
+                c.group_coherent_set,
+            ),
+            None => (None, None),
+        }
+    }
+
     pub fn contains_instance(&mut self, instance_handle: &InstanceHandle) -> bool {
-        self.registered_instance_list.contains(instance_handle)
+        self.registered_instance_list.contains_key(instance_handle)
+    }
+
+    fn has_key(&self) -> DdsResult<bool> {
+        for index in 0..self.type_support.get_member_count() {
+            if self
+                .type_support
+                .get_member_by_index(index)?
+                .get_descriptor()?
+                .is_key
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Serialized keys of the instances currently registered by the writer. The
+    /// single instance of a topic without key can't be unregistered.
+    pub fn registered_instance_keys(&self) -> DdsResult<Vec<Arc<[u8]>>> {
+        if !self.has_key()? {
+            return Ok(Vec::new());
+        }
+        Ok(self.registered_instance_list.values().cloned().collect())
     }
 
     pub fn write_w_timestamp(
         &mut self,
         serialized_data: Vec<u8>,
         timestamp: Time,
+        group_sequence_number: Option<i64>,
+    ) -> DdsResult<i64> {
+        self.write_shared_w_timestamp(serialized_data, None, timestamp, group_sequence_number)
+    }
+
+    /// Write the data and hand the shared value, if any, to the readers of this
+    /// application
+    pub fn write_shared_w_timestamp(
+        &mut self,
+        serialized_data: Vec<u8>,
+        shared_value: Option<SharedValue>,
+        timestamp: Time,
+        group_sequence_number: Option<i64>,
     ) -> DdsResult<i64> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
-        self.last_change_sequence_number += 1;
-
         let instance_handle =
             get_instance_handle_from_serialized_foo(&serialized_data, self.type_support.as_ref())?;
 
-        if !self.registered_instance_list.contains(&instance_handle) {
+        if !self.registered_instance_list.contains_key(&instance_handle) {
             if self.registered_instance_list.len() < self.qos.resource_limits.max_instances {
-                self.registered_instance_list.insert(instance_handle);
+                let serialized_key = get_serialized_key_from_serialized_foo(
+                    &serialized_data,
+                    self.type_support.as_ref(),
+                )?;
+                self.registered_instance_list
+                    .insert(instance_handle, serialized_key.into());
             } else {
                 return Err(DdsError::OutOfResources);
             }
@@ -215,19 +522,18 @@ impl DataWriterEntity {
             }
         }
 
-        let change = CacheChange {
-            kind: ChangeKind::Alive,
-            writer_guid: self.transport_writer().guid(),
-            sequence_number: self.last_change_sequence_number,
-            source_timestamp: Some(timestamp.into()),
-            instance_handle: Some(instance_handle.into()),
-            data_value: serialized_data.into(),
-        };
         if let HistoryQosPolicyKind::KeepLast(depth) = self.qos.history.kind {
             if let Some(s) = self.instance_samples.get_mut(&instance_handle) {
                 if s.len() == depth as usize {
                     if let Some(&smallest_seq_num_instance) = s.front() {
-                        if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
+                        // Changes still held by a suspended publisher were never sent
+                        // so there is no acknowledgment to wait for
+                        if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable
+                            && !self.suspended_changes.as_ref().is_some_and(|c| {
+                                c.iter()
+                                    .any(|cc| cc.sequence_number == smallest_seq_num_instance)
+                            })
+                        {
                             let start_time = std::time::Instant::now();
                             while let TransportWriterKind::Stateful(w) = &self.transport_writer {
                                 if w.is_change_acknowledged(smallest_seq_num_instance) {
@@ -245,14 +551,29 @@ impl DataWriterEntity {
                         }
                     }
                     if let Some(smallest_seq_num_instance) = s.pop_front() {
-                        self.transport_writer
-                            .history_cache()
-                            .remove_change(smallest_seq_num_instance);
+                        self.remove_change(smallest_seq_num_instance);
                     }
                 }
             }
         }
 
+        // The sequence number is only taken once the write can no longer fail so
+        // that readers don't see it as a missing change
+        self.last_change_sequence_number += 1;
+        let (coherent_set, group_coherent_set) = self.next_coherent_set();
+        let change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid: self.transport_writer().guid(),
+            sequence_number: self.last_change_sequence_number,
+            source_timestamp: Some(timestamp.into()),
+            instance_handle: Some(instance_handle.into()),
+            data_value: serialized_data.into(),
+            coherent_set,
+            group_coherent_set,
+            writer_group_info: None,
+            group_sequence_number,
+        };
+
         let seq_num = change.sequence_number();
 
         if seq_num > self.max_seq_num.unwrap_or(0) {
@@ -271,7 +592,7 @@ impl DataWriterEntity {
             .entry(instance_handle)
             .or_default()
             .push_back(change.sequence_number);
-        self.transport_writer.history_cache().add_change(change);
+        self.add_change(change, shared_value);
         Ok(self.last_change_sequence_number)
     }
 
@@ -279,33 +600,19 @@ impl DataWriterEntity {
         &mut self,
         serialized_key: Vec<u8>,
         timestamp: Time,
+        group_sequence_number: Option<i64>,
     ) -> DdsResult<()> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
-        let has_key = {
-            let mut has_key = false;
-            for index in 0..self.type_support.get_member_count() {
-                if self
-                    .type_support
-                    .get_member_by_index(index)?
-                    .get_descriptor()?
-                    .is_key
-                {
-                    has_key = true;
-                    break;
-                }
-            }
-            has_key
-        };
-        if !has_key {
+        if !self.has_key()? {
             return Err(DdsError::IllegalOperation);
         }
 
         let instance_handle =
             get_instance_handle_from_serialized_key(&serialized_key, self.type_support.as_ref())?;
-        if !self.registered_instance_list.contains(&instance_handle) {
+        if !self.registered_instance_list.contains_key(&instance_handle) {
             return Err(DdsError::BadParameter);
         }
 
@@ -315,6 +622,7 @@ impl DataWriterEntity {
 
         self.last_change_sequence_number += 1;
 
+        let (coherent_set, group_coherent_set) = self.next_coherent_set();
         let cache_change = CacheChange {
             kind: ChangeKind::NotAliveDisposed,
             writer_guid: self.transport_writer().guid(),
@@ -322,10 +630,12 @@ impl DataWriterEntity {
             source_timestamp: Some(timestamp.into()),
             instance_handle: Some(instance_handle.into()),
             data_value: serialized_key.into(),
+            coherent_set,
+            group_coherent_set,
+            writer_group_info: None,
+            group_sequence_number,
         };
-        self.transport_writer
-            .history_cache()
-            .add_change(cache_change);
+        self.add_change(cache_change, None);
 
         Ok(())
     }
@@ -334,33 +644,23 @@ impl DataWriterEntity {
         &mut self,
         serialized_key: Vec<u8>,
         timestamp: Time,
+        group_sequence_number: Option<i64>,
     ) -> DdsResult<()> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
-        let has_key = {
-            let mut has_key = false;
-            for index in 0..self.type_support.get_member_count() {
-                if self
-                    .type_support
-                    .get_member_by_index(index)?
-                    .get_descriptor()?
-                    .is_key
-                {
-                    has_key = true;
-                    break;
-                }
-            }
-            has_key
-        };
-        if !has_key {
+        if !self.has_key()? {
             return Err(DdsError::IllegalOperation);
         }
 
         let instance_handle =
             get_instance_handle_from_serialized_key(&serialized_key, self.type_support.as_ref())?;
-        if !self.registered_instance_list.contains(&instance_handle) {
+        if self
+            .registered_instance_list
+            .remove(&instance_handle)
+            .is_none()
+        {
             return Err(DdsError::BadParameter);
         }
 
@@ -370,21 +670,42 @@ impl DataWriterEntity {
 
         self.last_change_sequence_number += 1;
 
+        let kind = if self
+            .qos
+            .writer_data_lifecycle
+            .autodispose_unregistered_instances
+        {
+            ChangeKind::NotAliveDisposedUnregistered
+        } else {
+            ChangeKind::NotAliveUnregistered
+        };
+        let (coherent_set, group_coherent_set) = self.next_coherent_set();
         let cache_change = CacheChange {
-            kind: ChangeKind::NotAliveDisposed,
+            kind,
             writer_guid: self.transport_writer().guid(),
             sequence_number: self.last_change_sequence_number,
             source_timestamp: Some(timestamp.into()),
             instance_handle: Some(instance_handle.into()),
             data_value: serialized_key.into(),
+            coherent_set,
+            group_coherent_set,
+            writer_group_info: None,
+            group_sequence_number,
         };
-        self.transport_writer
-            .history_cache()
-            .add_change(cache_change);
+        self.add_change(cache_change, None);
         Ok(())
     }
 
     pub fn remove_change(&mut self, sequence_number: i64) {
+        if let Some(suspended_changes) = &mut self.suspended_changes {
+            if let Some(index) = suspended_changes
+                .iter()
+                .position(|cc| cc.sequence_number == sequence_number)
+            {
+                suspended_changes.remove(index);
+                return;
+            }
+        }
         self.transport_writer
             .history_cache()
             .remove_change(sequence_number);
@@ -485,6 +806,32 @@ impl DataWriterEntity {
         self.type_support.as_ref()
     }
 
+    /// Record a liveliness assertion of the writer. Returns true if the writer was
+    /// not considered alive before, meaning that the lease has to be monitored again.
+    pub fn assert_liveliness(&mut self, now: Time) -> bool {
+        self.last_liveliness_assertion.replace(now).is_none()
+    }
+
+    pub fn last_liveliness_assertion(&self) -> Option<Time> {
+        self.last_liveliness_assertion
+    }
+
+    pub fn liveliness_lost(&mut self) {
+        self.last_liveliness_assertion = None;
+        self.liveliness_lost_status.total_count += 1;
+        self.liveliness_lost_status.total_count_change += 1;
+    }
+
+    pub fn get_liveliness_lost_status(&mut self) -> LivelinessLostStatus {
+        let status = self.liveliness_lost_status.clone();
+        self.liveliness_lost_status.total_count_change = 0;
+        self.status_condition
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::LivelinessLost,
+            });
+        status
+    }
+
     pub fn insert_instance_deadline_missed_task(
         &mut self,
         instance_handle: InstanceHandle,
@@ -516,11 +863,15 @@ impl DataWriterEntity {
     }
 
     pub fn are_all_changes_acknowledged(&self) -> bool {
-        match &self.transport_writer {
-            TransportWriterKind::Stateful(w) => {
-                w.is_change_acknowledged(self.last_change_sequence_number)
+        let are_local_changes_delivered = self.local_reader_list.iter().all(|r| {
+            r.is_reader_matched || r.first_sequence_number > self.last_change_sequence_number
+        });
+        are_local_changes_delivered
+            && match &self.transport_writer {
+                TransportWriterKind::Stateful(w) => {
+                    w.is_change_acknowledged(self.last_change_sequence_number)
+                }
+                TransportWriterKind::Stateless(_) => true,
             }
-            TransportWriterKind::Stateless(_) => true,
-        }
     }
 }
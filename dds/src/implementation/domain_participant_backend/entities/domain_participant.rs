@@ -21,12 +21,15 @@ use crate::{
         instance::InstanceHandle,
         qos::{DomainParticipantQos, PublisherQos, SubscriberQos, TopicQos},
         status::StatusKind,
-        time::Time,
+        time::{Duration, Time},
     },
-    runtime::actor::Actor,
+    runtime::{actor::Actor, executor::TaskHandle},
 };
 
-use super::{publisher::PublisherEntity, subscriber::SubscriberEntity, topic::TopicEntity};
+use super::{
+    content_filtered_topic::ContentFilteredTopicEntity, publisher::PublisherEntity,
+    subscriber::SubscriberEntity, topic::TopicEntity,
+};
 
 pub struct DomainParticipantEntity {
     domain_id: DomainId,
@@ -35,13 +38,18 @@ pub struct DomainParticipantEntity {
     qos: DomainParticipantQos,
     builtin_subscriber: SubscriberEntity,
     builtin_publisher: PublisherEntity,
+    // Publisher of the writers with which the durability service serves the
+    // samples of deleted transient and persistent writers
+    durability_service_publisher: PublisherEntity,
     user_defined_subscriber_list: Vec<SubscriberEntity>,
     default_subscriber_qos: SubscriberQos,
     user_defined_publisher_list: Vec<PublisherEntity>,
     default_publisher_qos: PublisherQos,
     topic_list: HashMap<String, TopicEntity>,
+    content_filtered_topic_list: HashMap<String, ContentFilteredTopicEntity>,
     default_topic_qos: TopicQos,
     discovered_participant_list: HashMap<InstanceHandle, SpdpDiscoveredParticipantData>,
+    discovered_participant_lease_task: HashMap<InstanceHandle, TaskHandle>,
     discovered_topic_list: HashMap<InstanceHandle, TopicBuiltinTopicData>,
     discovered_reader_list: HashMap<InstanceHandle, DiscoveredReaderData>,
     discovered_writer_list: HashMap<InstanceHandle, DiscoveredWriterData>,
@@ -53,6 +61,8 @@ pub struct DomainParticipantEntity {
     listener: Option<Actor<DomainParticipantListenerActor>>,
     listener_mask: Vec<StatusKind>,
     status_condition: Actor<StatusConditionActor>,
+    lease_duration: Duration,
+    automatic_liveliness_task: Option<(Duration, TaskHandle)>,
 }
 
 impl DomainParticipantEntity {
@@ -65,9 +75,11 @@ impl DomainParticipantEntity {
         status_condition: Actor<StatusConditionActor>,
         instance_handle: InstanceHandle,
         builtin_publisher: PublisherEntity,
+        durability_service_publisher: PublisherEntity,
         builtin_subscriber: SubscriberEntity,
         topic_list: HashMap<String, TopicEntity>,
         domain_tag: String,
+        lease_duration: Duration,
     ) -> Self {
         Self {
             domain_id,
@@ -75,13 +87,16 @@ impl DomainParticipantEntity {
             qos: domain_participant_qos,
             builtin_subscriber,
             builtin_publisher,
+            durability_service_publisher,
             user_defined_subscriber_list: Vec::new(),
             default_subscriber_qos: SubscriberQos::default(),
             user_defined_publisher_list: Vec::new(),
             default_publisher_qos: PublisherQos::default(),
             topic_list,
+            content_filtered_topic_list: HashMap::new(),
             default_topic_qos: TopicQos::default(),
             discovered_participant_list: HashMap::new(),
+            discovered_participant_lease_task: HashMap::new(),
             discovered_topic_list: HashMap::new(),
             discovered_reader_list: HashMap::new(),
             discovered_writer_list: HashMap::new(),
@@ -94,6 +109,8 @@ impl DomainParticipantEntity {
             listener_mask,
             status_condition,
             domain_tag,
+            lease_duration,
+            automatic_liveliness_task: None,
         }
     }
 
@@ -218,6 +235,25 @@ impl DomainParticipantEntity {
     ) {
         self.discovered_participant_list
             .remove(discovered_participant_handle);
+        if let Some(t) = self
+            .discovered_participant_lease_task
+            .remove(discovered_participant_handle)
+        {
+            t.abort();
+        }
+    }
+
+    pub fn insert_discovered_participant_lease_task(
+        &mut self,
+        discovered_participant_handle: InstanceHandle,
+        task: TaskHandle,
+    ) {
+        if let Some(t) = self
+            .discovered_participant_lease_task
+            .insert(discovered_participant_handle, task)
+        {
+            t.abort();
+        }
     }
 
     pub fn add_discovered_reader(&mut self, discovered_reader_data: DiscoveredReaderData) {
@@ -293,6 +329,10 @@ impl DomainParticipantEntity {
         self.user_defined_subscriber_list.iter()
     }
 
+    pub fn subscriber_list_mut(&mut self) -> impl Iterator<Item = &mut SubscriberEntity> {
+        self.user_defined_subscriber_list.iter_mut()
+    }
+
     pub fn drain_subscriber_list(&mut self) -> impl Iterator<Item = SubscriberEntity> + '_ {
         self.user_defined_subscriber_list.drain(..)
     }
@@ -300,15 +340,25 @@ impl DomainParticipantEntity {
     pub fn get_publisher(&self, handle: InstanceHandle) -> Option<&PublisherEntity> {
         self.user_defined_publisher_list
             .iter()
+            .chain(std::iter::once(&self.durability_service_publisher))
             .find(|x| x.instance_handle() == handle)
     }
 
     pub fn get_mut_publisher(&mut self, handle: InstanceHandle) -> Option<&mut PublisherEntity> {
         self.user_defined_publisher_list
             .iter_mut()
+            .chain(std::iter::once(&mut self.durability_service_publisher))
             .find(|x| x.instance_handle() == handle)
     }
 
+    pub fn durability_service_publisher(&self) -> &PublisherEntity {
+        &self.durability_service_publisher
+    }
+
+    pub fn durability_service_publisher_mut(&mut self) -> &mut PublisherEntity {
+        &mut self.durability_service_publisher
+    }
+
     pub fn insert_publisher(&mut self, publisher: PublisherEntity) {
         self.user_defined_publisher_list.push(publisher);
     }
@@ -327,11 +377,15 @@ impl DomainParticipantEntity {
     }
 
     pub fn publisher_list(&mut self) -> impl Iterator<Item = &PublisherEntity> {
-        self.user_defined_publisher_list.iter()
+        self.user_defined_publisher_list
+            .iter()
+            .chain(std::iter::once(&self.durability_service_publisher))
     }
 
     pub fn publisher_list_mut(&mut self) -> impl Iterator<Item = &mut PublisherEntity> {
-        self.user_defined_publisher_list.iter_mut()
+        self.user_defined_publisher_list
+            .iter_mut()
+            .chain(std::iter::once(&mut self.durability_service_publisher))
     }
 
     pub fn get_topic(&self, topic_name: &str) -> Option<&TopicEntity> {
@@ -351,10 +405,37 @@ impl DomainParticipantEntity {
     }
 
     pub fn delete_all_topics(&mut self) {
+        self.content_filtered_topic_list.clear();
         self.topic_list
             .retain(|_, x| BUILT_IN_TOPIC_NAME_LIST.contains(&x.topic_name()));
     }
 
+    pub fn get_content_filtered_topic(
+        &self,
+        topic_name: &str,
+    ) -> Option<&ContentFilteredTopicEntity> {
+        self.content_filtered_topic_list.get(topic_name)
+    }
+
+    pub fn get_mut_content_filtered_topic(
+        &mut self,
+        topic_name: &str,
+    ) -> Option<&mut ContentFilteredTopicEntity> {
+        self.content_filtered_topic_list.get_mut(topic_name)
+    }
+
+    pub fn insert_content_filtered_topic(&mut self, topic: ContentFilteredTopicEntity) {
+        self.content_filtered_topic_list
+            .insert(topic.topic_name().to_owned(), topic);
+    }
+
+    pub fn remove_content_filtered_topic(
+        &mut self,
+        topic_name: &str,
+    ) -> Option<ContentFilteredTopicEntity> {
+        self.content_filtered_topic_list.remove(topic_name)
+    }
+
     pub fn topic_list(&mut self) -> impl Iterator<Item = &TopicEntity> {
         self.topic_list.values()
     }
@@ -370,6 +451,7 @@ impl DomainParticipantEntity {
         self.user_defined_publisher_list.is_empty()
             && self.user_defined_subscriber_list.is_empty()
             && no_user_defined_topics
+            && self.content_filtered_topic_list.is_empty()
     }
 
     pub fn listener_mask(&self) -> &[StatusKind] {
@@ -397,6 +479,20 @@ impl DomainParticipantEntity {
         &self.domain_tag
     }
 
+    pub fn lease_duration(&self) -> Duration {
+        self.lease_duration
+    }
+
+    pub fn automatic_liveliness_period(&self) -> Option<Duration> {
+        self.automatic_liveliness_task.as_ref().map(|(p, _)| *p)
+    }
+
+    pub fn set_automatic_liveliness_task(&mut self, period: Duration, task: TaskHandle) {
+        if let Some((_, t)) = self.automatic_liveliness_task.replace((period, task)) {
+            t.abort();
+        }
+    }
+
     pub fn discovered_participant_list(
         &self,
     ) -> impl Iterator<Item = &SpdpDiscoveredParticipantData> {
@@ -1,6 +1,8 @@
+pub mod content_filtered_topic;
 pub mod data_reader;
 pub mod data_writer;
 pub mod domain_participant;
 pub mod publisher;
+pub mod read_condition;
 pub mod subscriber;
 pub mod topic;
@@ -1,13 +1,16 @@
 use crate::{
     implementation::{
+        data_representation_inline_qos::types::writer_group_digest,
         listeners::publisher_listener::PublisherListenerActor,
         status_condition::status_condition_actor::StatusConditionActor,
     },
     infrastructure::{
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos},
+        qos_policy::PresentationQosPolicyAccessScopeKind,
         status::StatusKind,
+        time::Time,
     },
     runtime::actor::Actor,
 };
@@ -23,6 +26,10 @@ pub struct PublisherEntity {
     listener: Option<Actor<PublisherListenerActor>>,
     listener_mask: Vec<StatusKind>,
     status_condition: Actor<StatusConditionActor>,
+    coherent_changes_depth: usize,
+    group_coherent_set: i64,
+    suspended_publications_depth: usize,
+    group_sequence_number: i64,
 }
 
 impl PublisherEntity {
@@ -42,6 +49,10 @@ impl PublisherEntity {
             listener,
             listener_mask,
             status_condition,
+            coherent_changes_depth: 0,
+            group_coherent_set: 0,
+            suspended_publications_depth: 0,
+            group_sequence_number: 0,
         }
     }
 
@@ -57,7 +68,13 @@ impl PublisherEntity {
         self.data_writer_list.drain(..)
     }
 
-    pub fn insert_data_writer(&mut self, data_writer: DataWriterEntity) {
+    pub fn insert_data_writer(&mut self, mut data_writer: DataWriterEntity) {
+        if self.is_coherent_set_active() {
+            data_writer.begin_coherent_set(self.current_group_coherent_set());
+        }
+        if self.suspended_publications_depth > 0 {
+            data_writer.suspend_publications();
+        }
         self.data_writer_list.push(data_writer);
     }
 
@@ -69,6 +86,27 @@ impl PublisherEntity {
         Some(self.data_writer_list.remove(index))
     }
 
+    /// Unregister all the instances of a writer which is being deleted so that the
+    /// readers know it no longer updates them
+    pub fn unregister_data_writer_instances(
+        &mut self,
+        data_writer: &mut DataWriterEntity,
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        if !data_writer.enabled() {
+            return Ok(());
+        }
+        for serialized_key in data_writer.registered_instance_keys()? {
+            let group_sequence_number = self.next_group_sequence_number();
+            data_writer.unregister_w_timestamp(
+                serialized_key.to_vec(),
+                timestamp,
+                group_sequence_number,
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn get_data_writer(&self, handle: InstanceHandle) -> Option<&DataWriterEntity> {
         self.data_writer_list
             .iter()
@@ -117,11 +155,102 @@ impl PublisherEntity {
     }
 
     pub fn set_qos(&mut self, qos: PublisherQos) -> DdsResult<()> {
-        
         self.qos = qos;
         Ok(())
     }
 
+    pub fn begin_coherent_changes(&mut self) -> DdsResult<()> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.coherent_changes_depth += 1;
+        // Nested calls extend the coherent set which is already in progress
+        if self.coherent_changes_depth == 1 && self.qos.presentation.coherent_access {
+            self.group_coherent_set += 1;
+            let group_coherent_set = self.current_group_coherent_set();
+            for data_writer in self.data_writer_list.iter_mut() {
+                data_writer.begin_coherent_set(group_coherent_set);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn end_coherent_changes(&mut self, timestamp: Time) -> DdsResult<()> {
+        if self.coherent_changes_depth == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "No matching begin_coherent_changes".to_string(),
+            ));
+        }
+
+        self.coherent_changes_depth -= 1;
+        if self.coherent_changes_depth == 0 && self.qos.presentation.coherent_access {
+            let writer_group_info = self.current_group_coherent_set().map(|_| {
+                writer_group_digest(
+                    self.data_writer_list
+                        .iter()
+                        .filter(|dw| dw.has_coherent_changes())
+                        .map(|dw| dw.transport_writer().guid()),
+                )
+            });
+            for data_writer in self.data_writer_list.iter_mut() {
+                data_writer.end_coherent_set(writer_group_info, timestamp);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn suspend_publications(&mut self) -> DdsResult<()> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.suspended_publications_depth += 1;
+        for data_writer in self.data_writer_list.iter_mut() {
+            data_writer.suspend_publications();
+        }
+        Ok(())
+    }
+
+    pub fn resume_publications(&mut self) -> DdsResult<()> {
+        if self.suspended_publications_depth == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "No matching suspend_publications".to_string(),
+            ));
+        }
+
+        self.suspended_publications_depth -= 1;
+        // Nested calls only release the changes once the outermost suspension ends
+        if self.suspended_publications_depth == 0 {
+            for data_writer in self.data_writer_list.iter_mut() {
+                data_writer.resume_publications();
+            }
+        }
+        Ok(())
+    }
+
+    /// Sequence number ordering the next change of any writer of this publisher
+    /// when it provides ordered access with group scope
+    pub fn next_group_sequence_number(&mut self) -> Option<i64> {
+        if self.qos.presentation.ordered_access
+            && self.qos.presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group
+        {
+            self.group_sequence_number += 1;
+            Some(self.group_sequence_number)
+        } else {
+            None
+        }
+    }
+
+    fn is_coherent_set_active(&self) -> bool {
+        self.coherent_changes_depth > 0 && self.qos.presentation.coherent_access
+    }
+
+    fn current_group_coherent_set(&self) -> Option<i64> {
+        (self.qos.presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group)
+            .then_some(self.group_coherent_set)
+    }
+
     pub fn set_listener(
         &mut self,
         a_listener: Option<Actor<PublisherListenerActor>>,
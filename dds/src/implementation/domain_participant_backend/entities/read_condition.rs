@@ -0,0 +1,55 @@
+use crate::{
+    implementation::content_filter::filter::SqlFilter,
+    infrastructure::instance::InstanceHandle,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+};
+
+pub struct ReadConditionEntity {
+    instance_handle: InstanceHandle,
+    sample_state_mask: Vec<SampleStateKind>,
+    view_state_mask: Vec<ViewStateKind>,
+    instance_state_mask: Vec<InstanceStateKind>,
+    query: Option<SqlFilter>,
+}
+
+impl ReadConditionEntity {
+    pub fn new(
+        instance_handle: InstanceHandle,
+        sample_state_mask: Vec<SampleStateKind>,
+        view_state_mask: Vec<ViewStateKind>,
+        instance_state_mask: Vec<InstanceStateKind>,
+        query: Option<SqlFilter>,
+    ) -> Self {
+        Self {
+            instance_handle,
+            sample_state_mask,
+            view_state_mask,
+            instance_state_mask,
+            query,
+        }
+    }
+
+    pub fn instance_handle(&self) -> InstanceHandle {
+        self.instance_handle
+    }
+
+    pub fn sample_state_mask(&self) -> &[SampleStateKind] {
+        &self.sample_state_mask
+    }
+
+    pub fn view_state_mask(&self) -> &[ViewStateKind] {
+        &self.view_state_mask
+    }
+
+    pub fn instance_state_mask(&self) -> &[InstanceStateKind] {
+        &self.instance_state_mask
+    }
+
+    pub fn query(&self) -> Option<&SqlFilter> {
+        self.query.as_ref()
+    }
+
+    pub fn query_mut(&mut self) -> Option<&mut SqlFilter> {
+        self.query.as_mut()
+    }
+}
@@ -1,19 +1,30 @@
 use crate::{
     implementation::{
+        data_representation_inline_qos::types::writer_group_digest,
         listeners::subscriber_listener::SubscriberListenerActor,
         status_condition::status_condition_actor::StatusConditionActor,
     },
     infrastructure::{
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, SubscriberQos},
+        qos_policy::PresentationQosPolicyAccessScopeKind,
         status::StatusKind,
     },
     runtime::actor::Actor,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+    transport::{history_cache::CacheChange, types::Guid},
 };
 
 use super::data_reader::DataReaderEntity;
 
+struct GroupCoherentSet {
+    group_coherent_set: i64,
+    writer_group_info: [u8; 4],
+    writer_guids: Vec<Guid>,
+    complete: bool,
+}
+
 pub struct SubscriberEntity {
     instance_handle: InstanceHandle,
     qos: SubscriberQos,
@@ -23,6 +34,9 @@ pub struct SubscriberEntity {
     status_condition: Actor<StatusConditionActor>,
     listener: Option<Actor<SubscriberListenerActor>>,
     listener_mask: Vec<StatusKind>,
+    group_coherent_sets: Vec<GroupCoherentSet>,
+    access_depth: usize,
+    held_changes: Vec<(InstanceHandle, CacheChange)>,
 }
 
 impl SubscriberEntity {
@@ -42,6 +56,9 @@ impl SubscriberEntity {
             status_condition,
             listener,
             listener_mask,
+            group_coherent_sets: Vec::new(),
+            access_depth: 0,
+            held_changes: Vec::new(),
         }
     }
 
@@ -118,6 +135,141 @@ impl SubscriberEntity {
         Ok(())
     }
 
+    /// Registers that a writer has finished its part of a group coherent set. Once
+    /// all the writers taking part in the set have finished, returns the writers whose
+    /// changes can be made available to the application.
+    pub fn add_group_coherent_set_end(
+        &mut self,
+        writer_guid: Guid,
+        group_coherent_set: i64,
+        writer_group_info: [u8; 4],
+    ) -> Option<Vec<Guid>> {
+        let is_same_group = |s: &GroupCoherentSet| {
+            s.writer_group_info == writer_group_info
+                && s.writer_guids
+                    .first()
+                    .is_some_and(|g| g.prefix() == writer_guid.prefix())
+        };
+        let index = match self
+            .group_coherent_sets
+            .iter()
+            .position(|s| is_same_group(s) && s.group_coherent_set == group_coherent_set)
+        {
+            Some(i) => i,
+            None => {
+                self.group_coherent_sets.push(GroupCoherentSet {
+                    group_coherent_set,
+                    writer_group_info,
+                    writer_guids: Vec::new(),
+                    complete: false,
+                });
+                self.group_coherent_sets.len() - 1
+            }
+        };
+
+        let group = &mut self.group_coherent_sets[index];
+        if !group.writer_guids.contains(&writer_guid) {
+            group.writer_guids.push(writer_guid);
+        }
+        if !group.complete
+            && writer_group_digest(group.writer_guids.iter().copied()) == writer_group_info
+        {
+            group.complete = true;
+            // Only the last completed set of each publisher is kept, for the readers
+            // which receive their part of it later
+            self.group_coherent_sets.retain(|s| {
+                !(s.complete && is_same_group(s) && s.group_coherent_set < group_coherent_set)
+            });
+        }
+
+        self.group_coherent_sets
+            .iter()
+            .find(|s| is_same_group(s) && s.group_coherent_set == group_coherent_set)
+            .filter(|s| s.complete)
+            .map(|s| s.writer_guids.clone())
+    }
+
+    pub fn begin_access(&mut self) -> DdsResult<()> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.access_depth += 1;
+        Ok(())
+    }
+
+    /// Returns the changes which were received during the access and can now be
+    /// added to the readers.
+    pub fn end_access(&mut self) -> DdsResult<Vec<(InstanceHandle, CacheChange)>> {
+        if self.access_depth == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "No matching begin_access".to_string(),
+            ));
+        }
+
+        self.access_depth -= 1;
+        if self.access_depth == 0 {
+            let mut held_changes = core::mem::take(&mut self.held_changes);
+            held_changes.retain(|(h, _)| self.get_data_reader(*h).is_some());
+            Ok(held_changes)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// With group access the readers must keep the same content while the
+    /// application is accessing them so the received changes are held back until
+    /// the access ends.
+    pub fn is_access_active(&self) -> bool {
+        let presentation = &self.qos.presentation;
+        self.access_depth > 0
+            && presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group
+            && (presentation.coherent_access || presentation.ordered_access)
+    }
+
+    pub fn hold_changes(
+        &mut self,
+        changes: impl IntoIterator<Item = (InstanceHandle, CacheChange)>,
+    ) {
+        self.held_changes.extend(changes);
+    }
+
+    /// Returns the readers which have samples in the given states. With ordered
+    /// access and group scope a reader appears once for every one of its samples, in
+    /// the order in which the samples must be accessed.
+    pub fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> Vec<InstanceHandle> {
+        let presentation = &self.qos.presentation;
+        if presentation.ordered_access
+            && presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group
+        {
+            let mut samples: Vec<_> = self
+                .data_reader_list
+                .iter()
+                .flat_map(|dr| {
+                    dr.get_group_sample_order(sample_states, view_states, instance_states)
+                        .into_iter()
+                        .map(|o| (o, dr.instance_handle()))
+                })
+                .collect();
+            samples.sort_by(|a, b| a.0.cmp(&b.0));
+            samples.into_iter().map(|(_, h)| h).collect()
+        } else {
+            self.data_reader_list
+                .iter()
+                .filter(|dr| {
+                    !dr.get_group_sample_order(sample_states, view_states, instance_states)
+                        .is_empty()
+                })
+                .map(|dr| dr.instance_handle())
+                .collect()
+        }
+    }
+
     pub fn set_listener(
         &mut self,
         a_listener: Option<Actor<SubscriberListenerActor>>,
@@ -0,0 +1,74 @@
+use crate::{
+    implementation::domain_participant_backend::{
+        domain_participant_actor::DomainParticipantActor, services::discovery_service,
+    },
+    infrastructure::error::{DdsError, DdsResult},
+    runtime::actor::{ActorAddress, Mail, MailHandler},
+};
+
+pub struct GetExpressionParameters {
+    pub topic_name: String,
+}
+impl Mail for GetExpressionParameters {
+    type Result = DdsResult<Vec<String>>;
+}
+impl MailHandler<GetExpressionParameters> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetExpressionParameters,
+    ) -> <GetExpressionParameters as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .get_content_filtered_topic(&message.topic_name)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .content_filter()
+            .expression_parameters()
+            .to_vec())
+    }
+}
+
+pub struct SetExpressionParameters {
+    pub topic_name: String,
+    pub expression_parameters: Vec<String>,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for SetExpressionParameters {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<SetExpressionParameters> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: SetExpressionParameters,
+    ) -> <SetExpressionParameters as Mail>::Result {
+        self.domain_participant
+            .get_mut_content_filtered_topic(&message.topic_name)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .content_filter_mut()
+            .set_expression_parameters(message.expression_parameters.clone())?;
+
+        for subscriber in self.domain_participant.subscriber_list_mut() {
+            let subscriber_handle = subscriber.instance_handle();
+            for data_reader in subscriber.data_reader_list_mut() {
+                if let Some(content_filter) = data_reader.content_filter_mut() {
+                    if content_filter.content_filtered_topic_name() == message.topic_name {
+                        content_filter
+                            .set_expression_parameters(message.expression_parameters.clone())?;
+
+                        // The matched writers need the new parameters to filter the samples
+                        if data_reader.enabled() {
+                            message
+                                .participant_address
+                                .send_actor_mail(discovery_service::AnnounceDataReader {
+                                    subscriber_handle,
+                                    data_reader_handle: data_reader.instance_handle(),
+                                })
+                                .ok();
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
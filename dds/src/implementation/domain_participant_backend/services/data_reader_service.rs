@@ -1,5 +1,4 @@
 use core::{future::Future, pin::Pin};
-use std::sync::Arc;
 
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
@@ -7,6 +6,7 @@ use crate::{
         any_data_reader_listener::AnyDataReaderListener,
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
+            entities::data_reader::SampleData,
             services::message_service::IsHistoricalDataReceived,
         },
         listeners::data_reader_listener::DataReaderListenerActor,
@@ -16,14 +16,14 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind},
-        status::{StatusKind, SubscriptionMatchedStatus},
+        status::{LivelinessChangedStatus, StatusKind, SubscriptionMatchedStatus},
         time::Duration,
     },
     runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
     subscription::sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
 };
 
-use super::discovery_service;
+use super::{discovery_service, durability_service::DurabilityServiceTopic};
 
 pub struct Read {
     pub subscriber_handle: InstanceHandle,
@@ -35,7 +35,7 @@ pub struct Read {
     pub specific_instance_handle: Option<InstanceHandle>,
 }
 impl Mail for Read {
-    type Result = DdsResult<Vec<(Option<Arc<[u8]>>, SampleInfo)>>;
+    type Result = DdsResult<Vec<(Option<SampleData>, SampleInfo)>>;
 }
 impl MailHandler<Read> for DomainParticipantActor {
     fn handle(&mut self, message: Read) -> <Read as Mail>::Result {
@@ -72,7 +72,7 @@ pub struct Take {
     pub specific_instance_handle: Option<InstanceHandle>,
 }
 impl Mail for Take {
-    type Result = DdsResult<Vec<(Option<Arc<[u8]>>, SampleInfo)>>;
+    type Result = DdsResult<Vec<(Option<SampleData>, SampleInfo)>>;
 }
 impl MailHandler<Take> for DomainParticipantActor {
     fn handle(&mut self, message: Take) -> <Take as Mail>::Result {
@@ -93,6 +93,201 @@ impl MailHandler<Take> for DomainParticipantActor {
     }
 }
 
+pub struct ReadWCondition {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub max_samples: i32,
+    pub condition_handle: InstanceHandle,
+}
+impl Mail for ReadWCondition {
+    type Result = DdsResult<Vec<(Option<SampleData>, SampleInfo)>>;
+}
+impl MailHandler<ReadWCondition> for DomainParticipantActor {
+    fn handle(&mut self, message: ReadWCondition) -> <ReadWCondition as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber_mut())
+            } else {
+                self.domain_participant
+                    .get_mut_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        data_reader.read_w_condition(message.max_samples, message.condition_handle)
+    }
+}
+
+pub struct TakeWCondition {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub max_samples: i32,
+    pub condition_handle: InstanceHandle,
+}
+impl Mail for TakeWCondition {
+    type Result = DdsResult<Vec<(Option<SampleData>, SampleInfo)>>;
+}
+impl MailHandler<TakeWCondition> for DomainParticipantActor {
+    fn handle(&mut self, message: TakeWCondition) -> <TakeWCondition as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        data_reader.take_w_condition(message.max_samples, message.condition_handle)
+    }
+}
+
+pub struct CreateReadCondition {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub sample_states: Vec<SampleStateKind>,
+    pub view_states: Vec<ViewStateKind>,
+    pub instance_states: Vec<InstanceStateKind>,
+    pub query: Option<(String, Vec<String>)>,
+}
+impl Mail for CreateReadCondition {
+    type Result = DdsResult<InstanceHandle>;
+}
+impl MailHandler<CreateReadCondition> for DomainParticipantActor {
+    fn handle(&mut self, message: CreateReadCondition) -> <CreateReadCondition as Mail>::Result {
+        let condition_handle = self.instance_handle_counter.generate_new_instance_handle();
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber_mut())
+            } else {
+                self.domain_participant
+                    .get_mut_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        data_reader.create_read_condition(
+            condition_handle,
+            message.sample_states,
+            message.view_states,
+            message.instance_states,
+            message.query,
+        )?;
+        Ok(condition_handle)
+    }
+}
+
+pub struct DeleteReadCondition {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub condition_handle: InstanceHandle,
+}
+impl Mail for DeleteReadCondition {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<DeleteReadCondition> for DomainParticipantActor {
+    fn handle(&mut self, message: DeleteReadCondition) -> <DeleteReadCondition as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber_mut())
+            } else {
+                self.domain_participant
+                    .get_mut_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        data_reader.delete_read_condition(message.condition_handle)
+    }
+}
+
+pub struct GetReadConditionTriggerValue {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub condition_handle: InstanceHandle,
+}
+impl Mail for GetReadConditionTriggerValue {
+    type Result = DdsResult<bool>;
+}
+impl MailHandler<GetReadConditionTriggerValue> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetReadConditionTriggerValue,
+    ) -> <GetReadConditionTriggerValue as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber())
+            } else {
+                self.domain_participant
+                    .get_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        data_reader.get_read_condition_trigger_value(message.condition_handle)
+    }
+}
+
+pub struct GetQueryParameters {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub condition_handle: InstanceHandle,
+}
+impl Mail for GetQueryParameters {
+    type Result = DdsResult<Vec<String>>;
+}
+impl MailHandler<GetQueryParameters> for DomainParticipantActor {
+    fn handle(&mut self, message: GetQueryParameters) -> <GetQueryParameters as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber())
+            } else {
+                self.domain_participant
+                    .get_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        Ok(data_reader
+            .get_read_condition(message.condition_handle)?
+            .query()
+            .ok_or(DdsError::IllegalOperation)?
+            .expression_parameters()
+            .to_vec())
+    }
+}
+
+pub struct SetQueryParameters {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub condition_handle: InstanceHandle,
+    pub query_parameters: Vec<String>,
+}
+impl Mail for SetQueryParameters {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<SetQueryParameters> for DomainParticipantActor {
+    fn handle(&mut self, message: SetQueryParameters) -> <SetQueryParameters as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber_mut())
+            } else {
+                self.domain_participant
+                    .get_mut_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        data_reader.set_query_parameters(message.condition_handle, message.query_parameters)
+    }
+}
+
 pub struct ReadNextInstance {
     pub subscriber_handle: InstanceHandle,
     pub data_reader_handle: InstanceHandle,
@@ -103,7 +298,7 @@ pub struct ReadNextInstance {
     pub instance_states: Vec<InstanceStateKind>,
 }
 impl Mail for ReadNextInstance {
-    type Result = DdsResult<Vec<(Option<Arc<[u8]>>, SampleInfo)>>;
+    type Result = DdsResult<Vec<(Option<SampleData>, SampleInfo)>>;
 }
 impl MailHandler<ReadNextInstance> for DomainParticipantActor {
     fn handle(&mut self, message: ReadNextInstance) -> <ReadNextInstance as Mail>::Result {
@@ -134,7 +329,7 @@ pub struct TakeNextInstance {
     pub instance_states: Vec<InstanceStateKind>,
 }
 impl Mail for TakeNextInstance {
-    type Result = DdsResult<Vec<(Option<Arc<[u8]>>, SampleInfo)>>;
+    type Result = DdsResult<Vec<(Option<SampleData>, SampleInfo)>>;
 }
 impl MailHandler<TakeNextInstance> for DomainParticipantActor {
     fn handle(&mut self, message: TakeNextInstance) -> <TakeNextInstance as Mail>::Result {
@@ -184,6 +379,35 @@ impl MailHandler<GetSubscriptionMatchedStatus> for DomainParticipantActor {
     }
 }
 
+pub struct GetLivelinessChangedStatus {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for GetLivelinessChangedStatus {
+    type Result = DdsResult<LivelinessChangedStatus>;
+}
+impl MailHandler<GetLivelinessChangedStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetLivelinessChangedStatus,
+    ) -> <GetLivelinessChangedStatus as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let status = data_reader.get_liveliness_changed_status();
+        data_reader.status_condition().send_actor_mail(
+            status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::LivelinessChanged,
+            },
+        );
+        Ok(status)
+    }
+}
+
 pub struct WaitForHistoricalData {
     pub participant_address: ActorAddress<DomainParticipantActor>,
     pub subscriber_handle: InstanceHandle,
@@ -358,6 +582,31 @@ impl MailHandler<Enable> for DomainParticipantActor {
         if !data_reader.enabled() {
             data_reader.enable();
 
+            // Transient and persistent readers make the durability service serve the
+            // stored samples, e.g. those of an earlier run of the application
+            let durability_kind = data_reader.qos().durability.kind;
+            let topic_name = data_reader.topic_name().to_owned();
+            let type_name = data_reader.type_name().to_owned();
+            if let Some(topic_qos) = self
+                .domain_participant
+                .get_topic(&topic_name)
+                .map(|t| t.qos().clone())
+                .filter(|_| self.durability_stores.get(durability_kind).is_some())
+            {
+                let topic = self.durability_topic_key(&topic_name, &type_name);
+                let service_topic = DurabilityServiceTopic::new(
+                    topic_name,
+                    topic_qos.durability_service,
+                    topic_qos.lifespan,
+                );
+                self.start_durability_service(
+                    durability_kind,
+                    topic,
+                    service_topic,
+                    message.participant_address.clone(),
+                );
+            }
+
             for discovered_writer_data in self
                 .domain_participant
                 .publication_builtin_topic_data_list()
@@ -2,10 +2,13 @@ use core::{future::Future, pin::Pin};
 
 use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
+    durability::DurabilitySample,
     implementation::{
         any_data_writer_listener::AnyDataWriterListener,
+        data_representation_builtin_endpoints::participant_message_data::PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
+            entities::data_writer::SharedValue,
             services::message_service::AreAllChangesAcknowledged,
         },
         listeners::data_writer_listener::DataWriterListenerActor,
@@ -18,15 +21,24 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, QosKind},
-        status::{OfferedDeadlineMissedStatus, PublicationMatchedStatus, StatusKind},
+        qos_policy::{DurabilityQosPolicyKind, LivelinessQosPolicyKind},
+        status::{
+            LivelinessLostStatus, OfferedDeadlineMissedStatus, PublicationMatchedStatus, StatusKind,
+        },
         time::{Duration, DurationKind, Time},
     },
     runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
 };
+use tracing::warn;
 
-use super::{discovery_service, event_service, message_service};
+use super::{
+    discovery_service,
+    durability_service::{self, DurabilityServiceTopic},
+    event_service, message_service,
+};
 
 pub struct UnregisterInstance {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
     pub serialized_data: Vec<u8>,
@@ -41,6 +53,7 @@ impl MailHandler<UnregisterInstance> for DomainParticipantActor {
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        let group_sequence_number = publisher.next_group_sequence_number();
         let data_writer = publisher
             .data_writer_list_mut()
             .find(|x| x.instance_handle() == message.data_writer_handle)
@@ -49,9 +62,17 @@ impl MailHandler<UnregisterInstance> for DomainParticipantActor {
             &message.serialized_data,
             data_writer.type_support(),
         )?;
-        data_writer.unregister_w_timestamp(serialized_key, message.timestamp)?;
+        data_writer.unregister_w_timestamp(
+            serialized_key,
+            message.timestamp,
+            group_sequence_number,
+        )?;
 
-        Ok(())
+        self.assert_liveliness_on_write(
+            message.participant_address,
+            message.publisher_handle,
+            message.data_writer_handle,
+        )
     }
 }
 
@@ -93,6 +114,7 @@ pub struct WriteWTimestamp {
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
     pub serialized_data: Vec<u8>,
+    pub shared_value: Option<SharedValue>,
     pub timestamp: Time,
 }
 impl Mail for WriteWTimestamp {
@@ -105,6 +127,7 @@ impl MailHandler<WriteWTimestamp> for DomainParticipantActor {
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        let group_sequence_number = publisher.next_group_sequence_number();
         let data_writer = publisher
             .get_mut_data_writer(message.data_writer_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
@@ -113,13 +136,32 @@ impl MailHandler<WriteWTimestamp> for DomainParticipantActor {
             data_writer.type_support(),
         )?;
 
-        match data_writer.qos().lifespan.duration {
+        // The samples of transient and persistent writers are also kept by the
+        // durability service so that they outlive the writer
+        let durability_sample = match data_writer.qos().durability.kind {
+            DurabilityQosPolicyKind::Volatile | DurabilityQosPolicyKind::TransientLocal => None,
+            DurabilityQosPolicyKind::Transient | DurabilityQosPolicyKind::Persistent => Some((
+                data_writer.qos().durability.kind,
+                data_writer.qos().durability_service.clone(),
+                data_writer.topic_name().to_owned(),
+                data_writer.type_name().to_owned(),
+                InstanceHandle::new(data_writer.transport_writer().guid().into()),
+                message.serialized_data.clone(),
+            )),
+        };
+
+        // Samples whose lifespan already expired are not written
+        let written_sequence_number = match data_writer.qos().lifespan.duration {
             DurationKind::Finite(lifespan_duration) => {
                 let timer_handle = self.timer_driver.handle();
                 let sleep_duration = message.timestamp - now + lifespan_duration;
                 if sleep_duration > Duration::new(0, 0) {
-                    let sequence_number = data_writer
-                        .write_w_timestamp(message.serialized_data, message.timestamp)?;
+                    let sequence_number = data_writer.write_shared_w_timestamp(
+                        message.serialized_data,
+                        message.shared_value,
+                        message.timestamp,
+                        group_sequence_number,
+                    )?;
                     let participant_address = message.participant_address.clone();
                     self.backend_executor.handle().spawn(async move {
                         timer_handle.sleep(sleep_duration.into()).await;
@@ -131,25 +173,31 @@ impl MailHandler<WriteWTimestamp> for DomainParticipantActor {
                             })
                             .ok();
                     });
+                    Some(sequence_number)
+                } else {
+                    None
                 }
             }
-            DurationKind::Infinite => {
-                data_writer.write_w_timestamp(message.serialized_data, message.timestamp)?;
-            }
-        }
+            DurationKind::Infinite => Some(data_writer.write_shared_w_timestamp(
+                message.serialized_data,
+                message.shared_value,
+                message.timestamp,
+                group_sequence_number,
+            )?),
+        };
 
         if let DurationKind::Finite(deadline_missed_period) = data_writer.qos().deadline.period {
             let timer_handle = self.timer_driver.handle();
+            let participant_address = message.participant_address.clone();
             let offered_deadline_missed_task = self.backend_executor.handle().spawn(async move {
                 loop {
                     timer_handle.sleep(deadline_missed_period.into()).await;
-                    message
-                        .participant_address
+                    participant_address
                         .send_actor_mail(event_service::OfferedDeadlineMissed {
                             publisher_handle: message.publisher_handle,
                             data_writer_handle: message.data_writer_handle,
                             change_instance_handle: instance_handle,
-                            participant_address: message.participant_address.clone(),
+                            participant_address: participant_address.clone(),
                         })
                         .ok();
                 }
@@ -160,11 +208,39 @@ impl MailHandler<WriteWTimestamp> for DomainParticipantActor {
             );
         }
 
-        Ok(())
+        if let (
+            Some((durability_kind, durability_service, topic_name, type_name, writer_handle, data)),
+            Some(sequence_number),
+        ) = (durability_sample, written_sequence_number)
+        {
+            let topic = self.durability_topic_key(&topic_name, &type_name);
+            let sample = DurabilitySample::new(
+                writer_handle,
+                sequence_number,
+                instance_handle,
+                message.timestamp,
+                data,
+            );
+            if let Some(durability_store) = self.durability_stores.get(durability_kind) {
+                if let Err(e) = durability_store.add_sample(&topic, sample, &durability_service) {
+                    warn!(
+                        "Sample of topic {} not kept by the durability service: {:?}",
+                        topic_name, e
+                    );
+                }
+            }
+        }
+
+        self.assert_liveliness_on_write(
+            message.participant_address,
+            message.publisher_handle,
+            message.data_writer_handle,
+        )
     }
 }
 
 pub struct DisposeWTimestamp {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
     pub serialized_data: Vec<u8>,
@@ -179,6 +255,7 @@ impl MailHandler<DisposeWTimestamp> for DomainParticipantActor {
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        let group_sequence_number = publisher.next_group_sequence_number();
         let data_writer = publisher
             .get_mut_data_writer(message.data_writer_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
@@ -186,7 +263,55 @@ impl MailHandler<DisposeWTimestamp> for DomainParticipantActor {
             &message.serialized_data,
             data_writer.type_support(),
         )?;
-        data_writer.dispose_w_timestamp(serialized_key, message.timestamp)
+        let instance_handle = get_instance_handle_from_serialized_foo(
+            &message.serialized_data,
+            data_writer.type_support(),
+        )?;
+        data_writer.dispose_w_timestamp(
+            serialized_key,
+            message.timestamp,
+            group_sequence_number,
+        )?;
+        let durability_kind = data_writer.qos().durability.kind;
+        let service_cleanup_delay = data_writer.qos().durability_service.service_cleanup_delay;
+        let topic_name = data_writer.topic_name().to_owned();
+        let type_name = data_writer.type_name().to_owned();
+
+        // The durability service removes the samples of the disposed instance once
+        // the cleanup delay has elapsed
+        if self.durability_stores.get(durability_kind).is_some() {
+            let cleanup_disposed_instance = durability_service::CleanupDisposedInstance {
+                durability_kind,
+                topic: self.durability_topic_key(&topic_name, &type_name),
+                instance_handle,
+                disposed_timestamp: message.timestamp,
+                participant_address: message.participant_address.clone(),
+            };
+            match service_cleanup_delay {
+                DurationKind::Finite(delay) if delay > Duration::new(0, 0) => {
+                    let timer_handle = self.timer_driver.handle();
+                    let participant_address = message.participant_address.clone();
+                    self.backend_executor.handle().spawn(async move {
+                        timer_handle.sleep(delay.into()).await;
+                        participant_address
+                            .send_actor_mail(cleanup_disposed_instance)
+                            .ok();
+                    });
+                }
+                DurationKind::Finite(_) => {
+                    message
+                        .participant_address
+                        .send_actor_mail(cleanup_disposed_instance)?;
+                }
+                DurationKind::Infinite => (),
+            }
+        }
+
+        self.assert_liveliness_on_write(
+            message.participant_address,
+            message.publisher_handle,
+            message.data_writer_handle,
+        )
     }
 }
 
@@ -255,6 +380,71 @@ impl MailHandler<GetOfferedDeadlineMissedStatus> for DomainParticipantActor {
     }
 }
 
+pub struct GetLivelinessLostStatus {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for GetLivelinessLostStatus {
+    type Result = DdsResult<LivelinessLostStatus>;
+}
+impl MailHandler<GetLivelinessLostStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetLivelinessLostStatus,
+    ) -> <GetLivelinessLostStatus as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_liveliness_lost_status())
+    }
+}
+
+pub struct AssertLiveliness {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for AssertLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AssertLiveliness> for DomainParticipantActor {
+    fn handle(&mut self, message: AssertLiveliness) -> <AssertLiveliness as Mail>::Result {
+        let data_writer = self
+            .domain_participant
+            .get_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if !data_writer.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+
+        match data_writer.qos().liveliness.kind {
+            LivelinessQosPolicyKind::Automatic => Ok(()),
+            LivelinessQosPolicyKind::ManualByParticipant => {
+                self.assert_participant_manual_liveliness(message.participant_address)?;
+                self.announce_participant_message(
+                    PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
+                )
+            }
+            LivelinessQosPolicyKind::ManualByTopic => {
+                self.assert_data_writer_liveliness(
+                    message.participant_address,
+                    message.publisher_handle,
+                    message.data_writer_handle,
+                )?;
+                self.announce_data_writer_liveliness(
+                    message.publisher_handle,
+                    message.data_writer_handle,
+                )
+            }
+        }
+    }
+}
+
 pub struct GetPublicationMatchedStatus {
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
@@ -410,28 +600,34 @@ impl MailHandler<Enable> for DomainParticipantActor {
         if !data_writer.enabled() {
             data_writer.enable();
 
-            for discovered_reader_data in self
-                .domain_participant
-                .discovered_reader_data_list()
-                .cloned()
-            {
-                message
-                    .participant_address
-                    .send_actor_mail(discovery_service::AddDiscoveredReader {
-                        discovered_reader_data,
-                        publisher_handle: message.publisher_handle,
-                        data_writer_handle: message.data_writer_handle,
-                        participant_address: message.participant_address.clone(),
-                    })
-                    .ok();
+            // The samples of transient and persistent writers are served by the
+            // writers themselves while they are alive and by the durability service
+            // once they are deleted
+            let durability_kind = data_writer.qos().durability.kind;
+            if self.durability_stores.get(durability_kind).is_some() {
+                self.durability_stores.add_live_writer(InstanceHandle::new(
+                    data_writer.transport_writer().guid().into(),
+                ));
+                let type_name = data_writer.type_name().to_owned();
+                let service_topic = DurabilityServiceTopic::new(
+                    data_writer.topic_name().to_owned(),
+                    data_writer.qos().durability_service.clone(),
+                    data_writer.qos().lifespan.clone(),
+                );
+                let topic = self.durability_topic_key(service_topic.topic_name(), &type_name);
+                self.start_durability_service(
+                    durability_kind,
+                    topic,
+                    service_topic,
+                    message.participant_address.clone(),
+                );
             }
 
-            message
-                .participant_address
-                .send_actor_mail(discovery_service::AnnounceDataWriter {
-                    publisher_handle: message.publisher_handle,
-                    data_writer_handle: message.data_writer_handle,
-                })?;
+            self.announce_enabled_data_writer(
+                message.publisher_handle,
+                message.data_writer_handle,
+                message.participant_address,
+            )?;
         }
 
         Ok(())
@@ -465,3 +661,88 @@ impl MailHandler<SetListener> for DomainParticipantActor {
         Ok(())
     }
 }
+
+impl DomainParticipantActor {
+    /// Match an enabled writer with the discovered readers and announce it
+    pub fn announce_enabled_data_writer(
+        &mut self,
+        publisher_handle: InstanceHandle,
+        data_writer_handle: InstanceHandle,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) -> DdsResult<()> {
+        let liveliness = self
+            .domain_participant
+            .get_publisher(publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_writer(data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .qos()
+            .liveliness
+            .clone();
+
+        for discovered_reader_data in self
+            .domain_participant
+            .discovered_reader_data_list()
+            .cloned()
+        {
+            participant_address
+                .send_actor_mail(discovery_service::AddDiscoveredReader {
+                    discovered_reader_data,
+                    publisher_handle,
+                    data_writer_handle,
+                    participant_address: participant_address.clone(),
+                })
+                .ok();
+        }
+
+        if let DurationKind::Finite(lease_duration) = liveliness.lease_duration {
+            match liveliness.kind {
+                LivelinessQosPolicyKind::Automatic => {
+                    self.start_automatic_liveliness(participant_address.clone(), lease_duration)
+                }
+                LivelinessQosPolicyKind::ManualByParticipant
+                | LivelinessQosPolicyKind::ManualByTopic => self.assert_data_writer_liveliness(
+                    participant_address.clone(),
+                    publisher_handle,
+                    data_writer_handle,
+                )?,
+            }
+        }
+
+        participant_address.send_actor_mail(discovery_service::AnnounceDataWriter {
+            publisher_handle,
+            data_writer_handle,
+        })?;
+        Ok(())
+    }
+
+    /// Writing, disposing or unregistering data asserts the liveliness of writers
+    /// with a manual liveliness kind (2.2.3.11 DDS standard)
+    fn assert_liveliness_on_write(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        publisher_handle: InstanceHandle,
+        data_writer_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let liveliness_kind = self
+            .domain_participant
+            .get_publisher(publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_writer(data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .qos()
+            .liveliness
+            .kind;
+        match liveliness_kind {
+            LivelinessQosPolicyKind::Automatic => Ok(()),
+            LivelinessQosPolicyKind::ManualByParticipant => {
+                self.assert_participant_manual_liveliness(participant_address)
+            }
+            LivelinessQosPolicyKind::ManualByTopic => self.assert_data_writer_liveliness(
+                participant_address,
+                publisher_handle,
+                data_writer_handle,
+            ),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use fnmatch_regex::glob_to_regex;
 
 use crate::{
@@ -7,9 +9,13 @@ use crate::{
         DCPS_SUBSCRIPTION, DCPS_TOPIC,
     },
     implementation::{
+        content_filter::filter::{ContentFilter, WriterSideContentFilter},
         data_representation_builtin_endpoints::{
-            discovered_reader_data::{DiscoveredReaderData, ReaderProxy},
+            discovered_reader_data::{
+                ContentFilterProperty, DiscoveredReaderData, ReaderProxy, DDSSQL_FILTER_CLASS_NAME,
+            },
             discovered_writer_data::{DiscoveredWriterData, WriterProxy},
+            participant_message_data::{ParticipantMessageData, DCPS_PARTICIPANT_MESSAGE},
             spdp_discovered_participant_data::{
                 BuiltinEndpointQos, BuiltinEndpointSet, ParticipantProxy,
                 SpdpDiscoveredParticipantData,
@@ -19,10 +25,13 @@ use crate::{
             domain_participant_actor::DomainParticipantActor,
             entities::{
                 data_reader::{DataReaderEntity, TransportReaderKind},
-                data_writer::{DataWriterEntity, TransportWriterKind},
+                data_writer::{DataWriterEntity, LocalDataReader, TransportWriterKind},
             },
+            services::message_service::AddCacheChange,
         },
         domain_participant_factory::domain_participant_factory_actor::{
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
             ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
             ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
             ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
@@ -47,16 +56,21 @@ use crate::{
             RELIABILITY_QOS_POLICY_ID, XCDR_DATA_REPRESENTATION,
         },
         status::StatusKind,
-        time::Duration,
     },
     runtime::actor::{ActorAddress, Mail, MailHandler},
     topic_definition::type_support::DdsSerialize,
     transport::{
         self,
-        types::{DurabilityKind, Guid, ReliabilityKind, ENTITYID_UNKNOWN},
+        types::{DurabilityKind, Guid, GuidPrefix, ReliabilityKind, ENTITYID_UNKNOWN},
+        writer::ReaderContentFilter,
     },
 };
 
+// Longest content filter of a discovered reader, counting the expression and its
+// parameters, which is evaluated on the writer side. Filters received from the
+// network are bounded so that evaluating them for each written sample stays cheap.
+const MAX_REMOTE_CONTENT_FILTER_LENGTH: usize = 1024;
+
 pub struct AnnounceParticipant;
 impl Mail for AnnounceParticipant {
     type Result = DdsResult<()>;
@@ -100,7 +114,7 @@ impl MailHandler<AnnounceParticipant> for DomainParticipantActor {
             let spdp_discovered_participant_data = SpdpDiscoveredParticipantData {
                 dds_participant_data: participant_builtin_topic_data,
                 participant_proxy,
-                lease_duration: Duration::new(100, 0),
+                lease_duration: self.domain_participant.lease_duration(),
                 discovered_participant_list: self.domain_participant.get_discovered_participants(),
             };
             let timestamp = self.domain_participant.get_current_time();
@@ -113,6 +127,7 @@ impl MailHandler<AnnounceParticipant> for DomainParticipantActor {
                 dw.write_w_timestamp(
                     spdp_discovered_participant_data.serialize_data()?,
                     timestamp,
+                    None,
                 )?;
             }
         }
@@ -138,7 +153,7 @@ impl MailHandler<AnnounceDeletedParticipant> for DomainParticipantActor {
                 .lookup_datawriter_mut(DCPS_PARTICIPANT)
             {
                 let key = InstanceHandle::new(self.transport.guid().into());
-                dw.dispose_w_timestamp(key.serialize_data()?, timestamp)?;
+                dw.dispose_w_timestamp(key.serialize_data()?, timestamp, None)?;
             }
         }
 
@@ -211,7 +226,7 @@ impl MailHandler<AnnounceDataWriter> for DomainParticipantActor {
             .builtin_publisher_mut()
             .lookup_datawriter_mut(DCPS_PUBLICATION)
         {
-            dw.write_w_timestamp(discovered_writer_data.serialize_data()?, timestamp)?;
+            dw.write_w_timestamp(discovered_writer_data.serialize_data()?, timestamp, None)?;
         }
         Ok(())
     }
@@ -235,7 +250,7 @@ impl MailHandler<AnnounceDeletedDataWriter> for DomainParticipantActor {
             .lookup_datawriter_mut(DCPS_PUBLICATION)
         {
             let key = InstanceHandle::new(message.data_writer.transport_writer().guid().into());
-            dw.dispose_w_timestamp(key.serialize_data()?, timestamp)?;
+            dw.dispose_w_timestamp(key.serialize_data()?, timestamp, None)?;
         }
         Ok(())
     }
@@ -292,9 +307,22 @@ impl MailHandler<AnnounceDataReader> for DomainParticipantActor {
             multicast_locator_list: vec![],
             expects_inline_qos: false,
         };
+        let content_filter =
+            data_reader
+                .content_filter()
+                .map(|content_filter| ContentFilterProperty {
+                    content_filtered_topic_name: content_filter
+                        .content_filtered_topic_name()
+                        .to_owned(),
+                    related_topic_name: content_filter.related_topic_name().to_owned(),
+                    filter_class_name: DDSSQL_FILTER_CLASS_NAME.to_owned(),
+                    filter_expression: content_filter.filter_expression().to_owned(),
+                    expression_parameters: content_filter.expression_parameters().to_vec(),
+                });
         let discovered_reader_data = DiscoveredReaderData {
             dds_subscription_data,
             reader_proxy,
+            content_filter,
         };
         let timestamp = self.domain_participant.get_current_time();
         if let Some(dw) = self
@@ -302,7 +330,7 @@ impl MailHandler<AnnounceDataReader> for DomainParticipantActor {
             .builtin_publisher_mut()
             .lookup_datawriter_mut(DCPS_SUBSCRIPTION)
         {
-            dw.write_w_timestamp(discovered_reader_data.serialize_data()?, timestamp)?;
+            dw.write_w_timestamp(discovered_reader_data.serialize_data()?, timestamp, None)?;
         }
         Ok(())
     }
@@ -348,7 +376,7 @@ impl MailHandler<AnnounceTopic> for DomainParticipantActor {
             .builtin_publisher_mut()
             .lookup_datawriter_mut(DCPS_TOPIC)
         {
-            dw.write_w_timestamp(topic_builtin_topic_data.serialize_data()?, timestamp)?;
+            dw.write_w_timestamp(topic_builtin_topic_data.serialize_data()?, timestamp, None)?;
         }
         Ok(())
     }
@@ -373,12 +401,27 @@ impl MailHandler<AnnounceDeletedDataReader> for DomainParticipantActor {
         {
             let guid = message.data_reader.transport_reader().guid();
             let key = InstanceHandle::new(guid.into());
-            dw.dispose_w_timestamp(key.serialize_data()?, timestamp)?;
+            dw.dispose_w_timestamp(key.serialize_data()?, timestamp, None)?;
         }
         Ok(())
     }
 }
 
+pub struct AnnounceParticipantMessage {
+    pub kind: [u8; 4],
+}
+impl Mail for AnnounceParticipantMessage {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AnnounceParticipantMessage> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AnnounceParticipantMessage,
+    ) -> <AnnounceParticipantMessage as Mail>::Result {
+        self.announce_participant_message(message.kind)
+    }
+}
+
 pub struct AddDiscoveredTopic {
     pub topic_builtin_topic_data: TopicBuiltinTopicData,
     pub topic_name: String,
@@ -404,6 +447,7 @@ impl MailHandler<AddDiscoveredTopic> for DomainParticipantActor {
 
 pub struct AddDiscoveredParticipant {
     pub discovered_participant_data: SpdpDiscoveredParticipantData,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for AddDiscoveredParticipant {
     type Result = ();
@@ -458,6 +502,61 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
             add_matched_subscriptions_announcer(self, &message.discovered_participant_data);
             add_matched_topics_detector(self, &message.discovered_participant_data);
             add_matched_topics_announcer(self, &message.discovered_participant_data);
+            add_matched_participant_message_reader(self, &message.discovered_participant_data);
+            add_matched_participant_message_writer(self, &message.discovered_participant_data);
+
+            // Without multicast the discovered participant only knows this participant if
+            // it is in its initial peers, so the announcements are also sent to it directly
+            if self
+                .transport
+                .metatraffic_multicast_locator_list()
+                .is_empty()
+            {
+                if let Some(TransportWriterKind::Stateless(w)) = self
+                    .domain_participant
+                    .builtin_publisher_mut()
+                    .lookup_datawriter_mut(DCPS_PARTICIPANT)
+                    .map(|dw| dw.transport_writer_mut())
+                {
+                    for &locator in message
+                        .discovered_participant_data
+                        .participant_proxy
+                        .metatraffic_unicast_locator_list
+                        .iter()
+                    {
+                        w.add_reader_locator(locator);
+                    }
+                }
+            }
+        }
+
+        // Every announcement of the participant renews its lease. When no announcement
+        // is received within the lease duration the participant is considered gone.
+        let discovered_participant_handle = InstanceHandle::new(
+            message
+                .discovered_participant_data
+                .dds_participant_data
+                .key
+                .value,
+        );
+        let lease_duration = message.discovered_participant_data.lease_duration;
+        if lease_duration.sec() >= 0 {
+            let timer_handle = self.timer_driver.handle();
+            let participant_address = message.participant_address.clone();
+            let lease_task = self.backend_executor.handle().spawn(async move {
+                timer_handle.sleep(lease_duration.into()).await;
+                participant_address
+                    .send_actor_mail(RemoveDiscoveredParticipant {
+                        discovered_participant: discovered_participant_handle,
+                        participant_address: participant_address.clone(),
+                    })
+                    .ok();
+            });
+            self.domain_participant
+                .insert_discovered_participant_lease_task(
+                    discovered_participant_handle,
+                    lease_task,
+                );
         }
 
         self.domain_participant
@@ -467,6 +566,7 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
 
 pub struct RemoveDiscoveredParticipant {
     pub discovered_participant: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for RemoveDiscoveredParticipant {
     type Result = ();
@@ -476,8 +576,89 @@ impl MailHandler<RemoveDiscoveredParticipant> for DomainParticipantActor {
         &mut self,
         message: RemoveDiscoveredParticipant,
     ) -> <RemoveDiscoveredParticipant as Mail>::Result {
+        let Some((guid_prefix, metatraffic_unicast_locator_list)) = self
+            .domain_participant
+            .get_discovered_participant_data(&message.discovered_participant)
+            .map(|p| {
+                (
+                    p.participant_proxy.guid_prefix,
+                    p.participant_proxy.metatraffic_unicast_locator_list.clone(),
+                )
+            })
+        else {
+            return;
+        };
+        if self
+            .transport
+            .metatraffic_multicast_locator_list()
+            .is_empty()
+        {
+            let initial_peer_locator_list = self.transport.initial_peer_locator_list().to_vec();
+            if let Some(TransportWriterKind::Stateless(w)) = self
+                .domain_participant
+                .builtin_publisher_mut()
+                .lookup_datawriter_mut(DCPS_PARTICIPANT)
+                .map(|dw| dw.transport_writer_mut())
+            {
+                for locator in metatraffic_unicast_locator_list
+                    .iter()
+                    .filter(|l| !initial_peer_locator_list.contains(l))
+                {
+                    w.remove_reader_locator(locator);
+                }
+            }
+        }
         self.domain_participant
             .remove_discovered_participant(&message.discovered_participant);
+        remove_matched_builtin_endpoints(self, guid_prefix);
+
+        let discovered_writer_handle_list: Vec<_> = self
+            .domain_participant
+            .publication_builtin_topic_data_list()
+            .filter(|w| w.writer_proxy.remote_writer_guid.prefix() == guid_prefix)
+            .map(|w| InstanceHandle::new(w.dds_publication_data.key().value))
+            .collect();
+        for discovered_writer_handle in discovered_writer_handle_list {
+            self.domain_participant
+                .remove_discovered_writer(&discovered_writer_handle);
+            for subscriber in self.domain_participant.subscriber_list() {
+                for data_reader in subscriber.data_reader_list() {
+                    message
+                        .participant_address
+                        .send_actor_mail(RemoveDiscoveredWriter {
+                            publication_handle: discovered_writer_handle,
+                            subscriber_handle: subscriber.instance_handle(),
+                            data_reader_handle: data_reader.instance_handle(),
+                            participant_address: message.participant_address.clone(),
+                        })
+                        .ok();
+                }
+            }
+        }
+
+        let discovered_reader_handle_list: Vec<_> = self
+            .domain_participant
+            .discovered_reader_data_list()
+            .filter(|r| r.reader_proxy.remote_reader_guid.prefix() == guid_prefix)
+            .map(|r| InstanceHandle::new(r.dds_subscription_data.key().value))
+            .collect();
+        for discovered_reader_handle in discovered_reader_handle_list {
+            self.domain_participant
+                .remove_discovered_reader(&discovered_reader_handle);
+            for publisher in self.domain_participant.publisher_list() {
+                for data_writer in publisher.data_writer_list() {
+                    message
+                        .participant_address
+                        .send_actor_mail(RemoveDiscoveredReader {
+                            subscription_handle: discovered_reader_handle,
+                            publisher_handle: publisher.instance_handle(),
+                            data_writer_handle: data_writer.instance_handle(),
+                            participant_address: message.participant_address.clone(),
+                        })
+                        .ok();
+                }
+            }
+        }
     }
 }
 
@@ -522,6 +703,64 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
         } else {
             vec![]
         };
+        // Readers of this application receive the changes directly from the writer
+        // entity instead of through the transport. Only the readers which exist are
+        // served this way if the prefix is the one of this participant.
+        let reader_guid = message
+            .discovered_reader_data
+            .reader_proxy
+            .remote_reader_guid;
+        let local_reader = self
+            .local_participants
+            .get(&self.transport.guid().prefix(), &reader_guid.prefix())
+            .filter(|_| {
+                reader_guid.prefix() != self.transport.guid().prefix()
+                    || self
+                        .domain_participant
+                        .subscriber_list()
+                        .flat_map(|s| s.data_reader_list())
+                        .any(|dr| dr.transport_reader().guid() == reader_guid)
+            })
+            .map(|participant_address| LocalDataReader {
+                guid: reader_guid,
+                participant_address,
+            });
+        // The filter of the remote reader is evaluated by the writer to avoid sending
+        // samples which are discarded anyway. Filters which can not be evaluated locally,
+        // including those too long or nested too deeply, are ignored since the reader
+        // applies its filter on reception as well.
+        let content_filter = message
+            .discovered_reader_data
+            .content_filter
+            .as_ref()
+            .filter(|p| {
+                p.filter_class_name == DDSSQL_FILTER_CLASS_NAME
+                    && p.filter_expression.len()
+                        + p.expression_parameters
+                            .iter()
+                            .map(String::len)
+                            .sum::<usize>()
+                        <= MAX_REMOTE_CONTENT_FILTER_LENGTH
+            })
+            .and_then(|p| {
+                let type_support = self
+                    .domain_participant
+                    .get_topic(&p.related_topic_name)?
+                    .type_support()
+                    .clone();
+                let content_filter = ContentFilter::new(
+                    p.content_filtered_topic_name.clone(),
+                    p.related_topic_name.clone(),
+                    p.filter_expression.clone(),
+                    p.expression_parameters.clone(),
+                    type_support.as_ref(),
+                )
+                .ok()?;
+                Some(
+                    Arc::new(WriterSideContentFilter::new(content_filter, type_support))
+                        as Arc<dyn ReaderContentFilter>,
+                )
+            });
         let publisher = self
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
@@ -665,100 +904,26 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                         unicast_locator_list,
                         multicast_locator_list,
                         expects_inline_qos: false,
+                        content_filter,
                     };
-                    if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
-                        w.add_matched_reader(reader_proxy);
-                    }
-
-                    if data_writer
-                        .listener_mask()
-                        .contains(&StatusKind::PublicationMatched)
-                    {
-                        let status = data_writer.get_publication_matched_status();
-                        let the_writer = self.get_data_writer_async(
-                            message.participant_address,
-                            message.publisher_handle,
-                            message.data_writer_handle,
-                        )?;
-                        if let Some(l) = self
-                            .domain_participant
-                            .get_mut_publisher(message.publisher_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_mut_data_writer(message.data_writer_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .listener()
-                        {
-                            l.send_actor_mail(data_writer_listener::TriggerPublicationMatched {
-                                the_writer,
-                                status,
-                            });
-                        }
-                    } else if self
-                        .domain_participant
-                        .get_mut_publisher(message.publisher_handle)
-                        .ok_or(DdsError::AlreadyDeleted)?
-                        .listener_mask()
-                        .contains(&StatusKind::PublicationMatched)
+                    if let Some(local_reader) = local_reader {
+                        // The changes are delivered once the reader has matched this writer
+                        data_writer.add_local_reader(
+                            local_reader,
+                            durability_kind != DurabilityKind::Volatile,
+                            false,
+                        );
+                    } else if let TransportWriterKind::Stateful(w) =
+                        data_writer.transport_writer_mut()
                     {
-                        let the_writer = self.get_data_writer_async(
-                            message.participant_address,
-                            message.publisher_handle,
-                            message.data_writer_handle,
-                        )?;
-                        let status = self
-                            .domain_participant
-                            .get_mut_publisher(message.publisher_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_mut_data_writer(message.data_writer_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_publication_matched_status();
-                        if let Some(l) = self
-                            .domain_participant
-                            .get_mut_publisher(message.publisher_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .listener()
-                        {
-                            l.send_actor_mail(publisher_listener::TriggerOnPublicationMatched {
-                                the_writer,
-                                status,
-                            });
-                        }
-                    } else if self
-                        .domain_participant
-                        .listener_mask()
-                        .contains(&StatusKind::PublicationMatched)
-                    {
-                        let the_writer = self.get_data_writer_async(
-                            message.participant_address,
-                            message.publisher_handle,
-                            message.data_writer_handle,
-                        )?;
-                        let status = self
-                            .domain_participant
-                            .get_mut_publisher(message.publisher_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_mut_data_writer(message.data_writer_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_publication_matched_status();
-                        if let Some(l) = self.domain_participant.listener() {
-                            l.send_actor_mail(
-                                domain_participant_listener::TriggerPublicationMatched {
-                                    the_writer,
-                                    status,
-                                },
-                            );
-                        }
+                        w.add_matched_reader(reader_proxy);
                     }
 
-                    self.domain_participant
-                        .get_mut_publisher(message.publisher_handle)
-                        .ok_or(DdsError::AlreadyDeleted)?
-                        .get_mut_data_writer(message.data_writer_handle)
-                        .ok_or(DdsError::AlreadyDeleted)?
-                        .status_condition()
-                        .send_actor_mail(status_condition_actor::AddCommunicationState {
-                            state: StatusKind::PublicationMatched,
-                        });
+                    self.trigger_publication_matched(
+                        message.participant_address,
+                        message.publisher_handle,
+                        message.data_writer_handle,
+                    )?;
                 } else {
                     data_writer.add_incompatible_subscription(
                         InstanceHandle::new(
@@ -873,6 +1038,7 @@ pub struct RemoveDiscoveredReader {
     pub subscription_handle: InstanceHandle,
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for RemoveDiscoveredReader {
     type Result = DdsResult<()>;
@@ -894,12 +1060,17 @@ impl MailHandler<RemoveDiscoveredReader> for DomainParticipantActor {
             .is_some()
         {
             data_writer.remove_matched_subscription(&message.subscription_handle);
+            let reader_guid = Guid::from(<[u8; 16]>::from(message.subscription_handle));
+            data_writer.remove_local_reader(reader_guid);
+            if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+                w.remove_matched_reader(reader_guid);
+            }
 
-            data_writer.status_condition().send_actor_mail(
-                status_condition_actor::AddCommunicationState {
-                    state: StatusKind::PublicationMatched,
-                },
-            );
+            self.trigger_publication_matched(
+                message.participant_address,
+                message.publisher_handle,
+                message.data_writer_handle,
+            )?;
         }
         Ok(())
     }
@@ -916,6 +1087,21 @@ impl Mail for AddDiscoveredWriter {
 }
 impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
     fn handle(&mut self, message: AddDiscoveredWriter) -> <AddDiscoveredWriter as Mail>::Result {
+        let writer_guid = message
+            .discovered_writer_data
+            .writer_proxy
+            .remote_writer_guid;
+        let local_writer_participant_address = self
+            .local_participants
+            .get(&self.transport.guid().prefix(), &writer_guid.prefix())
+            .filter(|_| {
+                writer_guid.prefix() != self.transport.guid().prefix()
+                    || self
+                        .domain_participant
+                        .publisher_list()
+                        .flat_map(|p| p.data_writer_list())
+                        .any(|dw| dw.transport_writer().guid() == writer_guid)
+            });
         let default_unicast_locator_list = if let Some(p) = self
             .domain_participant
             .discovered_participant_list()
@@ -1085,99 +1271,47 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                         reliability_kind,
                         durability_kind,
                     };
-                    if let TransportReaderKind::Stateful(r) = data_reader.transport_reader_mut() {
-                        r.add_matched_writer(writer_proxy);
-                    }
-
-                    if data_reader
-                        .listener_mask()
-                        .contains(&StatusKind::SubscriptionMatched)
-                    {
-                        let status = data_reader.get_subscription_matched_status();
-                        let the_reader = self.get_data_reader_async(
-                            message.participant_address,
-                            message.subscriber_handle,
-                            message.data_reader_handle,
-                        )?;
-                        if let Some(l) = self
-                            .domain_participant
-                            .get_mut_subscriber(message.subscriber_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_mut_data_reader(message.data_reader_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .listener()
-                        {
-                            l.send_actor_mail(data_reader_listener::TriggerSubscriptionMatched {
-                                the_reader,
-                                status,
-                            });
-                        }
-                    } else if self
-                        .domain_participant
-                        .get_mut_subscriber(message.subscriber_handle)
-                        .ok_or(DdsError::AlreadyDeleted)?
-                        .listener_mask()
-                        .contains(&StatusKind::SubscriptionMatched)
-                    {
-                        let the_reader = self.get_data_reader_async(
-                            message.participant_address,
-                            message.subscriber_handle,
-                            message.data_reader_handle,
-                        )?;
-                        let status = self
-                            .domain_participant
-                            .get_mut_subscriber(message.subscriber_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_mut_data_reader(message.data_reader_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_subscription_matched_status();
-                        if let Some(l) = self
-                            .domain_participant
-                            .get_mut_subscriber(message.subscriber_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .listener()
-                        {
-                            l.send_actor_mail(subscriber_listener::TriggerSubscriptionMatched {
-                                the_reader,
-                                status,
-                            });
-                        }
-                    } else if self
-                        .domain_participant
-                        .listener_mask()
-                        .contains(&StatusKind::SubscriptionMatched)
-                    {
-                        let the_reader = self.get_data_reader_async(
-                            message.participant_address,
-                            message.subscriber_handle,
-                            message.data_reader_handle,
-                        )?;
-                        let status = self
-                            .domain_participant
-                            .get_mut_subscriber(message.subscriber_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_mut_data_reader(message.data_reader_handle)
-                            .ok_or(DdsError::AlreadyDeleted)?
-                            .get_subscription_matched_status();
-                        if let Some(l) = self.domain_participant.listener() {
-                            l.send_actor_mail(
-                                domain_participant_listener::TriggerSubscriptionMatched {
-                                    the_reader,
-                                    status,
+                    let data_reader_guid = data_reader.transport_reader().guid();
+                    let receive_history =
+                        data_reader.qos().durability.kind != DurabilityQosPolicyKind::Volatile;
+                    if let Some(writer_participant_address) = &local_writer_participant_address {
+                        // Writers of this application skip the serialized message and the
+                        // transport and add their changes straight to the reader
+                        writer_participant_address
+                            .send_actor_mail(AddLocalDataReader {
+                                data_writer_guid: writer_guid,
+                                data_reader: LocalDataReader {
+                                    guid: data_reader_guid,
+                                    participant_address: message.participant_address.clone(),
                                 },
-                            );
-                        }
+                                receive_history,
+                            })
+                            .ok();
+                    } else if let TransportReaderKind::Stateful(r) =
+                        data_reader.transport_reader_mut()
+                    {
+                        r.add_matched_writer(writer_proxy);
                     }
 
-                    self.domain_participant
-                        .get_mut_subscriber(message.subscriber_handle)
-                        .ok_or(DdsError::AlreadyDeleted)?
-                        .get_mut_data_reader(message.data_reader_handle)
-                        .ok_or(DdsError::AlreadyDeleted)?
-                        .status_condition()
-                        .send_actor_mail(status_condition_actor::AddCommunicationState {
-                            state: StatusKind::SubscriptionMatched,
-                        });
+                    self.trigger_subscription_matched(
+                        message.participant_address.clone(),
+                        message.subscriber_handle,
+                        message.data_reader_handle,
+                    )?;
+                    // A newly matched writer is considered alive until its lease expires
+                    let publication_handle = InstanceHandle::new(
+                        message
+                            .discovered_writer_data
+                            .dds_publication_data
+                            .key()
+                            .value,
+                    );
+                    self.assert_remote_writer_liveliness_on_reader(
+                        message.participant_address,
+                        message.subscriber_handle,
+                        message.data_reader_handle,
+                        publication_handle,
+                    )?;
                 } else {
                     data_reader.add_requested_incompatible_qos(
                         InstanceHandle::new(
@@ -1294,6 +1428,7 @@ pub struct RemoveDiscoveredWriter {
     pub publication_handle: InstanceHandle,
     pub subscriber_handle: InstanceHandle,
     pub data_reader_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for RemoveDiscoveredWriter {
     type Result = DdsResult<()>;
@@ -1303,19 +1438,339 @@ impl MailHandler<RemoveDiscoveredWriter> for DomainParticipantActor {
         &mut self,
         message: RemoveDiscoveredWriter,
     ) -> <RemoveDiscoveredWriter as Mail>::Result {
-        let subscriber = self
+        let timestamp = self.domain_participant.get_current_time();
+        let data_reader = self
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
-            .ok_or(DdsError::AlreadyDeleted)?;
-        let data_reader = subscriber
+            .ok_or(DdsError::AlreadyDeleted)?
             .get_mut_data_reader(message.data_reader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
         if data_reader
             .get_matched_publication_data(&message.publication_handle)
             .is_some()
         {
+            // The instances left without writer are no longer alive even if the
+            // unregistrations sent by the writer when it was deleted were lost
+            for cache_change in
+                data_reader.writer_unregistration_changes(&message.publication_handle, timestamp)
+            {
+                self.add_reader_change(AddCacheChange {
+                    participant_address: message.participant_address.clone(),
+                    cache_change,
+                    subscriber_handle: message.subscriber_handle,
+                    data_reader_handle: message.data_reader_handle,
+                })?;
+            }
+
+            let subscriber = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?;
+            let data_reader = subscriber
+                .get_mut_data_reader(message.data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?;
             data_reader.remove_matched_publication(&message.publication_handle);
+            let is_liveliness_changed =
+                data_reader.remove_writer_liveliness(message.publication_handle);
+            let writer_guid = Guid::from(<[u8; 16]>::from(message.publication_handle));
+            let data_reader_guid = data_reader.transport_reader().guid();
+            if let TransportReaderKind::Stateful(r) = data_reader.transport_reader_mut() {
+                r.remove_matched_writer(writer_guid);
+            }
+            if let Some(writer_participant_address) = self
+                .local_participants
+                .get(&self.transport.guid().prefix(), &writer_guid.prefix())
+            {
+                writer_participant_address
+                    .send_actor_mail(RemoveLocalDataReader {
+                        data_writer_guid: writer_guid,
+                        data_reader_guid,
+                    })
+                    .ok();
+            }
+
+            self.trigger_subscription_matched(
+                message.participant_address.clone(),
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+            if is_liveliness_changed {
+                self.trigger_liveliness_changed(
+                    message.participant_address,
+                    message.subscriber_handle,
+                    message.data_reader_handle,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A reader of this application has matched one of the writers of this participant
+pub struct AddLocalDataReader {
+    pub data_writer_guid: Guid,
+    pub data_reader: LocalDataReader,
+    pub receive_history: bool,
+}
+impl Mail for AddLocalDataReader {
+    type Result = ();
+}
+impl MailHandler<AddLocalDataReader> for DomainParticipantActor {
+    fn handle(&mut self, message: AddLocalDataReader) -> <AddLocalDataReader as Mail>::Result {
+        if let Some(data_writer) = self
+            .domain_participant
+            .publisher_list_mut()
+            .flat_map(|p| p.data_writer_list_mut())
+            .find(|dw| dw.transport_writer().guid() == message.data_writer_guid)
+        {
+            data_writer.add_local_reader(message.data_reader, message.receive_history, true);
+        }
+    }
+}
+
+/// A reader of this application no longer matches one of the writers of this participant
+pub struct RemoveLocalDataReader {
+    pub data_writer_guid: Guid,
+    pub data_reader_guid: Guid,
+}
+impl Mail for RemoveLocalDataReader {
+    type Result = ();
+}
+impl MailHandler<RemoveLocalDataReader> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: RemoveLocalDataReader,
+    ) -> <RemoveLocalDataReader as Mail>::Result {
+        if let Some(data_writer) = self
+            .domain_participant
+            .publisher_list_mut()
+            .flat_map(|p| p.data_writer_list_mut())
+            .find(|dw| dw.transport_writer().guid() == message.data_writer_guid)
+        {
+            data_writer.remove_local_reader(message.data_reader_guid);
+        }
+    }
+}
+
+impl DomainParticipantActor {
+    pub fn announce_participant_message(&mut self, kind: [u8; 4]) -> DdsResult<()> {
+        let participant_message_data = ParticipantMessageData {
+            participant_guid_prefix: self.transport.guid().prefix(),
+            kind,
+            data: Vec::new(),
+        };
+        let timestamp = self.domain_participant.get_current_time();
+        if let Some(dw) = self
+            .domain_participant
+            .builtin_publisher_mut()
+            .lookup_datawriter_mut(DCPS_PARTICIPANT_MESSAGE)
+        {
+            dw.write_w_timestamp(participant_message_data.serialize_data()?, timestamp, None)?;
+        }
+        Ok(())
+    }
+
+    fn trigger_publication_matched(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        publisher_handle: InstanceHandle,
+        data_writer_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let data_writer = self
+            .domain_participant
+            .get_mut_publisher(publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if data_writer
+            .listener_mask()
+            .contains(&StatusKind::PublicationMatched)
+        {
+            let status = data_writer.get_publication_matched_status();
+            let the_writer = self.get_data_writer_async(
+                participant_address,
+                publisher_handle,
+                data_writer_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_publisher(publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(data_writer_listener::TriggerPublicationMatched {
+                    the_writer,
+                    status,
+                });
+            }
+        } else if self
+            .domain_participant
+            .get_mut_publisher(publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .listener_mask()
+            .contains(&StatusKind::PublicationMatched)
+        {
+            let the_writer = self.get_data_writer_async(
+                participant_address,
+                publisher_handle,
+                data_writer_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_publisher(publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_publication_matched_status();
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_publisher(publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(publisher_listener::TriggerOnPublicationMatched {
+                    the_writer,
+                    status,
+                });
+            }
+        } else if self
+            .domain_participant
+            .listener_mask()
+            .contains(&StatusKind::PublicationMatched)
+        {
+            let the_writer = self.get_data_writer_async(
+                participant_address,
+                publisher_handle,
+                data_writer_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_publisher(publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_publication_matched_status();
+            if let Some(l) = self.domain_participant.listener() {
+                l.send_actor_mail(domain_participant_listener::TriggerPublicationMatched {
+                    the_writer,
+                    status,
+                });
+            }
         }
+
+        self.domain_participant
+            .get_mut_publisher(publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .status_condition()
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::PublicationMatched,
+            });
+        Ok(())
+    }
+
+    fn trigger_subscription_matched(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if data_reader
+            .listener_mask()
+            .contains(&StatusKind::SubscriptionMatched)
+        {
+            let status = data_reader.get_subscription_matched_status();
+            let the_reader = self.get_data_reader_async(
+                participant_address,
+                subscriber_handle,
+                data_reader_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(data_reader_listener::TriggerSubscriptionMatched {
+                    the_reader,
+                    status,
+                });
+            }
+        } else if self
+            .domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .listener_mask()
+            .contains(&StatusKind::SubscriptionMatched)
+        {
+            let the_reader = self.get_data_reader_async(
+                participant_address,
+                subscriber_handle,
+                data_reader_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_subscription_matched_status();
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(subscriber_listener::TriggerSubscriptionMatched {
+                    the_reader,
+                    status,
+                });
+            }
+        } else if self
+            .domain_participant
+            .listener_mask()
+            .contains(&StatusKind::SubscriptionMatched)
+        {
+            let the_reader = self.get_data_reader_async(
+                participant_address,
+                subscriber_handle,
+                data_reader_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_subscription_matched_status();
+            if let Some(l) = self.domain_participant.listener() {
+                l.send_actor_mail(domain_participant_listener::TriggerSubscriptionMatched {
+                    the_reader,
+                    status,
+                });
+            }
+        }
+
+        self.domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .status_condition()
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::SubscriptionMatched,
+            });
         Ok(())
     }
 }
@@ -1454,6 +1909,57 @@ fn is_discovered_topic_consistent(
         && &topic_qos.ownership == topic_builtin_topic_data.ownership()
 }
 
+fn remove_matched_builtin_endpoints(
+    domain_participant_actor: &mut DomainParticipantActor,
+    guid_prefix: GuidPrefix,
+) {
+    for dw in domain_participant_actor
+        .domain_participant
+        .builtin_publisher_mut()
+        .data_writer_list_mut()
+    {
+        let remote_reader_entity_id = match dw.transport_writer().guid().entity_id() {
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER => {
+                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR
+            }
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER => {
+                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR
+            }
+            ENTITYID_SEDP_BUILTIN_TOPICS_ANNOUNCER => ENTITYID_SEDP_BUILTIN_TOPICS_DETECTOR,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER => {
+                ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER
+            }
+            _ => continue,
+        };
+        if let TransportWriterKind::Stateful(w) = dw.transport_writer_mut() {
+            w.remove_matched_reader(Guid::new(guid_prefix, remote_reader_entity_id));
+        }
+    }
+
+    for dr in domain_participant_actor
+        .domain_participant
+        .builtin_subscriber_mut()
+        .data_reader_list_mut()
+    {
+        let remote_writer_entity_id = match dr.transport_reader().guid().entity_id() {
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR => {
+                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER
+            }
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR => {
+                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER
+            }
+            ENTITYID_SEDP_BUILTIN_TOPICS_DETECTOR => ENTITYID_SEDP_BUILTIN_TOPICS_ANNOUNCER,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER => {
+                ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER
+            }
+            _ => continue,
+        };
+        if let TransportReaderKind::Stateful(r) = dr.transport_reader_mut() {
+            r.remove_matched_writer(Guid::new(guid_prefix, remote_writer_entity_id));
+        }
+    }
+}
+
 fn add_matched_publications_detector(
     domain_participant_actor: &mut DomainParticipantActor,
     discovered_participant_data: &SpdpDiscoveredParticipantData,
@@ -1483,6 +1989,7 @@ fn add_matched_publications_detector(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             expects_inline_qos,
+            content_filter: None,
         };
         if let Some(dw) = domain_participant_actor
             .domain_participant
@@ -1576,6 +2083,7 @@ fn add_matched_subscriptions_detector(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             expects_inline_qos,
+            content_filter: None,
         };
         if let Some(dw) = domain_participant_actor
             .domain_participant
@@ -1669,6 +2177,7 @@ fn add_matched_topics_detector(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             expects_inline_qos,
+            content_filter: None,
         };
         if let Some(dw) = domain_participant_actor
             .domain_participant
@@ -1730,3 +2239,97 @@ fn add_matched_topics_announcer(
         }
     }
 }
+
+fn add_matched_participant_message_reader(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    if discovered_participant_data
+        .participant_proxy
+        .available_builtin_endpoints
+        .has(BuiltinEndpointSet::BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_READER)
+    {
+        let remote_reader_guid = Guid::new(
+            discovered_participant_data.participant_proxy.guid_prefix,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+        );
+        let remote_group_entity_id = ENTITYID_UNKNOWN;
+        let expects_inline_qos = false;
+        let reader_proxy = transport::writer::ReaderProxy {
+            remote_reader_guid,
+            remote_group_entity_id,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::TransientLocal,
+            unicast_locator_list: discovered_participant_data
+                .participant_proxy
+                .metatraffic_unicast_locator_list
+                .to_vec(),
+            multicast_locator_list: discovered_participant_data
+                .participant_proxy
+                .metatraffic_multicast_locator_list
+                .to_vec(),
+            expects_inline_qos,
+            content_filter: None,
+        };
+        if let Some(dw) = domain_participant_actor
+            .domain_participant
+            .builtin_publisher_mut()
+            .data_writer_list_mut()
+            .find(|dw| {
+                dw.transport_writer().guid().entity_id()
+                    == ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER
+            })
+        {
+            match dw.transport_writer_mut() {
+                TransportWriterKind::Stateful(w) => w.add_matched_reader(reader_proxy),
+                TransportWriterKind::Stateless(_) => panic!("Invalid built-in writer type"),
+            }
+        }
+    }
+}
+
+fn add_matched_participant_message_writer(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    if discovered_participant_data
+        .participant_proxy
+        .available_builtin_endpoints
+        .has(BuiltinEndpointSet::BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_WRITER)
+    {
+        let remote_writer_guid = Guid::new(
+            discovered_participant_data.participant_proxy.guid_prefix,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+        );
+        let remote_group_entity_id = ENTITYID_UNKNOWN;
+
+        let writer_proxy = transport::reader::WriterProxy {
+            remote_writer_guid,
+            remote_group_entity_id,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::TransientLocal,
+            unicast_locator_list: discovered_participant_data
+                .participant_proxy
+                .metatraffic_unicast_locator_list
+                .to_vec(),
+            multicast_locator_list: discovered_participant_data
+                .participant_proxy
+                .metatraffic_multicast_locator_list
+                .to_vec(),
+        };
+        if let Some(dr) = domain_participant_actor
+            .domain_participant
+            .builtin_subscriber_mut()
+            .data_reader_list_mut()
+            .find(|dr| {
+                dr.transport_reader().guid().entity_id()
+                    == ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER
+            })
+        {
+            match dr.transport_reader_mut() {
+                TransportReaderKind::Stateful(r) => r.add_matched_writer(writer_proxy),
+                TransportReaderKind::Stateless(_) => panic!("Invalid built-in reader type"),
+            }
+        }
+    }
+}
@@ -11,9 +11,14 @@ use crate::{
         topic_listener::TopicListenerAsync,
     },
     implementation::{
+        content_filter::filter::ContentFilter,
+        data_representation_builtin_endpoints::participant_message_data::{
+            DCPS_PARTICIPANT_MESSAGE, PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
+        },
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
             entities::{
+                content_filtered_topic::ContentFilteredTopicEntity, data_writer::DataWriterEntity,
                 publisher::PublisherEntity, subscriber::SubscriberEntity, topic::TopicEntity,
             },
         },
@@ -34,14 +39,16 @@ use crate::{
     runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
     xtypes::dynamic_type::DynamicType,
 };
+use tracing::warn;
 
 use super::{discovery_service, topic_service};
 
-pub const BUILT_IN_TOPIC_NAME_LIST: [&str; 4] = [
+pub const BUILT_IN_TOPIC_NAME_LIST: [&str; 5] = [
     DCPS_PARTICIPANT,
     DCPS_TOPIC,
     DCPS_PUBLICATION,
     DCPS_SUBSCRIPTION,
+    DCPS_PARTICIPANT_MESSAGE,
 ];
 
 pub struct CreateUserDefinedPublisher {
@@ -187,10 +194,7 @@ impl MailHandler<CreateUserDefinedSubscriber> for DomainParticipantActor {
 
         self.domain_participant.insert_subscriber(subscriber);
 
-        Ok((
-            subscriber_handle,
-            subscriber_status_condition_address,
-        ))
+        Ok((subscriber_handle, subscriber_status_condition_address))
     }
 }
 
@@ -308,6 +312,7 @@ impl MailHandler<CreateTopic> for DomainParticipantActor {
 pub struct DeleteUserDefinedTopic {
     pub participant_handle: InstanceHandle,
     pub topic_name: String,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for DeleteUserDefinedTopic {
     type Result = DdsResult<()>;
@@ -327,18 +332,20 @@ impl MailHandler<DeleteUserDefinedTopic> for DomainParticipantActor {
             return Ok(());
         }
 
+        // The writers of the durability service don't prevent the deletion of the topic
         if Arc::strong_count(
             self.domain_participant
                 .get_topic(&message.topic_name)
                 .ok_or(DdsError::AlreadyDeleted)?
                 .type_support(),
-        ) > 1
+        ) > 1 + self.durability_service_writer_count(&message.topic_name)
         {
             return Err(DdsError::PreconditionNotMet(
                 "Topic still attached to some data writer or data reader".to_string(),
             ));
         }
 
+        self.stop_durability_services(Some(&message.topic_name), message.participant_address);
         self.domain_participant
             .remove_topic(&message.topic_name)
             .ok_or(DdsError::AlreadyDeleted)?;
@@ -347,6 +354,94 @@ impl MailHandler<DeleteUserDefinedTopic> for DomainParticipantActor {
     }
 }
 
+pub struct CreateContentFilteredTopic {
+    pub name: String,
+    pub related_topic_name: String,
+    pub filter_expression: String,
+    pub expression_parameters: Vec<String>,
+}
+impl Mail for CreateContentFilteredTopic {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<CreateContentFilteredTopic> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: CreateContentFilteredTopic,
+    ) -> <CreateContentFilteredTopic as Mail>::Result {
+        if self.domain_participant.get_topic(&message.name).is_some()
+            || self
+                .domain_participant
+                .get_content_filtered_topic(&message.name)
+                .is_some()
+        {
+            return Err(DdsError::PreconditionNotMet(format!(
+                "Topic with name {} already exists.",
+                message.name
+            )));
+        }
+
+        let related_topic = self
+            .domain_participant
+            .get_topic(&message.related_topic_name)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let type_support = related_topic.type_support().clone();
+
+        let content_filter = ContentFilter::new(
+            message.name,
+            message.related_topic_name,
+            message.filter_expression,
+            message.expression_parameters,
+            type_support.as_ref(),
+        )?;
+
+        self.domain_participant
+            .insert_content_filtered_topic(ContentFilteredTopicEntity::new(
+                content_filter,
+                type_support,
+            ));
+
+        Ok(())
+    }
+}
+
+pub struct DeleteContentFilteredTopic {
+    pub participant_handle: InstanceHandle,
+    pub topic_name: String,
+}
+impl Mail for DeleteContentFilteredTopic {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<DeleteContentFilteredTopic> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: DeleteContentFilteredTopic,
+    ) -> <DeleteContentFilteredTopic as Mail>::Result {
+        if self.domain_participant.instance_handle() != message.participant_handle {
+            return Err(DdsError::PreconditionNotMet(
+                "Content filtered topic can only be deleted from its parent participant"
+                    .to_string(),
+            ));
+        }
+
+        if self.domain_participant.subscriber_list().any(|s| {
+            s.data_reader_list().any(|dr| {
+                dr.content_filter()
+                    .is_some_and(|f| f.content_filtered_topic_name() == message.topic_name)
+            })
+        }) {
+            return Err(DdsError::PreconditionNotMet(
+                "Content filtered topic still attached to some data reader".to_string(),
+            ));
+        }
+
+        self.domain_participant
+            .remove_content_filtered_topic(&message.topic_name)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        Ok(())
+    }
+}
+
 pub struct FindTopic {
     pub topic_name: String,
     pub type_support: Arc<dyn DynamicType + Send + Sync>,
@@ -369,6 +464,8 @@ impl MailHandler<FindTopic> for DomainParticipantActor {
                 let qos = TopicQos {
                     topic_data: discovered_topic_data.topic_data().clone(),
                     durability: discovered_topic_data.durability().clone(),
+                    // The durability service settings are not part of the discovered data
+                    durability_service: Default::default(),
                     deadline: discovered_topic_data.deadline().clone(),
                     latency_budget: discovered_topic_data.latency_budget().clone(),
                     liveliness: discovered_topic_data.liveliness().clone(),
@@ -485,6 +582,22 @@ impl MailHandler<IgnorePublication> for DomainParticipantActor {
     }
 }
 
+pub struct AssertLiveliness {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for AssertLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AssertLiveliness> for DomainParticipantActor {
+    fn handle(&mut self, message: AssertLiveliness) -> <AssertLiveliness as Mail>::Result {
+        if !self.domain_participant.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+        self.assert_participant_manual_liveliness(message.participant_address)?;
+        self.announce_participant_message(PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE)
+    }
+}
+
 pub struct DeleteContainedEntities {
     pub participant_address: ActorAddress<DomainParticipantActor>,
 }
@@ -496,10 +609,25 @@ impl MailHandler<DeleteContainedEntities> for DomainParticipantActor {
         &mut self,
         message: DeleteContainedEntities,
     ) -> <DeleteContainedEntities as Mail>::Result {
+        let timestamp = self.domain_participant.get_current_time();
         let deleted_publisher_list: Vec<PublisherEntity> =
             self.domain_participant.drain_publisher_list().collect();
         for mut publisher in deleted_publisher_list {
-            for data_writer in publisher.drain_data_writer_list() {
+            let deleted_data_writer_list: Vec<DataWriterEntity> =
+                publisher.drain_data_writer_list().collect();
+            for mut data_writer in deleted_data_writer_list {
+                if let Err(e) =
+                    publisher.unregister_data_writer_instances(&mut data_writer, timestamp)
+                {
+                    warn!(
+                        "Failed to unregister the instances of a deleted writer: {:?}",
+                        e
+                    );
+                }
+                self.stop_serving_durable_samples(
+                    &data_writer,
+                    message.participant_address.clone(),
+                );
                 message
                     .participant_address
                     .send_actor_mail(discovery_service::AnnounceDeletedDataWriter { data_writer })
@@ -518,6 +646,8 @@ impl MailHandler<DeleteContainedEntities> for DomainParticipantActor {
             }
         }
 
+        // The durability service writers use the deleted topics
+        self.stop_durability_services(None, message.participant_address);
         self.domain_participant.delete_all_topics();
 
         Ok(())
@@ -0,0 +1,425 @@
+use crate::{
+    durability::DurabilityTopicKey,
+    implementation::{
+        domain_participant_backend::{
+            domain_participant_actor::DomainParticipantActor,
+            entities::data_writer::{DataWriterEntity, TransportWriterKind},
+        },
+        status_condition::status_condition_actor::StatusConditionActor,
+    },
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
+        qos::DataWriterQos,
+        qos_policy::{
+            DurabilityQosPolicy, DurabilityQosPolicyKind, DurabilityServiceQosPolicy,
+            HistoryQosPolicy, HistoryQosPolicyKind, LifespanQosPolicy,
+        },
+        time::{DurationKind, Time},
+    },
+    runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
+    transport::types::{
+        EntityId, Guid, ReliabilityKind, TopicKind, USER_DEFINED_WRITER_NO_KEY,
+        USER_DEFINED_WRITER_WITH_KEY,
+    },
+};
+use std::collections::{HashMap, HashSet};
+use tracing::warn;
+
+use super::{discovery_service, publisher_service::get_topic_kind};
+
+/// Topic whose samples are served by the durability service of the participant
+/// once the writers that wrote them are deleted
+pub struct DurabilityServiceTopic {
+    topic_name: String,
+    durability_service: DurabilityServiceQosPolicy,
+    lifespan: LifespanQosPolicy,
+    data_writer_handle: Option<InstanceHandle>,
+    // Sequence number with which the durability service writer wrote each sample,
+    // identified by the handle of its original writer and its original sequence number
+    served_samples: HashMap<(InstanceHandle, i64), i64>,
+}
+
+impl DurabilityServiceTopic {
+    pub fn new(
+        topic_name: String,
+        durability_service: DurabilityServiceQosPolicy,
+        lifespan: LifespanQosPolicy,
+    ) -> Self {
+        Self {
+            topic_name,
+            durability_service,
+            lifespan,
+            data_writer_handle: None,
+            served_samples: HashMap::new(),
+        }
+    }
+
+    pub fn topic_name(&self) -> &str {
+        &self.topic_name
+    }
+}
+
+pub struct SyncDurabilityService {
+    pub durability_kind: DurabilityQosPolicyKind,
+    pub topic: DurabilityTopicKey,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for SyncDurabilityService {
+    type Result = ();
+}
+impl MailHandler<SyncDurabilityService> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: SyncDurabilityService,
+    ) -> <SyncDurabilityService as Mail>::Result {
+        if let Err(e) = self.sync_durability_service(
+            message.durability_kind,
+            &message.topic,
+            message.participant_address,
+        ) {
+            warn!(
+                "Samples of topic {} not served by the durability service: {:?}",
+                message.topic.topic_name(),
+                e
+            );
+        }
+    }
+}
+
+pub struct CleanupDisposedInstance {
+    pub durability_kind: DurabilityQosPolicyKind,
+    pub topic: DurabilityTopicKey,
+    pub instance_handle: InstanceHandle,
+    pub disposed_timestamp: Time,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for CleanupDisposedInstance {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<CleanupDisposedInstance> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: CleanupDisposedInstance,
+    ) -> <CleanupDisposedInstance as Mail>::Result {
+        if let Some(durability_store) = self.durability_stores.get(message.durability_kind) {
+            durability_store.dispose_instance(
+                &message.topic,
+                message.instance_handle,
+                message.disposed_timestamp,
+            )?;
+        }
+        self.request_durability_service_sync(
+            message.durability_kind,
+            &message.topic,
+            message.participant_address,
+        );
+        Ok(())
+    }
+}
+
+impl DomainParticipantActor {
+    pub fn durability_topic_key(&self, topic_name: &str, type_name: &str) -> DurabilityTopicKey {
+        DurabilityTopicKey::new(
+            self.domain_participant.domain_id(),
+            topic_name.to_owned(),
+            type_name.to_owned(),
+        )
+    }
+
+    /// Make the participant one of the providers of the durability service of the topic and
+    /// let the provider serving the samples of the topic serve those of the deleted writers
+    pub fn start_durability_service(
+        &mut self,
+        durability_kind: DurabilityQosPolicyKind,
+        topic: DurabilityTopicKey,
+        service_topic: DurabilityServiceTopic,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) {
+        if self.durability_stores.get(durability_kind).is_none() {
+            return;
+        }
+        self.durability_service_topics
+            .entry((durability_kind, topic.clone()))
+            .or_insert(service_topic);
+        self.durability_stores
+            .add_provider(durability_kind, &topic, &participant_address);
+        self.request_durability_service_sync(durability_kind, &topic, participant_address);
+    }
+
+    /// Let the durability service serve the samples of a deleted transient or persistent writer
+    pub fn stop_serving_durable_samples(
+        &mut self,
+        data_writer: &DataWriterEntity,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) {
+        let durability_kind = data_writer.qos().durability.kind;
+        if self.durability_stores.get(durability_kind).is_none() {
+            return;
+        }
+        self.durability_stores
+            .remove_live_writer(&InstanceHandle::new(
+                data_writer.transport_writer().guid().into(),
+            ));
+        let topic = self.durability_topic_key(data_writer.topic_name(), data_writer.type_name());
+        let service_topic = DurabilityServiceTopic::new(
+            data_writer.topic_name().to_owned(),
+            data_writer.qos().durability_service.clone(),
+            data_writer.qos().lifespan.clone(),
+        );
+        self.start_durability_service(durability_kind, topic, service_topic, participant_address);
+    }
+
+    /// Stop the durability service of the topics with the given name, or of all the topics,
+    /// and let the next provider take over
+    pub fn stop_durability_services(
+        &mut self,
+        topic_name: Option<&str>,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) {
+        let stopped_topics: Vec<_> = self
+            .durability_service_topics
+            .iter()
+            .filter(|(_, t)| topic_name.is_none_or(|n| n == t.topic_name))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for (durability_kind, topic) in stopped_topics {
+            let Some(service_topic) = self
+                .durability_service_topics
+                .remove(&(durability_kind, topic.clone()))
+            else {
+                continue;
+            };
+            if let Some(mut data_writer) = service_topic.data_writer_handle.and_then(|h| {
+                self.domain_participant
+                    .durability_service_publisher_mut()
+                    .remove_data_writer(h)
+            }) {
+                let matched_subscriptions = data_writer.get_matched_subscriptions();
+                if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+                    for subscription_handle in matched_subscriptions {
+                        w.remove_matched_reader(Guid::from(<[u8; 16]>::from(subscription_handle)));
+                    }
+                }
+                participant_address
+                    .send_actor_mail(discovery_service::AnnounceDeletedDataWriter { data_writer })
+                    .ok();
+            }
+            if let Some(provider) = self.durability_stores.remove_provider(
+                durability_kind,
+                &topic,
+                &participant_address,
+            ) {
+                provider
+                    .send_actor_mail(SyncDurabilityService {
+                        durability_kind,
+                        topic,
+                        participant_address: provider.clone(),
+                    })
+                    .ok();
+            }
+        }
+    }
+
+    /// Number of writers of the durability service using the type support of the topic
+    pub fn durability_service_writer_count(&self, topic_name: &str) -> usize {
+        self.domain_participant
+            .durability_service_publisher()
+            .data_writer_list()
+            .filter(|dw| dw.topic_name() == topic_name)
+            .count()
+    }
+
+    pub fn request_durability_service_sync(
+        &mut self,
+        durability_kind: DurabilityQosPolicyKind,
+        topic: &DurabilityTopicKey,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) {
+        match self.durability_stores.provider(durability_kind, topic) {
+            Some(provider) if provider == participant_address => {
+                if let Err(e) =
+                    self.sync_durability_service(durability_kind, topic, participant_address)
+                {
+                    warn!(
+                        "Samples of topic {} not served by the durability service: {:?}",
+                        topic.topic_name(),
+                        e
+                    );
+                }
+            }
+            Some(provider) => {
+                provider
+                    .send_actor_mail(SyncDurabilityService {
+                        durability_kind,
+                        topic: topic.clone(),
+                        participant_address: provider.clone(),
+                    })
+                    .ok();
+            }
+            None => (),
+        }
+    }
+
+    /// Serve with the durability service writer of the topic the stored samples whose
+    /// writers are deleted, and stop serving the samples no longer kept by the store
+    fn sync_durability_service(
+        &mut self,
+        durability_kind: DurabilityQosPolicyKind,
+        topic: &DurabilityTopicKey,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) -> DdsResult<()> {
+        if self
+            .durability_stores
+            .provider(durability_kind, topic)
+            .as_ref()
+            != Some(&participant_address)
+        {
+            return Ok(());
+        }
+        let Some(durability_store) = self.durability_stores.get(durability_kind) else {
+            return Ok(());
+        };
+        let Some(service_topic) = self
+            .durability_service_topics
+            .get(&(durability_kind, topic.clone()))
+        else {
+            return Ok(());
+        };
+
+        let now = self.domain_participant.get_current_time();
+        let samples: Vec<_> = durability_store
+            .get_samples(topic)?
+            .into_iter()
+            .filter(|s| !self.durability_stores.is_live_writer(&s.writer_handle()))
+            .filter(|s| match service_topic.lifespan.duration {
+                DurationKind::Finite(lifespan_duration) => {
+                    now - s.source_timestamp() <= lifespan_duration
+                }
+                DurationKind::Infinite => true,
+            })
+            .collect();
+
+        let data_writer_handle = match service_topic.data_writer_handle {
+            Some(h) => h,
+            None if samples.is_empty() => return Ok(()),
+            None => self.create_durability_service_writer(durability_kind, topic)?,
+        };
+        let service_topic = self
+            .durability_service_topics
+            .get_mut(&(durability_kind, topic.clone()))
+            .expect("Durability service topic exists");
+        let data_writer = self
+            .domain_participant
+            .durability_service_publisher_mut()
+            .get_mut_data_writer(data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        // Samples of disposed instances or replaced by newer ones are not served anymore
+        let sample_keys: HashSet<_> = samples
+            .iter()
+            .map(|s| (s.writer_handle(), s.sequence_number()))
+            .collect();
+        service_topic
+            .served_samples
+            .retain(|sample_key, sequence_number| {
+                let is_kept = sample_keys.contains(sample_key);
+                if !is_kept {
+                    data_writer.remove_change(*sequence_number);
+                }
+                is_kept
+            });
+        for sample in samples {
+            let sample_key = (sample.writer_handle(), sample.sequence_number());
+            if service_topic.served_samples.contains_key(&sample_key) {
+                continue;
+            }
+            let sequence_number = data_writer.write_w_timestamp(
+                sample.serialized_data().to_vec(),
+                sample.source_timestamp(),
+                None,
+            )?;
+            service_topic
+                .served_samples
+                .insert(sample_key, sequence_number);
+        }
+
+        if service_topic.data_writer_handle.is_none() {
+            service_topic.data_writer_handle = Some(data_writer_handle);
+            let publisher_handle = self
+                .domain_participant
+                .durability_service_publisher()
+                .instance_handle();
+            self.announce_enabled_data_writer(
+                publisher_handle,
+                data_writer_handle,
+                participant_address,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn create_durability_service_writer(
+        &mut self,
+        durability_kind: DurabilityQosPolicyKind,
+        topic: &DurabilityTopicKey,
+    ) -> DdsResult<InstanceHandle> {
+        let service_topic = &self.durability_service_topics[&(durability_kind, topic.clone())];
+        let type_support = self
+            .domain_participant
+            .get_topic(&service_topic.topic_name)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .type_support()
+            .clone();
+        // The durability service keeps the history of the samples itself
+        let qos = DataWriterQos {
+            durability: DurabilityQosPolicy {
+                kind: durability_kind,
+            },
+            durability_service: service_topic.durability_service.clone(),
+            history: HistoryQosPolicy {
+                kind: HistoryQosPolicyKind::KeepAll,
+            },
+            lifespan: service_topic.lifespan.clone(),
+            ..Default::default()
+        };
+
+        let entity_kind = match get_topic_kind(type_support.as_ref()) {
+            TopicKind::WithKey => USER_DEFINED_WRITER_WITH_KEY,
+            TopicKind::NoKey => USER_DEFINED_WRITER_NO_KEY,
+        };
+        self.entity_counter += 1;
+        let entity_id = EntityId::new(
+            [
+                0,
+                self.entity_counter.to_le_bytes()[0],
+                self.entity_counter.to_le_bytes()[1],
+            ],
+            entity_kind,
+        );
+        let transport_writer = self
+            .transport
+            .create_stateful_writer(entity_id, ReliabilityKind::Reliable);
+
+        let data_writer_handle = self.instance_handle_counter.generate_new_instance_handle();
+        let mut data_writer = DataWriterEntity::new(
+            data_writer_handle,
+            TransportWriterKind::Stateful(transport_writer),
+            topic.topic_name().to_owned(),
+            topic.type_name().to_owned(),
+            type_support,
+            Actor::spawn(
+                StatusConditionActor::default(),
+                &self.listener_executor.handle(),
+            ),
+            None,
+            vec![],
+            qos,
+        );
+        data_writer.enable();
+        self.domain_participant
+            .durability_service_publisher_mut()
+            .insert_data_writer(data_writer);
+        Ok(data_writer_handle)
+    }
+}
@@ -1,6 +1,11 @@
 use crate::{
+    builtin_topics::PublicationBuiltinTopicData,
     implementation::{
-        domain_participant_backend::domain_participant_actor::DomainParticipantActor,
+        data_representation_builtin_endpoints::participant_message_data::PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE,
+        domain_participant_backend::{
+            domain_participant_actor::DomainParticipantActor,
+            entities::data_writer::TransportWriterKind, services::message_service::AddCacheChange,
+        },
         listeners::{
             data_reader_listener, data_writer_listener, domain_participant_listener,
             publisher_listener, subscriber_listener,
@@ -10,11 +15,18 @@ use crate::{
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
+        qos_policy::LivelinessQosPolicyKind,
         status::StatusKind,
+        time::DurationKind,
     },
     runtime::actor::{ActorAddress, Mail, MailHandler},
+    transport::types::{Guid, GuidPrefix},
 };
 
+// Shortest period at which the automatic liveliness is announced so that writers
+// with a zero or very short lease duration don't flood the network
+const MIN_AUTOMATIC_LIVELINESS_PERIOD: std::time::Duration = std::time::Duration::from_millis(10);
+
 pub struct RequestedDeadlineMissed {
     pub subscriber_handle: InstanceHandle,
     pub data_reader_handle: InstanceHandle,
@@ -250,3 +262,573 @@ impl MailHandler<OfferedDeadlineMissed> for DomainParticipantActor {
         Ok(())
     }
 }
+
+pub struct CheckDataWriterLiveliness {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for CheckDataWriterLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<CheckDataWriterLiveliness> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: CheckDataWriterLiveliness,
+    ) -> <CheckDataWriterLiveliness as Mail>::Result {
+        let now = self.domain_participant.get_current_time();
+        let data_writer = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let DurationKind::Finite(lease_duration) = data_writer.qos().liveliness.lease_duration
+        else {
+            return Ok(());
+        };
+        let Some(last_liveliness_assertion) = data_writer.last_liveliness_assertion() else {
+            return Ok(());
+        };
+
+        let elapsed = now - last_liveliness_assertion;
+        if elapsed < lease_duration {
+            // The liveliness was asserted in the meantime so the lease restarts from
+            // the last assertion
+            let timer_handle = self.timer_driver.handle();
+            self.backend_executor.handle().spawn(async move {
+                timer_handle.sleep((lease_duration - elapsed).into()).await;
+                message
+                    .participant_address
+                    .send_actor_mail(CheckDataWriterLiveliness {
+                        publisher_handle: message.publisher_handle,
+                        data_writer_handle: message.data_writer_handle,
+                        participant_address: message.participant_address.clone(),
+                    })
+                    .ok();
+            });
+            return Ok(());
+        }
+
+        data_writer.liveliness_lost();
+
+        if data_writer
+            .listener_mask()
+            .contains(&StatusKind::LivelinessLost)
+        {
+            let status = data_writer.get_liveliness_lost_status();
+            let the_writer = self.get_data_writer_async(
+                message.participant_address,
+                message.publisher_handle,
+                message.data_writer_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(message.data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(data_writer_listener::TriggerLivelinessLost {
+                    the_writer,
+                    status,
+                });
+            }
+        } else if self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .listener_mask()
+            .contains(&StatusKind::LivelinessLost)
+        {
+            let the_writer = self.get_data_writer_async(
+                message.participant_address,
+                message.publisher_handle,
+                message.data_writer_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(message.data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_lost_status();
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(publisher_listener::TriggerLivelinessLost { the_writer, status });
+            }
+        } else if self
+            .domain_participant
+            .listener_mask()
+            .contains(&StatusKind::LivelinessLost)
+        {
+            let the_writer = self.get_data_writer_async(
+                message.participant_address,
+                message.publisher_handle,
+                message.data_writer_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(message.data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_lost_status();
+            if let Some(l) = self.domain_participant.listener() {
+                l.send_actor_mail(domain_participant_listener::TriggerLivelinessLost {
+                    the_writer,
+                    status,
+                });
+            }
+        }
+
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .status_condition()
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::LivelinessLost,
+            });
+
+        Ok(())
+    }
+}
+
+pub struct CheckRemoteWriterLiveliness {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub publication_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for CheckRemoteWriterLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<CheckRemoteWriterLiveliness> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: CheckRemoteWriterLiveliness,
+    ) -> <CheckRemoteWriterLiveliness as Mail>::Result {
+        let now = self.domain_participant.get_current_time();
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let Some(DurationKind::Finite(lease_duration)) = data_reader
+            .get_matched_publication_data(&message.publication_handle)
+            .map(|p| p.liveliness().lease_duration)
+        else {
+            return Ok(());
+        };
+        let Some(last_liveliness_assertion) =
+            data_reader.writer_last_liveliness_assertion(&message.publication_handle)
+        else {
+            return Ok(());
+        };
+
+        let elapsed = now - last_liveliness_assertion;
+        if elapsed < lease_duration {
+            let timer_handle = self.timer_driver.handle();
+            self.backend_executor.handle().spawn(async move {
+                timer_handle.sleep((lease_duration - elapsed).into()).await;
+                message
+                    .participant_address
+                    .send_actor_mail(CheckRemoteWriterLiveliness {
+                        subscriber_handle: message.subscriber_handle,
+                        data_reader_handle: message.data_reader_handle,
+                        publication_handle: message.publication_handle,
+                        participant_address: message.participant_address.clone(),
+                    })
+                    .ok();
+            });
+            return Ok(());
+        }
+
+        if data_reader.writer_liveliness_lost(message.publication_handle) {
+            // The instances written only by the writer are no longer alive. They are
+            // purged after the autopurge delay like when the writer unregisters them.
+            for cache_change in
+                data_reader.writer_unregistration_changes(&message.publication_handle, now)
+            {
+                self.add_reader_change(AddCacheChange {
+                    participant_address: message.participant_address.clone(),
+                    cache_change,
+                    subscriber_handle: message.subscriber_handle,
+                    data_reader_handle: message.data_reader_handle,
+                })?;
+            }
+            self.trigger_liveliness_changed(
+                message.participant_address,
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct AnnounceAutomaticLiveliness;
+impl Mail for AnnounceAutomaticLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AnnounceAutomaticLiveliness> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        _: AnnounceAutomaticLiveliness,
+    ) -> <AnnounceAutomaticLiveliness as Mail>::Result {
+        self.announce_participant_message(PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE)
+    }
+}
+
+impl DomainParticipantActor {
+    /// Record a liveliness assertion of a local writer with a manual liveliness kind
+    /// and start monitoring its lease if it was not being monitored
+    pub fn assert_data_writer_liveliness(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        publisher_handle: InstanceHandle,
+        data_writer_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let now = self.domain_participant.get_current_time();
+        let data_writer = self
+            .domain_participant
+            .get_mut_publisher(publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let DurationKind::Finite(lease_duration) = data_writer.qos().liveliness.lease_duration
+        else {
+            return Ok(());
+        };
+        if data_writer.qos().liveliness.kind == LivelinessQosPolicyKind::Automatic {
+            return Ok(());
+        }
+
+        if data_writer.assert_liveliness(now) {
+            let timer_handle = self.timer_driver.handle();
+            self.backend_executor.handle().spawn(async move {
+                timer_handle.sleep(lease_duration.into()).await;
+                participant_address
+                    .send_actor_mail(CheckDataWriterLiveliness {
+                        publisher_handle,
+                        data_writer_handle,
+                        participant_address: participant_address.clone(),
+                    })
+                    .ok();
+            });
+        }
+        Ok(())
+    }
+
+    /// Assert the liveliness of all the local writers with MANUAL_BY_PARTICIPANT liveliness
+    pub fn assert_participant_manual_liveliness(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+    ) -> DdsResult<()> {
+        let data_writer_list: Vec<_> = self
+            .domain_participant
+            .publisher_list()
+            .flat_map(|p| {
+                p.data_writer_list()
+                    .filter(|dw| {
+                        dw.qos().liveliness.kind == LivelinessQosPolicyKind::ManualByParticipant
+                    })
+                    .map(|dw| (p.instance_handle(), dw.instance_handle()))
+            })
+            .collect();
+        for (publisher_handle, data_writer_handle) in data_writer_list {
+            self.assert_data_writer_liveliness(
+                participant_address.clone(),
+                publisher_handle,
+                data_writer_handle,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Assert the liveliness of a MANUAL_BY_TOPIC writer on the wire by sending a
+    /// HEARTBEAT with the liveliness flag to the matched readers
+    pub fn announce_data_writer_liveliness(
+        &mut self,
+        publisher_handle: InstanceHandle,
+        data_writer_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let data_writer = self
+            .domain_participant
+            .get_mut_publisher(publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+            w.assert_liveliness();
+        }
+        data_writer.assert_local_readers_liveliness();
+        Ok(())
+    }
+
+    /// Make sure that the participant periodically announces its automatic liveliness
+    /// often enough for a writer with the given lease duration
+    pub fn start_automatic_liveliness(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        lease_duration: crate::infrastructure::time::Duration,
+    ) {
+        if self
+            .domain_participant
+            .automatic_liveliness_period()
+            .is_some_and(|p| p <= lease_duration)
+        {
+            return;
+        }
+        let announcement_period =
+            (std::time::Duration::from(lease_duration) / 3).max(MIN_AUTOMATIC_LIVELINESS_PERIOD);
+        let timer_handle = self.timer_driver.handle();
+        let task = self.backend_executor.handle().spawn(async move {
+            while participant_address
+                .send_actor_mail(AnnounceAutomaticLiveliness)
+                .is_ok()
+            {
+                timer_handle.sleep(announcement_period).await;
+            }
+        });
+        self.domain_participant
+            .set_automatic_liveliness_task(lease_duration, task);
+    }
+
+    /// Record a liveliness assertion of the matched writer on the given reader and
+    /// start monitoring its lease if it was not considered alive
+    pub fn assert_remote_writer_liveliness_on_reader(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+        publication_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let now = self.domain_participant.get_current_time();
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let Some(lease_duration) = data_reader
+            .get_matched_publication_data(&publication_handle)
+            .map(|p| p.liveliness().lease_duration)
+        else {
+            return Ok(());
+        };
+
+        if data_reader.assert_writer_liveliness(publication_handle, now) {
+            if let DurationKind::Finite(lease_duration) = lease_duration {
+                let timer_handle = self.timer_driver.handle();
+                let participant_address = participant_address.clone();
+                self.backend_executor.handle().spawn(async move {
+                    timer_handle.sleep(lease_duration.into()).await;
+                    participant_address
+                        .send_actor_mail(CheckRemoteWriterLiveliness {
+                            subscriber_handle,
+                            data_reader_handle,
+                            publication_handle,
+                            participant_address: participant_address.clone(),
+                        })
+                        .ok();
+                });
+            }
+            self.trigger_liveliness_changed(
+                participant_address,
+                subscriber_handle,
+                data_reader_handle,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Assert the liveliness of all the matched writers which fulfill the predicate
+    fn assert_remote_liveliness(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        is_asserted: impl Fn(&Guid, &PublicationBuiltinTopicData) -> bool,
+    ) -> DdsResult<()> {
+        let mut asserted_list = Vec::new();
+        for subscriber in self.domain_participant.subscriber_list() {
+            for data_reader in subscriber.data_reader_list() {
+                for publication_handle in data_reader.get_matched_publications() {
+                    let writer_guid = Guid::from(<[u8; 16]>::from(publication_handle));
+                    if data_reader
+                        .get_matched_publication_data(&publication_handle)
+                        .is_some_and(|p| is_asserted(&writer_guid, p))
+                    {
+                        asserted_list.push((
+                            subscriber.instance_handle(),
+                            data_reader.instance_handle(),
+                            publication_handle,
+                        ));
+                    }
+                }
+            }
+        }
+        for (subscriber_handle, data_reader_handle, publication_handle) in asserted_list {
+            self.assert_remote_writer_liveliness_on_reader(
+                participant_address.clone(),
+                subscriber_handle,
+                data_reader_handle,
+                publication_handle,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Handle a liveliness assertion of a remote writer, either by receiving data or a
+    /// HEARTBEAT with the liveliness flag. For writers which are not MANUAL_BY_TOPIC
+    /// this also shows that the remote participant is alive, so all its writers with the
+    /// same liveliness kind are asserted.
+    pub fn assert_remote_writer_liveliness(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        writer_guid: Guid,
+    ) -> DdsResult<()> {
+        let Some(kind) = self
+            .domain_participant
+            .publication_builtin_topic_data_list()
+            .find(|w| w.writer_proxy.remote_writer_guid == writer_guid)
+            .map(|w| w.dds_publication_data.liveliness().kind)
+        else {
+            return Ok(());
+        };
+        match kind {
+            LivelinessQosPolicyKind::ManualByTopic => {
+                self.assert_remote_liveliness(participant_address, |g, _| g == &writer_guid)
+            }
+            LivelinessQosPolicyKind::Automatic | LivelinessQosPolicyKind::ManualByParticipant => {
+                self.assert_remote_participant_liveliness(
+                    participant_address,
+                    writer_guid.prefix(),
+                    kind,
+                )
+            }
+        }
+    }
+
+    /// Assert the liveliness of all the matched writers of a remote participant with
+    /// the given liveliness kind
+    pub fn assert_remote_participant_liveliness(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        guid_prefix: GuidPrefix,
+        kind: LivelinessQosPolicyKind,
+    ) -> DdsResult<()> {
+        self.assert_remote_liveliness(participant_address, |g, p| {
+            g.prefix() == guid_prefix && p.liveliness().kind == kind
+        })
+    }
+
+    pub fn trigger_liveliness_changed(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if data_reader
+            .listener_mask()
+            .contains(&StatusKind::LivelinessChanged)
+        {
+            let status = data_reader.get_liveliness_changed_status();
+            let the_reader = self.get_data_reader_async(
+                participant_address,
+                subscriber_handle,
+                data_reader_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(data_reader_listener::TriggerLivelinessChanged {
+                    the_reader,
+                    status,
+                });
+            }
+        } else if self
+            .domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .listener_mask()
+            .contains(&StatusKind::LivelinessChanged)
+        {
+            let the_reader = self.get_data_reader_async(
+                participant_address,
+                subscriber_handle,
+                data_reader_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_changed_status();
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(subscriber_listener::TriggerLivelinessChanged {
+                    the_reader,
+                    status,
+                });
+            }
+        } else if self
+            .domain_participant
+            .listener_mask()
+            .contains(&StatusKind::LivelinessChanged)
+        {
+            let the_reader = self.get_data_reader_async(
+                participant_address,
+                subscriber_handle,
+                data_reader_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_changed_status();
+            if let Some(l) = self.domain_participant.listener() {
+                l.send_actor_mail(domain_participant_listener::TriggerLivelinessChanged {
+                    the_reader,
+                    status,
+                });
+            }
+        }
+
+        self.domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .status_condition()
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::LivelinessChanged,
+            });
+        Ok(())
+    }
+}
@@ -7,11 +7,19 @@ use crate::{
         data_representation_builtin_endpoints::{
             discovered_reader_data::DiscoveredReaderData,
             discovered_writer_data::DiscoveredWriterData,
+            participant_message_data::{
+                ParticipantMessageData, DCPS_PARTICIPANT_MESSAGE,
+                PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE,
+                PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
+            },
             spdp_discovered_participant_data::SpdpDiscoveredParticipantData,
         },
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
-            entities::data_reader::{AddChangeResult, TransportReaderKind},
+            entities::{
+                data_reader::{AddChangeResult, TransportReaderKind},
+                data_writer::SharedValue,
+            },
             services::discovery_service,
         },
         listeners::{data_reader_listener, domain_participant_listener, subscriber_listener},
@@ -21,17 +29,18 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos_policy::{
-            DurabilityQosPolicyKind, HistoryQosPolicy, LifespanQosPolicy, ResourceLimitsQosPolicy,
+            DurabilityQosPolicyKind, HistoryQosPolicy, LifespanQosPolicy, LivelinessQosPolicyKind,
+            PresentationQosPolicyAccessScopeKind, ResourceLimitsQosPolicy,
             TransportPriorityQosPolicy,
         },
         status::StatusKind,
-        time::DurationKind,
+        time::{Duration, DurationKind},
     },
     runtime::actor::{ActorAddress, Mail, MailHandler},
     topic_definition::type_support::DdsDeserialize,
     transport::{
         history_cache::CacheChange,
-        types::{ChangeKind, SequenceNumber},
+        types::{ChangeKind, Guid, SequenceNumber},
     },
 };
 
@@ -48,6 +57,204 @@ impl Mail for AddCacheChange {
 }
 impl MailHandler<AddCacheChange> for DomainParticipantActor {
     fn handle(&mut self, message: AddCacheChange) -> <AddCacheChange as Mail>::Result {
+        // Receiving data from a writer asserts its liveliness
+        self.assert_remote_writer_liveliness(
+            message.participant_address.clone(),
+            message.cache_change.writer_guid,
+        )?;
+
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let presentation = subscriber.qos().presentation.clone();
+        let group_access = presentation.coherent_access
+            && presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group;
+        let writer_guid = message.cache_change.writer_guid;
+        let group_coherent_set_end = match (
+            message.cache_change.is_end_of_coherent_set(),
+            message.cache_change.group_coherent_set,
+            message.cache_change.writer_group_info,
+        ) {
+            (true, Some(group_coherent_set), Some(writer_group_info)) if group_access => {
+                Some((group_coherent_set, writer_group_info))
+            }
+            _ => None,
+        };
+
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if data_reader
+            .get_matched_publication_data(&InstanceHandle::new(writer_guid.into()))
+            .is_none()
+        {
+            return Ok(());
+        }
+        let cache_change_list = if presentation.coherent_access {
+            data_reader.add_coherent_change(message.cache_change, group_access)
+        } else if message.cache_change.is_end_of_coherent_set() {
+            Vec::new()
+        } else {
+            vec![message.cache_change]
+        };
+
+        let mut reader_change_list = vec![(message.data_reader_handle, cache_change_list)];
+        if let Some((group_coherent_set, writer_group_info)) = group_coherent_set_end {
+            if let Some(writer_guids) = subscriber.add_group_coherent_set_end(
+                writer_guid,
+                group_coherent_set,
+                writer_group_info,
+            ) {
+                for data_reader in subscriber.data_reader_list_mut() {
+                    reader_change_list.push((
+                        data_reader.instance_handle(),
+                        data_reader.take_group_coherent_changes(&writer_guids, group_coherent_set),
+                    ));
+                }
+            }
+        }
+
+        if subscriber.is_access_active() {
+            subscriber.hold_changes(
+                reader_change_list
+                    .into_iter()
+                    .flat_map(|(h, changes)| changes.into_iter().map(move |cc| (h, cc))),
+            );
+            return Ok(());
+        }
+
+        // All the changes of a coherent set are added while handling the same mail so
+        // that the application can never access only part of the set
+        for (data_reader_handle, cache_change_list) in reader_change_list {
+            for cache_change in cache_change_list {
+                self.add_reader_change(AddCacheChange {
+                    participant_address: message.participant_address.clone(),
+                    cache_change,
+                    subscriber_handle: message.subscriber_handle,
+                    data_reader_handle,
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Change of a writer of this application delivered directly to one of the
+/// readers of this participant
+pub struct AddLocalCacheChange {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub data_reader_guid: Guid,
+    pub cache_change: CacheChange,
+    pub shared_value: Option<SharedValue>,
+}
+impl Mail for AddLocalCacheChange {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AddLocalCacheChange> for DomainParticipantActor {
+    fn handle(&mut self, message: AddLocalCacheChange) -> <AddLocalCacheChange as Mail>::Result {
+        let (subscriber_handle, data_reader_handle) = self
+            .domain_participant
+            .subscriber_list()
+            .find_map(|s| {
+                s.data_reader_list()
+                    .find(|dr| dr.transport_reader().guid() == message.data_reader_guid)
+                    .map(|dr| (s.instance_handle(), dr.instance_handle()))
+            })
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if let Some(shared_value) = message.shared_value {
+            self.domain_participant
+                .get_mut_subscriber(subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .add_shared_value(&message.cache_change, shared_value);
+        }
+        self.handle(AddCacheChange {
+            participant_address: message.participant_address,
+            cache_change: message.cache_change,
+            subscriber_handle,
+            data_reader_handle,
+        })
+    }
+}
+
+impl DomainParticipantActor {
+    /// Arm the lifespan timer of the reader to fire when the lifespan of its first
+    /// expiring sample elapses. The timer is only re-armed if this time changed.
+    pub fn schedule_reader_lifespan_timer(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let now = self.domain_participant.get_current_time();
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let next_expiration = data_reader.next_sample_expiration();
+        if next_expiration == data_reader.lifespan_timer_expiration() {
+            return Ok(());
+        }
+        let lifespan_timer = next_expiration.map(|expiration| {
+            let timer_handle = self.timer_driver.handle();
+            let sleep_duration = if expiration > now {
+                expiration - now
+            } else {
+                Duration::new(0, 0)
+            };
+            let task = self.backend_executor.handle().spawn(async move {
+                timer_handle.sleep(sleep_duration.into()).await;
+                participant_address
+                    .send_actor_mail(RemoveExpiredReaderSamples {
+                        participant_address: participant_address.clone(),
+                        subscriber_handle,
+                        data_reader_handle,
+                    })
+                    .ok();
+            });
+            (expiration, task)
+        });
+        data_reader.set_lifespan_timer(lifespan_timer);
+        Ok(())
+    }
+
+    /// Schedule the purge of an instance whose state is not alive according to the
+    /// reader data lifecycle QoS of the reader
+    pub fn schedule_instance_autopurge(
+        &mut self,
+        participant_address: ActorAddress<DomainParticipantActor>,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+        instance_handle: InstanceHandle,
+    ) -> DdsResult<()> {
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if let Some(autopurge_delay) = data_reader.instance_autopurge_delay(&instance_handle) {
+            let timer_handle = self.timer_driver.handle();
+            let autopurge_task = self.backend_executor.handle().spawn(async move {
+                timer_handle.sleep(autopurge_delay.into()).await;
+                participant_address
+                    .send_actor_mail(PurgeReaderInstance {
+                        subscriber_handle,
+                        data_reader_handle,
+                        instance_handle,
+                    })
+                    .ok();
+            });
+            data_reader.insert_instance_autopurge_task(instance_handle, autopurge_task);
+        }
+        Ok(())
+    }
+
+    pub fn add_reader_change(&mut self, message: AddCacheChange) -> DdsResult<()> {
         let reception_timestamp = self.domain_participant.get_current_time();
         let subscriber = self
             .domain_participant
@@ -65,6 +272,24 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
         {
             match data_reader.add_reader_change(message.cache_change, reception_timestamp)? {
                 AddChangeResult::Added(change_instance_handle) => {
+                    self.schedule_reader_lifespan_timer(
+                        message.participant_address.clone(),
+                        message.subscriber_handle,
+                        message.data_reader_handle,
+                    )?;
+                    self.schedule_instance_autopurge(
+                        message.participant_address.clone(),
+                        message.subscriber_handle,
+                        message.data_reader_handle,
+                        change_instance_handle,
+                    )?;
+
+                    let data_reader = self
+                        .domain_participant
+                        .get_mut_subscriber(message.subscriber_handle)
+                        .ok_or(DdsError::AlreadyDeleted)?
+                        .get_mut_data_reader(message.data_reader_handle)
+                        .ok_or(DdsError::AlreadyDeleted)?;
                     if let DurationKind::Finite(deadline_missed_period) =
                         data_reader.qos().deadline.period
                     {
@@ -282,6 +507,7 @@ impl MailHandler<AddBuiltinParticipantsDetectorCacheChange> for DomainParticipan
                         .participant_address
                         .send_actor_mail(discovery_service::AddDiscoveredParticipant {
                             discovered_participant_data,
+                            participant_address: message.participant_address.clone(),
                         })
                         .ok();
                 }
@@ -294,6 +520,7 @@ impl MailHandler<AddBuiltinParticipantsDetectorCacheChange> for DomainParticipan
                         .participant_address
                         .send_actor_mail(discovery_service::RemoveDiscoveredParticipant {
                             discovered_participant: discovered_participant_handle,
+                            participant_address: message.participant_address.clone(),
                         })
                         .ok();
                 }
@@ -382,6 +609,104 @@ impl MailHandler<AddBuiltinTopicsDetectorCacheChange> for DomainParticipantActor
     }
 }
 
+pub struct AddBuiltinParticipantMessageCacheChange {
+    pub cache_change: CacheChange,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for AddBuiltinParticipantMessageCacheChange {
+    type Result = ();
+}
+impl MailHandler<AddBuiltinParticipantMessageCacheChange> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AddBuiltinParticipantMessageCacheChange,
+    ) -> <AddBuiltinParticipantMessageCacheChange as Mail>::Result {
+        if let ChangeKind::Alive = message.cache_change.kind {
+            if let Ok(participant_message_data) =
+                ParticipantMessageData::deserialize_data(message.cache_change.data_value.as_ref())
+            {
+                let liveliness_kind = match participant_message_data.kind {
+                    PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE => {
+                        Some(LivelinessQosPolicyKind::Automatic)
+                    }
+                    PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE => {
+                        Some(LivelinessQosPolicyKind::ManualByParticipant)
+                    }
+                    _ => None,
+                };
+                if let Some(kind) = liveliness_kind {
+                    self.assert_remote_participant_liveliness(
+                        message.participant_address,
+                        participant_message_data.participant_guid_prefix,
+                        kind,
+                    )
+                    .ok();
+                }
+            }
+        }
+
+        let reception_timestamp = self.domain_participant.get_current_time();
+        if let Some(reader) = self
+            .domain_participant
+            .builtin_subscriber_mut()
+            .data_reader_list_mut()
+            .find(|dr| dr.topic_name() == DCPS_PARTICIPANT_MESSAGE)
+        {
+            reader
+                .add_reader_change(message.cache_change, reception_timestamp)
+                .ok();
+        }
+    }
+}
+
+pub struct AssertRemoteWriterLiveliness {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub writer_guid: Guid,
+}
+impl Mail for AssertRemoteWriterLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AssertRemoteWriterLiveliness> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AssertRemoteWriterLiveliness,
+    ) -> <AssertRemoteWriterLiveliness as Mail>::Result {
+        self.assert_remote_writer_liveliness(message.participant_address, message.writer_guid)
+    }
+}
+
+pub struct AddLostChanges {
+    pub writer_guid: Guid,
+    pub first_sequence_number: SequenceNumber,
+    pub last_sequence_number: SequenceNumber,
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for AddLostChanges {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AddLostChanges> for DomainParticipantActor {
+    fn handle(&mut self, message: AddLostChanges) -> <AddLostChanges as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        // Lost changes only matter to readers which hold back coherent sets
+        if !subscriber.qos().presentation.coherent_access {
+            return Ok(());
+        }
+        subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .add_lost_changes(
+                message.writer_guid,
+                message.first_sequence_number,
+                message.last_sequence_number,
+            );
+        Ok(())
+    }
+}
+
 pub struct RemoveBuiltinTopicsDetectorCacheChange {
     pub _sequence_number: SequenceNumber,
 }
@@ -475,6 +800,7 @@ impl MailHandler<AddBuiltinPublicationsDetectorCacheChange> for DomainParticipan
                                     publication_handle: discovered_writer_handle,
                                     subscriber_handle: subscriber.instance_handle(),
                                     data_reader_handle: data_reader.instance_handle(),
+                                    participant_address: message.participant_address.clone(),
                                 })
                                 .ok();
                         }
@@ -621,6 +947,7 @@ impl MailHandler<AddBuiltinSubscriptionsDetectorCacheChange> for DomainParticipa
                                     subscription_handle: discovered_reader_handle,
                                     publisher_handle: publisher.instance_handle(),
                                     data_writer_handle: data_writer.instance_handle(),
+                                    participant_address: message.participant_address.clone(),
                                 })
                                 .ok();
                         }
@@ -739,3 +1066,55 @@ impl MailHandler<RemoveWriterChange> for DomainParticipantActor {
         }
     }
 }
+
+pub struct RemoveExpiredReaderSamples {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for RemoveExpiredReaderSamples {
+    type Result = ();
+}
+impl MailHandler<RemoveExpiredReaderSamples> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: RemoveExpiredReaderSamples,
+    ) -> <RemoveExpiredReaderSamples as Mail>::Result {
+        let now = self.domain_participant.get_current_time();
+        if let Some(s) = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+        {
+            if let Some(dr) = s.get_mut_data_reader(message.data_reader_handle) {
+                dr.remove_expired_samples(now);
+                self.schedule_reader_lifespan_timer(
+                    message.participant_address,
+                    message.subscriber_handle,
+                    message.data_reader_handle,
+                )
+                .ok();
+            }
+        }
+    }
+}
+
+pub struct PurgeReaderInstance {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub instance_handle: InstanceHandle,
+}
+impl Mail for PurgeReaderInstance {
+    type Result = ();
+}
+impl MailHandler<PurgeReaderInstance> for DomainParticipantActor {
+    fn handle(&mut self, message: PurgeReaderInstance) -> <PurgeReaderInstance as Mail>::Result {
+        if let Some(s) = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+        {
+            if let Some(dr) = s.get_mut_data_reader(message.data_reader_handle) {
+                dr.purge_instance(&message.instance_handle);
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
+pub mod content_filtered_topic_service;
 pub mod data_reader_service;
 pub mod data_writer_service;
 pub mod discovery_service;
 pub mod domain_participant_service;
+pub mod durability_service;
 pub mod event_service;
 pub mod message_service;
 pub mod publisher_service;
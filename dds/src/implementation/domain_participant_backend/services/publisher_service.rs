@@ -16,16 +16,19 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos, QosKind},
-        qos_policy::ReliabilityQosPolicyKind,
+        qos_policy::{
+            DurabilityQosPolicyKind, ReliabilityQosPolicyKind, ReliabilityTimingQosPolicy,
+        },
         status::StatusKind,
     },
     runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
     transport::types::{
-        EntityId, ReliabilityKind, TopicKind, USER_DEFINED_WRITER_NO_KEY,
-        USER_DEFINED_WRITER_WITH_KEY,
+        EndpointReliabilityTiming, EntityId, Guid, ReliabilityKind, TopicKind,
+        USER_DEFINED_WRITER_NO_KEY, USER_DEFINED_WRITER_WITH_KEY,
     },
     xtypes::dynamic_type::DynamicType,
 };
+use tracing::warn;
 
 use super::{data_writer_service, discovery_service};
 
@@ -77,13 +80,42 @@ impl MailHandler<CreateDataWriter> for DomainParticipantActor {
                 q
             }
         };
+        if qos.durability.kind == DurabilityQosPolicyKind::Persistent
+            && self.durability_stores.get(qos.durability.kind).is_none()
+        {
+            return Err(DdsError::PreconditionNotMet(
+                "No durability store available for the persistent data".to_string(),
+            ));
+        }
         let reliablity_kind = match qos.reliability.kind {
             ReliabilityQosPolicyKind::BestEffort => ReliabilityKind::BestEffort,
             ReliabilityQosPolicyKind::Reliable => ReliabilityKind::Reliable,
         };
-        let transport_writer = self
-            .transport
-            .create_stateful_writer(entity_id, reliablity_kind);
+        let mut transport_writer = match &qos.publish_mode.flow_controller_name {
+            Some(flow_controller_name) => self
+                .transport
+                .create_flow_controlled_stateful_writer(
+                    entity_id,
+                    reliablity_kind,
+                    flow_controller_name,
+                    qos.publish_mode.priority,
+                )
+                .ok_or(DdsError::BadParameter)?,
+            None => self
+                .transport
+                .create_stateful_writer(entity_id, reliablity_kind),
+        };
+        if qos.reliability_timing != ReliabilityTimingQosPolicy::default() {
+            transport_writer.set_reliability_timing(EndpointReliabilityTiming {
+                heartbeat_period: qos.reliability_timing.heartbeat_period.map(Into::into),
+                nack_response_delay: qos.reliability_timing.nack_response_delay.map(Into::into),
+                nack_suppression_duration: qos
+                    .reliability_timing
+                    .nack_suppression_duration
+                    .map(Into::into),
+                heartbeat_response_delay: None,
+            });
+        }
 
         let topic_name = message.topic_name;
 
@@ -138,14 +170,30 @@ impl Mail for DeleteDataWriter {
 }
 impl MailHandler<DeleteDataWriter> for DomainParticipantActor {
     fn handle(&mut self, message: DeleteDataWriter) -> <DeleteDataWriter as Mail>::Result {
+        let timestamp = self.domain_participant.get_current_time();
         let publisher = self
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
 
-        let data_writer = publisher
+        let mut data_writer = publisher
             .remove_data_writer(message.datawriter_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        // The unregistrations must be sent before the matched readers are removed
+        if let Err(e) = publisher.unregister_data_writer_instances(&mut data_writer, timestamp) {
+            warn!(
+                "Failed to unregister the instances of a deleted writer: {:?}",
+                e
+            );
+        }
+        // Matched readers hold resources in the transport, e.g. their content filters
+        let matched_subscriptions = data_writer.get_matched_subscriptions();
+        if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+            for subscription_handle in matched_subscriptions {
+                w.remove_matched_reader(Guid::from(<[u8; 16]>::from(subscription_handle)));
+            }
+        }
+        self.stop_serving_durable_samples(&data_writer, message.participant_address.clone());
         message
             .participant_address
             .send_actor_mail(discovery_service::AnnounceDeletedDataWriter { data_writer })
@@ -264,7 +312,74 @@ impl MailHandler<SetListener> for DomainParticipantActor {
     }
 }
 
-fn get_topic_kind(type_support: &dyn DynamicType) -> TopicKind {
+pub struct BeginCoherentChanges {
+    pub publisher_handle: InstanceHandle,
+}
+impl Mail for BeginCoherentChanges {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<BeginCoherentChanges> for DomainParticipantActor {
+    fn handle(&mut self, message: BeginCoherentChanges) -> <BeginCoherentChanges as Mail>::Result {
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .begin_coherent_changes()
+    }
+}
+
+pub struct EndCoherentChanges {
+    pub publisher_handle: InstanceHandle,
+}
+impl Mail for EndCoherentChanges {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<EndCoherentChanges> for DomainParticipantActor {
+    fn handle(&mut self, message: EndCoherentChanges) -> <EndCoherentChanges as Mail>::Result {
+        let timestamp = self.domain_participant.get_current_time();
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .end_coherent_changes(timestamp)
+    }
+}
+
+pub struct SuspendPublications {
+    pub publisher_handle: InstanceHandle,
+}
+impl Mail for SuspendPublications {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<SuspendPublications> for DomainParticipantActor {
+    fn handle(&mut self, message: SuspendPublications) -> <SuspendPublications as Mail>::Result {
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .suspend_publications()
+    }
+}
+
+pub struct ResumePublications {
+    pub publisher_handle: InstanceHandle,
+}
+impl Mail for ResumePublications {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<ResumePublications> for DomainParticipantActor {
+    fn handle(&mut self, message: ResumePublications) -> <ResumePublications as Mail>::Result {
+        let publisher = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        // The queued changes are released inside a transport batch so that they
+        // are sent packed in as few messages as possible
+        self.transport.begin_batch();
+        let result = publisher.resume_publications();
+        self.transport.end_batch();
+        result
+    }
+}
+
+pub fn get_topic_kind(type_support: &dyn DynamicType) -> TopicKind {
     for index in 0..type_support.get_member_count() {
         if let Ok(m) = type_support.get_member_by_index(index) {
             if let Ok(d) = m.get_descriptor() {
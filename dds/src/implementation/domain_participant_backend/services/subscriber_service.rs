@@ -8,7 +8,7 @@ use crate::{
             services::{data_reader_service, discovery_service, message_service},
         },
         listeners::{
-            data_reader_listener::DataReaderListenerActor,
+            data_reader_listener::{self, DataReaderListenerActor},
             subscriber_listener::SubscriberListenerActor,
         },
         status_condition::status_condition_actor::StatusConditionActor,
@@ -17,15 +17,16 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind, SubscriberQos},
-        qos_policy::ReliabilityQosPolicyKind,
+        qos_policy::{ReliabilityQosPolicyKind, ReliabilityTimingQosPolicy},
         status::StatusKind,
     },
     runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
     transport::{
         history_cache::{CacheChange, HistoryCache},
         types::{
-            EntityId, ReliabilityKind, TopicKind, USER_DEFINED_READER_NO_KEY,
-            USER_DEFINED_READER_WITH_KEY,
+            EndpointReliabilityTiming, EntityId, Guid, ReliabilityKind, TopicKind,
+            USER_DEFINED_READER_NO_KEY, USER_DEFINED_READER_WITH_KEY,
         },
     },
     xtypes::dynamic_type::DynamicType,
@@ -65,11 +66,45 @@ impl MailHandler<CreateDataReader> for DomainParticipantActor {
             fn remove_change(&mut self, _sequence_number: i64) {
                 todo!()
             }
+
+            fn assert_writer_liveliness(&mut self, writer_guid: Guid) {
+                self.domain_participant_address
+                    .send_actor_mail(message_service::AssertRemoteWriterLiveliness {
+                        participant_address: self.domain_participant_address.clone(),
+                        writer_guid,
+                    })
+                    .ok();
+            }
+
+            fn lost_changes(
+                &mut self,
+                writer_guid: Guid,
+                first_sequence_number: i64,
+                last_sequence_number: i64,
+            ) {
+                self.domain_participant_address
+                    .send_actor_mail(message_service::AddLostChanges {
+                        writer_guid,
+                        first_sequence_number,
+                        last_sequence_number,
+                        subscriber_handle: self.subscriber_handle,
+                        data_reader_handle: self.data_reader_handle,
+                    })
+                    .ok();
+            }
         }
 
+        // Readers created on a content filtered topic are attached to the related topic
+        let content_filter = self
+            .domain_participant
+            .get_content_filtered_topic(&message.topic_name)
+            .map(|t| t.content_filter().clone());
+        let related_topic_name = content_filter
+            .as_ref()
+            .map_or(message.topic_name.as_str(), |f| f.related_topic_name());
         let topic = self
             .domain_participant
-            .get_topic(&message.topic_name)
+            .get_topic(related_topic_name)
             .ok_or(DdsError::AlreadyDeleted)?;
 
         let topic_kind = get_topic_kind(topic.type_support().as_ref());
@@ -107,16 +142,25 @@ impl MailHandler<CreateDataReader> for DomainParticipantActor {
             ReliabilityQosPolicyKind::BestEffort => ReliabilityKind::BestEffort,
             ReliabilityQosPolicyKind::Reliable => ReliabilityKind::Reliable,
         };
-        let transport_reader =
-            TransportReaderKind::Stateful(self.transport.create_stateful_reader(
-                entity_id,
-                reliablity_kind,
-                Box::new(UserDefinedReaderHistoryCache {
-                    domain_participant_address: message.domain_participant_address.clone(),
-                    subscriber_handle: subscriber.instance_handle(),
-                    data_reader_handle: reader_handle,
-                }),
-            ));
+        let mut stateful_reader = self.transport.create_stateful_reader(
+            entity_id,
+            reliablity_kind,
+            Box::new(UserDefinedReaderHistoryCache {
+                domain_participant_address: message.domain_participant_address.clone(),
+                subscriber_handle: subscriber.instance_handle(),
+                data_reader_handle: reader_handle,
+            }),
+        );
+        if qos.reliability_timing != ReliabilityTimingQosPolicy::default() {
+            stateful_reader.set_reliability_timing(EndpointReliabilityTiming {
+                heartbeat_response_delay: qos
+                    .reliability_timing
+                    .heartbeat_response_delay
+                    .map(Into::into),
+                ..Default::default()
+            });
+        }
+        let transport_reader = TransportReaderKind::Stateful(stateful_reader);
 
         let listener_mask = message.mask.to_vec();
         let status_condition = Actor::spawn(
@@ -139,6 +183,7 @@ impl MailHandler<CreateDataReader> for DomainParticipantActor {
             listener,
             listener_mask,
             transport_reader,
+            content_filter,
         );
 
         let data_reader_handle = data_reader.instance_handle();
@@ -173,6 +218,15 @@ impl MailHandler<DeleteDataReader> for DomainParticipantActor {
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        if subscriber
+            .get_data_reader(message.datareader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .has_read_conditions()
+        {
+            return Err(DdsError::PreconditionNotMet(
+                "Data reader still has read conditions".to_string(),
+            ));
+        }
         let data_reader = subscriber
             .remove_data_reader(message.datareader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
@@ -330,6 +384,100 @@ impl MailHandler<SetListener> for DomainParticipantActor {
     }
 }
 
+pub struct BeginAccess {
+    pub subscriber_handle: InstanceHandle,
+}
+impl Mail for BeginAccess {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<BeginAccess> for DomainParticipantActor {
+    fn handle(&mut self, message: BeginAccess) -> <BeginAccess as Mail>::Result {
+        self.domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .begin_access()
+    }
+}
+
+pub struct EndAccess {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub subscriber_handle: InstanceHandle,
+}
+impl Mail for EndAccess {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<EndAccess> for DomainParticipantActor {
+    fn handle(&mut self, message: EndAccess) -> <EndAccess as Mail>::Result {
+        let held_changes = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .end_access()?;
+        for (data_reader_handle, cache_change) in held_changes {
+            self.add_reader_change(message_service::AddCacheChange {
+                participant_address: message.participant_address.clone(),
+                cache_change,
+                subscriber_handle: message.subscriber_handle,
+                data_reader_handle,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+pub struct NotifyDataReaders {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub subscriber_handle: InstanceHandle,
+}
+impl Mail for NotifyDataReaders {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<NotifyDataReaders> for DomainParticipantActor {
+    fn handle(&mut self, message: NotifyDataReaders) -> <NotifyDataReaders as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        for data_reader in subscriber.data_reader_list().filter(|dr| {
+            dr.data_available_status_changed()
+                && dr.listener_mask().contains(&StatusKind::DataAvailable)
+        }) {
+            if let Some(l) = data_reader.listener() {
+                let the_reader = self.get_data_reader_async(
+                    message.participant_address.clone(),
+                    message.subscriber_handle,
+                    data_reader.instance_handle(),
+                )?;
+                l.send_actor_mail(data_reader_listener::TriggerDataAvailable { the_reader });
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct GetDataReaders {
+    pub subscriber_handle: InstanceHandle,
+    pub sample_states: Vec<SampleStateKind>,
+    pub view_states: Vec<ViewStateKind>,
+    pub instance_states: Vec<InstanceStateKind>,
+}
+impl Mail for GetDataReaders {
+    type Result = DdsResult<Vec<InstanceHandle>>;
+}
+impl MailHandler<GetDataReaders> for DomainParticipantActor {
+    fn handle(&mut self, message: GetDataReaders) -> <GetDataReaders as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .get_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_datareaders(
+                &message.sample_states,
+                &message.view_states,
+                &message.instance_states,
+            ))
+    }
+}
+
 fn get_topic_kind(type_support: &dyn DynamicType) -> TopicKind {
     for index in 0..type_support.get_member_count() {
         if let Ok(m) = type_support.get_member_by_index(index) {
@@ -3,15 +3,19 @@ use crate::{
     configuration::DustDdsConfiguration,
     dds_async::domain_participant_listener::DomainParticipantListenerAsync,
     domain::domain_participant_factory::DomainId,
+    durability::DurabilityStore,
     implementation::{
         data_representation_builtin_endpoints::{
             discovered_reader_data::DiscoveredReaderData,
             discovered_topic_data::DiscoveredTopicData,
             discovered_writer_data::DiscoveredWriterData,
+            participant_message_data::{ParticipantMessageData, DCPS_PARTICIPANT_MESSAGE},
             spdp_discovered_participant_data::SpdpDiscoveredParticipantData,
         },
         domain_participant_backend::{
-            domain_participant_actor::DomainParticipantActor,
+            domain_participant_actor::{
+                DomainParticipantActor, DurabilityStores, LocalParticipants,
+            },
             entities::{
                 data_reader::{DataReaderEntity, TransportReaderKind},
                 data_writer::{DataWriterEntity, TransportWriterKind},
@@ -51,7 +55,7 @@ use crate::{
         factory::TransportParticipantFactory,
         history_cache::{CacheChange, HistoryCache},
         types::{
-            EntityId, GuidPrefix, ReliabilityKind, BUILT_IN_READER_WITH_KEY,
+            EntityId, Guid, GuidPrefix, ReliabilityKind, BUILT_IN_READER_WITH_KEY,
             BUILT_IN_WRITER_WITH_KEY,
         },
     },
@@ -91,12 +95,20 @@ pub const ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER: EntityId =
 pub const ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR: EntityId =
     EntityId::new([0, 0, 0x04], BUILT_IN_READER_WITH_KEY);
 
+pub const ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER: EntityId =
+    EntityId::new([0x00, 0x02, 0x00], BUILT_IN_WRITER_WITH_KEY);
+
+pub const ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER: EntityId =
+    EntityId::new([0x00, 0x02, 0x00], BUILT_IN_READER_WITH_KEY);
+
 pub struct DomainParticipantFactoryActor {
     domain_participant_list: HashMap<InstanceHandle, Actor<DomainParticipantActor>>,
     qos: DomainParticipantFactoryQos,
     default_participant_qos: DomainParticipantQos,
     configuration: DustDdsConfiguration,
     transport: Box<dyn TransportParticipantFactory>,
+    durability_stores: DurabilityStores,
+    local_participants: LocalParticipants,
 }
 
 impl Default for DomainParticipantFactoryActor {
@@ -107,6 +119,8 @@ impl Default for DomainParticipantFactoryActor {
             default_participant_qos: Default::default(),
             configuration: Default::default(),
             transport: Box::new(RtpsParticipantFactory::default()),
+            durability_stores: DurabilityStores::default(),
+            local_participants: LocalParticipants::default(),
         }
     }
 }
@@ -196,7 +210,7 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
 
         let mut transport = self
             .transport
-            .create_participant(guid_prefix, message.domain_id);
+            .create_participant(guid_prefix, message.domain_id)?;
 
         let mut instance_handle_counter = InstanceHandleCounter::default();
         fn sedp_data_reader_qos() -> DataReaderQos {
@@ -292,6 +306,24 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         sedp_topic_subscriptions.enable();
         topic_list.insert(DCPS_SUBSCRIPTION.to_owned(), sedp_topic_subscriptions);
 
+        let participant_message_topic_handle =
+            instance_handle_counter.generate_new_instance_handle();
+        let mut participant_message_topic = TopicEntity::new(
+            TopicQos::default(),
+            "ParticipantMessageData".to_string(),
+            DCPS_PARTICIPANT_MESSAGE.to_owned(),
+            participant_message_topic_handle,
+            Actor::spawn(StatusConditionActor::default(), &listener_executor.handle()),
+            None,
+            vec![],
+            Arc::new(ParticipantMessageData::get_type()),
+        );
+        participant_message_topic.enable();
+        topic_list.insert(
+            DCPS_PARTICIPANT_MESSAGE.to_owned(),
+            participant_message_topic,
+        );
+
         let spdp_writer_qos = DataWriterQos {
             durability: DurabilityQosPolicy {
                 kind: DurabilityQosPolicyKind::TransientLocal,
@@ -335,6 +367,7 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             None,
             Vec::new(),
             TransportReaderKind::Stateless(dcps_participant_transport_reader),
+            None,
         );
         dcps_participant_reader.enable();
         let dcps_topic_transport_reader = transport.create_stateful_reader(
@@ -354,6 +387,7 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             None,
             Vec::new(),
             TransportReaderKind::Stateful(dcps_topic_transport_reader),
+            None,
         );
         dcps_topic_reader.enable();
         let dcps_publication_transport_reader = transport.create_stateful_reader(
@@ -373,6 +407,7 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             None,
             Vec::new(),
             TransportReaderKind::Stateful(dcps_publication_transport_reader),
+            None,
         );
         dcps_publication_reader.enable();
         let dcps_subscription_transport_reader = transport.create_stateful_reader(
@@ -392,8 +427,29 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             None,
             Vec::new(),
             TransportReaderKind::Stateful(dcps_subscription_transport_reader),
+            None,
         );
         dcps_subscription_reader.enable();
+        let dcps_participant_message_transport_reader = transport.create_stateful_reader(
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+            ReliabilityKind::Reliable,
+            Box::new(DcpsParticipantMessageReaderHistoryCache {
+                participant_address: participant_actor_builder.address(),
+            }),
+        );
+        let mut dcps_participant_message_reader = DataReaderEntity::new(
+            instance_handle_counter.generate_new_instance_handle(),
+            sedp_data_reader_qos(),
+            topic_list[DCPS_PARTICIPANT_MESSAGE].topic_name().to_owned(),
+            topic_list[DCPS_PARTICIPANT_MESSAGE].type_name().to_owned(),
+            topic_list[DCPS_PARTICIPANT_MESSAGE].type_support().clone(),
+            Actor::spawn(StatusConditionActor::default(), &listener_executor.handle()),
+            None,
+            Vec::new(),
+            TransportReaderKind::Stateful(dcps_participant_message_transport_reader),
+            None,
+        );
+        dcps_participant_message_reader.enable();
 
         let mut builtin_subscriber = SubscriberEntity::new(
             instance_handle_counter.generate_new_instance_handle(),
@@ -407,10 +463,15 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         builtin_subscriber.insert_data_reader(dcps_topic_reader);
         builtin_subscriber.insert_data_reader(dcps_publication_reader);
         builtin_subscriber.insert_data_reader(dcps_subscription_reader);
+        builtin_subscriber.insert_data_reader(dcps_participant_message_reader);
 
         let mut dcps_participant_transport_writer =
             transport.create_stateless_writer(ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER);
-        for &discovery_locator in transport.metatraffic_multicast_locator_list() {
+        for &discovery_locator in transport
+            .metatraffic_multicast_locator_list()
+            .iter()
+            .chain(transport.initial_peer_locator_list())
+        {
             dcps_participant_transport_writer.add_reader_locator(discovery_locator);
         }
         let mut dcps_participant_writer = DataWriterEntity::new(
@@ -475,6 +536,23 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             sedp_data_writer_qos(),
         );
         dcps_subscriptions_writer.enable();
+
+        let dcps_participant_message_transport_writer = transport.create_stateful_writer(
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+            ReliabilityKind::Reliable,
+        );
+        let mut dcps_participant_message_writer = DataWriterEntity::new(
+            instance_handle_counter.generate_new_instance_handle(),
+            TransportWriterKind::Stateful(dcps_participant_message_transport_writer),
+            topic_list[DCPS_PARTICIPANT_MESSAGE].topic_name().to_owned(),
+            topic_list[DCPS_PARTICIPANT_MESSAGE].type_name().to_owned(),
+            topic_list[DCPS_PARTICIPANT_MESSAGE].type_support().clone(),
+            Actor::spawn(StatusConditionActor::default(), &listener_executor.handle()),
+            None,
+            vec![],
+            sedp_data_writer_qos(),
+        );
+        dcps_participant_message_writer.enable();
         let mut builtin_publisher = PublisherEntity::new(
             PublisherQos::default(),
             instance_handle_counter.generate_new_instance_handle(),
@@ -487,6 +565,15 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         builtin_publisher.insert_data_writer(dcps_topics_writer);
         builtin_publisher.insert_data_writer(dcps_publications_writer);
         builtin_publisher.insert_data_writer(dcps_subscriptions_writer);
+        builtin_publisher.insert_data_writer(dcps_participant_message_writer);
+        let mut durability_service_publisher = PublisherEntity::new(
+            PublisherQos::default(),
+            instance_handle_counter.generate_new_instance_handle(),
+            None,
+            vec![],
+            Actor::spawn(StatusConditionActor::default(), &listener_executor.handle()),
+        );
+        durability_service_publisher.enable();
         let instance_handle = InstanceHandle::new(transport.guid().into());
 
         let status_condition =
@@ -505,9 +592,11 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             status_condition,
             instance_handle,
             builtin_publisher,
+            durability_service_publisher,
             builtin_subscriber,
             topic_list,
             self.configuration.domain_tag().to_owned(),
+            self.configuration.participant_lease_duration().into(),
         );
 
         let domain_participant_actor = DomainParticipantActor::new(
@@ -517,6 +606,8 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             listener_executor,
             timer_driver,
             instance_handle_counter,
+            self.durability_stores.clone(),
+            self.local_participants.clone(),
         );
         let participant_handle = domain_participant_actor
             .domain_participant
@@ -534,6 +625,10 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
 
         let participant_actor =
             participant_actor_builder.build(domain_participant_actor, &backend_executor_handle);
+        if self.configuration.intraprocess_delivery() {
+            self.local_participants
+                .add(guid_prefix, participant_actor.address());
+        }
 
         //****** Spawn the participant actor and tasks **********//
 
@@ -580,6 +675,8 @@ impl Mail for DeleteParticipant {
 }
 impl MailHandler<DeleteParticipant> for DomainParticipantFactoryActor {
     fn handle(&mut self, message: DeleteParticipant) -> <DeleteParticipant as Mail>::Result {
+        self.local_participants
+            .remove(&Guid::from(<[u8; 16]>::from(message.handle)).prefix());
         self.domain_participant_list
             .remove(&message.handle)
             .ok_or(DdsError::PreconditionNotMet(
@@ -700,6 +797,36 @@ impl MailHandler<SetTransport> for DomainParticipantFactoryActor {
     }
 }
 
+pub struct SetTransientDurabilityStore {
+    pub store: Box<dyn DurabilityStore>,
+}
+impl Mail for SetTransientDurabilityStore {
+    type Result = ();
+}
+impl MailHandler<SetTransientDurabilityStore> for DomainParticipantFactoryActor {
+    fn handle(
+        &mut self,
+        message: SetTransientDurabilityStore,
+    ) -> <SetTransientDurabilityStore as Mail>::Result {
+        self.durability_stores.transient = Arc::from(message.store);
+    }
+}
+
+pub struct SetPersistentDurabilityStore {
+    pub store: Box<dyn DurabilityStore>,
+}
+impl Mail for SetPersistentDurabilityStore {
+    type Result = ();
+}
+impl MailHandler<SetPersistentDurabilityStore> for DomainParticipantFactoryActor {
+    fn handle(
+        &mut self,
+        message: SetPersistentDurabilityStore,
+    ) -> <SetPersistentDurabilityStore as Mail>::Result {
+        self.durability_stores.persistent = Some(Arc::from(message.store));
+    }
+}
+
 struct DcpsParticipantReaderHistoryCache {
     participant_address: ActorAddress<DomainParticipantActor>,
 }
@@ -725,6 +852,23 @@ impl HistoryCache for DcpsParticipantReaderHistoryCache {
     }
 }
 
+struct DcpsParticipantMessageReaderHistoryCache {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+
+impl HistoryCache for DcpsParticipantMessageReaderHistoryCache {
+    fn add_change(&mut self, cache_change: CacheChange) {
+        self.participant_address
+            .send_actor_mail(message_service::AddBuiltinParticipantMessageCacheChange {
+                cache_change,
+                participant_address: self.participant_address.clone(),
+            })
+            .ok();
+    }
+
+    fn remove_change(&mut self, _sequence_number: i64) {}
+}
+
 struct DcpsTopicsReaderHistoryCache {
     pub participant_address: ActorAddress<DomainParticipantActor>,
 }
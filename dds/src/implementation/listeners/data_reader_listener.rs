@@ -2,8 +2,8 @@ use crate::{
     dds_async::data_reader::DataReaderAsync,
     implementation::any_data_reader_listener::AnyDataReaderListener,
     infrastructure::status::{
-        RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-        SubscriptionMatchedStatus,
+        LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+        SampleRejectedStatus, SubscriptionMatchedStatus,
     },
     runtime::actor::{Mail, MailHandler},
 };
@@ -96,3 +96,20 @@ impl MailHandler<TriggerRequestedIncompatibleQos> for DataReaderListenerActor {
             .trigger_on_requested_incompatible_qos(message.the_reader, message.status);
     }
 }
+
+pub struct TriggerLivelinessChanged {
+    pub the_reader: DataReaderAsync<()>,
+    pub status: LivelinessChangedStatus,
+}
+impl Mail for TriggerLivelinessChanged {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessChanged> for DataReaderListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessChanged,
+    ) -> <TriggerLivelinessChanged as Mail>::Result {
+        self.listener
+            .trigger_on_liveliness_changed(message.the_reader, message.status);
+    }
+}
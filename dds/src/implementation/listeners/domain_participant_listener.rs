@@ -4,9 +4,9 @@ use crate::{
         domain_participant_listener::DomainParticipantListenerAsync,
     },
     infrastructure::status::{
-        OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus, PublicationMatchedStatus,
-        RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-        SubscriptionMatchedStatus,
+        LivelinessChangedStatus, LivelinessLostStatus, OfferedDeadlineMissedStatus,
+        OfferedIncompatibleQosStatus, PublicationMatchedStatus, RequestedDeadlineMissedStatus,
+        RequestedIncompatibleQosStatus, SampleRejectedStatus, SubscriptionMatchedStatus,
     },
     runtime::{
         actor::{Mail, MailHandler},
@@ -156,3 +156,41 @@ impl MailHandler<TriggerOfferedDeadlineMissed> for DomainParticipantListenerActo
         )
     }
 }
+
+pub struct TriggerLivelinessLost {
+    pub the_writer: DataWriterAsync<()>,
+    pub status: LivelinessLostStatus,
+}
+impl Mail for TriggerLivelinessLost {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessLost> for DomainParticipantListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessLost,
+    ) -> <TriggerLivelinessLost as Mail>::Result {
+        block_on(
+            self.listener
+                .on_liveliness_lost(message.the_writer, message.status),
+        );
+    }
+}
+
+pub struct TriggerLivelinessChanged {
+    pub the_reader: DataReaderAsync<()>,
+    pub status: LivelinessChangedStatus,
+}
+impl Mail for TriggerLivelinessChanged {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessChanged> for DomainParticipantListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessChanged,
+    ) -> <TriggerLivelinessChanged as Mail>::Result {
+        block_on(
+            self.listener
+                .on_liveliness_changed(message.the_reader, message.status),
+        );
+    }
+}
@@ -4,8 +4,8 @@ use crate::{
         subscriber_listener::SubscriberListenerAsync,
     },
     infrastructure::status::{
-        RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-        SubscriptionMatchedStatus,
+        LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+        SampleRejectedStatus, SubscriptionMatchedStatus,
     },
     runtime::{
         actor::{Mail, MailHandler},
@@ -112,3 +112,22 @@ impl MailHandler<TriggerRequestedIncompatibleQos> for SubscriberListenerActor {
         );
     }
 }
+
+pub struct TriggerLivelinessChanged {
+    pub the_reader: DataReaderAsync<()>,
+    pub status: LivelinessChangedStatus,
+}
+impl Mail for TriggerLivelinessChanged {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessChanged> for SubscriberListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessChanged,
+    ) -> <TriggerLivelinessChanged as Mail>::Result {
+        block_on(
+            self.listener
+                .on_liveliness_changed(message.the_reader.change_foo_type(), message.status),
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod any_data_reader_listener;
 pub mod any_data_writer_listener;
+pub mod content_filter;
 pub mod data_representation_builtin_endpoints;
 pub mod data_representation_inline_qos;
 pub mod domain_participant_backend;
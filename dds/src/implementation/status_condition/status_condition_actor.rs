@@ -1,3 +1,5 @@
+use std::task::Waker;
+
 use crate::{
     infrastructure::status::StatusKind,
     runtime::actor::{Mail, MailHandler},
@@ -7,6 +9,7 @@ use crate::{
 pub struct StatusConditionActor {
     enabled_statuses: Vec<StatusKind>,
     status_changes: Vec<StatusKind>,
+    waiter_wakers: Vec<Waker>,
 }
 
 impl Default for StatusConditionActor {
@@ -28,6 +31,7 @@ impl Default for StatusConditionActor {
                 StatusKind::SubscriptionMatched,
             ],
             status_changes: Vec::new(),
+            waiter_wakers: Vec::new(),
         }
     }
 }
@@ -35,10 +39,26 @@ impl Default for StatusConditionActor {
 impl StatusConditionActor {
     pub fn add_communication_state(&mut self, state: StatusKind) {
         self.status_changes.push(state);
+        self.wake_waiters();
     }
 
     pub fn remove_communication_state(&mut self, state: StatusKind) {
         self.status_changes.retain(|x| x != &state);
+        self.wake_waiters();
+    }
+
+    /// Registers the waker of a task waiting on this condition. The waker is woken,
+    /// and forgotten, on the next change of the communication states.
+    pub fn register_waiter(&mut self, waker: Waker) {
+        if !self.waiter_wakers.iter().any(|w| w.will_wake(&waker)) {
+            self.waiter_wakers.push(waker);
+        }
+    }
+
+    fn wake_waiters(&mut self) {
+        for waker in self.waiter_wakers.drain(..) {
+            waker.wake();
+        }
     }
 
     pub fn get_enabled_statuses(&self) -> Vec<StatusKind> {
@@ -100,6 +120,21 @@ impl MailHandler<GetStatusConditionTriggerValue> for StatusConditionActor {
     }
 }
 
+pub struct RegisterStatusConditionWaiter {
+    pub waker: Waker,
+}
+impl Mail for RegisterStatusConditionWaiter {
+    type Result = ();
+}
+impl MailHandler<RegisterStatusConditionWaiter> for StatusConditionActor {
+    fn handle(
+        &mut self,
+        message: RegisterStatusConditionWaiter,
+    ) -> <RegisterStatusConditionWaiter as Mail>::Result {
+        self.register_waiter(message.waker);
+    }
+}
+
 pub struct AddCommunicationState {
     pub state: StatusKind,
 }
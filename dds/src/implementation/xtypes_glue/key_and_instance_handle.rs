@@ -216,7 +216,7 @@ where
     Ok(())
 }
 
-fn go_to_pid_le(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
+pub fn go_to_pid_le(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
     const PID_SENTINEL: u16 = 1;
     loop {
         let current_pid = u16::from_le_bytes([reader[0], reader[1]]);
@@ -231,7 +231,7 @@ fn go_to_pid_le(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
     }
 }
 
-fn go_to_pid_be(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
+pub fn go_to_pid_be(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
     const PID_SENTINEL: u16 = 1;
     loop {
         let current_pid = u16::from_be_bytes([reader[0], reader[1]]);
@@ -309,15 +309,15 @@ fn push_to_key_parameter_list_be(
     Ok(())
 }
 
-type RepresentationIdentifier = [u8; 2];
-const CDR_BE: RepresentationIdentifier = [0x00, 0x00];
-const CDR_LE: RepresentationIdentifier = [0x00, 0x01];
-const CDR2_BE: RepresentationIdentifier = [0x00, 0x06];
-const CDR2_LE: RepresentationIdentifier = [0x00, 0x07];
+pub type RepresentationIdentifier = [u8; 2];
+pub const CDR_BE: RepresentationIdentifier = [0x00, 0x00];
+pub const CDR_LE: RepresentationIdentifier = [0x00, 0x01];
+pub const CDR2_BE: RepresentationIdentifier = [0x00, 0x06];
+pub const CDR2_LE: RepresentationIdentifier = [0x00, 0x07];
 const _D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
 const _D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
-const PL_CDR_BE: RepresentationIdentifier = [0x00, 0x02];
-const PL_CDR_LE: RepresentationIdentifier = [0x00, 0x03];
+pub const PL_CDR_BE: RepresentationIdentifier = [0x00, 0x02];
+pub const PL_CDR_LE: RepresentationIdentifier = [0x00, 0x03];
 
 pub fn get_instance_handle_from_serialized_key(
     mut data: &[u8],
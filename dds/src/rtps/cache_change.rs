@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use crate::{
     implementation::data_representation_inline_qos::{
-        parameter_id_values::{PID_KEY_HASH, PID_STATUS_INFO},
+        parameter_id_values::{
+            PID_COHERENT_SET, PID_GROUP_COHERENT_SET, PID_GROUP_SEQ_NUM, PID_KEY_HASH,
+            PID_STATUS_INFO, PID_WRITER_GROUP_INFO,
+        },
         types::{
             StatusInfo, STATUS_INFO_DISPOSED, STATUS_INFO_DISPOSED_UNREGISTERED,
             STATUS_INFO_FILTERED, STATUS_INFO_UNREGISTERED,
@@ -20,15 +23,22 @@ use super::messages::{
     submessages::data::DataSubmessage,
 };
 
-impl CacheChange {
-    pub fn as_data_submessage(&self, reader_id: EntityId, writer_id: EntityId) -> DataSubmessage {
-        let (data_flag, key_flag) = match self.kind {
-            ChangeKind::Alive | ChangeKind::AliveFiltered => (true, false),
-            ChangeKind::NotAliveDisposed
-            | ChangeKind::NotAliveUnregistered
-            | ChangeKind::NotAliveDisposedUnregistered => (false, true),
-        };
+fn sequence_number_to_bytes(sequence_number: i64) -> [u8; 8] {
+    let high = ((sequence_number >> 32) as i32).to_le_bytes();
+    let low = (sequence_number as u32).to_le_bytes();
+    [
+        high[0], high[1], high[2], high[3], low[0], low[1], low[2], low[3],
+    ]
+}
 
+fn sequence_number_from_bytes(bytes: &[u8]) -> Option<i64> {
+    let high = i32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?);
+    let low = u32::from_le_bytes(bytes.get(4..8)?.try_into().ok()?);
+    Some(((high as i64) << 32) + low as i64)
+}
+
+impl CacheChange {
+    pub fn inline_qos(&self) -> ParameterList {
         let mut parameters = Vec::with_capacity(2);
         match self.kind {
             ChangeKind::Alive | ChangeKind::AliveFiltered => (),
@@ -49,7 +59,41 @@ impl CacheChange {
         if let Some(i) = self.instance_handle {
             parameters.push(Parameter::new(PID_KEY_HASH, Arc::from(i)));
         }
-        let parameter_list = ParameterList::new(parameters);
+        if let Some(coherent_set) = self.coherent_set {
+            parameters.push(Parameter::new(
+                PID_COHERENT_SET,
+                Arc::from(sequence_number_to_bytes(coherent_set)),
+            ));
+        }
+        if let Some(group_coherent_set) = self.group_coherent_set {
+            parameters.push(Parameter::new(
+                PID_GROUP_COHERENT_SET,
+                Arc::from(sequence_number_to_bytes(group_coherent_set)),
+            ));
+        }
+        if let Some(writer_group_info) = self.writer_group_info {
+            parameters.push(Parameter::new(
+                PID_WRITER_GROUP_INFO,
+                Arc::from(writer_group_info),
+            ));
+        }
+        if let Some(group_sequence_number) = self.group_sequence_number {
+            parameters.push(Parameter::new(
+                PID_GROUP_SEQ_NUM,
+                Arc::from(sequence_number_to_bytes(group_sequence_number)),
+            ));
+        }
+        ParameterList::new(parameters)
+    }
+
+    pub fn as_data_submessage(&self, reader_id: EntityId, writer_id: EntityId) -> DataSubmessage {
+        let (data_flag, key_flag) = match self.kind {
+            // The end of a coherent set is signalled with a change without payload
+            ChangeKind::Alive | ChangeKind::AliveFiltered => (!self.data_value.is_empty(), false),
+            ChangeKind::NotAliveDisposed
+            | ChangeKind::NotAliveUnregistered
+            | ChangeKind::NotAliveDisposedUnregistered => (false, true),
+        };
 
         DataSubmessage::new(
             true,
@@ -59,7 +103,7 @@ impl CacheChange {
             reader_id,
             writer_id,
             self.sequence_number,
-            parameter_list,
+            self.inline_qos(),
             self.data_value.clone().into(),
         )
     }
@@ -117,6 +161,24 @@ impl CacheChange {
             None => None,
         };
 
+        let find_sequence_number = |parameter_id| {
+            data_submessage
+                .inline_qos()
+                .parameter()
+                .iter()
+                .find(|&x| x.parameter_id() == parameter_id)
+                .and_then(|p| sequence_number_from_bytes(p.value()))
+        };
+        let coherent_set = find_sequence_number(PID_COHERENT_SET);
+        let group_coherent_set = find_sequence_number(PID_GROUP_COHERENT_SET);
+        let group_sequence_number = find_sequence_number(PID_GROUP_SEQ_NUM);
+        let writer_group_info = data_submessage
+            .inline_qos()
+            .parameter()
+            .iter()
+            .find(|&x| x.parameter_id() == PID_WRITER_GROUP_INFO)
+            .and_then(|p| <[u8; 4]>::try_from(p.value()).ok());
+
         Ok(CacheChange {
             kind,
             writer_guid: Guid::new(source_guid_prefix, data_submessage.writer_id()),
@@ -124,6 +186,40 @@ impl CacheChange {
             instance_handle,
             sequence_number: data_submessage.writer_sn(),
             data_value: data_submessage.serialized_payload().clone().into(),
+            coherent_set,
+            group_coherent_set,
+            writer_group_info,
+            group_sequence_number,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::types::{ENTITYID_UNKNOWN, GUIDPREFIX_UNKNOWN, GUID_UNKNOWN};
+
+    #[test]
+    fn coherent_set_end_roundtrip() {
+        let cache_change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid: GUID_UNKNOWN,
+            sequence_number: 5,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: Arc::from([]),
+            coherent_set: Some(3),
+            group_coherent_set: Some(1 << 33),
+            writer_group_info: Some([1, 2, 3, 4]),
+            group_sequence_number: Some(7),
+        };
+
+        let data_submessage = cache_change.as_data_submessage(ENTITYID_UNKNOWN, ENTITYID_UNKNOWN);
+        let received_cache_change =
+            CacheChange::try_from_data_submessage(&data_submessage, GUIDPREFIX_UNKNOWN, None)
+                .unwrap();
+
+        assert!(received_cache_change.is_end_of_coherent_set());
+        assert_eq!(received_cache_change, cache_change);
+    }
+}
@@ -1,13 +1,33 @@
-use crate::transport::{
-    factory::TransportParticipantFactory, participant::TransportParticipant, types::GuidPrefix,
+use core::net::IpAddr;
+
+use crate::{
+    infrastructure::error::DdsResult,
+    transport::{
+        factory::TransportParticipantFactory, participant::TransportParticipant, types::GuidPrefix,
+    },
 };
 
-use super::transport::RtpsTransport;
+use super::{
+    error::RtpsResult,
+    flow_controller::{check_flow_controllers, RtpsFlowController},
+    transport::{
+        check_port_mapping, Ipv6MulticastScope, RtpsPortMapping, RtpsReliabilityTiming,
+        RtpsTransport, UdpIpVersion,
+    },
+};
 
 pub struct RtpsParticipantFactoryBuilder {
     interface_name: Option<String>,
     fragment_size: usize,
     udp_receive_buffer_size: Option<usize>,
+    ip_version: UdpIpVersion,
+    ipv6_multicast_scope: Ipv6MulticastScope,
+    use_multicast: bool,
+    initial_peers: Vec<IpAddr>,
+    initial_peers_max_participant_id: u8,
+    port_mapping: RtpsPortMapping,
+    reliability_timing: RtpsReliabilityTiming,
+    flow_controllers: Vec<RtpsFlowController>,
 }
 
 impl Default for RtpsParticipantFactoryBuilder {
@@ -23,6 +43,14 @@ impl RtpsParticipantFactoryBuilder {
             interface_name: None,
             fragment_size: 1344,
             udp_receive_buffer_size: None,
+            ip_version: UdpIpVersion::default(),
+            ipv6_multicast_scope: Ipv6MulticastScope::default(),
+            use_multicast: true,
+            initial_peers: Vec::new(),
+            initial_peers_max_participant_id: 9,
+            port_mapping: RtpsPortMapping::default(),
+            reliability_timing: RtpsReliabilityTiming::default(),
+            flow_controllers: Vec::new(),
         }
     }
 
@@ -44,6 +72,54 @@ impl RtpsParticipantFactoryBuilder {
         self
     }
 
+    /// Set the IP version of the UDP sockets used for discovery and user traffic
+    pub fn ip_version(mut self, ip_version: UdpIpVersion) -> Self {
+        self.ip_version = ip_version;
+        self
+    }
+
+    /// Set the scope of the IPv6 multicast group used for discovery. Only used when IPv6 is enabled
+    pub fn ipv6_multicast_scope(mut self, ipv6_multicast_scope: Ipv6MulticastScope) -> Self {
+        self.ipv6_multicast_scope = ipv6_multicast_scope;
+        self
+    }
+
+    /// Enable or disable the use of multicast. Without multicast the participants are only discovered through the initial peers
+    pub fn use_multicast(mut self, use_multicast: bool) -> Self {
+        self.use_multicast = use_multicast;
+        self
+    }
+
+    /// Set the addresses of the hosts to which the participant announcements are sent by unicast
+    pub fn initial_peers(mut self, initial_peers: Vec<IpAddr>) -> Self {
+        self.initial_peers = initial_peers;
+        self
+    }
+
+    /// Set the highest participant id whose discovery port is probed on each of the initial peers
+    pub fn initial_peers_max_participant_id(mut self, max_participant_id: u8) -> Self {
+        self.initial_peers_max_participant_id = max_participant_id;
+        self
+    }
+
+    /// Set the parameters used to compute the well-known ports of the participants
+    pub fn port_mapping(mut self, port_mapping: RtpsPortMapping) -> Self {
+        self.port_mapping = port_mapping;
+        self
+    }
+
+    /// Set the heartbeat period and the response delays of the reliable protocol
+    pub fn reliability_timing(mut self, reliability_timing: RtpsReliabilityTiming) -> Self {
+        self.reliability_timing = reliability_timing;
+        self
+    }
+
+    /// Set the flow controllers to which the data writers can be attached with the [`PublishModeQosPolicy`](crate::infrastructure::qos_policy::PublishModeQosPolicy) to limit their bandwidth
+    pub fn flow_controllers(mut self, flow_controllers: Vec<RtpsFlowController>) -> Self {
+        self.flow_controllers = flow_controllers;
+        self
+    }
+
     /// Build a new participant factory
     pub fn build(self) -> Result<RtpsParticipantFactory, String> {
         let fragment_size_range = 8..=65000;
@@ -52,11 +128,25 @@ impl RtpsParticipantFactoryBuilder {
                 "Interface size out of range. Value must be between in {:?}",
                 fragment_size_range
             ))
+        } else if let Err(e) = check_port_mapping(&self.port_mapping) {
+            Err(e)
+        } else if self.reliability_timing.heartbeat_period.is_zero() {
+            Err("Heartbeat period must be greater than 0".to_string())
+        } else if let Err(e) = check_flow_controllers(&self.flow_controllers) {
+            Err(e)
         } else {
             Ok(RtpsParticipantFactory {
                 interface_name: self.interface_name,
                 fragment_size: self.fragment_size,
                 udp_receive_buffer_size: self.udp_receive_buffer_size,
+                ip_version: self.ip_version,
+                ipv6_multicast_scope: self.ipv6_multicast_scope,
+                use_multicast: self.use_multicast,
+                initial_peers: self.initial_peers,
+                initial_peers_max_participant_id: self.initial_peers_max_participant_id,
+                port_mapping: self.port_mapping,
+                reliability_timing: self.reliability_timing,
+                flow_controllers: self.flow_controllers,
             })
         }
     }
@@ -66,6 +156,14 @@ pub struct RtpsParticipantFactory {
     interface_name: Option<String>,
     fragment_size: usize,
     udp_receive_buffer_size: Option<usize>,
+    ip_version: UdpIpVersion,
+    ipv6_multicast_scope: Ipv6MulticastScope,
+    use_multicast: bool,
+    initial_peers: Vec<IpAddr>,
+    initial_peers_max_participant_id: u8,
+    port_mapping: RtpsPortMapping,
+    reliability_timing: RtpsReliabilityTiming,
+    flow_controllers: Vec<RtpsFlowController>,
 }
 
 impl Default for RtpsParticipantFactory {
@@ -81,16 +179,32 @@ impl TransportParticipantFactory for RtpsParticipantFactory {
         &self,
         guid_prefix: GuidPrefix,
         domain_id: i32,
-    ) -> Box<dyn TransportParticipant> {
-        Box::new(
-            RtpsTransport::new(
-                guid_prefix,
-                domain_id,
-                &self.interface_name,
-                self.udp_receive_buffer_size,
-                self.fragment_size,
-            )
-            .unwrap(),
-        )
+    ) -> DdsResult<Box<dyn TransportParticipant>> {
+        Ok(Box::new(self.create_transport(guid_prefix, domain_id)?))
+    }
+}
+
+impl RtpsParticipantFactory {
+    pub(crate) fn create_transport(
+        &self,
+        guid_prefix: GuidPrefix,
+        domain_id: i32,
+    ) -> RtpsResult<RtpsTransport> {
+        let transport = RtpsTransport::new(
+            guid_prefix,
+            domain_id,
+            &self.interface_name,
+            self.udp_receive_buffer_size,
+            self.fragment_size,
+            self.ip_version,
+            self.ipv6_multicast_scope,
+            self.use_multicast,
+            &self.initial_peers,
+            self.initial_peers_max_participant_id,
+            self.port_mapping,
+            self.reliability_timing,
+        )?;
+        transport.add_flow_controllers(&self.flow_controllers);
+        Ok(transport)
     }
 }
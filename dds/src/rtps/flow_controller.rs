@@ -0,0 +1,556 @@
+use core::{cmp::Reverse, time::Duration};
+use std::{collections::BinaryHeap, time::Instant};
+
+use super::messages::types::FragmentNumber;
+use crate::transport::types::{EntityId, Locator, ReliabilityKind, SequenceNumber};
+
+// Size of the RTPS header which is added to the submessages of every message
+const RTPS_HEADER_SIZE: usize = 20;
+
+// Maximum number of messages waiting for the tokens of a flow controller. Beyond it the
+// oldest best-effort message is dropped, or the oldest message if all are reliable since
+// the readers request again the reliable changes they miss.
+const MAX_QUEUED_MESSAGES: usize = 1024;
+
+/// Order in which a flow controller releases the messages of the writers attached to it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FlowControllerSchedulingPolicy {
+    /// Release the messages in the order they were written, whatever the writer
+    #[default]
+    Fifo,
+    /// Release first the messages of the writers with the highest priority. Messages of
+    /// the same priority are released in the order they were written.
+    HighestPriorityFirst,
+}
+
+/// Token bucket limiting the bandwidth of a flow controller. Every period the bucket is
+/// refilled with the given number of tokens, up to its capacity, and each message takes
+/// as many tokens as needed to cover its size. The sustained bandwidth is
+/// `tokens_added_per_period * bytes_per_token` bytes per period while `max_tokens`
+/// bounds the size of the bursts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtpsTokenBucket {
+    /// Capacity of the bucket. The bucket starts full.
+    pub max_tokens: usize,
+    /// Number of tokens added to the bucket every period
+    pub tokens_added_per_period: usize,
+    /// Number of bytes which can be sent with a single token
+    pub bytes_per_token: usize,
+    /// Period at which the tokens are added to the bucket
+    pub period: Duration,
+}
+
+/// Named flow controller of a participant. The data and repair messages of the
+/// [`DataWriter`](crate::publication::data_writer::DataWriter)s attached to it through the
+/// [`PublishModeQosPolicy`](crate::infrastructure::qos_policy::PublishModeQosPolicy) are
+/// queued and released as its token bucket allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtpsFlowController {
+    /// Name by which the writers refer to the flow controller
+    pub name: String,
+    /// Order in which the queued messages are released
+    pub scheduling_policy: FlowControllerSchedulingPolicy,
+    /// Bandwidth limit of the flow controller
+    pub token_bucket: RtpsTokenBucket,
+}
+
+/// Check that the flow controllers have distinct names and token buckets which
+/// let messages through
+pub fn check_flow_controllers(flow_controllers: &[RtpsFlowController]) -> Result<(), String> {
+    for (index, flow_controller) in flow_controllers.iter().enumerate() {
+        let token_bucket = &flow_controller.token_bucket;
+        if flow_controllers[..index]
+            .iter()
+            .any(|f| f.name == flow_controller.name)
+        {
+            return Err(format!(
+                "Flow controller name {} is used more than once",
+                flow_controller.name
+            ));
+        } else if token_bucket.max_tokens == 0
+            || token_bucket.tokens_added_per_period == 0
+            || token_bucket.bytes_per_token == 0
+            || token_bucket.period.is_zero()
+        {
+            return Err(format!(
+                "Token bucket of flow controller {} must have a capacity, refill, token size and period greater than 0",
+                flow_controller.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+struct QueuedMessage {
+    // Priority of the writer. All the messages of a FIFO flow controller have the same.
+    priority: i32,
+    // Position of the message in the order in which they were queued
+    order: u64,
+    writer_id: EntityId,
+    // Sequence number of the change whose data is carried by the message, if any
+    sequence_number: Option<SequenceNumber>,
+    // Fragment of the change carried by the message, if it is fragmented
+    fragment_number: Option<FragmentNumber>,
+    reliability: ReliabilityKind,
+    destination_locator: Locator,
+    submessage_bytes: Vec<u8>,
+}
+
+impl QueuedMessage {
+    fn release_order(&self) -> (i32, Reverse<u64>) {
+        (self.priority, Reverse(self.order))
+    }
+
+    fn carries_same_data(
+        &self,
+        writer_id: EntityId,
+        sequence_number: SequenceNumber,
+        fragment_number: Option<FragmentNumber>,
+        destination_locator: Locator,
+    ) -> bool {
+        self.writer_id == writer_id
+            && self.sequence_number == Some(sequence_number)
+            && self.fragment_number == fragment_number
+            && self.destination_locator == destination_locator
+    }
+}
+
+impl PartialEq for QueuedMessage {
+    fn eq(&self, other: &Self) -> bool {
+        self.release_order() == other.release_order()
+    }
+}
+
+impl Eq for QueuedMessage {}
+
+impl PartialOrd for QueuedMessage {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedMessage {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.release_order().cmp(&other.release_order())
+    }
+}
+
+/// Queue of the messages waiting for the tokens of a flow controller. Only the messages
+/// of the changes still in the history of their writer stay queued: those of the changes
+/// the writer removes, e.g. replaced by a newer sample of a KeepLast history, are dropped
+/// with [`Self::remove_change`]. A change resent to a destination while it is still
+/// queued for it is queued only once.
+pub struct FlowControllerQueue {
+    scheduling_policy: FlowControllerSchedulingPolicy,
+    token_bucket: RtpsTokenBucket,
+    tokens: usize,
+    next_refill: Instant,
+    next_order: u64,
+    // Max-heap on the priority, then on the age of the messages
+    queue: BinaryHeap<QueuedMessage>,
+}
+
+impl FlowControllerQueue {
+    pub fn new(flow_controller: &RtpsFlowController, now: Instant) -> Self {
+        Self {
+            scheduling_policy: flow_controller.scheduling_policy,
+            token_bucket: flow_controller.token_bucket,
+            tokens: flow_controller.token_bucket.max_tokens,
+            next_refill: now + flow_controller.token_bucket.period,
+            next_order: 0,
+            queue: BinaryHeap::new(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn enqueue(
+        &mut self,
+        writer_id: EntityId,
+        sequence_number: Option<SequenceNumber>,
+        fragment_number: Option<FragmentNumber>,
+        reliability: ReliabilityKind,
+        priority: i32,
+        submessage_bytes: Vec<u8>,
+        destination_locator: Locator,
+    ) {
+        if let Some(sequence_number) = sequence_number {
+            if self.queue.iter().any(|m| {
+                m.carries_same_data(
+                    writer_id,
+                    sequence_number,
+                    fragment_number,
+                    destination_locator,
+                )
+            }) {
+                return;
+            }
+        }
+        let priority = match self.scheduling_policy {
+            FlowControllerSchedulingPolicy::Fifo => 0,
+            FlowControllerSchedulingPolicy::HighestPriorityFirst => priority,
+        };
+        self.queue.push(QueuedMessage {
+            priority,
+            order: self.next_order,
+            writer_id,
+            sequence_number,
+            fragment_number,
+            reliability,
+            destination_locator,
+            submessage_bytes,
+        });
+        self.next_order += 1;
+        if self.queue.len() > MAX_QUEUED_MESSAGES {
+            self.drop_oldest_message();
+        }
+    }
+
+    fn drop_oldest_message(&mut self) {
+        let mut messages = core::mem::take(&mut self.queue).into_vec();
+        let oldest_best_effort = messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.reliability == ReliabilityKind::BestEffort)
+            .min_by_key(|(_, m)| m.order)
+            .map(|(index, _)| index);
+        let oldest = oldest_best_effort.or_else(|| {
+            messages
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, m)| m.order)
+                .map(|(index, _)| index)
+        });
+        if let Some(index) = oldest {
+            messages.swap_remove(index);
+        }
+        self.queue = BinaryHeap::from(messages);
+    }
+
+    /// Drop the queued messages carrying the data of a change which is no longer in
+    /// the history of the writer
+    pub fn remove_change(&mut self, writer_id: EntityId, sequence_number: SequenceNumber) {
+        self.queue
+            .retain(|m| m.writer_id != writer_id || m.sequence_number != Some(sequence_number));
+    }
+
+    /// Drop all the queued messages of the writer
+    pub fn remove_writer(&mut self, writer_id: EntityId) {
+        self.queue.retain(|m| m.writer_id != writer_id);
+    }
+
+    /// Refill the bucket with the tokens of the periods elapsed until now and return
+    /// the time of the next refill
+    pub fn refill(&mut self, now: Instant) -> Instant {
+        if now >= self.next_refill {
+            let period = self.token_bucket.period;
+            let elapsed_periods =
+                u32::try_from((now - self.next_refill).as_nanos() / period.as_nanos() + 1)
+                    .unwrap_or(u32::MAX);
+            self.tokens = self
+                .tokens
+                .saturating_add(
+                    self.token_bucket
+                        .tokens_added_per_period
+                        .saturating_mul(elapsed_periods as usize),
+                )
+                .min(self.token_bucket.max_tokens);
+            self.next_refill += period * elapsed_periods;
+        }
+        self.next_refill
+    }
+
+    /// Take the next message to send if there are enough tokens left for it. Messages
+    /// larger than the bucket are released once the bucket is full.
+    pub fn release_next(&mut self) -> Option<(Locator, Vec<u8>)> {
+        let message = self.queue.peek()?;
+        let cost = (RTPS_HEADER_SIZE + message.submessage_bytes.len())
+            .div_ceil(self.token_bucket.bytes_per_token)
+            .min(self.token_bucket.max_tokens);
+        if cost > self.tokens {
+            return None;
+        }
+        self.tokens -= cost;
+        let message = self.queue.pop()?;
+        Some((message.destination_locator, message.submessage_bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::types::LOCATOR_KIND_UDP_V4;
+
+    const LOCATOR: Locator = Locator::new(LOCATOR_KIND_UDP_V4, 7400, [0; 16]);
+    const WRITER_ID: EntityId = EntityId::new([0, 0, 1], 2);
+
+    fn flow_controller(
+        scheduling_policy: FlowControllerSchedulingPolicy,
+        max_tokens: usize,
+        tokens_added_per_period: usize,
+    ) -> RtpsFlowController {
+        RtpsFlowController {
+            name: "flow_controller".to_string(),
+            scheduling_policy,
+            token_bucket: RtpsTokenBucket {
+                max_tokens,
+                tokens_added_per_period,
+                bytes_per_token: 100,
+                period: Duration::from_millis(10),
+            },
+        }
+    }
+
+    fn new_queue(
+        scheduling_policy: FlowControllerSchedulingPolicy,
+        max_tokens: usize,
+        tokens_added_per_period: usize,
+    ) -> FlowControllerQueue {
+        FlowControllerQueue::new(
+            &flow_controller(scheduling_policy, max_tokens, tokens_added_per_period),
+            Instant::now(),
+        )
+    }
+
+    fn enqueue(
+        queue: &mut FlowControllerQueue,
+        writer_id: EntityId,
+        sequence_number: Option<SequenceNumber>,
+        priority: i32,
+        submessage_bytes: Vec<u8>,
+    ) {
+        queue.enqueue(
+            writer_id,
+            sequence_number,
+            None,
+            ReliabilityKind::Reliable,
+            priority,
+            submessage_bytes,
+            LOCATOR,
+        );
+    }
+
+    fn add_period_tokens(queue: &mut FlowControllerQueue) {
+        queue.refill(queue.next_refill);
+    }
+
+    fn release_all(queue: &mut FlowControllerQueue) -> Vec<Vec<u8>> {
+        core::iter::from_fn(|| queue.release_next())
+            .map(|(_, bytes)| bytes)
+            .collect()
+    }
+
+    #[test]
+    fn messages_are_released_as_tokens_are_added() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 2, 1);
+        // Each message takes one token with its header
+        for i in 0..4 {
+            enqueue(&mut queue, WRITER_ID, None, 0, vec![i; 80]);
+        }
+
+        assert_eq!(release_all(&mut queue), vec![vec![0; 80], vec![1; 80]]);
+        add_period_tokens(&mut queue);
+        assert_eq!(release_all(&mut queue), vec![vec![2; 80]]);
+        add_period_tokens(&mut queue);
+        assert_eq!(release_all(&mut queue), vec![vec![3; 80]]);
+    }
+
+    #[test]
+    fn tokens_do_not_exceed_the_bucket_capacity() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 2, 1);
+        for _ in 0..5 {
+            add_period_tokens(&mut queue);
+        }
+        for i in 0..3 {
+            enqueue(&mut queue, WRITER_ID, None, 0, vec![i; 80]);
+        }
+
+        assert_eq!(release_all(&mut queue).len(), 2);
+    }
+
+    #[test]
+    fn messages_larger_than_the_bucket_are_released_when_it_is_full() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 2, 1);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![0; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![1; 1000]);
+
+        assert_eq!(release_all(&mut queue), vec![vec![0; 80]]);
+        add_period_tokens(&mut queue);
+        assert_eq!(release_all(&mut queue), vec![vec![1; 1000]]);
+    }
+
+    #[test]
+    fn highest_priority_messages_are_released_first() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::HighestPriorityFirst, 1, 1);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![0; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 5, vec![1; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 5, vec![2; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![3; 80]);
+
+        let mut released = Vec::new();
+        for _ in 0..4 {
+            released.extend(release_all(&mut queue));
+            add_period_tokens(&mut queue);
+        }
+        assert_eq!(
+            released,
+            vec![vec![1; 80], vec![2; 80], vec![0; 80], vec![3; 80]]
+        );
+    }
+
+    #[test]
+    fn fifo_messages_are_released_in_order_whatever_the_priority() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 3, 1);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![0; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 5, vec![1; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![2; 80]);
+
+        assert_eq!(
+            release_all(&mut queue),
+            vec![vec![0; 80], vec![1; 80], vec![2; 80]]
+        );
+    }
+
+    #[test]
+    fn messages_of_removed_changes_are_dropped() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 4, 1);
+        let other_writer_id = EntityId::new([0, 0, 2], 2);
+        enqueue(&mut queue, WRITER_ID, Some(1), 0, vec![0; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![1; 80]);
+        enqueue(&mut queue, other_writer_id, Some(1), 0, vec![2; 80]);
+        enqueue(&mut queue, WRITER_ID, Some(2), 0, vec![3; 80]);
+
+        queue.remove_change(WRITER_ID, 1);
+
+        assert_eq!(
+            release_all(&mut queue),
+            vec![vec![1; 80], vec![2; 80], vec![3; 80]]
+        );
+    }
+
+    #[test]
+    fn messages_of_removed_writer_are_dropped() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 4, 1);
+        let other_writer_id = EntityId::new([0, 0, 2], 2);
+        enqueue(&mut queue, WRITER_ID, Some(1), 0, vec![0; 80]);
+        enqueue(&mut queue, other_writer_id, Some(1), 0, vec![1; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![2; 80]);
+
+        queue.remove_writer(WRITER_ID);
+
+        assert_eq!(release_all(&mut queue), vec![vec![1; 80]]);
+    }
+
+    #[test]
+    fn resent_changes_are_queued_once_per_destination() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 8, 1);
+        let other_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7410, [0; 16]);
+        for fragment_number in [Some(1), Some(2), Some(1)] {
+            queue.enqueue(
+                WRITER_ID,
+                Some(1),
+                fragment_number,
+                ReliabilityKind::Reliable,
+                0,
+                vec![fragment_number.unwrap() as u8; 80],
+                LOCATOR,
+            );
+        }
+        queue.enqueue(
+            WRITER_ID,
+            Some(1),
+            Some(1),
+            ReliabilityKind::Reliable,
+            0,
+            vec![3; 80],
+            other_locator,
+        );
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![4; 80]);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![4; 80]);
+
+        assert_eq!(
+            release_all(&mut queue),
+            vec![
+                vec![1; 80],
+                vec![2; 80],
+                vec![3; 80],
+                vec![4; 80],
+                vec![4; 80]
+            ]
+        );
+    }
+
+    #[test]
+    fn oldest_best_effort_messages_are_dropped_from_a_full_queue() {
+        let mut queue = new_queue(FlowControllerSchedulingPolicy::Fifo, 1, 1);
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![0; 80]);
+        queue.enqueue(
+            WRITER_ID,
+            None,
+            None,
+            ReliabilityKind::BestEffort,
+            0,
+            vec![1; 80],
+            LOCATOR,
+        );
+        for _ in 2..MAX_QUEUED_MESSAGES {
+            enqueue(&mut queue, WRITER_ID, None, 0, vec![2; 80]);
+        }
+        queue.enqueue(
+            WRITER_ID,
+            None,
+            None,
+            ReliabilityKind::BestEffort,
+            0,
+            vec![3; 80],
+            LOCATOR,
+        );
+        enqueue(&mut queue, WRITER_ID, None, 0, vec![4; 80]);
+
+        let mut released = Vec::new();
+        while !queue.queue.is_empty() {
+            released.extend(release_all(&mut queue));
+            add_period_tokens(&mut queue);
+        }
+        assert_eq!(released.len(), MAX_QUEUED_MESSAGES);
+        assert_eq!(released[0], vec![0; 80]);
+        assert_eq!(released[1], vec![2; 80]);
+        assert_eq!(released[MAX_QUEUED_MESSAGES - 1], vec![4; 80]);
+        assert!(!released.contains(&vec![1; 80]));
+        assert!(!released.contains(&vec![3; 80]));
+    }
+
+    #[test]
+    fn tokens_of_all_the_elapsed_periods_are_added_at_once() {
+        let start = Instant::now();
+        let mut queue = FlowControllerQueue::new(
+            &flow_controller(FlowControllerSchedulingPolicy::Fifo, 4, 1),
+            start,
+        );
+        for i in 0..8 {
+            enqueue(&mut queue, WRITER_ID, None, 0, vec![i; 80]);
+        }
+        assert_eq!(release_all(&mut queue).len(), 4);
+
+        assert_eq!(
+            queue.refill(start + Duration::from_millis(5)),
+            start + Duration::from_millis(10)
+        );
+        assert_eq!(release_all(&mut queue).len(), 0);
+        assert_eq!(
+            queue.refill(start + Duration::from_millis(35)),
+            start + Duration::from_millis(40)
+        );
+        assert_eq!(release_all(&mut queue).len(), 3);
+    }
+
+    #[test]
+    fn flow_controllers_must_have_unique_names_and_valid_buckets() {
+        let valid = flow_controller(FlowControllerSchedulingPolicy::Fifo, 2, 1);
+        assert!(check_flow_controllers(core::slice::from_ref(&valid)).is_ok());
+        assert!(check_flow_controllers(&[valid.clone(), valid.clone()]).is_err());
+
+        let mut empty_bucket = valid;
+        empty_bucket.token_bucket.tokens_added_per_period = 0;
+        assert!(check_flow_controllers(&[empty_bucket]).is_err());
+    }
+}
@@ -0,0 +1,504 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    sync::{
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    infrastructure::error::DdsResult,
+    runtime::actor::ActorAddress,
+    transport::{
+        factory::TransportParticipantFactory,
+        participant::TransportParticipant,
+        types::{GuidPrefix, Locator, Long, GUIDPREFIX_UNKNOWN},
+    },
+};
+
+use super::{
+    flow_controller::{check_flow_controllers, RtpsFlowController},
+    messages::overall_structure::RtpsMessageRead,
+    participant::{self, RtpsParticipant},
+    transport::{RtpsReliabilityTiming, RtpsTransport},
+};
+
+/// Vendor specific locator kind of the in-memory transport. The address holds the GUID
+/// prefix of the participant, or [`GUIDPREFIX_UNKNOWN`] to reach all the participants of
+/// the network, and the port tells whether the message is metatraffic or user-defined
+/// traffic.
+pub const LOCATOR_KIND_IN_MEMORY: Long = 0x0100_0002;
+
+/// Port of the in-memory locators of the metatraffic
+pub const METATRAFFIC_PORT: u32 = 0;
+
+/// Port of the in-memory locators of the user-defined traffic
+pub const USER_DEFINED_PORT: u32 = 1;
+
+/// Builder for the [`InMemoryParticipantFactory`]
+pub struct InMemoryParticipantFactoryBuilder {
+    fragment_size: usize,
+    faults: InMemoryFaults,
+    seed: u64,
+    reliability_timing: RtpsReliabilityTiming,
+    flow_controllers: Vec<RtpsFlowController>,
+}
+
+impl Default for InMemoryParticipantFactoryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryParticipantFactoryBuilder {
+    /// Construct an in-memory transport factory builder with all the default options. By default no message is lost, duplicated, reordered or delayed.
+    pub fn new() -> Self {
+        Self {
+            fragment_size: 1344,
+            faults: InMemoryFaults::default(),
+            seed: 0,
+            reliability_timing: RtpsReliabilityTiming::default(),
+            flow_controllers: Vec::new(),
+        }
+    }
+
+    /// Set the maximum size for the data fragments. Types with serialized data above this size will be transmitted as fragments.
+    pub fn fragment_size(mut self, fragment_size: usize) -> Self {
+        self.fragment_size = fragment_size;
+        self
+    }
+
+    /// Set the probability, between 0 and 1, that a message is lost
+    pub fn loss_probability(mut self, loss_probability: f64) -> Self {
+        self.faults.loss_probability = loss_probability;
+        self
+    }
+
+    /// Set the probability, between 0 and 1, that a message is delivered twice
+    pub fn duplication_probability(mut self, duplication_probability: f64) -> Self {
+        self.faults.duplication_probability = duplication_probability;
+        self
+    }
+
+    /// Set the probability, between 0 and 1, that a message is held back for the reordering delay so that the messages sent after it overtake it
+    pub fn reordering_probability(mut self, reordering_probability: f64) -> Self {
+        self.faults.reordering_probability = reordering_probability;
+        self
+    }
+
+    /// Set the additional delay of the messages which are held back to be reordered
+    pub fn reordering_delay(mut self, reordering_delay: Duration) -> Self {
+        self.faults.reordering_delay = reordering_delay;
+        self
+    }
+
+    /// Set the delay after which every message is delivered
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.faults.delay = delay;
+        self
+    }
+
+    /// Set the seed of the random generator deciding which messages are lost, duplicated or reordered
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the heartbeat period and the response delays of the reliable protocol
+    pub fn reliability_timing(mut self, reliability_timing: RtpsReliabilityTiming) -> Self {
+        self.reliability_timing = reliability_timing;
+        self
+    }
+
+    /// Set the flow controllers to which the data writers can be attached with the [`PublishModeQosPolicy`](crate::infrastructure::qos_policy::PublishModeQosPolicy) to limit their bandwidth
+    pub fn flow_controllers(mut self, flow_controllers: Vec<RtpsFlowController>) -> Self {
+        self.flow_controllers = flow_controllers;
+        self
+    }
+
+    /// Build a new in-memory participant factory
+    pub fn build(self) -> Result<InMemoryParticipantFactory, String> {
+        let fragment_size_range = 8..=65000;
+        let probability_range = 0.0..=1.0;
+        if !fragment_size_range.contains(&self.fragment_size) {
+            Err(format!(
+                "Interface size out of range. Value must be between in {:?}",
+                fragment_size_range
+            ))
+        } else if !probability_range.contains(&self.faults.loss_probability)
+            || !probability_range.contains(&self.faults.duplication_probability)
+            || !probability_range.contains(&self.faults.reordering_probability)
+        {
+            Err(format!(
+                "Probability out of range. Value must be in {:?}",
+                probability_range
+            ))
+        } else if self.reliability_timing.heartbeat_period.is_zero() {
+            Err("Heartbeat period must be greater than 0".to_string())
+        } else if let Err(e) = check_flow_controllers(&self.flow_controllers) {
+            Err(e)
+        } else {
+            Ok(InMemoryParticipantFactory {
+                fragment_size: self.fragment_size,
+                network: InMemoryNetwork::new(self.faults, self.seed),
+                reliability_timing: self.reliability_timing,
+                flow_controllers: self.flow_controllers,
+            })
+        }
+    }
+}
+
+/// Transport factory whose participants exchange all the traffic through in-memory
+/// channels. All the participants created by the same factory are connected to each
+/// other as if they were on a network with multicast. The network can lose, duplicate,
+/// reorder and delay the messages to test the behavior of the RTPS protocol.
+pub struct InMemoryParticipantFactory {
+    fragment_size: usize,
+    network: InMemoryNetwork,
+    reliability_timing: RtpsReliabilityTiming,
+    flow_controllers: Vec<RtpsFlowController>,
+}
+
+impl Default for InMemoryParticipantFactory {
+    fn default() -> Self {
+        InMemoryParticipantFactoryBuilder::new()
+            .build()
+            .expect("Default configuration should work")
+    }
+}
+
+impl TransportParticipantFactory for InMemoryParticipantFactory {
+    fn create_participant(
+        &self,
+        guid_prefix: GuidPrefix,
+        _domain_id: i32,
+    ) -> DdsResult<Box<dyn TransportParticipant>> {
+        let transport = RtpsTransport::new_in_memory(
+            guid_prefix,
+            self.fragment_size,
+            &self.network,
+            self.reliability_timing,
+        );
+        transport.add_flow_controllers(&self.flow_controllers);
+        Ok(Box::new(transport))
+    }
+}
+
+/// In-memory locator of the participant with the given GUID prefix
+pub fn in_memory_locator(guid_prefix: GuidPrefix, port: u32) -> Locator {
+    let mut address = [0; 16];
+    address[4..].copy_from_slice(&guid_prefix);
+    Locator::new(LOCATOR_KIND_IN_MEMORY, port, address)
+}
+
+#[derive(Clone, Copy, Default)]
+struct InMemoryFaults {
+    loss_probability: f64,
+    duplication_probability: f64,
+    reordering_probability: f64,
+    reordering_delay: Duration,
+    delay: Duration,
+}
+
+// SplitMix64 generator so that the faults are reproducible from the seed
+struct FaultGenerator(u64);
+
+impl FaultGenerator {
+    fn occurs(&mut self, probability: f64) -> bool {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        ((z >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
+
+/// Message in transit to a participant of an in-memory network
+pub struct InMemoryMessage {
+    delivery_time: Instant,
+    port: u32,
+    bytes: Arc<[u8]>,
+}
+
+struct InMemoryNetworkInner {
+    participant_list: Vec<(GuidPrefix, Sender<InMemoryMessage>)>,
+    faults: InMemoryFaults,
+    fault_generator: FaultGenerator,
+}
+
+/// Network connecting the participants of an [`InMemoryParticipantFactory`]
+#[derive(Clone)]
+pub struct InMemoryNetwork(Arc<Mutex<InMemoryNetworkInner>>);
+
+impl InMemoryNetwork {
+    fn new(faults: InMemoryFaults, seed: u64) -> Self {
+        Self(Arc::new(Mutex::new(InMemoryNetworkInner {
+            participant_list: Vec::new(),
+            faults,
+            fault_generator: FaultGenerator(seed),
+        })))
+    }
+
+    /// Connect a participant to the network. The messages sent to the participant are
+    /// delivered on the returned receiver.
+    pub fn add_participant(
+        &self,
+        guid_prefix: GuidPrefix,
+    ) -> (InMemorySender, Receiver<InMemoryMessage>) {
+        let (sender, receiver) = channel();
+        self.0
+            .lock()
+            .expect("Network lock poisoned")
+            .participant_list
+            .push((guid_prefix, sender));
+        let in_memory_sender = InMemorySender {
+            guid_prefix,
+            network: self.clone(),
+        };
+        (in_memory_sender, receiver)
+    }
+}
+
+/// Sends the messages of a participant to the other participants of its in-memory
+/// network. The participant is disconnected from the network when the sender is dropped.
+pub struct InMemorySender {
+    guid_prefix: GuidPrefix,
+    network: InMemoryNetwork,
+}
+
+impl InMemorySender {
+    pub fn send_to(&self, buf: &[u8], destination_locator: Locator) {
+        let destination: GuidPrefix = destination_locator.address()[4..]
+            .try_into()
+            .expect("Slice has 12 bytes");
+        let bytes: Arc<[u8]> = Arc::from(buf);
+        let now = Instant::now();
+
+        let mut network = self.network.0.lock().expect("Network lock poisoned");
+        let InMemoryNetworkInner {
+            participant_list,
+            faults,
+            fault_generator,
+        } = &mut *network;
+        for (guid_prefix, sender) in participant_list.iter() {
+            let is_destination = if destination == GUIDPREFIX_UNKNOWN {
+                guid_prefix != &self.guid_prefix
+            } else {
+                guid_prefix == &destination
+            };
+            if !is_destination || fault_generator.occurs(faults.loss_probability) {
+                continue;
+            }
+            let copies = if fault_generator.occurs(faults.duplication_probability) {
+                2
+            } else {
+                1
+            };
+            for _ in 0..copies {
+                let mut delivery_time = now + faults.delay;
+                if fault_generator.occurs(faults.reordering_probability) {
+                    delivery_time += faults.reordering_delay;
+                }
+                sender
+                    .send(InMemoryMessage {
+                        delivery_time,
+                        port: destination_locator.port(),
+                        bytes: bytes.clone(),
+                    })
+                    .ok();
+            }
+        }
+    }
+}
+
+impl Drop for InMemorySender {
+    fn drop(&mut self) {
+        if let Ok(mut network) = self.network.0.lock() {
+            network
+                .participant_list
+                .retain(|(g, _)| g != &self.guid_prefix);
+        }
+    }
+}
+
+// Delivery time, arrival order, port and bytes of a message
+type QueuedMessage = (Instant, u64, u32, Arc<[u8]>);
+
+// Messages waiting for their delivery time, in delivery order. Messages with the same
+// delivery time are delivered in the order in which they were sent.
+#[derive(Default)]
+struct DeliveryQueue {
+    queue: BinaryHeap<Reverse<QueuedMessage>>,
+    next_sequence: u64,
+}
+
+impl DeliveryQueue {
+    fn push(&mut self, message: InMemoryMessage) {
+        self.queue.push(Reverse((
+            message.delivery_time,
+            self.next_sequence,
+            message.port,
+            message.bytes,
+        )));
+        self.next_sequence += 1;
+    }
+
+    fn next_delivery_time(&self) -> Option<Instant> {
+        self.queue.peek().map(|Reverse(m)| m.0)
+    }
+
+    fn pop_due(&mut self, now: Instant) -> Option<(u32, Arc<[u8]>)> {
+        if self.next_delivery_time()? <= now {
+            self.queue
+                .pop()
+                .map(|Reverse((_, _, port, bytes))| (port, bytes))
+        } else {
+            None
+        }
+    }
+}
+
+// Spawn a thread which forwards the messages sent to the participant once their
+// delivery time is reached, until the participant is gone
+pub fn spawn_in_memory_receive_thread(
+    receiver: Receiver<InMemoryMessage>,
+    rtps_participant_address: ActorAddress<RtpsParticipant>,
+) {
+    std::thread::Builder::new()
+        .name("RTPS in-memory traffic".to_string())
+        .spawn(move || {
+            let mut delivery_queue = DeliveryQueue::default();
+            loop {
+                let received = match delivery_queue.next_delivery_time() {
+                    Some(t) => receiver.recv_timeout(t.saturating_duration_since(Instant::now())),
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match received {
+                    Ok(message) => delivery_queue.push(message),
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                while let Some((port, bytes)) = delivery_queue.pop_due(Instant::now()) {
+                    let Ok(rtps_message) = RtpsMessageRead::try_from(bytes.as_ref()) else {
+                        continue;
+                    };
+                    let r = match port {
+                        METATRAFFIC_PORT => rtps_participant_address
+                            .send_actor_mail(participant::ProcessBuiltinRtpsMessage {
+                                rtps_message,
+                            })
+                            .map(|_| ()),
+                        _ => rtps_participant_address
+                            .send_actor_mail(participant::ProcessUserDefinedRtpsMessage {
+                                rtps_message,
+                            })
+                            .map(|_| ()),
+                    };
+                    if r.is_err() {
+                        return;
+                    }
+                }
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receive_all(receiver: &Receiver<InMemoryMessage>) -> Vec<(u32, Vec<u8>)> {
+        let mut delivery_queue = DeliveryQueue::default();
+        while let Ok(message) = receiver.try_recv() {
+            delivery_queue.push(message);
+        }
+        let mut received = Vec::new();
+        while let Some((port, bytes)) =
+            delivery_queue.pop_due(Instant::now() + Duration::from_secs(3600))
+        {
+            received.push((port, bytes.to_vec()));
+        }
+        received
+    }
+
+    #[test]
+    fn messages_are_sent_to_the_destination_or_broadcast() {
+        let network = InMemoryNetwork::new(InMemoryFaults::default(), 0);
+        let (sender1, receiver1) = network.add_participant([1; 12]);
+        let (_sender2, receiver2) = network.add_participant([2; 12]);
+        let (_sender3, receiver3) = network.add_participant([3; 12]);
+
+        sender1.send_to(&[1], in_memory_locator([2; 12], USER_DEFINED_PORT));
+        sender1.send_to(
+            &[2],
+            in_memory_locator(GUIDPREFIX_UNKNOWN, METATRAFFIC_PORT),
+        );
+
+        assert!(receive_all(&receiver1).is_empty());
+        assert_eq!(
+            receive_all(&receiver2),
+            vec![(USER_DEFINED_PORT, vec![1]), (METATRAFFIC_PORT, vec![2])]
+        );
+        assert_eq!(receive_all(&receiver3), vec![(METATRAFFIC_PORT, vec![2])]);
+    }
+
+    #[test]
+    fn faults_are_injected_with_the_configured_probability() {
+        let all_lost = InMemoryFaults {
+            loss_probability: 1.0,
+            ..Default::default()
+        };
+        let network = InMemoryNetwork::new(all_lost, 0);
+        let (sender, _) = network.add_participant([1; 12]);
+        let (_sender2, receiver) = network.add_participant([2; 12]);
+        sender.send_to(&[1], in_memory_locator([2; 12], USER_DEFINED_PORT));
+        assert!(receive_all(&receiver).is_empty());
+
+        let all_duplicated = InMemoryFaults {
+            duplication_probability: 1.0,
+            ..Default::default()
+        };
+        let network = InMemoryNetwork::new(all_duplicated, 0);
+        let (sender, _) = network.add_participant([1; 12]);
+        let (_sender2, receiver) = network.add_participant([2; 12]);
+        sender.send_to(&[1], in_memory_locator([2; 12], USER_DEFINED_PORT));
+        assert_eq!(receive_all(&receiver).len(), 2);
+    }
+
+    #[test]
+    fn reordered_messages_are_overtaken() {
+        let faults = InMemoryFaults {
+            reordering_probability: 0.5,
+            reordering_delay: Duration::from_secs(1),
+            ..Default::default()
+        };
+        let network = InMemoryNetwork::new(faults, 7);
+        let (sender, _) = network.add_participant([1; 12]);
+        let (_sender2, receiver) = network.add_participant([2; 12]);
+        for i in 0..20 {
+            sender.send_to(&[i], in_memory_locator([2; 12], USER_DEFINED_PORT));
+        }
+
+        let received: Vec<_> = receive_all(&receiver).into_iter().map(|m| m.1[0]).collect();
+        let mut sorted = received.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_ne!(received, sorted);
+    }
+
+    #[test]
+    fn disconnected_participants_receive_nothing() {
+        let network = InMemoryNetwork::new(InMemoryFaults::default(), 0);
+        let (sender1, _) = network.add_participant([1; 12]);
+        let (sender2, receiver2) = network.add_participant([2; 12]);
+        drop(sender2);
+        sender1.send_to(
+            &[1],
+            in_memory_locator(GUIDPREFIX_UNKNOWN, METATRAFFIC_PORT),
+        );
+        assert!(receiver2.try_recv().is_err());
+    }
+}
@@ -16,8 +16,8 @@ pub struct MessageReceiver {
     source_vendor_id: VendorId,
     source_guid_prefix: GuidPrefix,
     dest_guid_prefix: GuidPrefix,
-    _unicast_reply_locator_list: Vec<Locator>,
-    _multicast_reply_locator_list: Vec<Locator>,
+    unicast_reply_locator_list: Vec<Locator>,
+    multicast_reply_locator_list: Vec<Locator>,
     have_timestamp: bool,
     timestamp: messages::types::Time,
     submessages: std::vec::IntoIter<RtpsSubmessageReadKind>,
@@ -40,7 +40,10 @@ impl Iterator for MessageReceiver {
                 RtpsSubmessageReadKind::InfoDestination(m) => {
                     self.dest_guid_prefix = m.guid_prefix();
                 }
-                RtpsSubmessageReadKind::InfoReply(_) => todo!(),
+                RtpsSubmessageReadKind::InfoReply(m) => {
+                    self.unicast_reply_locator_list = m.unicast_locator_list().value().to_vec();
+                    self.multicast_reply_locator_list = m.multicast_locator_list().value().to_vec();
+                }
                 RtpsSubmessageReadKind::InfoSource(m) => {
                     self.source_vendor_id = m.vendor_id();
                     self.source_version = m.protocol_version();
@@ -70,8 +73,8 @@ impl MessageReceiver {
             source_vendor_id: header.vendor_id(),
             source_guid_prefix: header.guid_prefix(),
             dest_guid_prefix: GUIDPREFIX_UNKNOWN,
-            _unicast_reply_locator_list: Vec::new(),
-            _multicast_reply_locator_list: Vec::new(),
+            unicast_reply_locator_list: Vec::new(),
+            multicast_reply_locator_list: Vec::new(),
             have_timestamp: false,
             timestamp: TIME_INVALID,
             submessages: message.submessages().into_iter(),
@@ -146,10 +149,18 @@ impl MessageReceiver {
                     }
                 }
                 RtpsSubmessageReadKind::Heartbeat(heartbeat_submessage) => {
+                    // The acknowledgments are sent to the locators of the last
+                    // INFO_REPLY, if any, and otherwise to those of the writer proxy
+                    let reply_locator_list = if !self.unicast_reply_locator_list.is_empty() {
+                        &self.unicast_reply_locator_list
+                    } else {
+                        &self.multicast_reply_locator_list
+                    };
                     for stateful_reader in stateful_reader_list.iter_mut() {
                         stateful_reader.on_heartbeat_submessage_received(
                             heartbeat_submessage,
                             self.source_guid_prefix,
+                            reply_locator_list,
                             message_sender,
                         );
                     }
@@ -167,7 +178,10 @@ impl MessageReceiver {
                 RtpsSubmessageReadKind::InfoDestination(m) => {
                     self.dest_guid_prefix = m.guid_prefix();
                 }
-                RtpsSubmessageReadKind::InfoReply(_) => todo!(),
+                RtpsSubmessageReadKind::InfoReply(m) => {
+                    self.unicast_reply_locator_list = m.unicast_locator_list().value().to_vec();
+                    self.multicast_reply_locator_list = m.multicast_locator_list().value().to_vec();
+                }
                 RtpsSubmessageReadKind::InfoSource(m) => {
                     self.source_vendor_id = m.vendor_id();
                     self.source_version = m.protocol_version();
@@ -204,11 +218,11 @@ impl MessageReceiver {
     }
 
     pub fn _unicast_reply_locator_list(&self) -> &[Locator] {
-        self._unicast_reply_locator_list.as_ref()
+        self.unicast_reply_locator_list.as_ref()
     }
 
     pub fn _multicast_reply_locator_list(&self) -> &[Locator] {
-        self._multicast_reply_locator_list.as_ref()
+        self.multicast_reply_locator_list.as_ref()
     }
 
     pub fn source_timestamp(&self) -> Option<messages::types::Time> {
@@ -219,3 +233,116 @@ impl MessageReceiver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rtps::{
+            messages::{
+                overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
+                submessage_elements::LocatorList,
+                submessages::{heartbeat::HeartbeatSubmessage, info_reply::InfoReplySubmessage},
+            },
+            types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+        },
+        transport::{
+            history_cache::{CacheChange, HistoryCache},
+            reader::WriterProxy,
+            types::{
+                DurabilityKind, EntityId, Guid, ReliabilityKind, ENTITYID_UNKNOWN,
+                LOCATOR_KIND_UDP_V4,
+            },
+        },
+    };
+    use std::{net::UdpSocket, time::Duration};
+
+    struct EmptyHistoryCache;
+
+    impl HistoryCache for EmptyHistoryCache {
+        fn add_change(&mut self, _cache_change: CacheChange) {}
+
+        fn remove_change(&mut self, _sequence_number: i64) {}
+    }
+
+    fn udp_locator(socket: &UdpSocket) -> Locator {
+        let mut address = [0; 16];
+        address[12..].copy_from_slice(&[127, 0, 0, 1]);
+        Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            socket.local_addr().unwrap().port() as u32,
+            address,
+        )
+    }
+
+    fn received_acknack_count(receiver: &UdpSocket) -> usize {
+        let mut buf = [0; 65507];
+        let mut acknack_count = 0;
+        while let Ok(length) = receiver.recv(&mut buf) {
+            let rtps_message = RtpsMessageRead::try_from(&buf[..length]).unwrap();
+            acknack_count += rtps_message
+                .submessages()
+                .iter()
+                .filter(|s| matches!(s, RtpsSubmessageReadKind::AckNack(_)))
+                .count();
+        }
+        acknack_count
+    }
+
+    #[test]
+    fn heartbeat_after_info_reply_is_answered_to_reply_locators() {
+        let proxy_receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        proxy_receiver
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+        let reply_receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        reply_receiver
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+        let message_sender =
+            MessageSender::new([2; 12], Some(UdpSocket::bind("127.0.0.1:0").unwrap()), None);
+        let reader_guid = Guid::new([2; 12], EntityId::new([0, 0, 1], 7));
+        let mut reader =
+            RtpsStatefulReader::new(reader_guid, Box::new(EmptyHistoryCache), Duration::ZERO);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 2], 2));
+        reader.add_matched_writer(&WriterProxy {
+            remote_writer_guid: writer_guid,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![udp_locator(&proxy_receiver)],
+            multicast_locator_list: vec![],
+        });
+
+        let submessages: Vec<Box<dyn Submessage + Send>> = vec![
+            Box::new(InfoReplySubmessage::_new(
+                false,
+                LocatorList::new(vec![udp_locator(&reply_receiver)]),
+                LocatorList::new(vec![]),
+            )),
+            Box::new(HeartbeatSubmessage::new(
+                false,
+                false,
+                reader_guid.entity_id(),
+                writer_guid.entity_id(),
+                1,
+                1,
+                1,
+            )),
+        ];
+        let header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, writer_guid.prefix());
+        let message =
+            RtpsMessageRead::try_from(RtpsMessageWrite::new(&header, &submessages).buffer())
+                .unwrap();
+
+        MessageReceiver::new(message).process_message(
+            &mut [],
+            std::slice::from_mut(&mut reader),
+            &mut [],
+            &message_sender,
+        );
+
+        assert_eq!(received_acknack_count(&reply_receiver), 1);
+        assert_eq!(received_acknack_count(&proxy_receiver), 0);
+    }
+}
@@ -1,31 +1,221 @@
-use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4};
-use std::net::{ToSocketAddrs, UdpSocket};
+use core::{
+    cell::RefCell,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+};
+use std::{
+    collections::HashMap,
+    net::{ToSocketAddrs, UdpSocket},
+    time::Instant,
+};
 
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
 
 use super::{
-    messages::overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
+    flow_controller::{FlowControllerQueue, RtpsFlowController},
+    in_memory::{InMemorySender, LOCATOR_KIND_IN_MEMORY},
+    messages::{
+        overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
+        submessages::{
+            info_destination::InfoDestinationSubmessage, info_timestamp::InfoTimestampSubmessage,
+        },
+        types::{FragmentNumber, TIME_INVALID},
+    },
+    shared_memory::{SharedMemorySender, LOCATOR_KIND_SHARED_MEMORY},
+    tcp::{
+        TcpSender, LOCATOR_KIND_TCP_CONNECTION, LOCATOR_KIND_TCP_SERVER_V4,
+        LOCATOR_KIND_TCP_SERVER_V6,
+    },
+    transport::MAX_DATAGRAM_SIZE,
     types::{PROTOCOLVERSION_2_4, VENDOR_ID_S2E},
 };
 
 use crate::transport::types::{
-    GuidPrefix, Locator, ProtocolVersion, VendorId, LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
+    EntityId, GuidPrefix, Locator, ProtocolVersion, ReliabilityKind, SequenceNumber, VendorId,
+    GUIDPREFIX_UNKNOWN, LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
 };
 
+// Serialized submessages waiting to be sent, grouped per destination locator
+type MessageBatch = Vec<(Locator, Vec<u8>)>;
+
 pub struct MessageSender {
     protocol_version: ProtocolVersion,
     vendor_id: VendorId,
     guid_prefix: GuidPrefix,
-    socket: UdpSocket,
+    // Sockets used to send to UDPv4 and UDPv6 locators. Locators of a kind without
+    // socket are not reachable and messages to them are dropped.
+    socket_v4: Option<UdpSocket>,
+    socket_v6: Option<UdpSocket>,
+    use_multicast: bool,
+    shared_memory_sender: Option<SharedMemorySender>,
+    tcp_sender: Option<TcpSender>,
+    in_memory_sender: Option<InMemorySender>,
+    // None when no batch is open
+    batch: RefCell<Option<MessageBatch>>,
+    flow_controllers: HashMap<String, RefCell<FlowControllerQueue>>,
+    // Name of the flow controller and priority of the writers whose messages are flow controlled
+    writer_flow_controllers: Vec<(EntityId, String, i32)>,
 }
 
 impl MessageSender {
-    pub fn new(guid_prefix: GuidPrefix, socket: UdpSocket) -> Self {
+    pub fn new(
+        guid_prefix: GuidPrefix,
+        socket_v4: Option<UdpSocket>,
+        socket_v6: Option<UdpSocket>,
+    ) -> Self {
         Self {
             protocol_version: PROTOCOLVERSION_2_4,
             vendor_id: VENDOR_ID_S2E,
             guid_prefix,
-            socket,
+            socket_v4,
+            socket_v6,
+            use_multicast: true,
+            shared_memory_sender: None,
+            tcp_sender: None,
+            in_memory_sender: None,
+            batch: RefCell::new(None),
+            flow_controllers: HashMap::new(),
+            writer_flow_controllers: Vec::new(),
+        }
+    }
+
+    /// Enable or disable sending to multicast locators. Messages to multicast
+    /// locators are dropped when disabled.
+    pub fn use_multicast(mut self, use_multicast: bool) -> Self {
+        self.use_multicast = use_multicast;
+        self
+    }
+
+    /// Send the messages to shared memory locators through the given sender. Messages to
+    /// shared memory locators are dropped when no sender is set.
+    pub fn set_shared_memory_sender(&mut self, shared_memory_sender: SharedMemorySender) {
+        self.shared_memory_sender = Some(shared_memory_sender);
+    }
+
+    pub fn uses_shared_memory(&self) -> bool {
+        self.shared_memory_sender.is_some()
+    }
+
+    /// Maximum size of the serialized data sent in a single submessage through shared
+    /// memory, if it is used
+    pub fn shared_memory_data_max_size_serialized(&self) -> Option<usize> {
+        self.shared_memory_sender
+            .as_ref()
+            .map(|s| s.data_max_size_serialized())
+    }
+
+    /// Send the messages to TCP locators through the given sender. Messages to TCP
+    /// locators are dropped when no sender is set.
+    pub fn set_tcp_sender(&mut self, tcp_sender: TcpSender) {
+        self.tcp_sender = Some(tcp_sender);
+    }
+
+    /// Send the messages to in-memory locators through the given sender. Messages to
+    /// in-memory locators are dropped when no sender is set.
+    pub fn set_in_memory_sender(&mut self, in_memory_sender: InMemorySender) {
+        self.in_memory_sender = Some(in_memory_sender);
+    }
+
+    /// Add a flow controller to which the writers can be attached with
+    /// [`Self::set_writer_flow_controller`]
+    pub fn add_flow_controller(&mut self, flow_controller: &RtpsFlowController) {
+        self.flow_controllers.insert(
+            flow_controller.name.clone(),
+            RefCell::new(FlowControllerQueue::new(flow_controller, Instant::now())),
+        );
+    }
+
+    /// Queue the messages of the writer in the named flow controller with the given
+    /// priority. Returns `false` when there is no flow controller with that name.
+    pub fn set_writer_flow_controller(
+        &mut self,
+        writer_id: EntityId,
+        flow_controller_name: &str,
+        priority: i32,
+    ) -> bool {
+        if self.flow_controllers.contains_key(flow_controller_name) {
+            self.writer_flow_controllers
+                .retain(|(id, _, _)| id != &writer_id);
+            self.writer_flow_controllers.push((
+                writer_id,
+                flow_controller_name.to_string(),
+                priority,
+            ));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Stop flow controlling the messages of the writer and drop those still queued
+    pub fn remove_writer_flow_controller(&mut self, writer_id: EntityId) {
+        if let Some((flow_controller, _)) = self.writer_flow_controller(writer_id) {
+            flow_controller.borrow_mut().remove_writer(writer_id);
+        }
+        self.writer_flow_controllers
+            .retain(|(id, _, _)| id != &writer_id);
+    }
+
+    /// Drop the queued messages carrying the data of a change the writer removed from
+    /// its history
+    pub fn remove_writer_change(&self, writer_id: EntityId, sequence_number: SequenceNumber) {
+        if let Some((flow_controller, _)) = self.writer_flow_controller(writer_id) {
+            flow_controller
+                .borrow_mut()
+                .remove_change(writer_id, sequence_number);
+        }
+    }
+
+    fn writer_flow_controller(
+        &self,
+        writer_id: EntityId,
+    ) -> Option<(&RefCell<FlowControllerQueue>, i32)> {
+        self.writer_flow_controllers
+            .iter()
+            .find(|(id, _, _)| id == &writer_id)
+            .and_then(|(_, name, priority)| Some((self.flow_controllers.get(name)?, *priority)))
+    }
+
+    /// Add the tokens of the periods elapsed until now to the flow controllers and send
+    /// the queued messages they allow. Returns the time of the next refill, if any.
+    pub fn refill_flow_controllers(&self, now: Instant) -> Option<Instant> {
+        self.flow_controllers
+            .values()
+            .map(|flow_controller| {
+                let next_refill = flow_controller.borrow_mut().refill(now);
+                self.release_flow_controlled_messages(flow_controller);
+                next_refill
+            })
+            .min()
+    }
+
+    fn release_flow_controlled_messages(&self, flow_controller: &RefCell<FlowControllerQueue>) {
+        loop {
+            let next_message = flow_controller.borrow_mut().release_next();
+            let Some((destination_locator, submessage_bytes)) = next_message else {
+                break;
+            };
+            self.send_submessage_bytes(&submessage_bytes, destination_locator);
+        }
+    }
+
+    /// Start collecting the messages written to this sender instead of sending them
+    /// immediately. The collected submessages are packed into as few datagrams as
+    /// possible per destination and sent when [`Self::end_batch`] is called.
+    pub fn begin_batch(&self) {
+        let mut batch = self.batch.borrow_mut();
+        if batch.is_none() {
+            *batch = Some(Vec::new());
+        }
+    }
+
+    /// Send all the messages collected since [`Self::begin_batch`] and go back to
+    /// sending every message immediately.
+    pub fn end_batch(&self) {
+        let batch = self.batch.borrow_mut().take();
+        if let Some(batch) = batch {
+            for (destination_locator, submessage_bytes) in batch {
+                self.send_submessage_bytes(&submessage_bytes, destination_locator);
+            }
         }
     }
 
@@ -34,37 +224,200 @@ impl MessageSender {
         submessages: &[Box<dyn Submessage + Send>],
         destination_locator_list: Vec<Locator>,
     ) {
+        if self.batch.borrow().is_some() {
+            let submessage_bytes = RtpsMessageWrite::submessages_into_bytes(submessages);
+            for destination_locator in destination_locator_list {
+                self.add_to_batch(&submessage_bytes, destination_locator);
+            }
+        } else {
+            let header =
+                RtpsMessageHeader::new(self.protocol_version, self.vendor_id, self.guid_prefix);
+            let rtpmessage = RtpsMessageWrite::new(&header, submessages);
+            for destination_locator in destination_locator_list {
+                self.send_to(rtpmessage.buffer(), destination_locator);
+            }
+        }
+    }
+
+    /// Write the data and repair messages of a writer to readers of the given reliability.
+    /// The messages of a writer with a flow controller are queued and sent once the flow
+    /// controller has the tokens for them, the others are written as with
+    /// [`Self::write_message`].
+    pub fn write_writer_message(
+        &self,
+        writer_id: EntityId,
+        reliability: ReliabilityKind,
+        submessages: &[Box<dyn Submessage + Send>],
+        destination_locator_list: Vec<Locator>,
+    ) {
+        self.write_flow_controlled_message(
+            writer_id,
+            reliability,
+            None,
+            None,
+            submessages,
+            destination_locator_list,
+        );
+    }
+
+    /// Write a message of a writer carrying the data of one of its changes, or of one
+    /// fragment of it. Unlike the other messages of the writer, it is queued only once per
+    /// destination by the flow controller and dropped from its queue when the change is
+    /// removed with [`Self::remove_writer_change`].
+    pub fn write_writer_change_message(
+        &self,
+        writer_id: EntityId,
+        reliability: ReliabilityKind,
+        sequence_number: SequenceNumber,
+        fragment_number: Option<FragmentNumber>,
+        submessages: &[Box<dyn Submessage + Send>],
+        destination_locator_list: Vec<Locator>,
+    ) {
+        self.write_flow_controlled_message(
+            writer_id,
+            reliability,
+            Some(sequence_number),
+            fragment_number,
+            submessages,
+            destination_locator_list,
+        );
+    }
+
+    fn write_flow_controlled_message(
+        &self,
+        writer_id: EntityId,
+        reliability: ReliabilityKind,
+        sequence_number: Option<SequenceNumber>,
+        fragment_number: Option<FragmentNumber>,
+        submessages: &[Box<dyn Submessage + Send>],
+        destination_locator_list: Vec<Locator>,
+    ) {
+        match self.writer_flow_controller(writer_id) {
+            Some((flow_controller, priority)) => {
+                let submessage_bytes = RtpsMessageWrite::submessages_into_bytes(submessages);
+                for destination_locator in destination_locator_list {
+                    flow_controller.borrow_mut().enqueue(
+                        writer_id,
+                        sequence_number,
+                        fragment_number,
+                        reliability,
+                        priority,
+                        submessage_bytes.clone(),
+                        destination_locator,
+                    );
+                }
+                self.release_flow_controlled_messages(flow_controller);
+            }
+            None => self.write_message(submessages, destination_locator_list),
+        }
+    }
+
+    fn add_to_batch(&self, submessage_bytes: &[u8], destination_locator: Locator) {
+        const HEADER_SIZE: usize = 20;
+        // The submessages of each message are written for a receiver starting with
+        // no destination and timestamp so those of the previous message are reset
+        let reset_bytes = RtpsMessageWrite::submessages_into_bytes(&[
+            Box::new(InfoDestinationSubmessage::new(GUIDPREFIX_UNKNOWN)),
+            Box::new(InfoTimestampSubmessage::new(true, TIME_INVALID)),
+        ]);
+        let full_batch = {
+            let mut batch = self.batch.borrow_mut();
+            let batch = batch.as_mut().expect("Batch must be open");
+            match batch.iter_mut().find(|(l, _)| l == &destination_locator) {
+                Some((_, buffer))
+                    if HEADER_SIZE + buffer.len() + reset_bytes.len() + submessage_bytes.len()
+                        > MAX_DATAGRAM_SIZE =>
+                {
+                    Some(core::mem::replace(buffer, submessage_bytes.to_vec()))
+                }
+                Some((_, buffer)) => {
+                    buffer.extend_from_slice(&reset_bytes);
+                    buffer.extend_from_slice(submessage_bytes);
+                    None
+                }
+                None => {
+                    batch.push((destination_locator, submessage_bytes.to_vec()));
+                    None
+                }
+            }
+        };
+        if let Some(full_batch) = full_batch {
+            self.send_submessage_bytes(&full_batch, destination_locator);
+        }
+    }
+
+    fn send_submessage_bytes(&self, submessage_bytes: &[u8], destination_locator: Locator) {
         let header =
             RtpsMessageHeader::new(self.protocol_version, self.vendor_id, self.guid_prefix);
-        let rtpmessage = RtpsMessageWrite::new(&header, submessages);
-        let buf = rtpmessage.buffer();
-
-        for destination_locator in destination_locator_list {
-            if UdpLocator(destination_locator).is_multicast() {
-                let socket2: socket2::Socket = self.socket.try_clone().unwrap().into();
-                let interface_addresses = NetworkInterface::show();
-                let interface_addresses: Vec<_> = interface_addresses
-                    .expect("Could not scan interfaces")
-                    .into_iter()
-                    .flat_map(|i| {
-                        i.addr.into_iter().filter_map(|a| match a {
-                            Addr::V4(v4) => Some(v4.ip),
-                            _ => None,
-                        })
+        let rtpmessage = RtpsMessageWrite::from_submessage_bytes(&header, submessage_bytes);
+        self.send_to(rtpmessage.buffer(), destination_locator);
+    }
+
+    fn send_to(&self, buf: &[u8], destination_locator: Locator) {
+        if destination_locator.kind() == LOCATOR_KIND_SHARED_MEMORY {
+            if let Some(shared_memory_sender) = &self.shared_memory_sender {
+                shared_memory_sender.send_to(buf, destination_locator);
+            }
+            return;
+        }
+        if destination_locator.kind() == LOCATOR_KIND_IN_MEMORY {
+            if let Some(in_memory_sender) = &self.in_memory_sender {
+                in_memory_sender.send_to(buf, destination_locator);
+            }
+            return;
+        }
+        if matches!(
+            destination_locator.kind(),
+            LOCATOR_KIND_TCP_SERVER_V4
+                | LOCATOR_KIND_TCP_SERVER_V6
+                | LOCATOR_KIND_TCP_CONNECTION
+        ) {
+            if let Some(tcp_sender) = &self.tcp_sender {
+                tcp_sender.send_to(buf, destination_locator);
+            }
+            return;
+        }
+        let socket = match destination_locator.kind() {
+            LOCATOR_KIND_UDP_V4 => self.socket_v4.as_ref(),
+            LOCATOR_KIND_UDP_V6 => self.socket_v6.as_ref(),
+            _ => None,
+        };
+        let Some(socket) = socket else {
+            return;
+        };
+
+        if UdpLocator(destination_locator).is_multicast() {
+            if !self.use_multicast {
+                return;
+            }
+            let socket2: socket2::Socket = socket.try_clone().unwrap().into();
+            let interface_list = NetworkInterface::show().expect("Could not scan interfaces");
+            if destination_locator.kind() == LOCATOR_KIND_UDP_V4 {
+                let interface_addresses = interface_list.into_iter().flat_map(|i| {
+                    i.addr.into_iter().filter_map(|a| match a {
+                        Addr::V4(v4) => Some(v4.ip),
+                        _ => None,
                     })
-                    .collect();
+                });
                 for address in interface_addresses {
                     if socket2.set_multicast_if_v4(&address).is_ok() {
-                        self.socket
-                            .send_to(buf, UdpLocator(destination_locator))
-                            .ok();
+                        socket.send_to(buf, UdpLocator(destination_locator)).ok();
                     }
                 }
             } else {
-                self.socket
-                    .send_to(buf, UdpLocator(destination_locator))
-                    .ok();
+                // IPv6 multicast interfaces are selected by index instead of address
+                let interface_indices = interface_list
+                    .into_iter()
+                    .filter(|i| i.addr.iter().any(|a| matches!(a, Addr::V6(_))))
+                    .map(|i| i.index);
+                for index in interface_indices {
+                    if socket2.set_multicast_if_v6(index).is_ok() {
+                        socket.send_to(buf, UdpLocator(destination_locator)).ok();
+                    }
+                }
             }
+        } else {
+            socket.send_to(buf, UdpLocator(destination_locator)).ok();
         }
     }
 }
@@ -88,7 +441,11 @@ impl ToSocketAddrs for UdpLocator {
                 );
                 Ok(Some(SocketAddr::V4(address)).into_iter())
             }
-            LOCATOR_KIND_UDP_V6 => todo!(),
+            LOCATOR_KIND_UDP_V6 => {
+                let address =
+                    SocketAddrV6::new(Ipv6Addr::from(locator_address), self.0.port() as u16, 0, 0);
+                Ok(Some(SocketAddr::V6(address)).into_iter())
+            }
             _ => Err(std::io::ErrorKind::InvalidInput.into()),
         }
     }
@@ -110,3 +467,122 @@ impl UdpLocator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rtps::messages::{
+            overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+            submessages::heartbeat::HeartbeatSubmessage,
+            types::Time,
+        },
+        transport::types::{ENTITYID_UNKNOWN, USER_DEFINED_WRITER_WITH_KEY},
+    };
+    use std::time::Duration;
+
+    fn udp_receiver() -> (UdpSocket, Locator) {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_millis(200)))
+            .unwrap();
+        let mut address = [0; 16];
+        address[12..].copy_from_slice(&[127, 0, 0, 1]);
+        let locator = Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            receiver.local_addr().unwrap().port() as u32,
+            address,
+        );
+        (receiver, locator)
+    }
+
+    #[test]
+    fn batched_messages_are_sent_in_a_single_datagram() {
+        let (receiver, locator) = udp_receiver();
+        let message_sender =
+            MessageSender::new([1; 12], Some(UdpSocket::bind("127.0.0.1:0").unwrap()), None);
+
+        message_sender.begin_batch();
+        for _ in 0..3 {
+            message_sender.write_message(
+                &[Box::new(InfoDestinationSubmessage::new(GUIDPREFIX_UNKNOWN))],
+                vec![locator],
+            );
+        }
+        message_sender.end_batch();
+
+        let mut buf = [0; MAX_DATAGRAM_SIZE];
+        let received_length = receiver.recv(&mut buf).unwrap();
+        let rtps_message = RtpsMessageRead::try_from(&buf[..received_length]).unwrap();
+        let info_destination_count = rtps_message
+            .submessages()
+            .iter()
+            .filter(|s| matches!(s, RtpsSubmessageReadKind::InfoDestination(_)))
+            .count();
+        // The destination and timestamp are reset before each message but the first
+        assert_eq!(info_destination_count, 5);
+        assert!(receiver.recv(&mut buf).is_err());
+    }
+
+    #[test]
+    fn batched_messages_do_not_inherit_the_destination_and_timestamp_of_previous_ones() {
+        let (receiver, locator) = udp_receiver();
+        let message_sender =
+            MessageSender::new([1; 12], Some(UdpSocket::bind("127.0.0.1:0").unwrap()), None);
+        let heartbeat = |writer_key| -> Box<dyn Submessage + Send> {
+            Box::new(HeartbeatSubmessage::new(
+                false,
+                false,
+                ENTITYID_UNKNOWN,
+                EntityId::new([0, 0, writer_key], USER_DEFINED_WRITER_WITH_KEY),
+                1,
+                1,
+                1,
+            ))
+        };
+
+        message_sender.begin_batch();
+        message_sender.write_message(
+            &[
+                Box::new(InfoDestinationSubmessage::new([2; 12])),
+                Box::new(InfoTimestampSubmessage::new(false, Time::new(10, 0))),
+                heartbeat(1),
+            ],
+            vec![locator],
+        );
+        message_sender.write_message(
+            &[
+                Box::new(InfoTimestampSubmessage::new(false, Time::new(20, 0))),
+                heartbeat(2),
+            ],
+            vec![locator],
+        );
+        message_sender.write_message(&[heartbeat(3)], vec![locator]);
+        message_sender.end_batch();
+
+        let mut buf = [0; MAX_DATAGRAM_SIZE];
+        let received_length = receiver.recv(&mut buf).unwrap();
+        let rtps_message = RtpsMessageRead::try_from(&buf[..received_length]).unwrap();
+        let mut destination = GUIDPREFIX_UNKNOWN;
+        let mut timestamp = TIME_INVALID;
+        let mut heartbeats = Vec::new();
+        for submessage in rtps_message.submessages() {
+            match submessage {
+                RtpsSubmessageReadKind::InfoDestination(m) => destination = m.guid_prefix(),
+                RtpsSubmessageReadKind::InfoTimestamp(m) => timestamp = m.timestamp(),
+                RtpsSubmessageReadKind::Heartbeat(m) => {
+                    heartbeats.push((m.writer_id().entity_key()[2], destination, timestamp))
+                }
+                _ => (),
+            }
+        }
+        assert_eq!(
+            heartbeats,
+            vec![
+                (1, [2; 12], Time::new(10, 0)),
+                (2, GUIDPREFIX_UNKNOWN, Time::new(20, 0)),
+                (3, GUIDPREFIX_UNKNOWN, TIME_INVALID),
+            ]
+        );
+    }
+}
@@ -60,8 +60,11 @@ impl dyn Submessage + Send + '_ {
         self.write_submessage_elements_into_bytes(buf);
         let pos = buf.position();
         buf.set_position(header_position);
-        let len = pos - elements_position;
-        self.write_submessage_header_into_bytes(len as u16, buf);
+        // A length which doesn't fit in the header is written as 0, meaning that the
+        // submessage extends up to the end of the message (9.4.5.1.3). It must then be
+        // the last submessage of the message.
+        let len = u16::try_from(pos - elements_position).unwrap_or(0);
+        self.write_submessage_header_into_bytes(len, buf);
         buf.set_position(pos);
     }
 }
@@ -69,7 +72,7 @@ impl dyn Submessage + Send + '_ {
 pub struct SubmessageHeaderRead {
     submessage_id: u8,
     flags: [SubmessageFlag; 8],
-    submessage_length: u16,
+    submessage_length: usize,
     endianness: Endianness,
 }
 
@@ -94,6 +97,12 @@ impl SubmessageHeaderRead {
             };
             let submessage_length = u16::try_read_from_bytes(&mut &data[2..], &endianness)?;
             data.consume(4);
+            // Except for the PAD and INFO_TS submessages, a length of 0 means that the
+            // submessage extends up to the end of the message (9.4.5.1.3)
+            let submessage_length = match submessage_length {
+                0 if submessage_id != PAD && submessage_id != INFO_TS => data.len(),
+                l => l as usize,
+            };
             Ok(Self {
                 submessage_id,
                 flags,
@@ -116,7 +125,7 @@ impl SubmessageHeaderRead {
         self.flags
     }
 
-    pub fn submessage_length(&self) -> u16 {
+    pub fn submessage_length(&self) -> usize {
         self.submessage_length
     }
 
@@ -170,7 +179,7 @@ impl TryFrom<&[u8]> for RtpsMessageRead {
                     }
                     if let Ok(submessage_header) = SubmessageHeaderRead::try_read_from_bytes(&mut v)
                     {
-                        let submessage_length = submessage_header.submessage_length() as usize;
+                        let submessage_length = submessage_header.submessage_length();
                         if v.len() < submessage_length {
                             break;
                         }
@@ -269,6 +278,23 @@ impl RtpsMessageWrite {
         }
     }
 
+    pub fn from_submessage_bytes(header: &RtpsMessageHeader, submessage_bytes: &[u8]) -> Self {
+        let mut buffer = Vec::with_capacity(20 + submessage_bytes.len());
+        header.write_into_bytes(&mut buffer);
+        buffer.extend_from_slice(submessage_bytes);
+        Self {
+            data: Arc::from(buffer.into_boxed_slice()),
+        }
+    }
+
+    pub fn submessages_into_bytes(submessages: &[Box<dyn Submessage + Send>]) -> Vec<u8> {
+        let mut cursor = Cursor::new(Vec::new());
+        for submessage in submessages {
+            submessage.write_submessage_into_bytes(&mut cursor);
+        }
+        cursor.into_inner()
+    }
+
     pub fn buffer(&self) -> &[u8] {
         &self.data
     }
@@ -588,6 +614,34 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn data_submessage_longer_than_header_length_extends_to_end_of_message() {
+        let header = RtpsMessageHeader {
+            version: ProtocolVersion::new(2, 4),
+            vendor_id: [9, 8],
+            guid_prefix: [3; 12],
+        };
+        let data_submessage = DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
+            EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP),
+            5,
+            ParameterList::empty(),
+            Data::new(vec![7; 100_000].into()),
+        );
+        let value = RtpsMessageWrite::new(&header, &[Box::new(data_submessage.clone())]);
+        assert_eq!(value.buffer()[22..24], [0, 0]);
+
+        let rtps_message = RtpsMessageRead::try_from(value.buffer()).unwrap();
+        assert_eq!(
+            rtps_message.submessages(),
+            vec![RtpsSubmessageReadKind::Data(data_submessage)]
+        );
+    }
+
     #[test]
     fn deserialize_rtps_message_unknown_submessage() {
         let expected_data_submessage = RtpsSubmessageReadKind::Data(DataSubmessage::new(
@@ -37,7 +37,7 @@ impl DataFragSubmessage {
         submessage_header: &SubmessageHeaderRead,
         data: &[u8],
     ) -> RtpsResult<Self> {
-        if submessage_header.submessage_length() as usize > data.len() {
+        if submessage_header.submessage_length() > data.len() {
             return Err(RtpsError::new(
                 RtpsErrorKind::InvalidData,
                 "Submessage header length value bigger than actual data in the buffer",
@@ -63,7 +63,7 @@ impl DataFragSubmessage {
             let fragment_size = u16::try_read_from_bytes(&mut slice, endianness)?;
             let data_size = u32::try_read_from_bytes(&mut slice, endianness)?;
 
-            if octets_to_inline_qos > submessage_header.submessage_length() as usize {
+            if octets_to_inline_qos > submessage_header.submessage_length() {
                 return Err(RtpsError::new(
                     RtpsErrorKind::InvalidData,
                     "Invalid octets to inline qos",
@@ -71,7 +71,7 @@ impl DataFragSubmessage {
             }
 
             let mut data_starting_at_inline_qos =
-                &data[octets_to_inline_qos..submessage_header.submessage_length() as usize];
+                &data[octets_to_inline_qos..submessage_header.submessage_length()];
 
             let inline_qos = if inline_qos_flag {
                 ParameterList::try_read_from_bytes(&mut data_starting_at_inline_qos, endianness)?
@@ -42,19 +42,23 @@ impl InfoReplySubmessage {
         self.multicast_flag
     }
 
-    pub fn _unicast_locator_list(&self) -> &LocatorList {
+    pub fn unicast_locator_list(&self) -> &LocatorList {
         &self.unicast_locator_list
     }
 
-    pub fn _multicast_locator_list(&self) -> &LocatorList {
+    pub fn multicast_locator_list(&self) -> &LocatorList {
         &self.multicast_locator_list
     }
 }
 
 impl Submessage for InfoReplySubmessage {
     fn write_submessage_header_into_bytes(&self, octets_to_next_header: u16, buf: &mut dyn Write) {
-        SubmessageHeaderWrite::new(SubmessageKind::INFO_REPLY, &[], octets_to_next_header)
-            .write_into_bytes(buf);
+        SubmessageHeaderWrite::new(
+            SubmessageKind::INFO_REPLY,
+            &[self.multicast_flag],
+            octets_to_next_header,
+        )
+        .write_into_bytes(buf);
     }
 
     fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write) {
@@ -132,11 +136,11 @@ mod tests {
         assert_eq!(expected_multicast_flag, submessage._multicast_flag());
         assert_eq!(
             &expected_unicast_locator_list,
-            submessage._unicast_locator_list()
+            submessage.unicast_locator_list()
         );
         assert_eq!(
             &expected_multicast_locator_list,
-            submessage._multicast_locator_list()
+            submessage.multicast_locator_list()
         );
     }
 
@@ -171,11 +175,11 @@ mod tests {
         assert_eq!(expected_multicast_flag, submessage._multicast_flag());
         assert_eq!(
             &expected_unicast_locator_list,
-            submessage._unicast_locator_list()
+            submessage.unicast_locator_list()
         );
         assert_eq!(
             &expected_multicast_locator_list,
-            submessage._multicast_locator_list()
+            submessage.multicast_locator_list()
         );
     }
 }
@@ -2,16 +2,20 @@ pub mod behavior_types;
 pub mod cache_change;
 pub mod error;
 pub mod factory;
+pub mod flow_controller;
+pub mod in_memory;
 pub mod message_receiver;
 pub mod message_sender;
 pub mod messages;
 pub mod participant;
 pub mod reader_locator;
 pub mod reader_proxy;
+pub mod shared_memory;
 pub mod stateful_reader;
 pub mod stateful_writer;
 pub mod stateless_reader;
 pub mod stateless_writer;
+pub mod tcp;
 pub mod transport;
 pub mod types;
 pub mod writer_proxy;
@@ -1,3 +1,6 @@
+use core::time::Duration;
+use std::time::Instant;
+
 use crate::{
     rtps::{message_receiver::MessageReceiver, stateful_writer::RtpsStatefulWriter},
     runtime::{
@@ -7,18 +10,23 @@ use crate::{
     transport::{
         history_cache::{CacheChange, HistoryCache},
         reader::WriterProxy,
-        types::{Guid, Locator, ProtocolVersion, SequenceNumber, VendorId},
+        types::{
+            EndpointReliabilityTiming, Guid, Locator, ProtocolVersion, SequenceNumber, VendorId,
+        },
         writer::{ReaderProxy, TransportStatefulWriter, TransportStatelessWriter},
     },
 };
 
 use super::{
     error::RtpsResult,
+    flow_controller::RtpsFlowController,
     message_sender::MessageSender,
     messages::overall_structure::RtpsMessageRead,
+    shared_memory::{self, SharedMemorySender},
     stateful_reader::RtpsStatefulReader,
     stateless_reader::RtpsStatelessReader,
     stateless_writer::RtpsStatelessWriter,
+    transport::{RtpsReliabilityTiming, UdpIpVersion},
     types::{PROTOCOLVERSION_2_4, VENDOR_ID_S2E},
 };
 
@@ -35,6 +43,7 @@ pub struct RtpsParticipant {
     stateless_reader_list: Vec<RtpsStatelessReader>,
     stateful_reader_list: Vec<RtpsStatefulReader>,
     message_sender: MessageSender,
+    reliability_timing: RtpsReliabilityTiming,
 }
 
 impl RtpsParticipant {
@@ -45,12 +54,46 @@ impl RtpsParticipant {
         default_multicast_locator_list: Vec<Locator>,
         metatraffic_unicast_locator_list: Vec<Locator>,
         metatraffic_multicast_locator_list: Vec<Locator>,
+        ip_version: UdpIpVersion,
+        use_multicast: bool,
+        reliability_timing: RtpsReliabilityTiming,
     ) -> RtpsResult<Self> {
         let guid_prefix = guid.prefix();
+        let socket_v4 = if ip_version.uses_ipv4() {
+            Some(std::net::UdpSocket::bind("0.0.0.0:0000")?)
+        } else {
+            None
+        };
+        let socket_v6 = if ip_version.uses_ipv6() {
+            Some(std::net::UdpSocket::bind("[::]:0000")?)
+        } else {
+            None
+        };
         let message_sender =
-            MessageSender::new(guid_prefix, std::net::UdpSocket::bind("0.0.0.0:0000")?);
+            MessageSender::new(guid_prefix, socket_v4, socket_v6).use_multicast(use_multicast);
+
+        Ok(Self::with_message_sender(
+            guid,
+            default_unicast_locator_list,
+            default_multicast_locator_list,
+            metatraffic_unicast_locator_list,
+            metatraffic_multicast_locator_list,
+            message_sender,
+            reliability_timing,
+        ))
+    }
 
-        Ok(Self {
+    /// Construct a participant which sends all its messages through the given sender
+    pub fn with_message_sender(
+        guid: Guid,
+        default_unicast_locator_list: Vec<Locator>,
+        default_multicast_locator_list: Vec<Locator>,
+        metatraffic_unicast_locator_list: Vec<Locator>,
+        metatraffic_multicast_locator_list: Vec<Locator>,
+        message_sender: MessageSender,
+        reliability_timing: RtpsReliabilityTiming,
+    ) -> Self {
+        Self {
             guid,
             protocol_version: PROTOCOLVERSION_2_4,
             vendor_id: VENDOR_ID_S2E,
@@ -64,7 +107,8 @@ impl RtpsParticipant {
             stateful_reader_list: vec![],
 
             message_sender,
-        })
+            reliability_timing,
+        }
     }
 
     pub fn guid(&self) -> Guid {
@@ -117,13 +161,19 @@ impl RtpsParticipant {
     }
 
     pub fn create_stateful_writer(&mut self, writer_guid: Guid, data_max_size_serialized: usize) {
-        let writer = RtpsStatefulWriter::new(writer_guid, data_max_size_serialized);
+        let writer = RtpsStatefulWriter::new(
+            writer_guid,
+            data_max_size_serialized,
+            &self.reliability_timing,
+        );
         self.stateful_writer_list.push(writer);
     }
 
     pub fn delete_writer(&mut self, writer_guid: Guid) {
         self.stateful_writer_list
             .retain(|x| x.guid() != writer_guid);
+        self.message_sender
+            .remove_writer_flow_controller(writer_guid.entity_id());
     }
 
     pub fn create_stateful_reader(
@@ -131,7 +181,11 @@ impl RtpsParticipant {
         reader_guid: Guid,
         reader_history_cache: Box<dyn HistoryCache>,
     ) {
-        let reader = RtpsStatefulReader::new(reader_guid, reader_history_cache);
+        let reader = RtpsStatefulReader::new(
+            reader_guid,
+            reader_history_cache,
+            self.reliability_timing.heartbeat_response_delay,
+        );
 
         self.stateful_reader_list.push(reader);
     }
@@ -200,15 +254,34 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for RtpsParticipant {
     }
 }
 
-pub struct SendHeartbeat;
-impl Mail for SendHeartbeat {
-    type Result = ();
+// Refills the flow controllers and sends the heartbeats and delayed responses which are
+// due. Replies with the period after which the next of them is due, which is the shortest
+// tick period of the participant and of the endpoints overriding its timing, or earlier
+// if a flow controller is refilled before.
+pub struct SendPeriodicMessages;
+impl Mail for SendPeriodicMessages {
+    type Result = Duration;
 }
-impl MailHandler<SendHeartbeat> for RtpsParticipant {
-    fn handle(&mut self, _: SendHeartbeat) -> <SendHeartbeat as Mail>::Result {
+impl MailHandler<SendPeriodicMessages> for RtpsParticipant {
+    fn handle(&mut self, _: SendPeriodicMessages) -> <SendPeriodicMessages as Mail>::Result {
+        let mut tick_period = self.reliability_timing.tick_period();
+        let now = Instant::now();
+        if let Some(next_refill) = self.message_sender.refill_flow_controllers(now) {
+            tick_period = tick_period.min(next_refill - now);
+        }
         for writer in self.stateful_writer_list.iter_mut() {
             writer.send_message(&self.message_sender);
+            tick_period = tick_period.min(writer.reliability_timing().tick_period());
         }
+        for reader in self.stateful_reader_list.iter_mut() {
+            reader.send_message(&self.message_sender);
+            let reader_timing = RtpsReliabilityTiming {
+                heartbeat_response_delay: reader.heartbeat_response_delay(),
+                ..self.reliability_timing
+            };
+            tick_period = tick_period.min(reader_timing.tick_period());
+        }
+        tick_period
     }
 }
 
@@ -250,6 +323,15 @@ impl MailHandler<CreateStatefulWriter> for RtpsParticipant {
                 )
             }
 
+            fn changes(&self) -> Vec<CacheChange> {
+                block_on(
+                    self.rtps_participant_address
+                        .send_actor_mail(GetStatefulWriterChanges { guid: self.guid })
+                        .expect("Actor must exist")
+                        .receive_reply(),
+                )
+            }
+
             fn add_matched_reader(&mut self, reader_proxy: ReaderProxy) {
                 block_on(
                     self.rtps_participant_address
@@ -273,6 +355,24 @@ impl MailHandler<CreateStatefulWriter> for RtpsParticipant {
                         .receive_reply(),
                 )
             }
+
+            fn assert_liveliness(&mut self) {
+                self.rtps_participant_address
+                    .send_actor_mail(AssertWriterLiveliness { writer: self.guid })
+                    .ok();
+            }
+
+            fn set_reliability_timing(&mut self, reliability_timing: EndpointReliabilityTiming) {
+                block_on(
+                    self.rtps_participant_address
+                        .send_actor_mail(SetStatefulWriterReliabilityTiming {
+                            writer: self.guid,
+                            reliability_timing,
+                        })
+                        .expect("Actor must exist")
+                        .receive_reply(),
+                )
+            }
         }
         impl HistoryCache for RtpsUserDefinedWriterHistoryCache {
             fn add_change(&mut self, cache_change: CacheChange) {
@@ -469,6 +569,11 @@ impl MailHandler<AddMatchedReader> for RtpsParticipant {
                     .multicast_locator_list
                     .clone_from(&self.default_multicast_locator_list);
             }
+            shared_memory::select_locators(
+                &mut reader_proxy.unicast_locator_list,
+                reader_proxy.remote_reader_guid.prefix(),
+                self.message_sender.uses_shared_memory(),
+            );
 
             w.add_matched_reader(&reader_proxy);
         }
@@ -520,6 +625,11 @@ impl MailHandler<AddMatchedWriter> for RtpsParticipant {
                     .multicast_locator_list
                     .clone_from(&self.default_multicast_locator_list);
             }
+            shared_memory::select_locators(
+                &mut writer_proxy.unicast_locator_list,
+                writer_proxy.remote_writer_guid.prefix(),
+                self.message_sender.uses_shared_memory(),
+            );
 
             r.add_matched_writer(&writer_proxy);
         }
@@ -545,6 +655,27 @@ impl MailHandler<RemoveMatchedWriter> for RtpsParticipant {
     }
 }
 
+pub struct AssertWriterLiveliness {
+    pub writer: Guid,
+}
+impl Mail for AssertWriterLiveliness {
+    type Result = ();
+}
+impl MailHandler<AssertWriterLiveliness> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: AssertWriterLiveliness,
+    ) -> <AssertWriterLiveliness as Mail>::Result {
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|x| x.guid() == message.writer)
+        {
+            w.send_liveliness_heartbeat(&self.message_sender);
+        }
+    }
+}
+
 pub struct AddStatelessWriterCacheChange {
     pub writer: Guid,
     pub cache_change: CacheChange,
@@ -586,6 +717,8 @@ impl MailHandler<RemoveStatelessWriterCacheChange> for RtpsParticipant {
             .find(|dw| dw.guid() == message.writer)
         {
             w.remove_change(message.sequence_number);
+            self.message_sender
+                .remove_writer_change(message.writer.entity_id(), message.sequence_number);
         }
     }
 }
@@ -613,6 +746,127 @@ impl MailHandler<AddStatefulWriterCacheChange> for RtpsParticipant {
     }
 }
 
+pub struct SetStatefulWriterReliabilityTiming {
+    pub writer: Guid,
+    pub reliability_timing: EndpointReliabilityTiming,
+}
+impl Mail for SetStatefulWriterReliabilityTiming {
+    type Result = ();
+}
+impl MailHandler<SetStatefulWriterReliabilityTiming> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: SetStatefulWriterReliabilityTiming,
+    ) -> <SetStatefulWriterReliabilityTiming as Mail>::Result {
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|dw| dw.guid() == message.writer)
+        {
+            w.set_reliability_timing(
+                self.reliability_timing
+                    .with_endpoint_timing(&message.reliability_timing),
+            );
+        }
+    }
+}
+
+pub struct SetStatefulReaderReliabilityTiming {
+    pub reader: Guid,
+    pub reliability_timing: EndpointReliabilityTiming,
+}
+impl Mail for SetStatefulReaderReliabilityTiming {
+    type Result = ();
+}
+impl MailHandler<SetStatefulReaderReliabilityTiming> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: SetStatefulReaderReliabilityTiming,
+    ) -> <SetStatefulReaderReliabilityTiming as Mail>::Result {
+        if let Some(r) = self
+            .stateful_reader_list
+            .iter_mut()
+            .find(|dr| dr.guid() == message.reader)
+        {
+            r.set_heartbeat_response_delay(
+                self.reliability_timing
+                    .with_endpoint_timing(&message.reliability_timing)
+                    .heartbeat_response_delay,
+            );
+        }
+    }
+}
+
+pub struct EnableSharedMemory {
+    pub shared_memory_sender: SharedMemorySender,
+    pub shared_memory_locator: Locator,
+}
+impl Mail for EnableSharedMemory {
+    type Result = ();
+}
+impl MailHandler<EnableSharedMemory> for RtpsParticipant {
+    fn handle(&mut self, message: EnableSharedMemory) -> <EnableSharedMemory as Mail>::Result {
+        self.message_sender
+            .set_shared_memory_sender(message.shared_memory_sender);
+        self.default_unicast_locator_list
+            .insert(0, message.shared_memory_locator);
+    }
+}
+
+pub struct AddFlowController {
+    pub flow_controller: RtpsFlowController,
+}
+impl Mail for AddFlowController {
+    type Result = ();
+}
+impl MailHandler<AddFlowController> for RtpsParticipant {
+    fn handle(&mut self, message: AddFlowController) -> <AddFlowController as Mail>::Result {
+        self.message_sender
+            .add_flow_controller(&message.flow_controller);
+    }
+}
+
+pub struct SetWriterFlowController {
+    pub writer_guid: Guid,
+    pub flow_controller_name: String,
+    pub priority: i32,
+}
+impl Mail for SetWriterFlowController {
+    type Result = bool;
+}
+impl MailHandler<SetWriterFlowController> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: SetWriterFlowController,
+    ) -> <SetWriterFlowController as Mail>::Result {
+        self.message_sender.set_writer_flow_controller(
+            message.writer_guid.entity_id(),
+            &message.flow_controller_name,
+            message.priority,
+        )
+    }
+}
+
+pub struct BeginBatch;
+impl Mail for BeginBatch {
+    type Result = ();
+}
+impl MailHandler<BeginBatch> for RtpsParticipant {
+    fn handle(&mut self, _: BeginBatch) -> <BeginBatch as Mail>::Result {
+        self.message_sender.begin_batch();
+    }
+}
+
+pub struct EndBatch;
+impl Mail for EndBatch {
+    type Result = ();
+}
+impl MailHandler<EndBatch> for RtpsParticipant {
+    fn handle(&mut self, _: EndBatch) -> <EndBatch as Mail>::Result {
+        self.message_sender.end_batch();
+    }
+}
+
 pub struct RemoveStatefulWriterCacheChange {
     pub writer: Guid,
     pub sequence_number: SequenceNumber,
@@ -631,6 +885,8 @@ impl MailHandler<RemoveStatefulWriterCacheChange> for RtpsParticipant {
             .find(|dw| dw.guid() == message.writer)
         {
             w.remove_change(message.sequence_number);
+            self.message_sender
+                .remove_writer_change(message.writer.entity_id(), message.sequence_number);
         }
     }
 }
@@ -656,6 +912,25 @@ impl MailHandler<IsChangeAcknowledged> for RtpsParticipant {
     }
 }
 
+pub struct GetStatefulWriterChanges {
+    pub guid: Guid,
+}
+impl Mail for GetStatefulWriterChanges {
+    type Result = Vec<CacheChange>;
+}
+impl MailHandler<GetStatefulWriterChanges> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: GetStatefulWriterChanges,
+    ) -> <GetStatefulWriterChanges as Mail>::Result {
+        self.stateful_writer_list
+            .iter()
+            .find(|dw| dw.guid() == message.guid)
+            .map(|w| w.changes().to_vec())
+            .unwrap_or_default()
+    }
+}
+
 pub struct IsHistoricalDataReceived {
     pub guid: Guid,
 }
@@ -1,6 +1,13 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use crate::transport::{
     history_cache::CacheChange,
     types::{EntityId, Guid, Locator, ReliabilityKind, SequenceNumber},
+    writer::ReaderContentFilter,
 };
 
 use super::messages::{
@@ -22,6 +29,9 @@ impl HeartbeatMachine {
             timer: std::time::Instant::now(),
         }
     }
+    pub fn is_heartbeat_sent(&self) -> bool {
+        self.count > 0
+    }
     pub fn is_time_for_heartbeat(&self, heartbeat_period: std::time::Duration) -> bool {
         self.timer.elapsed() >= heartbeat_period
     }
@@ -43,6 +53,23 @@ impl HeartbeatMachine {
             self.count,
         )
     }
+    pub fn generate_new_liveliness_heartbeat(
+        &mut self,
+        writer_id: EntityId,
+        first_sn: SequenceNumber,
+        last_sn: SequenceNumber,
+    ) -> HeartbeatSubmessage {
+        self.count = self.count.wrapping_add(1);
+        HeartbeatSubmessage::new(
+            true,
+            true,
+            self.reader_id,
+            writer_id,
+            first_sn,
+            last_sn,
+            self.count,
+        )
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -92,6 +119,11 @@ pub struct RtpsReaderProxy {
     heartbeat_frag_machine: HeartbeatFragMachine,
     reliability: ReliabilityKind,
     first_relevant_sample_seq_num: SequenceNumber,
+    content_filter: Option<Arc<dyn ReaderContentFilter>>,
+    // Reception time of the oldest request which is not answered yet
+    nack_received_time: Option<Instant>,
+    // Last time each unacknowledged change was sent to the reader
+    change_sent_time: HashMap<SequenceNumber, Instant>,
 }
 
 impl RtpsReaderProxy {
@@ -105,6 +137,7 @@ impl RtpsReaderProxy {
         is_active: bool,
         reliability: ReliabilityKind,
         first_relevant_sample_seq_num: SequenceNumber,
+        content_filter: Option<Arc<dyn ReaderContentFilter>>,
     ) -> Self {
         let heartbeat_machine = HeartbeatMachine::new(remote_reader_guid.entity_id());
         let heartbeat_frag_machine = HeartbeatFragMachine::new(remote_reader_guid.entity_id());
@@ -124,6 +157,9 @@ impl RtpsReaderProxy {
             heartbeat_frag_machine,
             reliability,
             first_relevant_sample_seq_num,
+            content_filter,
+            nack_received_time: None,
+            change_sent_time: HashMap::new(),
         }
     }
 
@@ -147,12 +183,25 @@ impl RtpsReaderProxy {
         &mut self.heartbeat_frag_machine
     }
 
+    pub fn set_content_filter(&mut self, content_filter: Option<Arc<dyn ReaderContentFilter>>) {
+        self.content_filter = content_filter;
+    }
+
+    // Corresponds to the DDS_FILTER(the_reader_proxy, a_change) of the Rtps Standard
+    pub fn is_relevant(&self, cache_change: &CacheChange) -> bool {
+        self.content_filter
+            .as_ref()
+            .is_none_or(|f| f.is_relevant(cache_change))
+    }
+
     // //////////////   ReaderProxy operations defined in the Rtps Standard
 
     pub fn acked_changes_set(&mut self, committed_seq_num: SequenceNumber) {
         if committed_seq_num > self.highest_acked_seq_num {
             self.highest_acked_seq_num = committed_seq_num
         }
+        self.change_sent_time
+            .retain(|&sn, _| sn > committed_seq_num);
     }
 
     pub fn next_requested_change(&mut self) -> Option<SequenceNumber> {
@@ -251,4 +300,31 @@ impl RtpsReaderProxy {
     pub fn set_last_received_nack_frag_count(&mut self, count: Count) {
         self.last_received_nack_frag_count = count;
     }
+
+    pub fn nack_received(&mut self) {
+        self.nack_received_time.get_or_insert_with(Instant::now);
+    }
+
+    pub fn is_time_for_nack_response(&self, nack_response_delay: Duration) -> bool {
+        self.nack_received_time
+            .is_some_and(|t| t.elapsed() >= nack_response_delay)
+    }
+
+    pub fn nack_response_sent(&mut self) {
+        self.nack_received_time = None;
+    }
+
+    pub fn change_sent(&mut self, seq_num: SequenceNumber) {
+        self.change_sent_time.insert(seq_num, Instant::now());
+    }
+
+    pub fn is_change_recently_sent(
+        &self,
+        seq_num: SequenceNumber,
+        nack_suppression_duration: Duration,
+    ) -> bool {
+        self.change_sent_time
+            .get(&seq_num)
+            .is_some_and(|t| t.elapsed() < nack_suppression_duration)
+    }
 }
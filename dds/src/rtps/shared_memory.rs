@@ -0,0 +1,581 @@
+use core::cell::Cell;
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    net::UdpSocket,
+    path::{Path, PathBuf},
+    sync::{Once, OnceLock},
+};
+
+use crate::{
+    infrastructure::error::DdsResult,
+    runtime::actor::ActorAddress,
+    transport::{
+        factory::TransportParticipantFactory,
+        participant::TransportParticipant,
+        types::{GuidPrefix, Locator, Long},
+    },
+};
+
+use super::{
+    error::{RtpsError, RtpsErrorKind, RtpsResult},
+    factory::RtpsParticipantFactory,
+    messages::overall_structure::RtpsMessageRead,
+    participant::{self, RtpsParticipant},
+};
+
+/// Vendor specific locator kind of the shared memory transport. The address of the
+/// locator identifies the host and the port is the notification port of the participant.
+pub const LOCATOR_KIND_SHARED_MEMORY: Long = 0x0100_0000;
+
+const DEFAULT_SEGMENT_SIZE: usize = 16 * 1024 * 1024;
+const MIN_SEGMENT_SIZE: usize = 1024 * 1024;
+// The segment holds at least this number of messages of the maximum size so that a message
+// is not overwritten by the following ones before the receivers read it. Bigger samples are
+// fragmented.
+const SLOT_COUNT: usize = 16;
+
+// Each message in the segment is preceded by its generation and length
+const MESSAGE_HEADER_SIZE: usize = 16;
+// Notification sent to the receiver: segment guid prefix, offset, length and generation
+const NOTIFICATION_SIZE: usize = 12 + 8 + 8 + 8;
+// Room left in a message for the RTPS header, the submessages sent along with the data
+// and the submessage elements and inline QoS of the data submessage
+const DATA_OVERHEAD: usize = 1024;
+
+/// Builder for the [`SharedMemoryParticipantFactory`]
+pub struct SharedMemoryParticipantFactoryBuilder {
+    udp_participant_factory: RtpsParticipantFactory,
+    segment_size: usize,
+}
+
+impl Default for SharedMemoryParticipantFactoryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SharedMemoryParticipantFactoryBuilder {
+    /// Construct a shared memory transport factory builder with all the default options.
+    pub fn new() -> Self {
+        Self {
+            udp_participant_factory: RtpsParticipantFactory::default(),
+            segment_size: DEFAULT_SEGMENT_SIZE,
+        }
+    }
+
+    /// Set the UDP transport used for discovery and for the communication with participants on other hosts
+    pub fn udp_participant_factory(
+        mut self,
+        udp_participant_factory: RtpsParticipantFactory,
+    ) -> Self {
+        self.udp_participant_factory = udp_participant_factory;
+        self
+    }
+
+    /// Set the size of the shared memory segment in which each participant writes its outgoing messages. The segment
+    /// holds at least 16 messages so the samples bigger than a sixteenth of the segment are fragmented.
+    pub fn segment_size(mut self, segment_size: usize) -> Self {
+        self.segment_size = segment_size;
+        self
+    }
+
+    /// Build a new shared memory participant factory
+    pub fn build(self) -> Result<SharedMemoryParticipantFactory, String> {
+        if self.segment_size < MIN_SEGMENT_SIZE {
+            Err(format!(
+                "Segment size too small. Value must be at least {}",
+                MIN_SEGMENT_SIZE
+            ))
+        } else {
+            Ok(SharedMemoryParticipantFactory {
+                udp_participant_factory: self.udp_participant_factory,
+                segment_size: self.segment_size,
+            })
+        }
+    }
+}
+
+/// Transport factory whose participants exchange the user-defined traffic with the
+/// participants of the same host through shared memory segments. Discovery and the
+/// traffic with other hosts use the UDP transport. The segments are POSIX shared memory
+/// objects in `/dev/shm`, so on the systems without it only the UDP transport is used.
+pub struct SharedMemoryParticipantFactory {
+    udp_participant_factory: RtpsParticipantFactory,
+    segment_size: usize,
+}
+
+impl Default for SharedMemoryParticipantFactory {
+    fn default() -> Self {
+        SharedMemoryParticipantFactoryBuilder::new()
+            .build()
+            .expect("Default configuration should work")
+    }
+}
+
+impl TransportParticipantFactory for SharedMemoryParticipantFactory {
+    fn create_participant(
+        &self,
+        guid_prefix: GuidPrefix,
+        domain_id: i32,
+    ) -> DdsResult<Box<dyn TransportParticipant>> {
+        let mut transport = self
+            .udp_participant_factory
+            .create_transport(guid_prefix, domain_id)?;
+        if let Err(e) = transport.enable_shared_memory(self.segment_size) {
+            tracing::warn!(
+                "Failed to enable the shared memory transport, using only UDP: {}",
+                e
+            );
+        }
+        Ok(Box::new(transport))
+    }
+}
+
+/// Identifier of the host used as address of the shared memory locators
+pub fn host_id() -> [u8; 16] {
+    static HOST_ID: OnceLock<[u8; 16]> = OnceLock::new();
+    *HOST_ID.get_or_init(|| {
+        let machine_id = std::fs::read("/etc/machine-id")
+            .or_else(|_| std::fs::read("/proc/sys/kernel/hostname"))
+            .unwrap_or_default();
+        let mut context = md5::Context::new();
+        context.consume(machine_id);
+        context.consume(SEGMENT_DIRECTORY.as_bytes());
+        context.compute().0
+    })
+}
+
+/// Keep only the locators through which the remote endpoint is reached. The shared
+/// memory locators are preferred when the endpoint is on the same host and its segment
+/// is visible to this participant, otherwise they are removed. Hosts sharing the same
+/// machine id, e.g. containers and cloned virtual machines, can have separate shared
+/// memory in which case the other locators are kept.
+pub fn select_locators(
+    locator_list: &mut Vec<Locator>,
+    remote_guid_prefix: GuidPrefix,
+    use_shared_memory: bool,
+) {
+    let is_local_shared_memory_locator =
+        |l: &Locator| l.kind() == LOCATOR_KIND_SHARED_MEMORY && l.address() == host_id();
+    if use_shared_memory
+        && locator_list.iter().any(is_local_shared_memory_locator)
+        && File::open(segment_path(remote_guid_prefix)).is_ok()
+    {
+        locator_list.retain(is_local_shared_memory_locator);
+    } else {
+        locator_list.retain(|l| l.kind() != LOCATOR_KIND_SHARED_MEMORY);
+    }
+}
+
+/// Whether all the locators are shared memory locators
+pub fn is_shared_memory_only(locator_list: &[Locator]) -> bool {
+    !locator_list.is_empty()
+        && locator_list
+            .iter()
+            .all(|l| l.kind() == LOCATOR_KIND_SHARED_MEMORY)
+}
+
+// POSIX shared memory objects live in /dev/shm, whose files stay in memory. A file of
+// another directory would be written to the disk so there is no fallback.
+const SEGMENT_DIRECTORY: &str = "/dev/shm";
+
+const SEGMENT_FILE_PREFIX: &str = "dust_dds_";
+
+fn segment_path(guid_prefix: GuidPrefix) -> PathBuf {
+    let name: String = guid_prefix.iter().map(|b| format!("{:02x}", b)).collect();
+    Path::new(SEGMENT_DIRECTORY).join(format!("{}{}", SEGMENT_FILE_PREFIX, name))
+}
+
+// Only the user running the participant can read its segment. The lock telling that
+// the segment is in use is held as long as it is open and is taken before the segment
+// gets its name so that it is never seen unlocked.
+fn create_segment(segment_path: &Path) -> std::io::Result<File> {
+    let file_name = segment_path
+        .file_name()
+        .expect("Segment path has a file name")
+        .to_string_lossy();
+    let creation_path = segment_path.with_file_name(format!(".{}", file_name));
+    std::fs::remove_file(&creation_path).ok();
+    let mut options = OpenOptions::new();
+    options.read(true).write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let segment = options.open(&creation_path)?;
+    #[cfg(unix)]
+    segment.try_lock().map_err(std::io::Error::other)?;
+    std::fs::rename(&creation_path, segment_path)?;
+    Ok(segment)
+}
+
+// Remove the segments left behind by the participants of processes which terminated
+// without deleting them. A segment is in use as long as its owner holds its lock.
+#[cfg(unix)]
+fn reclaim_stale_segments() {
+    let Ok(entries) = std::fs::read_dir(SEGMENT_DIRECTORY) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(SEGMENT_FILE_PREFIX)
+        {
+            continue;
+        }
+        if let Ok(segment) = File::open(entry.path()) {
+            if segment.try_lock().is_ok() {
+                tracing::info!(path = ?entry.path(), "Removing stale shared memory segment");
+                std::fs::remove_file(entry.path()).ok();
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn reclaim_stale_segments() {}
+
+#[cfg(unix)]
+fn write_all_at(file: &File, buf: &[u8], offset: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::write_all_at(file, buf, offset)
+}
+
+#[cfg(unix)]
+fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn write_all_at(file: &File, mut buf: &[u8], mut offset: u64) -> std::io::Result<()> {
+    while !buf.is_empty() {
+        let written = std::os::windows::fs::FileExt::seek_write(file, buf, offset)?;
+        buf = &buf[written..];
+        offset += written as u64;
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> std::io::Result<()> {
+    while !buf.is_empty() {
+        let read = std::os::windows::fs::FileExt::seek_read(file, buf, offset)?;
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        buf = &mut buf[read..];
+        offset += read as u64;
+    }
+    Ok(())
+}
+
+/// Ring buffer in a shared memory segment in which a participant writes its outgoing
+/// messages. The receivers are told where to find each message by a notification sent
+/// to their notification port on the loopback interface. A message is at most the size
+/// of a slot, a sixteenth of the segment, so that it is only overwritten once sixteen
+/// messages are sent after it. A message which is overwritten before the receiver reads
+/// it is dropped and recovered by the reliability protocol. The segment is not mapped
+/// in memory: the messages are copied in and out of it with positional reads and
+/// writes, which stay in memory since the segment is a file of the shared memory file
+/// system. What makes it faster than UDP is that a sample fitting in a slot is sent in
+/// a single DATA submessage, without fragmentation.
+pub struct SharedMemorySender {
+    guid_prefix: GuidPrefix,
+    segment: File,
+    segment_path: PathBuf,
+    segment_size: u64,
+    write_offset: Cell<u64>,
+    generation: Cell<u64>,
+    notification_socket: UdpSocket,
+}
+
+impl SharedMemorySender {
+    pub fn new(guid_prefix: GuidPrefix, segment_size: usize) -> RtpsResult<Self> {
+        if !Path::new(SEGMENT_DIRECTORY).is_dir() {
+            return Err(RtpsError::new(
+                RtpsErrorKind::Io,
+                format!("Shared memory directory {} not available", SEGMENT_DIRECTORY),
+            ));
+        }
+        static RECLAIM_STALE_SEGMENTS: Once = Once::new();
+        RECLAIM_STALE_SEGMENTS.call_once(reclaim_stale_segments);
+
+        let segment_path = segment_path(guid_prefix);
+        let segment = create_segment(&segment_path)?;
+        segment.set_len(segment_size as u64)?;
+        Ok(Self {
+            guid_prefix,
+            segment,
+            segment_path,
+            segment_size: segment_size as u64,
+            write_offset: Cell::new(0),
+            generation: Cell::new(0),
+            notification_socket: UdpSocket::bind("127.0.0.1:0")?,
+        })
+    }
+
+    fn slot_size(&self) -> u64 {
+        self.segment_size / SLOT_COUNT as u64
+    }
+
+    /// Maximum size of the serialized data sent in a single submessage to readers
+    /// reached through shared memory. Only data which doesn't fit in a slot of the
+    /// segment is fragmented.
+    pub fn data_max_size_serialized(&self) -> usize {
+        self.slot_size() as usize - MESSAGE_HEADER_SIZE - DATA_OVERHEAD
+    }
+
+    pub fn send_to(&self, buf: &[u8], destination_locator: Locator) {
+        if destination_locator.address() != host_id() {
+            return;
+        }
+        let message_size = (MESSAGE_HEADER_SIZE + buf.len()) as u64;
+        if message_size > self.slot_size() {
+            tracing::info!(
+                size = buf.len(),
+                "Message too big for a slot of the shared memory segment"
+            );
+            return;
+        }
+        let mut offset = self.write_offset.get();
+        if offset + message_size > self.segment_size {
+            offset = 0;
+        }
+        let generation = self.generation.get() + 1;
+        self.generation.set(generation);
+        self.write_offset.set(offset + message_size);
+
+        let mut header = [0; MESSAGE_HEADER_SIZE];
+        header[..8].copy_from_slice(&generation.to_le_bytes());
+        header[8..].copy_from_slice(&(buf.len() as u64).to_le_bytes());
+        if write_all_at(&self.segment, &header, offset).is_err()
+            || write_all_at(&self.segment, buf, offset + MESSAGE_HEADER_SIZE as u64).is_err()
+        {
+            return;
+        }
+
+        let mut notification = [0; NOTIFICATION_SIZE];
+        notification[..12].copy_from_slice(&self.guid_prefix);
+        notification[12..20].copy_from_slice(&offset.to_le_bytes());
+        notification[20..28].copy_from_slice(&(buf.len() as u64).to_le_bytes());
+        notification[28..].copy_from_slice(&generation.to_le_bytes());
+        self.notification_socket
+            .send_to(
+                &notification,
+                ("127.0.0.1", destination_locator.port() as u16),
+            )
+            .ok();
+    }
+}
+
+impl Drop for SharedMemorySender {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.segment_path).ok();
+    }
+}
+
+// Segments of the remote participants opened by a receiver
+#[derive(Default)]
+struct SharedMemorySegmentList(HashMap<GuidPrefix, File>);
+
+impl SharedMemorySegmentList {
+    fn read_message(&mut self, notification: &[u8; NOTIFICATION_SIZE]) -> RtpsResult<Vec<u8>> {
+        let guid_prefix: GuidPrefix = notification[..12].try_into().expect("Slice has 12 bytes");
+        let offset = u64::from_le_bytes(notification[12..20].try_into().expect("8 bytes"));
+        let length = u64::from_le_bytes(notification[20..28].try_into().expect("8 bytes"));
+        let generation = u64::from_le_bytes(notification[28..].try_into().expect("8 bytes"));
+
+        let segment = match self.0.entry(guid_prefix) {
+            std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+            std::collections::hash_map::Entry::Vacant(e) => {
+                e.insert(File::open(segment_path(guid_prefix))?)
+            }
+        };
+        if offset + MESSAGE_HEADER_SIZE as u64 + length > segment.metadata()?.len() {
+            return Err(RtpsError::new(
+                RtpsErrorKind::InvalidData,
+                "Message out of the shared memory segment",
+            ));
+        }
+
+        let mut message = vec![0; MESSAGE_HEADER_SIZE + length as usize];
+        read_exact_at(segment, &mut message, offset)?;
+        // Read the header again to detect that the message was overwritten by the
+        // sender while it was being read
+        let mut header = [0; MESSAGE_HEADER_SIZE];
+        read_exact_at(segment, &mut header, offset)?;
+        if message[..MESSAGE_HEADER_SIZE] != header
+            || header[..8] != generation.to_le_bytes()
+            || header[8..] != length.to_le_bytes()
+        {
+            return Err(RtpsError::new(
+                RtpsErrorKind::InvalidData,
+                "Message overwritten in the shared memory segment",
+            ));
+        }
+        message.drain(..MESSAGE_HEADER_SIZE);
+        Ok(message)
+    }
+}
+
+// Spawn a thread which reads the messages notified on the socket from the shared memory
+// segments and forwards them to the participant until the participant is gone
+pub fn spawn_shared_memory_receive_thread(
+    notification_socket: UdpSocket,
+    rtps_participant_address: ActorAddress<RtpsParticipant>,
+) {
+    std::thread::Builder::new()
+        .name("RTPS shared memory user defined traffic".to_string())
+        .spawn(move || {
+            let mut segment_list = SharedMemorySegmentList::default();
+            let mut notification = [0; NOTIFICATION_SIZE];
+            loop {
+                match notification_socket.recv(&mut notification) {
+                    Ok(NOTIFICATION_SIZE) => (),
+                    _ => continue,
+                }
+                let Ok(rtps_message) = segment_list
+                    .read_message(&notification)
+                    .and_then(|m| RtpsMessageRead::try_from(m.as_slice()))
+                else {
+                    continue;
+                };
+                let r = rtps_participant_address
+                    .send_actor_mail(participant::ProcessUserDefinedRtpsMessage { rtps_message });
+                if r.is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::types::LOCATOR_KIND_UDP_V4;
+    use std::time::Duration;
+
+    #[test]
+    fn messages_are_read_from_the_segment_of_the_sender() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_millis(500)))
+            .unwrap();
+        let locator = Locator::new(
+            LOCATOR_KIND_SHARED_MEMORY,
+            receiver.local_addr().unwrap().port() as u32,
+            host_id(),
+        );
+        let sender = SharedMemorySender::new([7; 12], MIN_SEGMENT_SIZE).unwrap();
+        let big_message = vec![3; MIN_SEGMENT_SIZE / SLOT_COUNT - MESSAGE_HEADER_SIZE];
+
+        sender.send_to(&[1, 2, 3], locator);
+        sender.send_to(&big_message, locator);
+        // Messages which don't fit in a slot are not sent
+        sender.send_to(&[4; MIN_SEGMENT_SIZE / SLOT_COUNT], locator);
+
+        let mut segment_list = SharedMemorySegmentList::default();
+        let mut notification = [0; NOTIFICATION_SIZE];
+        receiver.recv(&mut notification).unwrap();
+        assert_eq!(
+            segment_list.read_message(&notification).unwrap(),
+            vec![1, 2, 3]
+        );
+        receiver.recv(&mut notification).unwrap();
+        assert_eq!(
+            segment_list.read_message(&notification).unwrap(),
+            big_message
+        );
+        assert!(receiver.recv(&mut notification).is_err());
+    }
+
+    #[test]
+    fn overwritten_messages_are_dropped() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_millis(500)))
+            .unwrap();
+        let locator = Locator::new(
+            LOCATOR_KIND_SHARED_MEMORY,
+            receiver.local_addr().unwrap().port() as u32,
+            host_id(),
+        );
+        let sender = SharedMemorySender::new([8; 12], MIN_SEGMENT_SIZE).unwrap();
+        let message = vec![5; MIN_SEGMENT_SIZE / SLOT_COUNT - MESSAGE_HEADER_SIZE];
+
+        for _ in 0..=SLOT_COUNT {
+            sender.send_to(&message, locator);
+        }
+
+        let mut segment_list = SharedMemorySegmentList::default();
+        let mut notification = [0; NOTIFICATION_SIZE];
+        receiver.recv(&mut notification).unwrap();
+        assert!(segment_list.read_message(&notification).is_err());
+        for _ in 0..SLOT_COUNT {
+            receiver.recv(&mut notification).unwrap();
+            assert_eq!(segment_list.read_message(&notification).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn shared_memory_locators_are_selected_only_on_the_same_host() {
+        let remote_guid_prefix = [9; 12];
+        let _remote_sender = SharedMemorySender::new(remote_guid_prefix, MIN_SEGMENT_SIZE).unwrap();
+        let udp_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7411, [1; 16]);
+        let local_locator = Locator::new(LOCATOR_KIND_SHARED_MEMORY, 1000, host_id());
+        let remote_locator = Locator::new(LOCATOR_KIND_SHARED_MEMORY, 1000, [0xff; 16]);
+
+        let mut locator_list = vec![udp_locator, local_locator];
+        select_locators(&mut locator_list, remote_guid_prefix, true);
+        assert_eq!(locator_list, vec![local_locator]);
+
+        let mut locator_list = vec![udp_locator, local_locator];
+        select_locators(&mut locator_list, remote_guid_prefix, false);
+        assert_eq!(locator_list, vec![udp_locator]);
+
+        let mut locator_list = vec![udp_locator, remote_locator];
+        select_locators(&mut locator_list, remote_guid_prefix, true);
+        assert_eq!(locator_list, vec![udp_locator]);
+    }
+
+    #[test]
+    fn shared_memory_locators_are_not_selected_without_the_remote_segment() {
+        // Same machine id but the segment of the remote participant is not in the
+        // shared memory of this one
+        let udp_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7411, [1; 16]);
+        let local_locator = Locator::new(LOCATOR_KIND_SHARED_MEMORY, 1000, host_id());
+
+        let mut locator_list = vec![udp_locator, local_locator];
+        select_locators(&mut locator_list, [10; 12], true);
+        assert_eq!(locator_list, vec![udp_locator]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn segments_are_private_and_stale_ones_are_reclaimed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let sender = SharedMemorySender::new([12; 12], MIN_SEGMENT_SIZE).unwrap();
+        let stale_segment_path = segment_path([13; 12]);
+        File::create(&stale_segment_path).unwrap();
+
+        reclaim_stale_segments();
+
+        let permissions = std::fs::metadata(segment_path([12; 12]))
+            .unwrap()
+            .permissions();
+        assert_eq!(permissions.mode() & 0o777, 0o600);
+        assert!(!stale_segment_path.exists());
+        drop(sender);
+        assert!(!segment_path([12; 12]).exists());
+    }
+
+    #[test]
+    fn data_max_size_fits_in_the_segment() {
+        let sender = SharedMemorySender::new([11; 12], MIN_SEGMENT_SIZE).unwrap();
+        assert_eq!(
+            sender.data_max_size_serialized(),
+            MIN_SEGMENT_SIZE / SLOT_COUNT - MESSAGE_HEADER_SIZE - DATA_OVERHEAD
+        );
+    }
+}
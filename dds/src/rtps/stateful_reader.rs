@@ -12,22 +12,29 @@ use super::{
 use crate::transport::{
     history_cache::{CacheChange, HistoryCache},
     reader::WriterProxy,
-    types::{Guid, GuidPrefix, ReliabilityKind},
+    types::{Guid, GuidPrefix, Locator, ReliabilityKind},
 };
+use std::time::Duration;
 use tracing::error;
 
 pub struct RtpsStatefulReader {
     guid: Guid,
     matched_writers: Vec<RtpsWriterProxy>,
     history_cache: Box<dyn HistoryCache>,
+    heartbeat_response_delay: Duration,
 }
 
 impl RtpsStatefulReader {
-    pub fn new(guid: Guid, history_cache: Box<dyn HistoryCache>) -> Self {
+    pub fn new(
+        guid: Guid,
+        history_cache: Box<dyn HistoryCache>,
+        heartbeat_response_delay: Duration,
+    ) -> Self {
         Self {
             guid,
             matched_writers: Vec::new(),
             history_cache,
+            heartbeat_response_delay,
         }
     }
 
@@ -35,6 +42,14 @@ impl RtpsStatefulReader {
         self.guid
     }
 
+    pub fn heartbeat_response_delay(&self) -> Duration {
+        self.heartbeat_response_delay
+    }
+
+    pub fn set_heartbeat_response_delay(&mut self, heartbeat_response_delay: Duration) {
+        self.heartbeat_response_delay = heartbeat_response_delay;
+    }
+
     pub fn add_matched_writer(&mut self, writer_proxy: &WriterProxy) {
         if self
             .matched_writers
@@ -81,6 +96,11 @@ impl RtpsStatefulReader {
                         writer_proxy.received_change_set(sequence_number);
                         if sequence_number > expected_seq_num {
                             writer_proxy.lost_changes_update(sequence_number);
+                            self.history_cache.lost_changes(
+                                writer_guid,
+                                expected_seq_num,
+                                sequence_number - 1,
+                            );
                         }
 
                         if let Ok(change) = CacheChange::try_from_data_submessage(
@@ -146,6 +166,20 @@ impl RtpsStatefulReader {
             .iter_mut()
             .find(|w| w.remote_writer_guid() == writer_guid)
         {
+            // A best-effort writer sends its changes in order so the ones preceding
+            // the gap which were not received are lost
+            let expected_seq_num = writer_proxy.available_changes_max() + 1;
+            if writer_proxy.reliability() == ReliabilityKind::BestEffort
+                && gap_submessage.gap_start() > expected_seq_num
+            {
+                writer_proxy.lost_changes_update(gap_submessage.gap_start());
+                self.history_cache.lost_changes(
+                    writer_guid,
+                    expected_seq_num,
+                    gap_submessage.gap_start() - 1,
+                );
+            }
+
             for seq_num in gap_submessage.gap_start()..gap_submessage.gap_list().base() {
                 writer_proxy.irrelevant_change_set(seq_num)
             }
@@ -160,6 +194,7 @@ impl RtpsStatefulReader {
         &mut self,
         heartbeat_submessage: &HeartbeatSubmessage,
         source_guid_prefix: GuidPrefix,
+        reply_locator_list: &[Locator],
         message_sender: &MessageSender,
     ) {
         let writer_guid = Guid::new(source_guid_prefix, heartbeat_submessage.writer_id());
@@ -168,6 +203,9 @@ impl RtpsStatefulReader {
             .iter_mut()
             .find(|w| w.remote_writer_guid() == writer_guid)
         {
+            if heartbeat_submessage.liveliness_flag() {
+                self.history_cache.assert_writer_liveliness(writer_guid);
+            }
             if writer_proxy.last_received_heartbeat_count() < heartbeat_submessage.count() {
                 writer_proxy.set_last_received_heartbeat_count(heartbeat_submessage.count());
 
@@ -182,7 +220,11 @@ impl RtpsStatefulReader {
                 }
                 writer_proxy.missing_changes_update(heartbeat_submessage.last_sn());
                 writer_proxy.lost_changes_update(heartbeat_submessage.first_sn());
-                writer_proxy.send_message(&self.guid, message_sender);
+                writer_proxy.set_reply_locator_list(reply_locator_list);
+                writer_proxy.heartbeat_received();
+                if writer_proxy.is_time_for_heartbeat_response(self.heartbeat_response_delay) {
+                    writer_proxy.send_message(&self.guid, message_sender);
+                }
             }
         }
     }
@@ -208,6 +250,15 @@ impl RtpsStatefulReader {
 
 // The methods in this impl block are not defined by the standard
 impl RtpsStatefulReader {
+    /// Answer the heartbeats whose heartbeatResponseDelay has elapsed
+    pub fn send_message(&mut self, message_sender: &MessageSender) {
+        for writer_proxy in self.matched_writers.iter_mut() {
+            if writer_proxy.is_time_for_heartbeat_response(self.heartbeat_response_delay) {
+                writer_proxy.send_message(&self.guid, message_sender);
+            }
+        }
+    }
+
     pub fn is_historical_data_received(&self) -> bool {
         !self
             .matched_writers
@@ -215,3 +266,92 @@ impl RtpsStatefulReader {
             .any(|p| !p.is_historical_data_received())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rtps::messages::overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+        transport::types::{DurabilityKind, EntityId, ENTITYID_UNKNOWN, LOCATOR_KIND_UDP_V4},
+    };
+    use std::net::UdpSocket;
+
+    struct EmptyHistoryCache;
+
+    impl HistoryCache for EmptyHistoryCache {
+        fn add_change(&mut self, _cache_change: CacheChange) {}
+
+        fn remove_change(&mut self, _sequence_number: i64) {}
+    }
+
+    fn received_acknack_count(receiver: &UdpSocket) -> usize {
+        let mut buf = [0; 65507];
+        let mut acknack_count = 0;
+        while let Ok(length) = receiver.recv(&mut buf) {
+            let rtps_message = RtpsMessageRead::try_from(&buf[..length]).unwrap();
+            acknack_count += rtps_message
+                .submessages()
+                .iter()
+                .filter(|s| matches!(s, RtpsSubmessageReadKind::AckNack(_)))
+                .count();
+        }
+        acknack_count
+    }
+
+    #[test]
+    fn heartbeat_is_answered_after_heartbeat_response_delay() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+        let mut address = [0; 16];
+        address[12..].copy_from_slice(&[127, 0, 0, 1]);
+        let writer_locator = Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            receiver.local_addr().unwrap().port() as u32,
+            address,
+        );
+        let message_sender =
+            MessageSender::new([2; 12], Some(UdpSocket::bind("127.0.0.1:0").unwrap()), None);
+        let heartbeat_response_delay = Duration::from_millis(300);
+        let reader_guid = Guid::new([2; 12], EntityId::new([0, 0, 1], 7));
+        let mut reader = RtpsStatefulReader::new(
+            reader_guid,
+            Box::new(EmptyHistoryCache),
+            heartbeat_response_delay,
+        );
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 2], 2));
+        reader.add_matched_writer(&WriterProxy {
+            remote_writer_guid: writer_guid,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![writer_locator],
+            multicast_locator_list: vec![],
+        });
+
+        let heartbeat = HeartbeatSubmessage::new(
+            false,
+            false,
+            reader_guid.entity_id(),
+            writer_guid.entity_id(),
+            1,
+            1,
+            1,
+        );
+        reader.on_heartbeat_submessage_received(
+            &heartbeat,
+            writer_guid.prefix(),
+            &[],
+            &message_sender,
+        );
+        reader.send_message(&message_sender);
+        assert_eq!(received_acknack_count(&receiver), 0);
+
+        std::thread::sleep(heartbeat_response_delay);
+        reader.send_message(&message_sender);
+        assert_eq!(received_acknack_count(&receiver), 1);
+        reader.send_message(&message_sender);
+        assert_eq!(received_acknack_count(&receiver), 0);
+    }
+}
@@ -5,10 +5,9 @@ use crate::transport::{
 };
 
 use super::{
-    behavior_types::Duration,
     message_sender::MessageSender,
     messages::{
-        submessage_elements::{ParameterList, SequenceNumberSet, SerializedDataFragment},
+        submessage_elements::{SequenceNumberSet, SerializedDataFragment},
         submessages::{
             ack_nack::AckNackSubmessage, data_frag::DataFragSubmessage, gap::GapSubmessage,
             info_destination::InfoDestinationSubmessage, info_timestamp::InfoTimestampSubmessage,
@@ -17,6 +16,8 @@ use super::{
         types::TIME_INVALID,
     },
     reader_proxy::RtpsReaderProxy,
+    shared_memory::is_shared_memory_only,
+    transport::RtpsReliabilityTiming,
 };
 use crate::transport::types::{
     DurabilityKind, EntityId, Guid, GuidPrefix, SequenceNumber, ENTITYID_UNKNOWN,
@@ -26,17 +27,21 @@ pub struct RtpsStatefulWriter {
     guid: Guid,
     changes: Vec<CacheChange>,
     matched_readers: Vec<RtpsReaderProxy>,
-    heartbeat_period: Duration,
+    reliability_timing: RtpsReliabilityTiming,
     data_max_size_serialized: usize,
 }
 
 impl RtpsStatefulWriter {
-    pub fn new(guid: Guid, data_max_size_serialized: usize) -> Self {
+    pub fn new(
+        guid: Guid,
+        data_max_size_serialized: usize,
+        reliability_timing: &RtpsReliabilityTiming,
+    ) -> Self {
         Self {
             guid,
             changes: Vec::new(),
             matched_readers: Vec::new(),
-            heartbeat_period: Duration::from_millis(200),
+            reliability_timing: *reliability_timing,
             data_max_size_serialized,
         }
     }
@@ -49,6 +54,18 @@ impl RtpsStatefulWriter {
         self.data_max_size_serialized
     }
 
+    pub fn reliability_timing(&self) -> &RtpsReliabilityTiming {
+        &self.reliability_timing
+    }
+
+    pub fn set_reliability_timing(&mut self, reliability_timing: RtpsReliabilityTiming) {
+        self.reliability_timing = reliability_timing;
+    }
+
+    pub fn changes(&self) -> &[CacheChange] {
+        &self.changes
+    }
+
     pub fn add_change(&mut self, cache_change: CacheChange, message_sender: &MessageSender) {
         self.changes.push(cache_change);
         self.send_message(message_sender);
@@ -68,11 +85,13 @@ impl RtpsStatefulWriter {
     }
 
     pub fn add_matched_reader(&mut self, reader_proxy: &ReaderProxy) {
-        if self
+        // A reader which is announced again might have changed its content filter
+        if let Some(rp) = self
             .matched_readers
-            .iter()
-            .any(|rp| rp.remote_reader_guid() == reader_proxy.remote_reader_guid)
+            .iter_mut()
+            .find(|rp| rp.remote_reader_guid() == reader_proxy.remote_reader_guid)
         {
+            rp.set_content_filter(reader_proxy.content_filter.clone());
             return;
         }
 
@@ -96,6 +115,7 @@ impl RtpsStatefulWriter {
             true,
             reader_proxy.reliability_kind,
             first_relevant_sample_seq_num,
+            reader_proxy.content_filter.clone(),
         );
         self.matched_readers.push(rtps_reader_proxy);
     }
@@ -105,6 +125,39 @@ impl RtpsStatefulWriter {
             .retain(|rp| rp.remote_reader_guid() != reader_guid);
     }
 
+    /// Send a HEARTBEAT with the liveliness flag set to all the matched readers
+    /// to manually assert the liveliness of the writer (8.4.13.5)
+    pub fn send_liveliness_heartbeat(&mut self, message_sender: &MessageSender) {
+        let first_sn = self
+            .changes
+            .iter()
+            .map(|cc| cc.sequence_number())
+            .min()
+            .unwrap_or(1);
+        let last_sn = self
+            .changes
+            .iter()
+            .map(|cc| cc.sequence_number())
+            .max()
+            .unwrap_or(0);
+        for reader_proxy in &mut self.matched_readers {
+            let heartbeat_submessage = Box::new(
+                reader_proxy
+                    .heartbeat_machine()
+                    .generate_new_liveliness_heartbeat(self.guid.entity_id(), first_sn, last_sn),
+            );
+            let info_dst = Box::new(InfoDestinationSubmessage::new(
+                reader_proxy.remote_reader_guid().prefix(),
+            ));
+            message_sender.write_writer_message(
+                self.guid.entity_id(),
+                reader_proxy.reliability(),
+                &[info_dst, heartbeat_submessage],
+                reader_proxy.unicast_locator_list().to_vec(),
+            );
+        }
+    }
+
     pub fn send_message(&mut self, message_sender: &MessageSender) {
         for reader_proxy in &mut self.matched_readers {
             match reader_proxy.reliability() {
@@ -122,7 +175,7 @@ impl RtpsStatefulWriter {
                     self.changes.iter().map(|cc| cc.sequence_number()).min(),
                     self.changes.iter().map(|cc| cc.sequence_number()).max(),
                     self.data_max_size_serialized,
-                    self.heartbeat_period,
+                    &self.reliability_timing,
                     message_sender,
                 ),
             }
@@ -147,7 +200,18 @@ impl RtpsStatefulWriter {
                     && acknack_submessage.count() > reader_proxy.last_received_acknack_count()
                 {
                     reader_proxy.acked_changes_set(acknack_submessage.reader_sn_state().base() - 1);
-                    reader_proxy.requested_changes_set(acknack_submessage.reader_sn_state().set());
+                    let requested_changes: Vec<_> = acknack_submessage
+                        .reader_sn_state()
+                        .set()
+                        .filter(|&sn| {
+                            !reader_proxy.is_change_recently_sent(
+                                sn,
+                                self.reliability_timing.nack_suppression_duration,
+                            )
+                        })
+                        .collect();
+                    reader_proxy.requested_changes_set(requested_changes.into_iter());
+                    reader_proxy.nack_received();
 
                     reader_proxy.set_last_received_acknack_count(acknack_submessage.count());
 
@@ -158,7 +222,7 @@ impl RtpsStatefulWriter {
                         self.changes.iter().map(|cc| cc.sequence_number()).min(),
                         self.changes.iter().map(|cc| cc.sequence_number()).max(),
                         self.data_max_size_serialized,
-                        self.heartbeat_period,
+                        &self.reliability_timing,
                         message_sender,
                     );
                 }
@@ -182,8 +246,14 @@ impl RtpsStatefulWriter {
             if reader_proxy.reliability() == ReliabilityKind::Reliable
                 && nackfrag_submessage.count() > reader_proxy.last_received_nack_frag_count()
             {
-                reader_proxy
-                    .requested_changes_set(std::iter::once(nackfrag_submessage.writer_sn()));
+                let writer_sn = nackfrag_submessage.writer_sn();
+                if !reader_proxy.is_change_recently_sent(
+                    writer_sn,
+                    self.reliability_timing.nack_suppression_duration,
+                ) {
+                    reader_proxy.requested_changes_set(std::iter::once(writer_sn));
+                    reader_proxy.nack_received();
+                }
                 reader_proxy.set_last_received_nack_frag_count(nackfrag_submessage.count());
 
                 send_message_to_reader_proxy_reliable(
@@ -193,7 +263,7 @@ impl RtpsStatefulWriter {
                     self.changes.iter().map(|cc| cc.sequence_number()).min(),
                     self.changes.iter().map(|cc| cc.sequence_number()).max(),
                     self.data_max_size_serialized,
-                    self.heartbeat_period,
+                    &self.reliability_timing,
                     message_sender,
                 );
             }
@@ -201,6 +271,25 @@ impl RtpsStatefulWriter {
     }
 }
 
+// Readers reached only through shared memory are not limited by the datagram size and
+// receive the data fitting in the shared memory segment in a single DATA submessage
+fn reader_data_max_size_serialized(
+    reader_proxy: &RtpsReaderProxy,
+    data_size: usize,
+    data_max_size_serialized: usize,
+    message_sender: &MessageSender,
+) -> usize {
+    match message_sender.shared_memory_data_max_size_serialized() {
+        Some(shared_memory_data_max_size_serialized)
+            if is_shared_memory_only(reader_proxy.unicast_locator_list())
+                && data_size <= shared_memory_data_max_size_serialized =>
+        {
+            data_max_size_serialized.max(data_size)
+        }
+        _ => data_max_size_serialized,
+    }
+}
+
 fn send_message_to_reader_proxy_best_effort(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
@@ -243,7 +332,9 @@ fn send_message_to_reader_proxy_best_effort(
                 SequenceNumberSet::new(gap_end_sequence_number + 1, []),
             ));
 
-            message_sender.write_message(
+            message_sender.write_writer_message(
+                writer_id,
+                reader_proxy.reliability(),
                 &[gap_submessage],
                 reader_proxy.unicast_locator_list().to_vec(),
             );
@@ -252,7 +343,14 @@ fn send_message_to_reader_proxy_best_effort(
         } else if let Some(cache_change) = changes
             .iter()
             .find(|cc| cc.sequence_number() == next_unsent_change_seq_num)
+            .filter(|cc| reader_proxy.is_relevant(cc))
         {
+            let data_max_size_serialized = reader_data_max_size_serialized(
+                reader_proxy,
+                cache_change.data_value().len(),
+                data_max_size_serialized,
+                message_sender,
+            );
             let number_of_fragments = cache_change
                 .data_value()
                 .len()
@@ -274,7 +372,9 @@ fn send_message_to_reader_proxy_best_effort(
                     let inline_qos_flag = true;
                     let key_flag = match cache_change.kind() {
                         ChangeKind::Alive => false,
-                        ChangeKind::NotAliveDisposed | ChangeKind::NotAliveUnregistered => true,
+                        ChangeKind::NotAliveDisposed
+                        | ChangeKind::NotAliveUnregistered
+                        | ChangeKind::NotAliveDisposedUnregistered => true,
                         _ => todo!(),
                     };
                     let non_standard_payload_flag = false;
@@ -307,11 +407,15 @@ fn send_message_to_reader_proxy_best_effort(
                         fragments_in_submessage,
                         fragment_size,
                         data_size,
-                        ParameterList::new(vec![]),
+                        cache_change.inline_qos(),
                         serialized_payload,
                     ));
 
-                    message_sender.write_message(
+                    message_sender.write_writer_change_message(
+                        writer_id,
+                        reader_proxy.reliability(),
+                        cache_change.sequence_number(),
+                        Some(fragment_starting_num),
                         &[info_dst, info_timestamp, data_frag],
                         reader_proxy.unicast_locator_list().to_vec(),
                     );
@@ -333,13 +437,19 @@ fn send_message_to_reader_proxy_best_effort(
                         writer_id,
                     ));
 
-                message_sender.write_message(
+                message_sender.write_writer_change_message(
+                    writer_id,
+                    reader_proxy.reliability(),
+                    cache_change.sequence_number(),
+                    None,
                     &[info_dst, info_timestamp, data_submessage],
                     reader_proxy.unicast_locator_list().to_vec(),
                 );
             }
         } else {
-            message_sender.write_message(
+            message_sender.write_writer_message(
+                writer_id,
+                reader_proxy.reliability(),
                 &[Box::new(GapSubmessage::new(
                     ENTITYID_UNKNOWN,
                     writer_id,
@@ -362,7 +472,7 @@ fn send_message_to_reader_proxy_reliable(
     seq_num_min: Option<SequenceNumber>,
     seq_num_max: Option<SequenceNumber>,
     data_max_size_serialized: usize,
-    heartbeat_period: Duration,
+    reliability_timing: &RtpsReliabilityTiming,
     message_sender: &MessageSender,
 ) {
     // Top part of the state machine - Figure 8.19 RTPS standard
@@ -388,7 +498,9 @@ fn send_message_to_reader_proxy_reliable(
                 let info_dst = Box::new(InfoDestinationSubmessage::new(
                     reader_proxy.remote_reader_guid().prefix(),
                 ));
-                message_sender.write_message(
+                message_sender.write_writer_message(
+                    writer_id,
+                    reader_proxy.reliability(),
                     &[info_dst, gap_submessage, heartbeat_submessage],
                     reader_proxy.unicast_locator_list().to_vec(),
                 );
@@ -406,11 +518,14 @@ fn send_message_to_reader_proxy_reliable(
             }
             reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
         }
-    } else if !reader_proxy.unacked_changes(seq_num_max) {
-        // Idle
+    } else if !reader_proxy.unacked_changes(seq_num_max)
+        && reader_proxy.heartbeat_machine().is_heartbeat_sent()
+    {
+        // Idle. A writer without changes still sends a first heartbeat so that the
+        // reader knows that there is no historical data to wait for.
     } else if reader_proxy
         .heartbeat_machine()
-        .is_time_for_heartbeat(heartbeat_period.into())
+        .is_time_for_heartbeat(reliability_timing.heartbeat_period)
     {
         let first_sn = seq_num_min.unwrap_or(1);
         let last_sn = seq_num_max.unwrap_or(0);
@@ -424,14 +539,19 @@ fn send_message_to_reader_proxy_reliable(
             reader_proxy.remote_reader_guid().prefix(),
         ));
 
-        message_sender.write_message(
+        message_sender.write_writer_message(
+            writer_id,
+            reader_proxy.reliability(),
             &[info_dst, heartbeat_submessage],
             reader_proxy.unicast_locator_list().to_vec(),
         );
     }
 
-    // Middle-part of the state-machine - Figure 8.19 RTPS standard
-    if !reader_proxy.requested_changes().is_empty() {
+    // Middle-part of the state-machine - Figure 8.19 RTPS standard. The requested changes
+    // are sent once the nackResponseDelay has elapsed since the request.
+    if !reader_proxy.requested_changes().is_empty()
+        && reader_proxy.is_time_for_nack_response(reliability_timing.nack_response_delay)
+    {
         while let Some(next_requested_change_seq_num) = reader_proxy.next_requested_change() {
             // "a_change.status := UNDERWAY;" should be done by next_requested_change() as
             // it's not done here to avoid the change being a mutable reference
@@ -449,6 +569,7 @@ fn send_message_to_reader_proxy_reliable(
                 message_sender,
             );
         }
+        reader_proxy.nack_response_sent();
     }
 }
 
@@ -467,7 +588,21 @@ fn send_change_message_reader_proxy_reliable(
         .iter()
         .find(|cc| cc.sequence_number() == change_seq_num)
     {
-        Some(cache_change) if change_seq_num > reader_proxy.first_relevant_sample_seq_num() => {
+        Some(cache_change)
+            if change_seq_num > reader_proxy.first_relevant_sample_seq_num()
+                && reader_proxy.is_relevant(cache_change) =>
+        {
+            reader_proxy.change_sent(change_seq_num);
+            // The length of a DATA submessage bigger than a fragment may not fit in its
+            // header, in which case it must be the last submessage of the message
+            let is_data_bigger_than_fragment =
+                cache_change.data_value().len() > data_max_size_serialized;
+            let data_max_size_serialized = reader_data_max_size_serialized(
+                reader_proxy,
+                cache_change.data_value().len(),
+                data_max_size_serialized,
+                message_sender,
+            );
             let number_of_fragments = cache_change
                 .data_value()
                 .len()
@@ -489,7 +624,9 @@ fn send_change_message_reader_proxy_reliable(
                     let inline_qos_flag = true;
                     let key_flag = match cache_change.kind() {
                         ChangeKind::Alive => false,
-                        ChangeKind::NotAliveDisposed | ChangeKind::NotAliveUnregistered => true,
+                        ChangeKind::NotAliveDisposed
+                        | ChangeKind::NotAliveUnregistered
+                        | ChangeKind::NotAliveDisposedUnregistered => true,
                         _ => todo!(),
                     };
                     let non_standard_payload_flag = false;
@@ -522,11 +659,15 @@ fn send_change_message_reader_proxy_reliable(
                         fragments_in_submessage,
                         fragment_size,
                         data_size,
-                        ParameterList::new(vec![]),
+                        cache_change.inline_qos(),
                         serialized_payload,
                     ));
 
-                    message_sender.write_message(
+                    message_sender.write_writer_change_message(
+                        writer_id,
+                        reader_proxy.reliability(),
+                        cache_change.sequence_number(),
+                        Some(fragment_starting_num),
                         &[info_dst, info_timestamp, data_frag],
                         reader_proxy.unicast_locator_list().to_vec(),
                     );
@@ -556,10 +697,34 @@ fn send_change_message_reader_proxy_reliable(
                         .generate_new_heartbeat(writer_id, first_sn, last_sn),
                 );
 
-                message_sender.write_message(
-                    &[info_dst, info_timestamp, data_submessage, heartbeat],
-                    reader_proxy.unicast_locator_list().to_vec(),
-                );
+                if is_data_bigger_than_fragment {
+                    message_sender.write_writer_change_message(
+                        writer_id,
+                        reader_proxy.reliability(),
+                        cache_change.sequence_number(),
+                        None,
+                        &[info_dst, info_timestamp, data_submessage],
+                        reader_proxy.unicast_locator_list().to_vec(),
+                    );
+                    let info_dst = Box::new(InfoDestinationSubmessage::new(
+                        reader_proxy.remote_reader_guid().prefix(),
+                    ));
+                    message_sender.write_writer_message(
+                        writer_id,
+                        reader_proxy.reliability(),
+                        &[info_dst, heartbeat],
+                        reader_proxy.unicast_locator_list().to_vec(),
+                    );
+                } else {
+                    message_sender.write_writer_change_message(
+                        writer_id,
+                        reader_proxy.reliability(),
+                        cache_change.sequence_number(),
+                        None,
+                        &[info_dst, info_timestamp, data_submessage, heartbeat],
+                        reader_proxy.unicast_locator_list().to_vec(),
+                    );
+                }
             }
         }
         _ => {
@@ -574,10 +739,139 @@ fn send_change_message_reader_proxy_reliable(
                 SequenceNumberSet::new(change_seq_num + 1, []),
             ));
 
-            message_sender.write_message(
+            message_sender.write_writer_message(
+                writer_id,
+                reader_proxy.reliability(),
                 &[info_dst, gap_submessage],
                 reader_proxy.unicast_locator_list().to_vec(),
             );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rtps::messages::overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+        transport::types::{Locator, LOCATOR_KIND_UDP_V4},
+    };
+    use std::net::UdpSocket;
+
+    const READER_GUID: Guid = Guid::new([2; 12], EntityId::new([0, 0, 1], 7));
+
+    fn reader_socket_and_proxy() -> (UdpSocket, ReaderProxy) {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(std::time::Duration::from_millis(100)))
+            .unwrap();
+        let mut address = [0; 16];
+        address[12..].copy_from_slice(&[127, 0, 0, 1]);
+        let locator = Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            receiver.local_addr().unwrap().port() as u32,
+            address,
+        );
+        let reader_proxy = ReaderProxy {
+            remote_reader_guid: READER_GUID,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            content_filter: None,
+        };
+        (receiver, reader_proxy)
+    }
+
+    fn cache_change(writer_guid: Guid, sequence_number: SequenceNumber) -> CacheChange {
+        CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid,
+            sequence_number,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: vec![0, 0, 0, 0, 1, 2, 3, 4].into(),
+            coherent_set: None,
+            group_coherent_set: None,
+            writer_group_info: None,
+            group_sequence_number: None,
+        }
+    }
+
+    fn requesting_acknack(writer_guid: Guid, sequence_number: SequenceNumber) -> AckNackSubmessage {
+        AckNackSubmessage::new(
+            false,
+            READER_GUID.entity_id(),
+            writer_guid.entity_id(),
+            SequenceNumberSet::new(sequence_number, [sequence_number]),
+            1,
+        )
+    }
+
+    fn received_data_count(receiver: &UdpSocket) -> usize {
+        let mut buf = [0; 65507];
+        let mut data_count = 0;
+        while let Ok(length) = receiver.recv(&mut buf) {
+            let rtps_message = RtpsMessageRead::try_from(&buf[..length]).unwrap();
+            data_count += rtps_message
+                .submessages()
+                .iter()
+                .filter(|s| matches!(s, RtpsSubmessageReadKind::Data(_)))
+                .count();
+        }
+        data_count
+    }
+
+    #[test]
+    fn requested_changes_are_sent_after_nack_response_delay() {
+        let (receiver, reader_proxy) = reader_socket_and_proxy();
+        let message_sender =
+            MessageSender::new([1; 12], Some(UdpSocket::bind("127.0.0.1:0").unwrap()), None);
+        let reliability_timing = RtpsReliabilityTiming {
+            nack_response_delay: std::time::Duration::from_millis(300),
+            ..Default::default()
+        };
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 2], 2));
+        let mut writer = RtpsStatefulWriter::new(writer_guid, 1344, &reliability_timing);
+        writer.add_matched_reader(&reader_proxy);
+        writer.add_change(cache_change(writer_guid, 1), &message_sender);
+        assert_eq!(received_data_count(&receiver), 1);
+
+        writer.on_acknack_submessage_received(
+            &requesting_acknack(writer_guid, 1),
+            READER_GUID.prefix(),
+            &message_sender,
+        );
+        writer.send_message(&message_sender);
+        assert_eq!(received_data_count(&receiver), 0);
+
+        std::thread::sleep(reliability_timing.nack_response_delay);
+        writer.send_message(&message_sender);
+        assert_eq!(received_data_count(&receiver), 1);
+    }
+
+    #[test]
+    fn requests_for_recently_sent_changes_are_ignored() {
+        let (receiver, reader_proxy) = reader_socket_and_proxy();
+        let message_sender =
+            MessageSender::new([1; 12], Some(UdpSocket::bind("127.0.0.1:0").unwrap()), None);
+        let reliability_timing = RtpsReliabilityTiming {
+            nack_suppression_duration: std::time::Duration::from_secs(10),
+            ..Default::default()
+        };
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 2], 2));
+        let mut writer = RtpsStatefulWriter::new(writer_guid, 1344, &reliability_timing);
+        writer.add_matched_reader(&reader_proxy);
+        writer.add_change(cache_change(writer_guid, 1), &message_sender);
+        assert_eq!(received_data_count(&receiver), 1);
+
+        writer.on_acknack_submessage_received(
+            &requesting_acknack(writer_guid, 1),
+            READER_GUID.prefix(),
+            &message_sender,
+        );
+        assert_eq!(received_data_count(&receiver), 0);
+    }
+}
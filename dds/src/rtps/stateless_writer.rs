@@ -1,6 +1,6 @@
 use crate::transport::{
     history_cache::CacheChange,
-    types::{Guid, Locator, SequenceNumber, ENTITYID_UNKNOWN},
+    types::{Guid, Locator, ReliabilityKind, SequenceNumber, ENTITYID_UNKNOWN},
 };
 
 use super::{
@@ -76,7 +76,11 @@ impl RtpsStatelessWriter {
                         cache_change.as_data_submessage(ENTITYID_UNKNOWN, self.guid.entity_id()),
                     );
 
-                    message_sender.write_message(
+                    message_sender.write_writer_change_message(
+                        self.guid.entity_id(),
+                        ReliabilityKind::BestEffort,
+                        cache_change.sequence_number(),
+                        None,
                         &[info_ts_submessage, data_submessage],
                         vec![reader_locator.locator()],
                     );
@@ -88,7 +92,12 @@ impl RtpsStatelessWriter {
                         SequenceNumberSet::new(unsent_change_seq_num + 1, []),
                     ));
 
-                    message_sender.write_message(&[gap_submessage], vec![reader_locator.locator()]);
+                    message_sender.write_writer_message(
+                        self.guid.entity_id(),
+                        ReliabilityKind::BestEffort,
+                        &[gap_submessage],
+                        vec![reader_locator.locator()],
+                    );
                 }
                 reader_locator.set_highest_sent_change_sn(unsent_change_seq_num);
             }
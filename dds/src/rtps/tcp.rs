@@ -0,0 +1,718 @@
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, TrySendError},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use crate::{
+    infrastructure::error::DdsResult,
+    runtime::actor::ActorAddress,
+    transport::{
+        factory::TransportParticipantFactory,
+        participant::TransportParticipant,
+        types::{GuidPrefix, Locator, Long},
+    },
+};
+
+use super::{
+    error::{RtpsError, RtpsErrorKind, RtpsResult},
+    flow_controller::{check_flow_controllers, RtpsFlowController},
+    messages::overall_structure::RtpsMessageRead,
+    participant::{self, RtpsParticipant},
+    transport::{RtpsReliabilityTiming, RtpsTransport, MAX_DATAGRAM_SIZE},
+};
+
+/// Vendor specific locator kind of the TCPv4 servers. The port is the port on which the
+/// server accepts connections. The kind of the RTPS TCP PSM is not used since the messages
+/// are exchanged with the Dust DDS framing instead of the framing of the TCP PSM.
+pub const LOCATOR_KIND_TCP_SERVER_V4: Long = 0x0100_0003;
+
+/// Vendor specific locator kind of the TCPv6 servers. The port is the port on which the
+/// server accepts connections. The kind of the RTPS TCP PSM is not used since the messages
+/// are exchanged with the Dust DDS framing instead of the framing of the TCP PSM.
+pub const LOCATOR_KIND_TCP_SERVER_V6: Long = 0x0100_0004;
+
+/// Vendor specific locator kind of the participants reached through an established TCP
+/// connection. The address holds the GUID prefix of the participant and the port tells
+/// whether the message is metatraffic or user-defined traffic.
+pub const LOCATOR_KIND_TCP_CONNECTION: Long = 0x0100_0001;
+
+/// Logical port of the metatraffic sent through a TCP connection
+pub const METATRAFFIC_PORT: u32 = 0;
+
+/// Logical port of the user-defined traffic sent through a TCP connection
+pub const USER_DEFINED_PORT: u32 = 1;
+
+// Each message is preceded by its length and the logical port of its destination, both as
+// big-endian 32-bit integers. This framing is specific to Dust DDS.
+const FRAME_HEADER_SIZE: usize = 8;
+// The messages are built to fit in a UDP datagram whatever the transport
+const MAX_FRAME_SIZE: usize = MAX_DATAGRAM_SIZE;
+const DEFAULT_FRAGMENT_SIZE: usize = 60000;
+const DEFAULT_MAX_CONNECTIONS: usize = 64;
+// Number of messages waiting to be written to a connection beyond which new ones are dropped
+const WRITE_QUEUE_SIZE: usize = 256;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+// Time after which a connection whose peer stopped writing in the middle of a message, or
+// stopped reading the messages written to it, is closed
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+const RECONNECT_PERIOD: Duration = Duration::from_secs(1);
+const ACCEPT_POLL_PERIOD: Duration = Duration::from_millis(50);
+
+/// Builder for the [`TcpParticipantFactory`]
+pub struct TcpParticipantFactoryBuilder {
+    listen_address: Option<SocketAddr>,
+    server_address_list: Vec<SocketAddr>,
+    fragment_size: usize,
+    max_connections: usize,
+    reliability_timing: RtpsReliabilityTiming,
+    flow_controllers: Vec<RtpsFlowController>,
+}
+
+impl Default for TcpParticipantFactoryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TcpParticipantFactoryBuilder {
+    /// Construct a TCP transport factory builder with all the default options.
+    pub fn new() -> Self {
+        Self {
+            listen_address: None,
+            server_address_list: Vec::new(),
+            fragment_size: DEFAULT_FRAGMENT_SIZE,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            reliability_timing: RtpsReliabilityTiming::default(),
+            flow_controllers: Vec::new(),
+        }
+    }
+
+    /// Set the address on which the participant accepts connections as server. [`None`] means that the participant only acts as client.
+    /// Creating a participant fails if the address can't be bound, e.g. because another participant already listens on it
+    pub fn listen_address(mut self, listen_address: Option<SocketAddr>) -> Self {
+        self.listen_address = listen_address;
+        self
+    }
+
+    /// Set the addresses of the servers to which the participant connects as client. The connections are restored when lost
+    pub fn server_addresses(mut self, server_address_list: Vec<SocketAddr>) -> Self {
+        self.server_address_list = server_address_list;
+        self
+    }
+
+    /// Set the maximum size for the data fragments. Types with serialized data above this size will be transmitted as fragments.
+    pub fn fragment_size(mut self, fragment_size: usize) -> Self {
+        self.fragment_size = fragment_size;
+        self
+    }
+
+    /// Set the maximum number of connections of the participant, both accepted and opened. The connections accepted
+    /// beyond this number are closed right away.
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Set the heartbeat period and the response delays of the reliable protocol
+    pub fn reliability_timing(mut self, reliability_timing: RtpsReliabilityTiming) -> Self {
+        self.reliability_timing = reliability_timing;
+        self
+    }
+
+    /// Set the flow controllers to which the data writers can be attached with the [`PublishModeQosPolicy`](crate::infrastructure::qos_policy::PublishModeQosPolicy) to limit their bandwidth
+    pub fn flow_controllers(mut self, flow_controllers: Vec<RtpsFlowController>) -> Self {
+        self.flow_controllers = flow_controllers;
+        self
+    }
+
+    /// Build a new TCP participant factory
+    pub fn build(self) -> Result<TcpParticipantFactory, String> {
+        let fragment_size_range = 8..=65000;
+        if !fragment_size_range.contains(&self.fragment_size) {
+            Err(format!(
+                "Interface size out of range. Value must be between in {:?}",
+                fragment_size_range
+            ))
+        } else if self.listen_address.is_none() && self.server_address_list.is_empty() {
+            Err("A listen address or at least one server address is required".to_string())
+        } else if let Some(a) = self
+            .server_address_list
+            .iter()
+            .find(|a| a.ip().is_unspecified() || a.port() == 0)
+        {
+            Err(format!(
+                "Server address {a} must have a specified IP address and port"
+            ))
+        } else if self.max_connections == 0 {
+            Err("Maximum number of connections must be greater than 0".to_string())
+        } else if self.reliability_timing.heartbeat_period.is_zero() {
+            Err("Heartbeat period must be greater than 0".to_string())
+        } else if let Err(e) = check_flow_controllers(&self.flow_controllers) {
+            Err(e)
+        } else {
+            Ok(TcpParticipantFactory {
+                listen_address: self.listen_address,
+                server_address_list: self.server_address_list,
+                fragment_size: self.fragment_size,
+                max_connections: self.max_connections,
+                reliability_timing: self.reliability_timing,
+                flow_controllers: self.flow_controllers,
+            })
+        }
+    }
+}
+
+/// Transport factory whose participants exchange all the traffic, discovery included,
+/// through persistent TCP connections. Each participant accepts connections on the
+/// listen address and connects as client to the server addresses, so only the
+/// participants connected to each other discover each other. A participant behind a
+/// NAT or a firewall is reached through the connection it opened to the server.
+///
+/// The messages are exchanged with a framing specific to Dust DDS, and the locators use
+/// vendor specific kinds, so these participants only communicate with Dust DDS participants
+/// using this transport and not with implementations of the RTPS TCP PSM.
+pub struct TcpParticipantFactory {
+    listen_address: Option<SocketAddr>,
+    server_address_list: Vec<SocketAddr>,
+    fragment_size: usize,
+    max_connections: usize,
+    reliability_timing: RtpsReliabilityTiming,
+    flow_controllers: Vec<RtpsFlowController>,
+}
+
+impl TransportParticipantFactory for TcpParticipantFactory {
+    fn create_participant(
+        &self,
+        guid_prefix: GuidPrefix,
+        _domain_id: i32,
+    ) -> DdsResult<Box<dyn TransportParticipant>> {
+        let transport = RtpsTransport::new_tcp(
+            guid_prefix,
+            self.fragment_size,
+            self.listen_address,
+            &self.server_address_list,
+            self.max_connections,
+            self.reliability_timing,
+        )?;
+        transport.add_flow_controllers(&self.flow_controllers);
+        Ok(Box::new(transport))
+    }
+}
+
+/// Locator through which the participant with the given GUID prefix is reached
+pub fn connection_locator(guid_prefix: GuidPrefix, port: u32) -> Locator {
+    let mut address = [0; 16];
+    address[4..].copy_from_slice(&guid_prefix);
+    Locator::new(LOCATOR_KIND_TCP_CONNECTION, port, address)
+}
+
+/// Locator of the server accepting connections on the given address
+pub fn server_locator(server_address: SocketAddr) -> Locator {
+    match server_address {
+        SocketAddr::V4(a) => {
+            let mut address = [0; 16];
+            address[12..].copy_from_slice(&a.ip().octets());
+            Locator::new(LOCATOR_KIND_TCP_SERVER_V4, a.port() as u32, address)
+        }
+        SocketAddr::V6(a) => Locator::new(
+            LOCATOR_KIND_TCP_SERVER_V6,
+            a.port() as u32,
+            a.ip().octets(),
+        ),
+    }
+}
+
+fn server_address(locator: &Locator) -> Option<SocketAddr> {
+    let address = locator.address();
+    match locator.kind() {
+        LOCATOR_KIND_TCP_SERVER_V4 => Some(SocketAddr::V4(SocketAddrV4::new(
+            Ipv4Addr::new(address[12], address[13], address[14], address[15]),
+            locator.port() as u16,
+        ))),
+        LOCATOR_KIND_TCP_SERVER_V6 => Some(SocketAddr::V6(SocketAddrV6::new(
+            Ipv6Addr::from(address),
+            locator.port() as u16,
+            0,
+            0,
+        ))),
+        _ => None,
+    }
+}
+
+fn frame(port: u32, buf: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + buf.len());
+    frame.extend_from_slice(&(buf.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&port.to_be_bytes());
+    frame.extend_from_slice(buf);
+    frame
+}
+
+// Read the next frame. None if no frame starts before the read timeout of the stream, in
+// which case nothing is consumed. Once a frame starts, the read timeout applies to the
+// reception of each of its parts.
+fn read_frame(stream: &mut TcpStream) -> RtpsResult<Option<(u32, Vec<u8>)>> {
+    let mut header = [0; FRAME_HEADER_SIZE];
+    let header_start_length = match stream.read(&mut header) {
+        Ok(0) => {
+            return Err(RtpsError::new(
+                RtpsErrorKind::NotEnoughData,
+                "TCP connection closed",
+            ))
+        }
+        Ok(length) => length,
+        Err(e)
+            if matches!(
+                e.kind(),
+                ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+            ) =>
+        {
+            return Ok(None)
+        }
+        Err(e) => return Err(e.into()),
+    };
+    stream.read_exact(&mut header[header_start_length..])?;
+    let length = u32::from_be_bytes(header[..4].try_into().expect("4 bytes")) as usize;
+    let port = u32::from_be_bytes(header[4..].try_into().expect("4 bytes"));
+    if length > MAX_FRAME_SIZE {
+        return Err(RtpsError::new(
+            RtpsErrorKind::InvalidData,
+            "TCP frame bigger than the maximum size",
+        ));
+    }
+    let mut message = vec![0; length];
+    stream.read_exact(&mut message)?;
+    Ok(Some((port, message)))
+}
+
+struct TcpConnection {
+    id: u64,
+    stream: TcpStream,
+    // Frames written to the stream by the thread of the connection so that a slow
+    // connection doesn't block the senders
+    frame_sender: mpsc::SyncSender<Vec<u8>>,
+    // Address of the server when the connection was opened by this participant
+    server_address: Option<SocketAddr>,
+    // Known once the first message is received on the connection
+    remote_guid_prefix: Option<GuidPrefix>,
+}
+
+struct TcpConnectionListInner {
+    next_id: u64,
+    connection_list: Vec<TcpConnection>,
+    max_connections: usize,
+    is_closed: bool,
+}
+
+/// Connections of a participant, shared between the sender and the threads accepting,
+/// opening, reading and writing the connections
+#[derive(Clone)]
+pub struct TcpConnectionList(Arc<Mutex<TcpConnectionListInner>>);
+
+impl TcpConnectionList {
+    pub fn new(max_connections: usize) -> Self {
+        Self(Arc::new(Mutex::new(TcpConnectionListInner {
+            next_id: 0,
+            connection_list: Vec::new(),
+            max_connections,
+            is_closed: false,
+        })))
+    }
+
+    // Register the connection and spawn a thread which writes the frames sent to it.
+    // None if the connection can't be added, e.g. because there are too many connections
+    fn add(&self, stream: &TcpStream, server_address: Option<SocketAddr>) -> Option<u64> {
+        let mut inner = self.0.lock().expect("Connection list lock poisoned");
+        if inner.is_closed || inner.connection_list.len() >= inner.max_connections {
+            return None;
+        }
+        let (frame_sender, frame_receiver) = mpsc::sync_channel::<Vec<u8>>(WRITE_QUEUE_SIZE);
+        let mut write_stream = stream.try_clone().ok()?;
+        std::thread::Builder::new()
+            .name("RTPS TCP connection writer".to_string())
+            .spawn(move || {
+                for frame in frame_receiver {
+                    if write_stream.write_all(&frame).is_err() {
+                        // The connection is removed by the thread reading it once shut down
+                        write_stream.shutdown(Shutdown::Both).ok();
+                        break;
+                    }
+                }
+            })
+            .ok()?;
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.connection_list.push(TcpConnection {
+            id,
+            stream: stream.try_clone().ok()?,
+            frame_sender,
+            server_address,
+            remote_guid_prefix: None,
+        });
+        Some(id)
+    }
+
+    fn remove(&self, id: u64) {
+        let mut inner = self.0.lock().expect("Connection list lock poisoned");
+        if let Some(index) = inner.connection_list.iter().position(|c| c.id == id) {
+            let connection = inner.connection_list.remove(index);
+            connection.stream.shutdown(Shutdown::Both).ok();
+        }
+    }
+
+    fn set_remote_guid_prefix(&self, id: u64, guid_prefix: GuidPrefix) {
+        let mut inner = self.0.lock().expect("Connection list lock poisoned");
+        if let Some(connection) = inner.connection_list.iter_mut().find(|c| c.id == id) {
+            connection.remote_guid_prefix = Some(guid_prefix);
+        }
+    }
+
+    fn is_connected_to(&self, server_address: SocketAddr) -> bool {
+        let inner = self.0.lock().expect("Connection list lock poisoned");
+        inner
+            .connection_list
+            .iter()
+            .any(|c| c.server_address == Some(server_address))
+    }
+
+    fn is_closed(&self) -> bool {
+        self.0
+            .lock()
+            .expect("Connection list lock poisoned")
+            .is_closed
+    }
+
+    fn close(&self) {
+        let mut inner = self.0.lock().expect("Connection list lock poisoned");
+        inner.is_closed = true;
+        for connection in inner.connection_list.drain(..) {
+            connection.stream.shutdown(Shutdown::Both).ok();
+        }
+    }
+}
+
+/// Sends the messages to the TCP locators through the connection to the destination.
+/// Messages to participants without connection, or whose connection has too many messages
+/// waiting to be written, are dropped and recovered by the reliability protocol.
+pub struct TcpSender {
+    connection_list: TcpConnectionList,
+}
+
+impl TcpSender {
+    pub fn new(connection_list: TcpConnectionList) -> Self {
+        Self { connection_list }
+    }
+
+    pub fn send_to(&self, buf: &[u8], destination_locator: Locator) {
+        let (port, guid_prefix, server_address) = match destination_locator.kind() {
+            LOCATOR_KIND_TCP_CONNECTION => {
+                let guid_prefix: GuidPrefix = destination_locator.address()[4..]
+                    .try_into()
+                    .expect("Slice has 12 bytes");
+                (destination_locator.port(), Some(guid_prefix), None)
+            }
+            // Servers are only used as initial peers for the discovery
+            _ => match server_address(&destination_locator) {
+                Some(address) => (METATRAFFIC_PORT, None, Some(address)),
+                None => return,
+            },
+        };
+        let is_destination = |c: &TcpConnection| {
+            (guid_prefix.is_some() && c.remote_guid_prefix == guid_prefix)
+                || (server_address.is_some() && c.server_address == server_address)
+        };
+
+        let inner = self
+            .connection_list
+            .0
+            .lock()
+            .expect("Connection list lock poisoned");
+        if let Some(connection) = inner.connection_list.iter().find(|c| is_destination(c)) {
+            match connection.frame_sender.try_send(frame(port, buf)) {
+                Ok(()) | Err(TrySendError::Full(_)) => (),
+                // The connection is removed by the thread reading it
+                Err(TrySendError::Disconnected(_)) => {
+                    connection.stream.shutdown(Shutdown::Both).ok();
+                }
+            }
+        }
+    }
+}
+
+impl Drop for TcpSender {
+    fn drop(&mut self) {
+        self.connection_list.close();
+    }
+}
+
+// Register the connection and spawn a thread which forwards all the RTPS messages
+// received on it to the participant until the connection or the participant is gone.
+// Connections beyond the maximum number of connections are closed.
+fn start_connection(
+    stream: TcpStream,
+    server_address: Option<SocketAddr>,
+    connection_list: &TcpConnectionList,
+    rtps_participant_address: &ActorAddress<RtpsParticipant>,
+) {
+    stream.set_nodelay(true).ok();
+    if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err()
+        || stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err()
+    {
+        return;
+    }
+    let Some(id) = connection_list.add(&stream, server_address) else {
+        return;
+    };
+    let mut stream = stream;
+    let connection_list = connection_list.clone();
+    let rtps_participant_address = rtps_participant_address.clone();
+    std::thread::Builder::new()
+        .name("RTPS TCP connection".to_string())
+        .spawn(move || {
+            while let Ok(frame) = read_frame(&mut stream) {
+                let Some((port, message)) = frame else {
+                    if rtps_participant_address.is_closed() {
+                        break;
+                    }
+                    continue;
+                };
+                let Ok(rtps_message) = RtpsMessageRead::try_from(message.as_slice()) else {
+                    continue;
+                };
+                connection_list.set_remote_guid_prefix(id, rtps_message.header().guid_prefix());
+                let r = match port {
+                    METATRAFFIC_PORT => rtps_participant_address
+                        .send_actor_mail(participant::ProcessBuiltinRtpsMessage { rtps_message })
+                        .map(|_| ()),
+                    USER_DEFINED_PORT => rtps_participant_address
+                        .send_actor_mail(participant::ProcessUserDefinedRtpsMessage {
+                            rtps_message,
+                        })
+                        .map(|_| ()),
+                    _ => Ok(()),
+                };
+                if r.is_err() {
+                    break;
+                }
+            }
+            connection_list.remove(id);
+        })
+        .expect("failed to spawn thread");
+}
+
+fn connect_to_servers(
+    server_address_list: &[SocketAddr],
+    connection_list: &TcpConnectionList,
+    rtps_participant_address: &ActorAddress<RtpsParticipant>,
+) {
+    for &server_address in server_address_list {
+        if connection_list.is_connected_to(server_address) {
+            continue;
+        }
+        match TcpStream::connect_timeout(&server_address, CONNECT_TIMEOUT) {
+            Ok(stream) => start_connection(
+                stream,
+                Some(server_address),
+                connection_list,
+                rtps_participant_address,
+            ),
+            Err(e) => {
+                tracing::debug!(%server_address, error = %e, "Failed to connect to TCP server")
+            }
+        }
+    }
+}
+
+/// Open the connections of a participant. The servers are connected before returning so
+/// that the first announcements of the participant reach them. The threads accepting the
+/// clients and restoring the lost connections run until the participant is gone.
+pub fn start_connections(
+    listener: Option<TcpListener>,
+    server_address_list: Vec<SocketAddr>,
+    connection_list: TcpConnectionList,
+    rtps_participant_address: ActorAddress<RtpsParticipant>,
+) -> RtpsResult<()> {
+    if let Some(listener) = listener {
+        listener.set_nonblocking(true)?;
+        let connection_list = connection_list.clone();
+        let rtps_participant_address = rtps_participant_address.clone();
+        std::thread::Builder::new()
+            .name("RTPS TCP server".to_string())
+            .spawn(move || {
+                while !connection_list.is_closed() && !rtps_participant_address.is_closed() {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            if stream.set_nonblocking(false).is_ok() {
+                                start_connection(
+                                    stream,
+                                    None,
+                                    &connection_list,
+                                    &rtps_participant_address,
+                                );
+                            }
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                            std::thread::sleep(ACCEPT_POLL_PERIOD)
+                        }
+                        Err(_) => std::thread::sleep(ACCEPT_POLL_PERIOD),
+                    }
+                }
+            })
+            .expect("failed to spawn thread");
+    }
+
+    if !server_address_list.is_empty() {
+        connect_to_servers(
+            &server_address_list,
+            &connection_list,
+            &rtps_participant_address,
+        );
+        std::thread::Builder::new()
+            .name("RTPS TCP client".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(RECONNECT_PERIOD);
+                if connection_list.is_closed() || rtps_participant_address.is_closed() {
+                    break;
+                }
+                connect_to_servers(
+                    &server_address_list,
+                    &connection_list,
+                    &rtps_participant_address,
+                );
+            })
+            .expect("failed to spawn thread");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_millis(200)))
+            .unwrap();
+        (client, server)
+    }
+
+    #[test]
+    fn messages_are_sent_through_the_connection_of_the_destination() {
+        let (client1, mut server1) = connected_pair();
+        let (client2, mut server2) = connected_pair();
+        let connection_list = TcpConnectionList::new(DEFAULT_MAX_CONNECTIONS);
+        let id1 = connection_list.add(&client1, None).unwrap();
+        let id2 = connection_list.add(&client2, None).unwrap();
+        connection_list.set_remote_guid_prefix(id1, [1; 12]);
+        connection_list.set_remote_guid_prefix(id2, [2; 12]);
+        let sender = TcpSender::new(connection_list);
+
+        sender.send_to(&[1, 2, 3], connection_locator([2; 12], USER_DEFINED_PORT));
+        sender.send_to(&[4, 5], connection_locator([1; 12], METATRAFFIC_PORT));
+        sender.send_to(&[6], connection_locator([3; 12], METATRAFFIC_PORT));
+
+        assert_eq!(
+            read_frame(&mut server2).unwrap(),
+            Some((USER_DEFINED_PORT, vec![1, 2, 3]))
+        );
+        assert_eq!(
+            read_frame(&mut server1).unwrap(),
+            Some((METATRAFFIC_PORT, vec![4, 5]))
+        );
+        assert_eq!(read_frame(&mut server1).unwrap(), None);
+        assert_eq!(read_frame(&mut server2).unwrap(), None);
+    }
+
+    #[test]
+    fn messages_to_servers_are_sent_as_metatraffic() {
+        let (client, mut server) = connected_pair();
+        let server_address = server.local_addr().unwrap();
+        let connection_list = TcpConnectionList::new(DEFAULT_MAX_CONNECTIONS);
+        connection_list.add(&client, Some(server_address)).unwrap();
+        let sender = TcpSender::new(connection_list.clone());
+
+        sender.send_to(&[7; 10], server_locator(server_address));
+
+        assert_eq!(
+            read_frame(&mut server).unwrap(),
+            Some((METATRAFFIC_PORT, vec![7; 10]))
+        );
+        assert!(connection_list.is_connected_to(server_address));
+        drop(sender);
+        assert!(connection_list.is_closed());
+        assert!(!connection_list.is_connected_to(server_address));
+    }
+
+    #[test]
+    fn frames_bigger_than_a_message_are_rejected() {
+        let (mut client, mut server) = connected_pair();
+        client
+            .write_all(&((MAX_FRAME_SIZE + 1) as u32).to_be_bytes())
+            .unwrap();
+        client.write_all(&USER_DEFINED_PORT.to_be_bytes()).unwrap();
+
+        assert!(read_frame(&mut server).is_err());
+    }
+
+    #[test]
+    fn incomplete_frame_times_out() {
+        let (mut client, mut server) = connected_pair();
+        client.write_all(&frame(USER_DEFINED_PORT, &[1; 10])[..12]).unwrap();
+
+        assert!(read_frame(&mut server).is_err());
+    }
+
+    #[test]
+    fn connections_beyond_the_maximum_are_not_added() {
+        let (client1, _server1) = connected_pair();
+        let (client2, _server2) = connected_pair();
+        let connection_list = TcpConnectionList::new(1);
+
+        assert!(connection_list.add(&client1, None).is_some());
+        assert!(connection_list.add(&client2, None).is_none());
+    }
+
+    #[test]
+    fn participant_creation_fails_when_listen_address_is_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let factory = TcpParticipantFactoryBuilder::new()
+            .listen_address(Some(listener.local_addr().unwrap()))
+            .build()
+            .unwrap();
+
+        assert!(factory.create_participant([1; 12], 0).is_err());
+    }
+
+    #[test]
+    fn server_addresses_must_be_specified() {
+        for server_address in ["0.0.0.0:7400", "127.0.0.1:0"] {
+            assert!(TcpParticipantFactoryBuilder::new()
+                .server_addresses(vec![server_address.parse().unwrap()])
+                .build()
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn server_locators_preserve_the_address() {
+        let address_v4: SocketAddr = "192.168.1.10:7400".parse().unwrap();
+        let address_v6: SocketAddr = "[2001:db8::1]:7401".parse().unwrap();
+        assert_eq!(
+            server_address(&server_locator(address_v4)),
+            Some(address_v4)
+        );
+        assert_eq!(
+            server_address(&server_locator(address_v6)),
+            Some(address_v6)
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use core::net::{Ipv4Addr, SocketAddr};
+use core::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
 
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
 use socket2::Socket;
@@ -8,7 +11,7 @@ use crate::{
     domain::domain_participant_factory::DomainId,
     rtps::participant,
     runtime::{
-        actor::{Actor, ActorAddress, ActorBuilder},
+        actor::{Actor, ActorAddress, ActorBuilder, Mail, MailHandler},
         executor::{block_on, Executor},
     },
     transport::{
@@ -16,8 +19,9 @@ use crate::{
         participant::TransportParticipant,
         reader::{TransportStatefulReader, TransportStatelessReader, WriterProxy},
         types::{
-            EntityId, Guid, GuidPrefix, Locator, ProtocolVersion, ReliabilityKind, VendorId,
-            ENTITYID_PARTICIPANT, LOCATOR_KIND_UDP_V4,
+            EndpointReliabilityTiming, EntityId, Guid, GuidPrefix, Locator, ProtocolVersion,
+            ReliabilityKind, VendorId, ENTITYID_PARTICIPANT, GUIDPREFIX_UNKNOWN,
+            LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
         },
         writer::{TransportStatefulWriter, TransportStatelessWriter},
     },
@@ -25,24 +29,277 @@ use crate::{
 
 use super::{
     error::{RtpsError, RtpsErrorKind, RtpsResult},
+    flow_controller::RtpsFlowController,
+    in_memory::{self, InMemoryNetwork},
+    message_sender::MessageSender,
     messages::overall_structure::RtpsMessageRead,
     participant::RtpsParticipant,
+    shared_memory::{self, SharedMemorySender, LOCATOR_KIND_SHARED_MEMORY},
+    tcp::{self, TcpConnectionList, TcpSender},
     types::{PROTOCOLVERSION, VENDOR_ID_S2E},
 };
 
-const MAX_DATAGRAM_SIZE: usize = 65507;
+pub(crate) const MAX_DATAGRAM_SIZE: usize = 65507;
 
 type LocatorAddress = [u8; 16];
 // As of 9.6.1.4.1  Default multicast address
 const DEFAULT_MULTICAST_LOCATOR_ADDRESS: LocatorAddress =
     [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 239, 255, 0, 1];
+// Highest domain id whose ports fit in the port range with the default port mapping
+const MAX_DOMAIN_ID: DomainId = 232;
+
+/// Parameters mapping the domain id and the participant id to the well-known port numbers
+/// (9.6.1.1 RTPS standard). The default values are the ones given by the standard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtpsPortMapping {
+    /// Port base number (PB)
+    pub port_base_number: u16,
+    /// Domain id gain (DG)
+    pub domain_id_gain: u16,
+    /// Participant id gain (PG)
+    pub participant_id_gain: u16,
+    /// Offset of the builtin multicast port (d0)
+    pub offset_builtin_multicast: u16,
+    /// Offset of the builtin unicast port (d1)
+    pub offset_builtin_unicast: u16,
+    /// Offset of the user-defined multicast port (d2)
+    pub offset_user_multicast: u16,
+    /// Offset of the user-defined unicast port (d3)
+    pub offset_user_unicast: u16,
+}
+
+impl Default for RtpsPortMapping {
+    fn default() -> Self {
+        Self {
+            port_base_number: 7400,
+            domain_id_gain: 250,
+            participant_id_gain: 2,
+            offset_builtin_multicast: 0,
+            offset_builtin_unicast: 10,
+            offset_user_multicast: 1,
+            offset_user_unicast: 11,
+        }
+    }
+}
+
+impl RtpsPortMapping {
+    fn port(&self, domain_id: DomainId, participant_id: u32, offset: u16) -> Option<u16> {
+        let port = u32::from(self.port_base_number)
+            + u32::from(self.domain_id_gain) * u32::try_from(domain_id).ok()?
+            + u32::from(offset)
+            + u32::from(self.participant_id_gain) * participant_id;
+        u16::try_from(port).ok()
+    }
+
+    /// Port for the multicast metatraffic of the domain. [`None`] if it is not a valid port number
+    pub fn builtin_multicast_port(&self, domain_id: DomainId) -> Option<u16> {
+        self.port(domain_id, 0, self.offset_builtin_multicast)
+    }
+
+    /// Port for the unicast metatraffic of the participant. [`None`] if it is not a valid port number
+    pub fn builtin_unicast_port(&self, domain_id: DomainId, participant_id: u32) -> Option<u16> {
+        self.port(domain_id, participant_id, self.offset_builtin_unicast)
+    }
+
+    /// Port for the multicast user-defined traffic of the domain. [`None`] if it is not a valid port number
+    pub fn user_multicast_port(&self, domain_id: DomainId) -> Option<u16> {
+        self.port(domain_id, 0, self.offset_user_multicast)
+    }
+
+    /// Port for the unicast user-defined traffic of the participant. [`None`] if it is not a valid port number
+    pub fn user_unicast_port(&self, domain_id: DomainId, participant_id: u32) -> Option<u16> {
+        self.port(domain_id, participant_id, self.offset_user_unicast)
+    }
+
+    /// Highest participant id whose unicast ports do not overlap with the ports of the next domain
+    pub fn max_participant_id(&self) -> u32 {
+        let highest_offset = self.offset_builtin_unicast.max(self.offset_user_unicast);
+        match self.domain_id_gain.checked_sub(highest_offset + 1) {
+            Some(range) if self.participant_id_gain > 0 => {
+                u32::from(range) / u32::from(self.participant_id_gain)
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Check that the port mapping gives valid ports to every domain up to the highest
+/// domain id of the standard and that the ports of the participants of a domain
+/// are all different and do not overlap with the ports of the next domain.
+pub fn check_port_mapping(port_mapping: &RtpsPortMapping) -> Result<(), String> {
+    if port_mapping.participant_id_gain == 0 || port_mapping.domain_id_gain == 0 {
+        return Err("Domain id gain and participant id gain must be greater than 0".to_string());
+    }
+    let offsets = [
+        port_mapping.offset_builtin_multicast,
+        port_mapping.offset_builtin_unicast,
+        port_mapping.offset_user_multicast,
+        port_mapping.offset_user_unicast,
+    ];
+    if offsets.iter().any(|&o| o >= port_mapping.domain_id_gain) {
+        return Err("Port offsets must be smaller than the domain id gain".to_string());
+    }
+    for domain_id in 0..=MAX_DOMAIN_ID {
+        if port_mapping.builtin_multicast_port(domain_id).is_none()
+            || port_mapping.user_multicast_port(domain_id).is_none()
+            || port_mapping.builtin_unicast_port(domain_id, 0).is_none()
+            || port_mapping.user_unicast_port(domain_id, 0).is_none()
+        {
+            return Err(format!(
+                "Port mapping results in ports out of range for domain {domain_id}"
+            ));
+        }
+    }
+    let mut port_list = vec![
+        port_mapping.builtin_multicast_port(0),
+        port_mapping.user_multicast_port(0),
+    ];
+    for participant_id in 0..=port_mapping.max_participant_id() {
+        port_list.push(port_mapping.builtin_unicast_port(0, participant_id));
+        port_list.push(port_mapping.user_unicast_port(0, participant_id));
+    }
+    let port_count = port_list.len();
+    port_list.sort_unstable();
+    port_list.dedup();
+    if port_list.len() != port_count {
+        return Err("Port mapping results in the same port for different uses".to_string());
+    }
+    Ok(())
+}
+
+/// Timing parameters of the reliable protocol (8.4.7 RTPS standard), used by all the
+/// stateful writers and readers of the participant. The default values favor a fast
+/// repair: heartbeats are sent every 200 ms and the requests are answered immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtpsReliabilityTiming {
+    /// Period at which a writer with unacknowledged changes announces its available changes (heartbeatPeriod)
+    pub heartbeat_period: Duration,
+    /// Delay before a writer sends the changes requested by a reader (nackResponseDelay)
+    pub nack_response_delay: Duration,
+    /// Time after sending a change during which the requests of the reader for it are ignored (nackSuppressionDuration)
+    pub nack_suppression_duration: Duration,
+    /// Delay before a reader answers a heartbeat of a writer (heartbeatResponseDelay)
+    pub heartbeat_response_delay: Duration,
+}
+
+impl Default for RtpsReliabilityTiming {
+    fn default() -> Self {
+        Self {
+            heartbeat_period: Duration::from_millis(200),
+            nack_response_delay: Duration::ZERO,
+            nack_suppression_duration: Duration::ZERO,
+            heartbeat_response_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl RtpsReliabilityTiming {
+    /// Period at which the writers and readers check whether a heartbeat or a delayed
+    /// response is due. It is short enough to respect the smallest of the configured
+    /// periods and delays.
+    pub fn tick_period(&self) -> Duration {
+        const MIN_TICK_PERIOD: Duration = Duration::from_millis(1);
+        const MAX_TICK_PERIOD: Duration = Duration::from_millis(50);
+        [
+            self.heartbeat_period,
+            self.nack_response_delay,
+            self.heartbeat_response_delay,
+        ]
+        .into_iter()
+        .filter(|d| !d.is_zero())
+        .min()
+        .unwrap_or(MAX_TICK_PERIOD)
+        .clamp(MIN_TICK_PERIOD, MAX_TICK_PERIOD)
+    }
+
+    /// Timing of an endpoint, taking the values it overrides and keeping the other ones
+    pub fn with_endpoint_timing(&self, endpoint_timing: &EndpointReliabilityTiming) -> Self {
+        Self {
+            heartbeat_period: endpoint_timing
+                .heartbeat_period
+                .unwrap_or(self.heartbeat_period),
+            nack_response_delay: endpoint_timing
+                .nack_response_delay
+                .unwrap_or(self.nack_response_delay),
+            nack_suppression_duration: endpoint_timing
+                .nack_suppression_duration
+                .unwrap_or(self.nack_suppression_duration),
+            heartbeat_response_delay: endpoint_timing
+                .heartbeat_response_delay
+                .unwrap_or(self.heartbeat_response_delay),
+        }
+    }
+}
+
+/// IP version of the UDP sockets used by the transport
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UdpIpVersion {
+    /// Use only UDPv4 sockets and locators
+    #[default]
+    V4,
+    /// Use only UDPv6 sockets and locators
+    V6,
+    /// Use both UDPv4 and UDPv6 sockets and announce the locators of both versions
+    V4AndV6,
+}
+
+impl UdpIpVersion {
+    pub fn uses_ipv4(&self) -> bool {
+        matches!(self, UdpIpVersion::V4 | UdpIpVersion::V4AndV6)
+    }
+
+    pub fn uses_ipv6(&self) -> bool {
+        matches!(self, UdpIpVersion::V6 | UdpIpVersion::V4AndV6)
+    }
+}
+
+/// Scope of the IPv6 multicast group used for discovery
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Ipv6MulticastScope {
+    /// Link-local scope (FF02::/16)
+    #[default]
+    LinkLocal,
+    /// Realm-local scope (FF03::/16)
+    RealmLocal,
+}
 
-const PB: i32 = 7400;
-const DG: i32 = 250;
-#[allow(non_upper_case_globals)]
-const d0: i32 = 0;
-fn port_builtin_multicast(domain_id: DomainId) -> u16 {
-    (PB + DG * domain_id + d0) as u16
+impl Ipv6MulticastScope {
+    // The IPv4 default multicast address mapped into the IPv6 multicast range of the scope
+    fn default_multicast_locator_address(&self) -> LocatorAddress {
+        let scope = match self {
+            Ipv6MulticastScope::LinkLocal => 0x02,
+            Ipv6MulticastScope::RealmLocal => 0x03,
+        };
+        [
+            0xff, scope, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 239, 255, 0, 1,
+        ]
+    }
+}
+
+// IPv6 link-local unicast addresses (FE80::/10) are only meaningful together with the
+// interface they belong to, which can not be represented in a locator
+fn is_ipv6_unicast_link_local(address: &Ipv6Addr) -> bool {
+    (address.segments()[0] & 0xffc0) == 0xfe80
+}
+
+fn get_unicast_socket(
+    domain: socket2::Domain,
+    port: u16,
+    udp_receive_buffer_size: Option<usize>,
+) -> std::io::Result<std::net::UdpSocket> {
+    let socket = Socket::new(domain, socket2::Type::DGRAM, None)?;
+    let socket_addr = if domain == socket2::Domain::IPV6 {
+        socket.set_only_v6(true)?;
+        SocketAddr::from((Ipv6Addr::UNSPECIFIED, port))
+    } else {
+        SocketAddr::from((Ipv4Addr::UNSPECIFIED, port))
+    };
+    socket.bind(&socket_addr.into())?;
+    socket.set_nonblocking(false)?;
+    if let Some(buffer_size) = udp_receive_buffer_size {
+        socket.set_recv_buffer_size(buffer_size)?;
+    }
+    Ok(socket.into())
 }
 
 fn get_multicast_socket(
@@ -91,6 +348,43 @@ fn get_multicast_socket(
     Ok(socket.into())
 }
 
+fn get_multicast_socket_v6(
+    multicast_address: LocatorAddress,
+    port: u16,
+    interface_index_list: impl IntoIterator<Item = u32>,
+) -> std::io::Result<std::net::UdpSocket> {
+    let socket_addr = SocketAddr::from((Ipv6Addr::UNSPECIFIED, port));
+
+    let socket = Socket::new(
+        socket2::Domain::IPV6,
+        socket2::Type::DGRAM,
+        Some(socket2::Protocol::UDP),
+    )?;
+
+    socket.set_only_v6(true)?;
+    socket.set_reuse_address(true)?;
+    #[cfg(target_family = "unix")]
+    socket.set_reuse_port(true)?;
+    socket.set_nonblocking(false)?;
+    socket.set_read_timeout(Some(std::time::Duration::from_millis(50)))?;
+
+    socket.bind(&socket_addr.into())?;
+    let addr = Ipv6Addr::from(multicast_address);
+    for interface_index in interface_index_list {
+        let r = socket.join_multicast_v6(&addr, interface_index);
+        if let Err(e) = r {
+            info!(
+                "Failed to join multicast group on interface {} with error {}",
+                interface_index, e
+            )
+        }
+    }
+
+    socket.set_multicast_loop_v6(true)?;
+
+    Ok(socket.into())
+}
+
 pub fn read_message(
     socket: &mut std::net::UdpSocket,
     buf: &mut [u8],
@@ -103,6 +397,61 @@ pub fn read_message(
     }
 }
 
+// Spawn a thread which forwards all the RTPS messages received on the socket to the
+// participant until the participant is gone
+fn spawn_receive_thread<M>(
+    name: &str,
+    mut socket: std::net::UdpSocket,
+    rtps_participant_address: ActorAddress<RtpsParticipant>,
+    into_mail: fn(RtpsMessageRead) -> M,
+) where
+    RtpsParticipant: MailHandler<M>,
+    M: Mail + Send + 'static,
+    M::Result: Send,
+{
+    let thread_name = name.to_string();
+    std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || {
+            let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
+            loop {
+                if let Ok(rtps_message) = read_message(&mut socket, buf.as_mut_slice()) {
+                    tracing::trace!(
+                        rtps_message = ?rtps_message,
+                        thread = thread_name,
+                        "Received RTPS message"
+                    );
+                    let r = rtps_participant_address.send_actor_mail(into_mail(rtps_message));
+                    if r.is_err() {
+                        break;
+                    }
+                }
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
+// Spawn a thread which sends the heartbeats and delayed responses of the endpoints and
+// refills the flow controllers when they are due until the participant is gone
+fn spawn_timer_thread(
+    rtps_participant_address: ActorAddress<RtpsParticipant>,
+    tick_period: Duration,
+) {
+    std::thread::Builder::new()
+        .name("RTPS timer".to_string())
+        .spawn(move || {
+            let mut tick_period = tick_period;
+            loop {
+                std::thread::sleep(tick_period);
+                match rtps_participant_address.send_actor_mail(participant::SendPeriodicMessages) {
+                    Ok(r) => tick_period = block_on(r.receive_reply()),
+                    Err(_) => break,
+                }
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
 pub struct RtpsTransport {
     guid: Guid,
     fragment_size: usize,
@@ -111,6 +460,7 @@ pub struct RtpsTransport {
     default_multicast_locator_list: Vec<Locator>,
     metatraffic_unicast_locator_list: Vec<Locator>,
     metatraffic_multicast_locator_list: Vec<Locator>,
+    initial_peer_locator_list: Vec<Locator>,
     _executor: Executor,
 }
 
@@ -122,11 +472,17 @@ impl RtpsTransport {
         interface_name: &Option<String>,
         udp_receive_buffer_size: Option<usize>,
         fragment_size: usize,
+        ip_version: UdpIpVersion,
+        ipv6_multicast_scope: Ipv6MulticastScope,
+        use_multicast: bool,
+        initial_peers: &[IpAddr],
+        initial_peers_max_participant_id: u8,
+        port_mapping: RtpsPortMapping,
+        reliability_timing: RtpsReliabilityTiming,
     ) -> RtpsResult<Self> {
         let executor = Executor::new();
 
-        // Open socket for unicast user-defined data
-        let interface_address_list = NetworkInterface::show()
+        let interface_list: Vec<_> = NetworkInterface::show()
             .expect("Could not scan interfaces")
             .into_iter()
             .filter(|x| {
@@ -136,54 +492,201 @@ impl RtpsTransport {
                     true
                 }
             })
+            .collect();
+        let interface_address_v4_list: Vec<_> = interface_list
+            .iter()
+            .flat_map(|i| i.addr.iter().filter(|a| matches!(a, Addr::V4(_))).cloned())
+            .collect();
+        let interface_address_v6_list: Vec<_> = interface_list
+            .iter()
             .flat_map(|i| {
-                i.addr.into_iter().filter(|a| match a {
-                    #[rustfmt::skip]
-                    Addr::V4(_) => true,
-                    _ => false,
+                i.addr
+                    .iter()
+                    .filter(|a| matches!(a, Addr::V6(v6) if !is_ipv6_unicast_link_local(&v6.ip)))
+                    .cloned()
+            })
+            .collect();
+        let mut interface_index_v6_list: Vec<_> = interface_list
+            .iter()
+            .filter(|i| i.addr.iter().any(|a| matches!(a, Addr::V6(_))))
+            .map(|i| i.index)
+            .collect();
+        interface_index_v6_list.sort_unstable();
+        interface_index_v6_list.dedup();
+
+        let mut default_unicast_locator_list = Vec::new();
+        let mut default_multicast_locator_list = Vec::new();
+        let mut metatraffic_unicast_locator_list = Vec::new();
+        let mut metatraffic_multicast_locator_list = Vec::new();
+        let mut default_unicast_socket_list = Vec::new();
+        let mut metatraffic_unicast_socket_list = Vec::new();
+        let mut metatraffic_multicast_socket_list = Vec::new();
+        let mut default_multicast_socket_list = Vec::new();
+
+        let mut socket_domain_list = Vec::new();
+        if ip_version.uses_ipv4() {
+            socket_domain_list.push(socket2::Domain::IPV4);
+        }
+        if ip_version.uses_ipv6() {
+            socket_domain_list.push(socket2::Domain::IPV6);
+        }
+        let open_unicast_sockets = |metatraffic_port: u16, user_defined_port: u16| {
+            socket_domain_list
+                .iter()
+                .map(|&domain| {
+                    Ok((
+                        get_unicast_socket(domain, metatraffic_port, None)?,
+                        get_unicast_socket(domain, user_defined_port, udp_receive_buffer_size)?,
+                    ))
                 })
-            });
-
-        let default_unicast_socket =
-            socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None)?;
-        default_unicast_socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)).into())?;
-        default_unicast_socket.set_nonblocking(false)?;
-        if let Some(buffer_size) = udp_receive_buffer_size {
-            default_unicast_socket.set_recv_buffer_size(buffer_size)?;
+                .collect::<std::io::Result<Vec<_>>>()
+        };
+        // Use the well-known ports of the first free participant id (9.6.1.1) so that
+        // participants can be found by probing the ports of the initial peers. If none is
+        // free the operating system chooses the ports.
+        let max_participant_id = port_mapping.max_participant_id();
+        let mut unicast_socket_list = match (0..=max_participant_id).find_map(|participant_id| {
+            open_unicast_sockets(
+                port_mapping.builtin_unicast_port(domain_id, participant_id)?,
+                port_mapping.user_unicast_port(domain_id, participant_id)?,
+            )
+            .ok()
+        }) {
+            Some(unicast_socket_list) => unicast_socket_list,
+            None => open_unicast_sockets(0, 0)?,
+        }
+        .into_iter();
+
+        let mut initial_peer_locator_list = Vec::new();
+        for peer in initial_peers {
+            let locator_kind = match peer {
+                IpAddr::V4(_) if ip_version.uses_ipv4() => LOCATOR_KIND_UDP_V4,
+                IpAddr::V6(_) if ip_version.uses_ipv6() => LOCATOR_KIND_UDP_V6,
+                _ => continue,
+            };
+            let address = match peer {
+                IpAddr::V4(a) => {
+                    let mut address = [0; 16];
+                    address[12..].copy_from_slice(&a.octets());
+                    address
+                }
+                IpAddr::V6(a) => a.octets(),
+            };
+            for participant_id in 0..=initial_peers_max_participant_id as u32 {
+                if let Some(port) = port_mapping.builtin_unicast_port(domain_id, participant_id) {
+                    initial_peer_locator_list.push(Locator::new(
+                        locator_kind,
+                        port as u32,
+                        address,
+                    ));
+                }
+            }
         }
 
-        let mut default_unicast_socket = std::net::UdpSocket::from(default_unicast_socket);
-        let user_defined_unicast_port = default_unicast_socket.local_addr()?.port().into();
-        let default_unicast_locator_list: Vec<_> = interface_address_list
-            .clone()
-            .map(|a| Locator::from_ip_and_port(&a, user_defined_unicast_port))
-            .collect();
-
-        let default_multicast_locator_list = vec![];
-
-        // Open socket for unicast metatraffic data
-        let mut metatraffic_unicast_socket =
-            std::net::UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)))?;
-        metatraffic_unicast_socket.set_nonblocking(false)?;
-        let metattrafic_unicast_locator_port =
-            metatraffic_unicast_socket.local_addr()?.port().into();
-        let metatraffic_unicast_locator_list: Vec<Locator> = interface_address_list
-            .clone()
-            .map(|a| Locator::from_ip_and_port(&a, metattrafic_unicast_locator_port))
-            .collect();
+        let mut ip_family_list = Vec::new();
+        if ip_version.uses_ipv4() {
+            ip_family_list.push((
+                socket2::Domain::IPV4,
+                &interface_address_v4_list,
+                LOCATOR_KIND_UDP_V4,
+                DEFAULT_MULTICAST_LOCATOR_ADDRESS,
+            ));
+        }
+        if ip_version.uses_ipv6() {
+            ip_family_list.push((
+                socket2::Domain::IPV6,
+                &interface_address_v6_list,
+                LOCATOR_KIND_UDP_V6,
+                ipv6_multicast_scope.default_multicast_locator_address(),
+            ));
+        }
 
-        // Open socket for multicast metatraffic data
-        let metatraffic_multicast_locator_list = vec![Locator::new(
-            LOCATOR_KIND_UDP_V4,
-            port_builtin_multicast(domain_id) as u32,
-            DEFAULT_MULTICAST_LOCATOR_ADDRESS,
-        )];
-
-        let mut metatraffic_multicast_socket = get_multicast_socket(
-            DEFAULT_MULTICAST_LOCATOR_ADDRESS,
-            port_builtin_multicast(domain_id),
-            interface_address_list,
-        )?;
+        for (domain, interface_address_list, locator_kind, multicast_locator_address) in
+            ip_family_list
+        {
+            let (metatraffic_unicast_socket, default_unicast_socket) = unicast_socket_list
+                .next()
+                .expect("A unicast socket pair must exist for each domain");
+
+            // Socket for unicast user-defined data
+            let user_defined_unicast_port = default_unicast_socket.local_addr()?.port().into();
+            default_unicast_locator_list.extend(
+                interface_address_list
+                    .iter()
+                    .map(|a| Locator::from_ip_and_port(a, user_defined_unicast_port)),
+            );
+            default_unicast_socket_list.push(default_unicast_socket);
+
+            // Socket for unicast metatraffic data
+            let metattrafic_unicast_locator_port =
+                metatraffic_unicast_socket.local_addr()?.port().into();
+            metatraffic_unicast_locator_list.extend(
+                interface_address_list
+                    .iter()
+                    .map(|a| Locator::from_ip_and_port(a, metattrafic_unicast_locator_port)),
+            );
+            metatraffic_unicast_socket_list.push(metatraffic_unicast_socket);
+
+            // Open socket for multicast metatraffic data
+            if !use_multicast {
+                continue;
+            }
+            let metatraffic_multicast_port = port_mapping
+                .builtin_multicast_port(domain_id)
+                .ok_or_else(|| {
+                    RtpsError::new(
+                        RtpsErrorKind::InvalidData,
+                        format!("Domain id {domain_id} has no valid multicast port"),
+                    )
+                })?;
+            metatraffic_multicast_locator_list.push(Locator::new(
+                locator_kind,
+                metatraffic_multicast_port as u32,
+                multicast_locator_address,
+            ));
+            let metatraffic_multicast_socket = if domain == socket2::Domain::IPV6 {
+                get_multicast_socket_v6(
+                    multicast_locator_address,
+                    metatraffic_multicast_port,
+                    interface_index_v6_list.iter().copied(),
+                )?
+            } else {
+                get_multicast_socket(
+                    multicast_locator_address,
+                    metatraffic_multicast_port,
+                    interface_address_list.iter().cloned(),
+                )?
+            };
+            metatraffic_multicast_socket_list.push(metatraffic_multicast_socket);
+
+            // Open socket for multicast user-defined data
+            let user_defined_multicast_port =
+                port_mapping.user_multicast_port(domain_id).ok_or_else(|| {
+                    RtpsError::new(
+                        RtpsErrorKind::InvalidData,
+                        format!("Domain id {domain_id} has no valid multicast port"),
+                    )
+                })?;
+            default_multicast_locator_list.push(Locator::new(
+                locator_kind,
+                user_defined_multicast_port as u32,
+                multicast_locator_address,
+            ));
+            let default_multicast_socket = if domain == socket2::Domain::IPV6 {
+                get_multicast_socket_v6(
+                    multicast_locator_address,
+                    user_defined_multicast_port,
+                    interface_index_v6_list.iter().copied(),
+                )?
+            } else {
+                get_multicast_socket(
+                    multicast_locator_address,
+                    user_defined_multicast_port,
+                    interface_address_list.iter().cloned(),
+                )?
+            };
+            default_multicast_socket_list.push(default_multicast_socket);
+        }
 
         let rtps_participant_actor_builder = ActorBuilder::new();
 
@@ -195,95 +698,50 @@ impl RtpsTransport {
                 default_multicast_locator_list.clone(),
                 metatraffic_unicast_locator_list.clone(),
                 metatraffic_multicast_locator_list.clone(),
+                ip_version,
+                use_multicast,
+                reliability_timing,
             )?,
             &executor.handle(),
         );
 
-        let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS metatraffic multicast discovery".to_string())
-            .spawn(move || {
-                let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
-                loop {
-                    if let Ok(rtps_message) =
-                        read_message(&mut metatraffic_multicast_socket, buf.as_mut_slice())
-                    {
-                        tracing::trace!(
-                            rtps_message = ?rtps_message,
-                            "Received metatraffic multicast RTPS message"
-                        );
-                        let r = rtps_participant_address.send_actor_mail(
-                            participant::ProcessBuiltinRtpsMessage { rtps_message },
-                        );
-                        if r.is_err() {
-                            break;
-                        }
-                    }
-                }
-            })
-            .expect("failed to spawn thread");
-
-        let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS metatraffic unicast discovery".to_string())
-            .spawn(move || {
-                let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
-                loop {
-                    if let Ok(rtps_message) =
-                        read_message(&mut metatraffic_unicast_socket, buf.as_mut_slice())
-                    {
-                        tracing::trace!(
-                            rtps_message = ?rtps_message,
-                            "Received metatraffic unicast RTPS message"
-                        );
-
-                        let r = rtps_participant_address.send_actor_mail(
-                            participant::ProcessBuiltinRtpsMessage { rtps_message },
-                        );
-                        if r.is_err() {
-                            break;
-                        }
-                    }
-                }
-            })
-            .expect("failed to spawn thread");
-
-        let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS user defined traffic".to_string())
-            .spawn(move || {
-                let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
-                loop {
-                    if let Ok(rtps_message) =
-                        read_message(&mut default_unicast_socket, buf.as_mut_slice())
-                    {
-                        tracing::trace!(
-                            rtps_message = ?rtps_message,
-                            "Received user defined data unicast RTPS message"
-                        );
-                        let r = rtps_participant_address.send_actor_mail(
-                            participant::ProcessUserDefinedRtpsMessage { rtps_message },
-                        );
-                        if r.is_err() {
-                            break;
-                        }
-                    }
-                }
-            })
-            .expect("failed to spawn thread");
-
-        // Heartbeat thread
-        let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS heartbeat".to_string())
-            .spawn(move || loop {
-                std::thread::sleep(std::time::Duration::from_millis(50));
-                let r = rtps_participant_address.send_actor_mail(participant::SendHeartbeat);
-                if r.is_err() {
-                    break;
-                }
-            })
-            .expect("failed to spawn thread");
+        for metatraffic_multicast_socket in metatraffic_multicast_socket_list {
+            spawn_receive_thread(
+                "RTPS metatraffic multicast discovery",
+                metatraffic_multicast_socket,
+                rtps_participant.address(),
+                |rtps_message| participant::ProcessBuiltinRtpsMessage { rtps_message },
+            );
+        }
+
+        for metatraffic_unicast_socket in metatraffic_unicast_socket_list {
+            spawn_receive_thread(
+                "RTPS metatraffic unicast discovery",
+                metatraffic_unicast_socket,
+                rtps_participant.address(),
+                |rtps_message| participant::ProcessBuiltinRtpsMessage { rtps_message },
+            );
+        }
+
+        for default_unicast_socket in default_unicast_socket_list {
+            spawn_receive_thread(
+                "RTPS user defined traffic",
+                default_unicast_socket,
+                rtps_participant.address(),
+                |rtps_message| participant::ProcessUserDefinedRtpsMessage { rtps_message },
+            );
+        }
+
+        for default_multicast_socket in default_multicast_socket_list {
+            spawn_receive_thread(
+                "RTPS user defined multicast traffic",
+                default_multicast_socket,
+                rtps_participant.address(),
+                |rtps_message| participant::ProcessUserDefinedRtpsMessage { rtps_message },
+            );
+        }
+
+        spawn_timer_thread(rtps_participant.address(), reliability_timing.tick_period());
 
         Ok(Self {
             guid,
@@ -292,10 +750,177 @@ impl RtpsTransport {
             default_multicast_locator_list,
             metatraffic_unicast_locator_list,
             metatraffic_multicast_locator_list,
+            initial_peer_locator_list,
             fragment_size,
             _executor: executor,
         })
     }
+
+    /// Construct a transport which exchanges all the traffic through TCP connections.
+    /// The participant accepts connections on the listen address, if any, and connects
+    /// as client to the servers.
+    pub fn new_tcp(
+        guid_prefix: GuidPrefix,
+        fragment_size: usize,
+        listen_address: Option<SocketAddr>,
+        server_address_list: &[SocketAddr],
+        max_connections: usize,
+        reliability_timing: RtpsReliabilityTiming,
+    ) -> RtpsResult<Self> {
+        let listener = listen_address
+            .map(std::net::TcpListener::bind)
+            .transpose()?;
+
+        // The participant is reached through the connections it has open, whatever the
+        // address it is seen from, so the locators only identify the participant
+        let default_unicast_locator_list =
+            vec![tcp::connection_locator(guid_prefix, tcp::USER_DEFINED_PORT)];
+        let metatraffic_unicast_locator_list =
+            vec![tcp::connection_locator(guid_prefix, tcp::METATRAFFIC_PORT)];
+        let initial_peer_locator_list = server_address_list
+            .iter()
+            .map(|&a| tcp::server_locator(a))
+            .collect();
+
+        let connection_list = TcpConnectionList::new(max_connections);
+        let mut message_sender = MessageSender::new(guid_prefix, None, None).use_multicast(false);
+        message_sender.set_tcp_sender(TcpSender::new(connection_list.clone()));
+
+        let transport = Self::with_message_sender(
+            guid_prefix,
+            fragment_size,
+            message_sender,
+            default_unicast_locator_list,
+            metatraffic_unicast_locator_list,
+            vec![],
+            initial_peer_locator_list,
+            reliability_timing,
+        );
+        tcp::start_connections(
+            listener,
+            server_address_list.to_vec(),
+            connection_list,
+            transport.rtps_participant.address(),
+        )?;
+        Ok(transport)
+    }
+
+    /// Construct a transport which exchanges all the traffic with the participants
+    /// connected to the same in-memory network
+    pub fn new_in_memory(
+        guid_prefix: GuidPrefix,
+        fragment_size: usize,
+        network: &InMemoryNetwork,
+        reliability_timing: RtpsReliabilityTiming,
+    ) -> Self {
+        let (in_memory_sender, receiver) = network.add_participant(guid_prefix);
+        let mut message_sender = MessageSender::new(guid_prefix, None, None).use_multicast(false);
+        message_sender.set_in_memory_sender(in_memory_sender);
+
+        let transport = Self::with_message_sender(
+            guid_prefix,
+            fragment_size,
+            message_sender,
+            vec![in_memory::in_memory_locator(
+                guid_prefix,
+                in_memory::USER_DEFINED_PORT,
+            )],
+            vec![in_memory::in_memory_locator(
+                guid_prefix,
+                in_memory::METATRAFFIC_PORT,
+            )],
+            vec![in_memory::in_memory_locator(
+                GUIDPREFIX_UNKNOWN,
+                in_memory::METATRAFFIC_PORT,
+            )],
+            vec![],
+            reliability_timing,
+        );
+        in_memory::spawn_in_memory_receive_thread(receiver, transport.rtps_participant.address());
+        transport
+    }
+
+    // Transport whose participant sends all the messages through the given sender. The
+    // receiving side is left to the caller.
+    #[allow(clippy::too_many_arguments)]
+    fn with_message_sender(
+        guid_prefix: GuidPrefix,
+        fragment_size: usize,
+        message_sender: MessageSender,
+        default_unicast_locator_list: Vec<Locator>,
+        metatraffic_unicast_locator_list: Vec<Locator>,
+        metatraffic_multicast_locator_list: Vec<Locator>,
+        initial_peer_locator_list: Vec<Locator>,
+        reliability_timing: RtpsReliabilityTiming,
+    ) -> Self {
+        let executor = Executor::new();
+        let guid = Guid::new(guid_prefix, ENTITYID_PARTICIPANT);
+        let rtps_participant = ActorBuilder::new().build(
+            RtpsParticipant::with_message_sender(
+                guid,
+                default_unicast_locator_list.clone(),
+                vec![],
+                metatraffic_unicast_locator_list.clone(),
+                metatraffic_multicast_locator_list.clone(),
+                message_sender,
+                reliability_timing,
+            ),
+            &executor.handle(),
+        );
+        spawn_timer_thread(rtps_participant.address(), reliability_timing.tick_period());
+
+        Self {
+            guid,
+            rtps_participant,
+            default_unicast_locator_list,
+            default_multicast_locator_list: vec![],
+            metatraffic_unicast_locator_list,
+            metatraffic_multicast_locator_list,
+            initial_peer_locator_list,
+            fragment_size,
+            _executor: executor,
+        }
+    }
+
+    /// Add the flow controllers to which the writers of the participant can be attached
+    pub fn add_flow_controllers(&self, flow_controllers: &[RtpsFlowController]) {
+        for flow_controller in flow_controllers {
+            block_on(
+                self.rtps_participant
+                    .send_actor_mail(participant::AddFlowController {
+                        flow_controller: flow_controller.clone(),
+                    })
+                    .receive_reply(),
+            );
+        }
+    }
+
+    /// Exchange the user-defined traffic with the participants of the same host through
+    /// a shared memory segment of the given size
+    pub fn enable_shared_memory(&mut self, segment_size: usize) -> RtpsResult<()> {
+        let shared_memory_sender = SharedMemorySender::new(self.guid.prefix(), segment_size)?;
+        let notification_socket = std::net::UdpSocket::bind("127.0.0.1:0")?;
+        let shared_memory_locator = Locator::new(
+            LOCATOR_KIND_SHARED_MEMORY,
+            notification_socket.local_addr()?.port() as u32,
+            shared_memory::host_id(),
+        );
+        self.default_unicast_locator_list
+            .insert(0, shared_memory_locator);
+        block_on(
+            self.rtps_participant
+                .send_actor_mail(participant::EnableSharedMemory {
+                    shared_memory_sender,
+                    shared_memory_locator,
+                })
+                .receive_reply(),
+        );
+        shared_memory::spawn_shared_memory_receive_thread(
+            notification_socket,
+            self.rtps_participant.address(),
+        );
+        Ok(())
+    }
 }
 
 impl TransportParticipant for RtpsTransport {
@@ -327,6 +952,18 @@ impl TransportParticipant for RtpsTransport {
         &self.default_multicast_locator_list
     }
 
+    fn initial_peer_locator_list(&self) -> &[Locator] {
+        &self.initial_peer_locator_list
+    }
+
+    fn begin_batch(&mut self) {
+        self.rtps_participant.send_actor_mail(participant::BeginBatch);
+    }
+
+    fn end_batch(&mut self) {
+        self.rtps_participant.send_actor_mail(participant::EndBatch);
+    }
+
     fn create_stateless_reader(
         &mut self,
         entity_id: EntityId,
@@ -407,8 +1044,28 @@ impl TransportParticipant for RtpsTransport {
                 )
             }
 
-            fn remove_matched_writer(&mut self, _remote_writer_guid: Guid) {
-                todo!()
+            fn remove_matched_writer(&mut self, remote_writer_guid: Guid) {
+                block_on(
+                    self.rtps_participant_address
+                        .send_actor_mail(participant::RemoveMatchedWriter {
+                            reader: self.guid,
+                            writer_proxy: remote_writer_guid,
+                        })
+                        .expect("Actor must exist")
+                        .receive_reply(),
+                )
+            }
+
+            fn set_reliability_timing(&mut self, reliability_timing: EndpointReliabilityTiming) {
+                block_on(
+                    self.rtps_participant_address
+                        .send_actor_mail(participant::SetStatefulReaderReliabilityTiming {
+                            reader: self.guid,
+                            reliability_timing,
+                        })
+                        .expect("Actor must exist")
+                        .receive_reply(),
+                )
             }
         }
 
@@ -440,20 +1097,135 @@ impl TransportParticipant for RtpsTransport {
                 .receive_reply(),
         )
     }
+
+    fn create_flow_controlled_stateful_writer(
+        &mut self,
+        entity_id: EntityId,
+        reliability_kind: ReliabilityKind,
+        flow_controller_name: &str,
+        priority: i32,
+    ) -> Option<Box<dyn TransportStatefulWriter>> {
+        let flow_controller_set = block_on(
+            self.rtps_participant
+                .send_actor_mail(participant::SetWriterFlowController {
+                    writer_guid: Guid::new(self.guid.prefix(), entity_id),
+                    flow_controller_name: flow_controller_name.to_string(),
+                    priority,
+                })
+                .receive_reply(),
+        );
+        flow_controller_set.then(|| self.create_stateful_writer(entity_id, reliability_kind))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::mpsc::{sync_channel, SyncSender};
+    use std::sync::{
+        mpsc::{sync_channel, SyncSender},
+        Arc,
+    };
 
     use crate::transport::{
         history_cache::CacheChange,
         types::{ChangeKind, DurabilityKind, ENTITYID_UNKNOWN},
-        writer::ReaderProxy,
+        writer::{ReaderContentFilter, ReaderProxy},
     };
 
     use super::*;
 
+    #[test]
+    fn default_port_mapping() {
+        let port_mapping = RtpsPortMapping::default();
+
+        assert_eq!(port_mapping.builtin_multicast_port(0), Some(7400));
+        assert_eq!(port_mapping.user_multicast_port(0), Some(7401));
+        assert_eq!(port_mapping.builtin_unicast_port(0, 0), Some(7410));
+        assert_eq!(port_mapping.user_unicast_port(0, 0), Some(7411));
+        assert_eq!(port_mapping.builtin_multicast_port(1), Some(7650));
+        assert_eq!(port_mapping.builtin_unicast_port(1, 2), Some(7664));
+        assert_eq!(port_mapping.user_unicast_port(1, 2), Some(7665));
+        assert_eq!(port_mapping.max_participant_id(), 119);
+        assert_eq!(port_mapping.user_unicast_port(0, 119), Some(7649));
+    }
+
+    #[test]
+    fn custom_port_mapping() {
+        let port_mapping = RtpsPortMapping {
+            port_base_number: 20000,
+            domain_id_gain: 100,
+            participant_id_gain: 4,
+            offset_builtin_multicast: 1,
+            offset_builtin_unicast: 20,
+            offset_user_multicast: 2,
+            offset_user_unicast: 22,
+        };
+
+        assert_eq!(port_mapping.builtin_multicast_port(3), Some(20301));
+        assert_eq!(port_mapping.user_multicast_port(3), Some(20302));
+        assert_eq!(port_mapping.builtin_unicast_port(3, 5), Some(20340));
+        assert_eq!(port_mapping.user_unicast_port(3, 5), Some(20342));
+        assert_eq!(port_mapping.max_participant_id(), 19);
+    }
+
+    #[test]
+    fn port_mapping_out_of_range() {
+        let port_mapping = RtpsPortMapping::default();
+
+        assert_eq!(port_mapping.builtin_multicast_port(232), Some(65400));
+        assert_eq!(port_mapping.builtin_multicast_port(233), None);
+        assert_eq!(port_mapping.builtin_unicast_port(232, 100), None);
+        assert_eq!(port_mapping.builtin_multicast_port(-1), None);
+    }
+
+    #[test]
+    fn port_mapping_validation() {
+        assert_eq!(check_port_mapping(&RtpsPortMapping::default()), Ok(()));
+        assert!(check_port_mapping(&RtpsPortMapping {
+            port_base_number: 20000,
+            ..Default::default()
+        })
+        .is_err());
+        assert!(check_port_mapping(&RtpsPortMapping {
+            offset_user_unicast: 12,
+            ..Default::default()
+        })
+        .is_err());
+        assert!(check_port_mapping(&RtpsPortMapping {
+            offset_builtin_unicast: 300,
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn endpoint_timing_overrides_participant_timing() {
+        let participant_timing = RtpsReliabilityTiming::default();
+        let endpoint_timing = participant_timing.with_endpoint_timing(&EndpointReliabilityTiming {
+            heartbeat_period: Some(Duration::from_millis(10)),
+            nack_response_delay: Some(Duration::from_millis(5)),
+            ..Default::default()
+        });
+
+        assert_eq!(endpoint_timing.heartbeat_period, Duration::from_millis(10));
+        assert_eq!(
+            endpoint_timing.nack_response_delay,
+            Duration::from_millis(5)
+        );
+        assert_eq!(
+            endpoint_timing.nack_suppression_duration,
+            participant_timing.nack_suppression_duration
+        );
+        assert_eq!(
+            endpoint_timing.heartbeat_response_delay,
+            participant_timing.heartbeat_response_delay
+        );
+        assert_eq!(endpoint_timing.tick_period(), Duration::from_millis(5));
+        assert_eq!(
+            participant_timing.with_endpoint_timing(&EndpointReliabilityTiming::default()),
+            participant_timing
+        );
+    }
+
     #[test]
     fn basic_transport_stateful_reader_writer_usage() {
         let guid_prefix = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
@@ -466,6 +1238,13 @@ mod tests {
             &interface_name,
             udp_receive_buffer_size,
             1344,
+            UdpIpVersion::V4,
+            Ipv6MulticastScope::LinkLocal,
+            true,
+            &[],
+            0,
+            RtpsPortMapping::default(),
+            RtpsReliabilityTiming::default(),
         )
         .unwrap();
 
@@ -499,6 +1278,7 @@ mod tests {
             unicast_locator_list: vec![],
             multicast_locator_list: vec![],
             expects_inline_qos: false,
+            content_filter: None,
         };
         writer.add_matched_reader(reader_proxy);
 
@@ -519,6 +1299,10 @@ mod tests {
             source_timestamp: None,
             instance_handle: None,
             data_value: vec![0, 0, 0, 0, 1, 2, 3, 4].into(),
+            coherent_set: None,
+            group_coherent_set: None,
+            writer_group_info: None,
+            group_sequence_number: None,
         };
         writer.history_cache().add_change(cache_change.clone());
 
@@ -528,6 +1312,111 @@ mod tests {
         assert_eq!(cache_change, received_cache_change);
     }
 
+    #[test]
+    fn stateful_writer_does_not_send_changes_filtered_for_reader() {
+        let guid_prefix = [2, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let domain_id = 0;
+        let interface_name = None;
+        let udp_receive_buffer_size = None;
+        let mut transport = RtpsTransport::new(
+            guid_prefix,
+            domain_id,
+            &interface_name,
+            udp_receive_buffer_size,
+            1344,
+            UdpIpVersion::V4,
+            Ipv6MulticastScope::LinkLocal,
+            true,
+            &[],
+            0,
+            RtpsPortMapping::default(),
+            RtpsReliabilityTiming::default(),
+        )
+        .unwrap();
+
+        struct MockHistoryCache(SyncSender<CacheChange>);
+
+        impl HistoryCache for MockHistoryCache {
+            fn add_change(&mut self, cache_change: CacheChange) {
+                self.0.send(cache_change).unwrap();
+            }
+
+            fn remove_change(&mut self, _sequence_number: i64) {
+                todo!()
+            }
+        }
+
+        struct OddSequenceNumberFilter;
+
+        impl ReaderContentFilter for OddSequenceNumberFilter {
+            fn is_relevant(&self, cache_change: &CacheChange) -> bool {
+                cache_change.sequence_number % 2 == 1
+            }
+        }
+
+        let entity_id = EntityId::new([1, 2, 3], 4);
+        let reliability_kind = ReliabilityKind::Reliable;
+        let (sender, receiver) = sync_channel(0);
+        let reader_history_cache = Box::new(MockHistoryCache(sender));
+        let mut reader =
+            transport.create_stateful_reader(entity_id, reliability_kind, reader_history_cache);
+
+        let entity_id = EntityId::new([5, 6, 7], 8);
+        let mut writer = transport.create_stateful_writer(entity_id, reliability_kind);
+
+        let reader_proxy = ReaderProxy {
+            remote_reader_guid: reader.guid(),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            content_filter: Some(Arc::new(OddSequenceNumberFilter)),
+        };
+        writer.add_matched_reader(reader_proxy);
+
+        let writer_proxy = WriterProxy {
+            remote_writer_guid: writer.guid(),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+        };
+        reader.add_matched_writer(writer_proxy);
+
+        let cache_change_list: Vec<_> = (1..=4)
+            .map(|sequence_number| CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid: writer.guid(),
+                sequence_number,
+                source_timestamp: None,
+                instance_handle: None,
+                data_value: vec![0, 0, 0, 0, sequence_number as u8].into(),
+                coherent_set: None,
+                group_coherent_set: None,
+                writer_group_info: None,
+                group_sequence_number: None,
+            })
+            .collect();
+        for cache_change in &cache_change_list {
+            writer.history_cache().add_change(cache_change.clone());
+        }
+
+        let received_cache_change = receiver
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(received_cache_change, cache_change_list[0]);
+        let received_cache_change = receiver
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(received_cache_change, cache_change_list[2]);
+        assert!(receiver
+            .recv_timeout(std::time::Duration::from_millis(500))
+            .is_err());
+    }
+
     #[test]
     fn basic_transport_stateless_reader_writer_usage() {
         let guid_prefix = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
@@ -540,6 +1429,13 @@ mod tests {
             &interface_name,
             udp_receive_buffer_size,
             1344,
+            UdpIpVersion::V4,
+            Ipv6MulticastScope::LinkLocal,
+            true,
+            &[],
+            0,
+            RtpsPortMapping::default(),
+            RtpsReliabilityTiming::default(),
         )
         .unwrap();
 
@@ -573,6 +1469,10 @@ mod tests {
             source_timestamp: None,
             instance_handle: None,
             data_value: vec![0, 0, 0, 0, 1, 2, 3, 4].into(),
+            coherent_set: None,
+            group_coherent_set: None,
+            writer_group_info: None,
+            group_sequence_number: None,
         };
         writer.history_cache().add_change(cache_change.clone());
 
@@ -581,4 +1481,153 @@ mod tests {
             .unwrap();
         assert_eq!(cache_change, received_cache_change);
     }
+
+    #[test]
+    fn udp_v6_transport_stateless_reader_writer_usage() {
+        let guid_prefix = [3, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let domain_id = 0;
+        let interface_name = None;
+        let udp_receive_buffer_size = None;
+        let mut transport = RtpsTransport::new(
+            guid_prefix,
+            domain_id,
+            &interface_name,
+            udp_receive_buffer_size,
+            1344,
+            UdpIpVersion::V6,
+            Ipv6MulticastScope::LinkLocal,
+            true,
+            &[],
+            0,
+            RtpsPortMapping::default(),
+            RtpsReliabilityTiming::default(),
+        )
+        .unwrap();
+        assert!(transport
+            .default_unicast_locator_list()
+            .iter()
+            .chain(transport.metatraffic_unicast_locator_list())
+            .chain(transport.metatraffic_multicast_locator_list())
+            .all(|l| l.kind() == LOCATOR_KIND_UDP_V6));
+        assert_eq!(
+            transport.metatraffic_multicast_locator_list()[0].address()[..2],
+            [0xff, 0x02]
+        );
+
+        struct MockHistoryCache(SyncSender<CacheChange>);
+
+        impl HistoryCache for MockHistoryCache {
+            fn add_change(&mut self, cache_change: CacheChange) {
+                self.0.send(cache_change).unwrap();
+            }
+
+            fn remove_change(&mut self, _sequence_number: i64) {
+                todo!()
+            }
+        }
+
+        let entity_id = EntityId::new([1, 2, 3], 4);
+        let (sender, receiver) = sync_channel(0);
+        let reader_history_cache = Box::new(MockHistoryCache(sender));
+        let _reader = transport.create_stateless_reader(entity_id, reader_history_cache);
+
+        let entity_id = EntityId::new([5, 6, 7], 8);
+        let mut writer = transport.create_stateless_writer(entity_id);
+        for locator in transport.default_unicast_locator_list() {
+            writer.add_reader_locator(*locator);
+        }
+
+        let cache_change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid: writer.guid(),
+            sequence_number: 1,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: vec![0, 0, 0, 0, 1, 2, 3, 4].into(),
+            coherent_set: None,
+            group_coherent_set: None,
+            writer_group_info: None,
+            group_sequence_number: None,
+        };
+        writer.history_cache().add_change(cache_change.clone());
+
+        let received_cache_change = receiver
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(cache_change, received_cache_change);
+    }
+
+    #[test]
+    fn dual_stack_transport_announces_both_locator_kinds() {
+        let guid_prefix = [4, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let transport = RtpsTransport::new(
+            guid_prefix,
+            0,
+            &None,
+            None,
+            1344,
+            UdpIpVersion::V4AndV6,
+            Ipv6MulticastScope::RealmLocal,
+            true,
+            &[],
+            0,
+            RtpsPortMapping::default(),
+            RtpsReliabilityTiming::default(),
+        )
+        .unwrap();
+
+        let multicast_locator_kinds: Vec<_> = transport
+            .metatraffic_multicast_locator_list()
+            .iter()
+            .map(|l| l.kind())
+            .collect();
+        assert_eq!(
+            multicast_locator_kinds,
+            [LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6]
+        );
+        assert_eq!(
+            transport.metatraffic_multicast_locator_list()[1].address()[..2],
+            [0xff, 0x03]
+        );
+    }
+
+    #[test]
+    fn transport_binds_ports_of_custom_port_mapping() {
+        let port_mapping = RtpsPortMapping {
+            port_base_number: 27400,
+            ..Default::default()
+        };
+        let domain_id = 1;
+        let transport = RtpsTransport::new(
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 14],
+            domain_id,
+            &None,
+            None,
+            1344,
+            UdpIpVersion::V4,
+            Ipv6MulticastScope::LinkLocal,
+            true,
+            &[],
+            0,
+            port_mapping,
+            RtpsReliabilityTiming::default(),
+        )
+        .unwrap();
+
+        assert!(transport
+            .metatraffic_multicast_locator_list()
+            .iter()
+            .all(|l| l.port() == 27650));
+        let metatraffic_port = transport.metatraffic_unicast_locator_list()[0].port();
+        let participant_id = (0..=port_mapping.max_participant_id())
+            .find(|&id| {
+                port_mapping.builtin_unicast_port(domain_id, id) == Some(metatraffic_port as u16)
+            })
+            .expect("Metatraffic port must be a well-known port");
+        assert!(transport
+            .default_unicast_locator_list()
+            .iter()
+            .all(|l| Some(l.port() as u16)
+                == port_mapping.user_unicast_port(domain_id, participant_id)));
+    }
 }
@@ -13,7 +13,12 @@ use super::{
     },
 };
 
-use std::{cmp::max, collections::HashMap, sync::Arc};
+use std::{
+    cmp::max,
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 fn total_fragments_expected(data_frag_submessage: &DataFragSubmessage) -> u32 {
     let data_size = data_frag_submessage.data_size();
@@ -38,6 +43,10 @@ pub struct RtpsWriterProxy {
     nack_frag_count: Count,
     frag_buffer: HashMap<SequenceNumber, Vec<DataFragSubmessage>>,
     reliability: ReliabilityKind,
+    // Reception time of the oldest heartbeat which is not answered yet
+    heartbeat_received_time: Option<Instant>,
+    // Locators announced with INFO_REPLY together with the last heartbeat
+    reply_locator_list: Vec<Locator>,
 }
 
 impl RtpsWriterProxy {
@@ -63,6 +72,8 @@ impl RtpsWriterProxy {
             nack_frag_count: 0,
             frag_buffer: HashMap::new(),
             reliability,
+            heartbeat_received_time: None,
+            reply_locator_list: Vec::new(),
         }
     }
 
@@ -229,7 +240,22 @@ impl RtpsWriterProxy {
         self.acknack_count = self.acknack_count.wrapping_add(1);
     }
 
+    pub fn set_reply_locator_list(&mut self, reply_locator_list: &[Locator]) {
+        self.reply_locator_list = reply_locator_list.to_vec();
+    }
+
+    pub fn heartbeat_received(&mut self) {
+        self.heartbeat_received_time
+            .get_or_insert_with(Instant::now);
+    }
+
+    pub fn is_time_for_heartbeat_response(&self, heartbeat_response_delay: Duration) -> bool {
+        self.heartbeat_received_time
+            .is_some_and(|t| t.elapsed() >= heartbeat_response_delay)
+    }
+
     pub fn send_message(&mut self, reader_guid: &Guid, message_sender: &MessageSender) {
+        self.heartbeat_received_time = None;
         if self.must_send_acknacks() || !self.missing_changes().count() == 0 {
             self.set_must_send_acknacks(false);
             self.increment_acknack_count();
@@ -264,15 +290,19 @@ impl RtpsWriterProxy {
                     }
                 }
 
-                if !missing_fragment_number.is_empty() {
+                if let Some(&base) = missing_fragment_number.first() {
                     self.nack_frag_count = self.nack_frag_count.wrapping_add(1);
+                    // The set only holds the 256 fragments following its base, the
+                    // next ones are requested once these are received
                     let nack_frag_submessage = NackFragSubmessage::new(
                         reader_guid.entity_id(),
                         self.remote_writer_guid().entity_id(),
                         *seq_num,
                         FragmentNumberSet::new(
-                            missing_fragment_number[0],
-                            missing_fragment_number.into_iter(),
+                            base,
+                            missing_fragment_number
+                                .into_iter()
+                                .take_while(|&n| n < base + 256),
                         ),
                         self.nack_frag_count,
                     );
@@ -281,7 +311,12 @@ impl RtpsWriterProxy {
                 }
             }
 
-            message_sender.write_message(&submessages, self.unicast_locator_list().to_vec());
+            let locator_list = if self.reply_locator_list.is_empty() {
+                self.unicast_locator_list().to_vec()
+            } else {
+                self.reply_locator_list.clone()
+            };
+            message_sender.write_message(&submessages, locator_list);
         }
     }
 
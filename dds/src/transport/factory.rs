@@ -1,9 +1,13 @@
+use crate::infrastructure::error::DdsResult;
+
 use super::{participant::TransportParticipant, types::GuidPrefix};
 
 pub trait TransportParticipantFactory: Send + Sync {
+    /// Create the transport of a new participant. Failing to create it, e.g. because
+    /// its sockets can't be opened, makes the creation of the participant fail.
     fn create_participant(
         &self,
         guid_prefix: GuidPrefix,
         domain_id: i32,
-    ) -> Box<dyn TransportParticipant>;
+    ) -> DdsResult<Box<dyn TransportParticipant>>;
 }
@@ -10,6 +10,15 @@ pub struct CacheChange {
     pub source_timestamp: Option<Time>,
     pub instance_handle: Option<[u8; 16]>,
     pub data_value: Arc<[u8]>,
+    /// Sequence number of the first change of the coherent set this change belongs to
+    pub coherent_set: Option<i64>,
+    /// Identifier of the publisher coherent set this change belongs to
+    pub group_coherent_set: Option<i64>,
+    /// Digest of the writers taking part in the group coherent set
+    pub writer_group_info: Option<[u8; 4]>,
+    /// Position of this change among all the changes of the publisher when it
+    /// provides ordered access with group scope
+    pub group_sequence_number: Option<i64>,
 }
 
 impl CacheChange {
@@ -28,10 +37,30 @@ impl CacheChange {
     pub fn data_value(&self) -> &Arc<[u8]> {
         &self.data_value
     }
+
+    /// A change without payload which only signals that the writer has
+    /// finished the coherent set
+    pub fn is_end_of_coherent_set(&self) -> bool {
+        self.kind == ChangeKind::Alive && self.data_value.is_empty() && self.coherent_set.is_some()
+    }
 }
 
 pub trait HistoryCache: Send + Sync {
     fn add_change(&mut self, cache_change: CacheChange);
 
     fn remove_change(&mut self, sequence_number: i64);
+
+    /// Called when a matched writer asserts its liveliness without sending any data,
+    /// for example with a HEARTBEAT submessage with the liveliness flag set
+    fn assert_writer_liveliness(&mut self, _writer_guid: Guid) {}
+
+    /// Called when changes of a matched best-effort writer were not received and can
+    /// no longer be received. Changes which the writer declared irrelevant are not lost.
+    fn lost_changes(
+        &mut self,
+        _writer_guid: Guid,
+        _first_sequence_number: i64,
+        _last_sequence_number: i64,
+    ) {
+    }
 }
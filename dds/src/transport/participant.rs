@@ -14,6 +14,12 @@ pub trait TransportParticipant: Send + Sync {
     fn default_unicast_locator_list(&self) -> &[Locator];
     fn default_multicast_locator_list(&self) -> &[Locator];
 
+    /// Unicast locators of the remote participants to which the participant
+    /// announcements are sent in addition to the metatraffic multicast locators.
+    fn initial_peer_locator_list(&self) -> &[Locator] {
+        &[]
+    }
+
     fn create_stateless_reader(
         &mut self,
         entity_id: EntityId,
@@ -35,4 +41,25 @@ pub trait TransportParticipant: Send + Sync {
         entity_id: EntityId,
         reliability_kind: ReliabilityKind,
     ) -> Box<dyn TransportStatefulWriter>;
+
+    /// Create a stateful writer whose data and repair messages are released by the flow
+    /// controller with the given name, ahead of the writers of lower priority when the flow
+    /// controller schedules by priority. Returns [`None`] when the participant has no flow
+    /// controller with that name.
+    fn create_flow_controlled_stateful_writer(
+        &mut self,
+        _entity_id: EntityId,
+        _reliability_kind: ReliabilityKind,
+        _flow_controller_name: &str,
+        _priority: i32,
+    ) -> Option<Box<dyn TransportStatefulWriter>> {
+        None
+    }
+
+    /// Start packing the messages generated by the changes added to the writers of this
+    /// participant together until [`TransportParticipant::end_batch`] is called.
+    fn begin_batch(&mut self) {}
+
+    /// Send all the messages packed since [`TransportParticipant::begin_batch`].
+    fn end_batch(&mut self) {}
 }
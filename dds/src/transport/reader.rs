@@ -1,4 +1,6 @@
-use super::types::{DurabilityKind, EntityId, Guid, Locator, ReliabilityKind};
+use super::types::{
+    DurabilityKind, EndpointReliabilityTiming, EntityId, Guid, Locator, ReliabilityKind,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct WriterProxy {
@@ -19,4 +21,7 @@ pub trait TransportStatefulReader: Send + Sync {
     fn is_historical_data_received(&self) -> bool;
     fn add_matched_writer(&mut self, writer_proxy: WriterProxy);
     fn remove_matched_writer(&mut self, remote_writer_guid: Guid);
+
+    /// Override the timing of the reliable protocol used by this reader
+    fn set_reliability_timing(&mut self, _reliability_timing: EndpointReliabilityTiming) {}
 }
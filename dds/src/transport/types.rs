@@ -182,6 +182,16 @@ pub enum DurabilityKind {
     Persistent,
 }
 
+/// Timing of the reliable protocol of a single endpoint. The values left to [`None`]
+/// keep the timing configured for the whole participant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EndpointReliabilityTiming {
+    pub heartbeat_period: Option<core::time::Duration>,
+    pub nack_response_delay: Option<core::time::Duration>,
+    pub nack_suppression_duration: Option<core::time::Duration>,
+    pub heartbeat_response_delay: Option<core::time::Duration>,
+}
+
 /// Locator_t
 /// Type used to represent the addressing information needed to send a message to an RTPS Endpoint using one of the supported transports.
 /// Should be able to hold a discriminator identifying the kind of transport, an address, and a port number. It must be possible to represent the discriminator and port number using 4 octets each, the address using 16 octets.
@@ -1,8 +1,30 @@
+use std::sync::Arc;
+
 use super::{
-    history_cache::HistoryCache,
-    types::{DurabilityKind, EntityId, Guid, Locator, ReliabilityKind},
+    history_cache::{CacheChange, HistoryCache},
+    types::{DurabilityKind, EndpointReliabilityTiming, EntityId, Guid, Locator, ReliabilityKind},
 };
 
+/// Filter evaluated by a writer for each change before sending it to a matched
+/// reader. Changes which are not relevant for the reader are announced as GAP.
+pub trait ReaderContentFilter: Send + Sync {
+    fn is_relevant(&self, cache_change: &CacheChange) -> bool;
+}
+
+impl core::fmt::Debug for dyn ReaderContentFilter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ReaderContentFilter")
+    }
+}
+
+impl PartialEq for dyn ReaderContentFilter {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::addr_eq(self, other)
+    }
+}
+
+impl Eq for dyn ReaderContentFilter {}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ReaderProxy {
     pub remote_reader_guid: Guid,
@@ -12,6 +34,7 @@ pub struct ReaderProxy {
     pub unicast_locator_list: Vec<Locator>,
     pub multicast_locator_list: Vec<Locator>,
     pub expects_inline_qos: bool,
+    pub content_filter: Option<Arc<dyn ReaderContentFilter>>,
 }
 
 pub trait TransportStatelessWriter: Send + Sync {
@@ -30,7 +53,16 @@ pub trait TransportStatefulWriter: Send + Sync {
 
     fn is_change_acknowledged(&self, sequence_number: i64) -> bool;
 
+    /// Changes currently kept in the history of the writer
+    fn changes(&self) -> Vec<CacheChange>;
+
     fn add_matched_reader(&mut self, reader_proxy: ReaderProxy);
 
     fn remove_matched_reader(&mut self, remote_reader_guid: Guid);
+
+    /// Manually assert the liveliness of the writer towards all the matched readers
+    fn assert_liveliness(&mut self) {}
+
+    /// Override the timing of the reliable protocol used by this writer
+    fn set_reliability_timing(&mut self, _reliability_timing: EndpointReliabilityTiming) {}
 }
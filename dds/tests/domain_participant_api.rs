@@ -262,6 +262,112 @@ fn not_allowed_to_delete_topic_attached_to_writer() {
     );
 }
 
+#[test]
+fn create_delete_content_filtered_topic() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("Test", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let content_filtered_topic = participant
+        .create_contentfilteredtopic("FilteredTest", &topic, "id > %0", &["1".to_string()])
+        .unwrap();
+    assert_eq!(content_filtered_topic.get_name(), "FilteredTest");
+    assert_eq!(content_filtered_topic.get_type_name(), "MyData");
+    assert_eq!(content_filtered_topic.get_filter_expression(), "id > %0");
+    assert_eq!(
+        content_filtered_topic.get_expression_parameters(),
+        Ok(vec!["1".to_string()])
+    );
+    assert_eq!(
+        content_filtered_topic.get_related_topic().get_name(),
+        "Test"
+    );
+
+    assert_eq!(
+        participant.delete_topic(&topic),
+        Err(DdsError::PreconditionNotMet(
+            "Topic still attached to some data writer or data reader".to_string()
+        ))
+    );
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&content_filtered_topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    assert_eq!(
+        participant.delete_contentfilteredtopic(&content_filtered_topic),
+        Err(DdsError::PreconditionNotMet(
+            "Content filtered topic still attached to some data reader".to_string()
+        ))
+    );
+
+    subscriber.delete_datareader(&reader).unwrap();
+    assert_eq!(
+        participant.delete_contentfilteredtopic(&content_filtered_topic),
+        Ok(())
+    );
+    assert_eq!(participant.delete_topic(&topic), Ok(()));
+}
+
+#[test]
+fn not_allowed_to_create_content_filtered_topic_with_invalid_filter() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("Test", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        participant.create_contentfilteredtopic("Filtered", &topic, "id >", &[]),
+        Err(DdsError::BadParameter)
+    ));
+    assert!(matches!(
+        participant.create_contentfilteredtopic("Filtered", &topic, "unknown = 1", &[]),
+        Err(DdsError::BadParameter)
+    ));
+    assert!(matches!(
+        participant.create_contentfilteredtopic("Filtered", &topic, "id = %1", &["1".to_string()]),
+        Err(DdsError::BadParameter)
+    ));
+    assert!(matches!(
+        participant.create_contentfilteredtopic("Test", &topic, "id = 1", &[]),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[derive(DdsType)]
+#[dust_dds(extensibility = "Appendable")]
+struct AppendableData {
+    id: u8,
+}
+
+#[test]
+fn content_filtered_topic_of_appendable_type_is_unsupported() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<AppendableData>("Test", "AppendableData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        participant.create_contentfilteredtopic("Filtered", &topic, "id = 1", &[]),
+        Err(DdsError::Unsupported)
+    ));
+}
+
 #[test]
 fn not_allowed_to_create_topic_with_builtin_topic_name() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
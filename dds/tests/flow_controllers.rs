@@ -0,0 +1,288 @@
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        error::DdsError,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, PublishModeQosPolicy, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    rtps::{
+        flow_controller::{FlowControllerSchedulingPolicy, RtpsFlowController, RtpsTokenBucket},
+        in_memory::InMemoryParticipantFactoryBuilder,
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+use std::sync::Once;
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Debug, PartialEq, DdsType)]
+struct Frame {
+    #[dust_dds(key)]
+    id: u8,
+    data: Vec<u8>,
+}
+
+// The transport is shared by the tests since it applies to all the participants created
+// afterwards by the factory. The participants of the tests exchange the samples through
+// it only when the intraprocess delivery is disabled.
+fn set_flow_controlled_transport() {
+    static SET_TRANSPORT: Once = Once::new();
+    SET_TRANSPORT.call_once(|| {
+        DomainParticipantFactory::get_instance()
+            .set_configuration(
+                DustDdsConfigurationBuilder::new()
+                    .intraprocess_delivery(false)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        // Each fragment takes one token, so at most one fragment is sent every 5 ms once
+        // the initial burst of 4 fragments is spent
+        let radio_link = RtpsFlowController {
+            name: "radio_link".to_string(),
+            scheduling_policy: FlowControllerSchedulingPolicy::Fifo,
+            token_bucket: RtpsTokenBucket {
+                max_tokens: 4,
+                tokens_added_per_period: 1,
+                bytes_per_token: 2000,
+                period: std::time::Duration::from_millis(5),
+            },
+        };
+        let slow_link = RtpsFlowController {
+            name: "slow_link".to_string(),
+            scheduling_policy: FlowControllerSchedulingPolicy::Fifo,
+            token_bucket: RtpsTokenBucket {
+                max_tokens: 1,
+                tokens_added_per_period: 1,
+                bytes_per_token: 2000,
+                period: std::time::Duration::from_millis(500),
+            },
+        };
+        let transport = InMemoryParticipantFactoryBuilder::new()
+            .fragment_size(1000)
+            .flow_controllers(vec![radio_link, slow_link])
+            .build()
+            .unwrap();
+        DomainParticipantFactory::get_instance()
+            .set_transport(Box::new(transport))
+            .unwrap();
+    });
+}
+
+#[test]
+fn flow_controlled_writer_output_is_limited_by_the_token_bucket() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+    set_flow_controlled_transport();
+
+    let participant1 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<Frame>("MyTopic", "Frame", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let unknown_flow_controller_qos = DataWriterQos {
+        publish_mode: PublishModeQosPolicy {
+            flow_controller_name: Some("unknown".to_string()),
+            priority: 0,
+        },
+        ..Default::default()
+    };
+    assert!(matches!(
+        publisher.create_datawriter::<Frame>(
+            &topic1,
+            QosKind::Specific(unknown_flow_controller_qos),
+            None,
+            NO_STATUS
+        ),
+        Err(DdsError::BadParameter)
+    ));
+    let writer_qos = DataWriterQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        publish_mode: PublishModeQosPolicy {
+            flow_controller_name: Some("radio_link".to_string()),
+            priority: 0,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<Frame>(&topic1, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<Frame>("MyTopic", "Frame", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<Frame>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    // 4 frames of 10 fragments each
+    let frames: Vec<_> = (0..4)
+        .map(|id| Frame {
+            id,
+            data: vec![id; 9500],
+        })
+        .collect();
+    let start = std::time::Instant::now();
+    for frame in &frames {
+        writer.write(frame, None).unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(elapsed >= std::time::Duration::from_millis(150));
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let received_frames: Vec<_> = samples.iter().map(|s| s.data().unwrap()).collect();
+    assert_eq!(received_frames, frames);
+}
+
+#[test]
+fn samples_replaced_in_the_writer_history_are_not_sent() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+    set_flow_controlled_transport();
+
+    let participant1 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<Frame>("MyTopic", "Frame", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(1),
+        },
+        publish_mode: PublishModeQosPolicy {
+            flow_controller_name: Some("slow_link".to_string()),
+            priority: 0,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<Frame>(&topic1, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<Frame>("MyTopic", "Frame", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<Frame>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    // The first frame takes the only token and the following ones replace each other
+    // while waiting for the next period
+    let frames: Vec<_> = (0..20)
+        .map(|i| Frame {
+            id: 1,
+            data: vec![i],
+        })
+        .collect();
+    for frame in &frames {
+        writer.write(frame, None).unwrap();
+    }
+
+    let mut received_frames = Vec::new();
+    let start = std::time::Instant::now();
+    while received_frames.last() != frames.last()
+        && start.elapsed() < std::time::Duration::from_secs(5)
+    {
+        if let Ok(samples) = reader.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE) {
+            received_frames.extend(samples.iter().map(|s| s.data().unwrap()));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    assert_eq!(
+        received_frames,
+        vec![
+            Frame {
+                id: 1,
+                data: vec![0]
+            },
+            Frame {
+                id: 1,
+                data: vec![19]
+            }
+        ]
+    );
+}
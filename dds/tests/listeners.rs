@@ -835,6 +835,106 @@ fn data_available_listener_not_called_when_data_on_readers_listener() {
         .is_err());
 }
 
+#[test]
+fn data_on_readers_listener_notifies_data_available_listeners() {
+    struct DataOnReadersListener {
+        sender: std::sync::mpsc::SyncSender<()>,
+    }
+
+    impl SubscriberListener for DataOnReadersListener {
+        fn on_data_on_readers(&mut self, the_subscriber: Subscriber) {
+            the_subscriber.notify_datareaders().unwrap();
+            self.sender.send(()).unwrap();
+        }
+    }
+
+    struct DataAvailableListener {
+        sender: std::sync::mpsc::SyncSender<()>,
+    }
+
+    impl DataReaderListener<'_> for DataAvailableListener {
+        type Foo = MyData;
+        fn on_data_available(&mut self, _the_reader: DataReader<MyData>) {
+            self.sender.send(()).unwrap();
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let (sender, data_on_readers_receiver) = std::sync::mpsc::sync_channel(1);
+    let subscriber_listener = DataOnReadersListener { sender };
+
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Default,
+            Some(Box::new(subscriber_listener)),
+            &[StatusKind::DataOnReaders],
+        )
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+
+    let (sender, data_available_receiver) = std::sync::mpsc::sync_channel(1);
+    let reader_listener = DataAvailableListener { sender };
+
+    let _reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(reader_listener)),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = MyData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+
+    assert!(data_on_readers_receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .is_ok());
+    assert!(data_available_receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .is_ok());
+}
+
 #[test]
 fn participant_requested_deadline_missed_listener() {
     struct DeadlineMissedListener {
@@ -0,0 +1,128 @@
+use dust_dds::{
+    domain::domain_participant_factory::DomainParticipantFactory,
+    durability::FileDurabilityStore,
+    infrastructure::{
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            DurabilityQosPolicy, DurabilityQosPolicyKind, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u8,
+}
+
+#[test]
+fn persistent_samples_are_served_after_application_restart_without_a_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let directory = std::env::temp_dir().join(format!(
+        "dust_dds_persistent_durability_test_{}",
+        std::process::id()
+    ));
+    let participant_factory = DomainParticipantFactory::get_instance();
+    participant_factory
+        .set_persistent_durability_store(Box::new(FileDurabilityStore::new(&directory)))
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::Persistent,
+        },
+        ..Default::default()
+    };
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "PersistentTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+    participant.delete_contained_entities().unwrap();
+    participant_factory
+        .delete_participant(&participant)
+        .unwrap();
+
+    // A store on the same directory behaves as the store of a restarted application
+    participant_factory
+        .set_persistent_durability_store(Box::new(FileDurabilityStore::new(&directory)))
+        .unwrap();
+    let reader_participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_topic = reader_participant
+        .create_topic::<KeyedData>(
+            "PersistentTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = reader_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::Persistent,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &reader_topic,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    reader
+        .wait_for_historical_data(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), KeyedData { id: 1, value: 2 });
+}
@@ -1,6 +1,7 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
+        error::DdsError,
         qos::{DataWriterQos, QosKind},
         qos_policy::UserDataQosPolicy,
         status::NO_STATUS,
@@ -161,3 +162,51 @@ fn data_writer_get_topic_should_return_same_topic_as_used_for_creation() {
 
     assert!(writer.get_topic().get_instance_handle() == topic.get_instance_handle());
 }
+
+#[test]
+fn end_coherent_changes_requires_matching_begin() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(publisher.begin_coherent_changes(), Ok(()));
+    assert_eq!(publisher.begin_coherent_changes(), Ok(()));
+    assert_eq!(publisher.end_coherent_changes(), Ok(()));
+    assert_eq!(publisher.end_coherent_changes(), Ok(()));
+    assert_eq!(
+        publisher.end_coherent_changes(),
+        Err(DdsError::PreconditionNotMet(
+            "No matching begin_coherent_changes".to_string()
+        ))
+    );
+}
+
+#[test]
+fn resume_publications_requires_matching_suspend() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(publisher.suspend_publications(), Ok(()));
+    assert_eq!(publisher.suspend_publications(), Ok(()));
+    assert_eq!(publisher.resume_publications(), Ok(()));
+    assert_eq!(publisher.resume_publications(), Ok(()));
+    assert_eq!(
+        publisher.resume_publications(),
+        Err(DdsError::PreconditionNotMet(
+            "No matching suspend_publications".to_string()
+        ))
+    );
+}
@@ -1186,3 +1186,184 @@ fn reliable_writer_should_not_remove_unacked_sample_from_history() {
         Err(DdsError::Timeout)
     );
 }
+
+#[test]
+fn discovered_participant_is_removed_when_lease_expires() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_participant_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+    let mock_participant_port =
+        (mock_participant_socket.local_addr().unwrap().port() as u32).to_le_bytes();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let builtin_subscriber = participant.get_builtin_subscriber();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if !participant.get_discovered_participants().unwrap().is_empty() {
+            break;
+        }
+    }
+    assert!(participant.get_discovered_participants().unwrap().len() == 1);
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<DynamicType>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .metatraffic_unicast_locator_port();
+
+    // Announce a mock participant with a lease of 1 second which never renews it
+    let mock_guid_prefix = [7; 12];
+    let participant_key = [&mock_guid_prefix[..], &[0, 0, 1, 0xc1]].concat();
+    let serialized_participant_discovery_bytes = [
+        &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x50, 0x00, 16, 0, // PID_PARTICIPANT_GUID, length
+        ],
+        participant_key.as_slice(),
+        &[
+            0x15, 0x00, 4, 0, // PID_PROTOCOL_VERSION, length
+            2, 4, 0, 0, //
+            0x16, 0x00, 4, 0, // PID_VENDORID, length
+            1, 3, 0, 0, //
+            0x32, 0x00, 24, 0, // PID_METATRAFFIC_UNICAST_LOCATOR, length
+            1, 0, 0, 0, // locator kind
+        ],
+        &mock_participant_port, // locator port
+        &[
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            127, 0, 0, 1, // locator address
+            0x58, 0x00, 4, 0, // PID_BUILTIN_ENDPOINT_SET, length
+            0x04, 0, 0, 0, // BUILTIN_ENDPOINT_PUBLICATIONS_ANNOUNCER
+            0x02, 0x00, 8, 0, // PID_PARTICIPANT_LEASE_DURATION, length
+            1, 0, 0, 0, // sec
+            0, 0, 0, 0, // nanosec
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ],
+    ]
+    .concat();
+    let participant_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_participant_discovery_bytes.into()),
+    );
+    let participant_rtps_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, mock_guid_prefix),
+        &[Box::new(participant_data_submessage)],
+    );
+    mock_participant_socket
+        .send_to(
+            participant_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if participant.get_discovered_participants().unwrap().len() >= 2 {
+            break;
+        }
+    }
+    assert_eq!(participant.get_discovered_participants().unwrap().len(), 2);
+
+    // Announce a writer of the mock participant
+    let serialized_writer_discovery_bytes = [
+        &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x5a, 0x00, 16, 0, //PID_ENDPOINT_GUID, length
+        ],
+        &mock_guid_prefix[..],
+        &[
+            0, 0, 0, 2, // Entity ID
+            0x50, 0x00, 16, 0, // PID_PARTICIPANT_GUID, length
+        ],
+        participant_key.as_slice(),
+        &[
+            0x05, 0x00, 12, 0x00, // PID_TOPIC_NAME, Length
+            8, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'M', b'y', b'T', b'o', //
+            b'p', b'i', b'c', 0, //
+            0x07, 0x00, 16, 0x00, // PID_TYPE_NAME, Length
+            10, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'K', b'e', b'y', b'e', //
+            b'd', b'D', b'a', b't', //
+            b'a', 0, 0, 0, //
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ],
+    ]
+    .concat();
+    let writer_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_writer_discovery_bytes.into()),
+    );
+    let writer_rtps_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, mock_guid_prefix),
+        &[Box::new(writer_data_submessage)],
+    );
+    mock_participant_socket
+        .send_to(
+            writer_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let reader_status_condition = reader.get_statuscondition();
+    reader_status_condition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(reader_status_condition))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        reader
+            .get_subscription_matched_status()
+            .unwrap()
+            .current_count,
+        1
+    );
+
+    // The mock participant never renews its lease so it is removed together with its writer
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        reader
+            .get_subscription_matched_status()
+            .unwrap()
+            .current_count,
+        0
+    );
+    assert_eq!(participant.get_discovered_participants().unwrap().len(), 1);
+}
@@ -1,6 +1,7 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
+        error::DdsError,
         qos::{DataReaderQos, QosKind},
         qos_policy::UserDataQosPolicy,
         status::NO_STATUS,
@@ -161,3 +162,27 @@ fn data_reader_get_topicdescription_should_return_same_topic_as_used_for_creatio
 
     assert!(reader.get_topicdescription().get_instance_handle() == topic.get_instance_handle());
 }
+
+#[test]
+fn end_access_requires_matching_begin() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(subscriber.begin_access(), Ok(()));
+    assert_eq!(subscriber.begin_access(), Ok(()));
+    assert_eq!(subscriber.end_access(), Ok(()));
+    assert_eq!(subscriber.end_access(), Ok(()));
+    assert_eq!(
+        subscriber.end_access(),
+        Err(DdsError::PreconditionNotMet(
+            "No matching begin_access".to_string()
+        ))
+    );
+}
@@ -2,12 +2,20 @@ mod utils;
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
+        condition::GuardCondition,
+        error::DdsError,
         qos::{DataReaderQos, DataWriterQos, QosKind},
-        qos_policy::{DeadlineQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        qos_policy::{
+            DeadlineQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, LivelinessQosPolicy,
+            LivelinessQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+        },
         status::{StatusKind, NO_STATUS},
         time::{Duration, DurationKind},
         wait_set::{Condition, WaitSet},
     },
+    subscription::sample_info::{
+        SampleStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE,
+    },
     topic_definition::type_support::DdsType,
 };
 
@@ -96,3 +104,391 @@ fn writer_offered_deadline_missed_waitset() {
     assert_eq!(status.total_count, 1);
     assert_eq!(status.total_count_change, 1);
 }
+
+#[test]
+fn writer_liveliness_lost_and_reader_liveliness_changed_waitset() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByTopic,
+            lease_duration: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let writer_cond = writer.get_statuscondition();
+    writer_cond
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(writer_cond.clone()))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let reader_cond = reader.get_statuscondition();
+    reader_cond
+        .set_enabled_statuses(&[StatusKind::LivelinessChanged])
+        .unwrap();
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(reader_cond))
+        .unwrap();
+    reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+
+    writer.write(&MyData { id: 1, value: 1 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    writer_cond
+        .set_enabled_statuses(&[StatusKind::LivelinessLost])
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    let status = writer.get_liveliness_lost_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 1);
+
+    reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 0);
+    assert_eq!(status.not_alive_count, 1);
+
+    writer.assert_liveliness().unwrap();
+    reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+}
+
+#[test]
+fn read_condition_waitset() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let read_condition = reader
+        .create_readcondition(
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(read_condition.get_trigger_value(), Ok(false));
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::ReadCondition(read_condition.clone()))
+        .unwrap();
+
+    writer.write(&MyData { id: 1, value: 1 }, None).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = reader.read_w_condition(10, &read_condition).unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), MyData { id: 1, value: 1 });
+    assert_eq!(read_condition.get_trigger_value(), Ok(false));
+    assert_eq!(
+        reader.read_w_condition(10, &read_condition).err(),
+        Some(DdsError::NoData)
+    );
+
+    assert!(matches!(
+        subscriber.delete_datareader(&reader),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    reader.delete_readcondition(&read_condition).unwrap();
+    assert_eq!(
+        read_condition.get_trigger_value(),
+        Err(DdsError::AlreadyDeleted)
+    );
+    subscriber.delete_datareader(&reader).unwrap();
+}
+
+#[test]
+fn query_condition_waitset() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        reader.create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            "unknown_field > 1",
+            &[],
+        ),
+        Err(DdsError::BadParameter)
+    ));
+    let query_condition = reader
+        .create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            "value > %0",
+            &["10".to_string()],
+        )
+        .unwrap();
+    assert_eq!(query_condition.get_query_expression(), "value > %0");
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer.write(&MyData { id: 1, value: 5 }, None).unwrap();
+    writer.write(&MyData { id: 2, value: 20 }, None).unwrap();
+    writer.write(&MyData { id: 3, value: 30 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::QueryCondition(query_condition.clone()))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    query_condition
+        .set_query_parameters(&["50".to_string()])
+        .unwrap();
+    assert_eq!(query_condition.get_trigger_value(), Ok(false));
+    let later_query_condition = reader
+        .create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            "value < %0",
+            &["10".to_string()],
+        )
+        .unwrap();
+    assert_eq!(later_query_condition.get_trigger_value(), Ok(true));
+    reader.delete_readcondition(&later_query_condition).unwrap();
+
+    query_condition
+        .set_query_parameters(&["25".to_string()])
+        .unwrap();
+    assert_eq!(
+        query_condition.get_query_parameters(),
+        Ok(vec!["25".to_string()])
+    );
+    assert_eq!(query_condition.get_trigger_value(), Ok(true));
+    let samples = reader.take_w_condition(10, &query_condition).unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), MyData { id: 3, value: 30 });
+    assert_eq!(query_condition.get_trigger_value(), Ok(false));
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+
+    reader.delete_readcondition(&query_condition).unwrap();
+}
+
+#[test]
+fn detach_condition_from_waitset() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let read_condition = reader
+        .create_readcondition(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::ReadCondition(read_condition.clone()))
+        .unwrap();
+    wait_set
+        .attach_condition(Condition::ReadCondition(read_condition.clone()))
+        .unwrap();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond.clone()))
+        .unwrap();
+    assert_eq!(wait_set.get_conditions().unwrap().len(), 2);
+
+    let triggered = wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert!(triggered == vec![Condition::StatusCondition(cond.clone())]);
+
+    wait_set
+        .detach_condition(Condition::ReadCondition(read_condition.clone()))
+        .unwrap();
+    assert!(wait_set.get_conditions().unwrap() == vec![Condition::StatusCondition(cond)]);
+    assert!(matches!(
+        wait_set.detach_condition(Condition::ReadCondition(read_condition)),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn guard_condition_wakes_up_waitset() {
+    let guard_condition = GuardCondition::new();
+    assert_eq!(guard_condition.get_trigger_value(), Ok(false));
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::GuardCondition(guard_condition.clone()))
+        .unwrap();
+    assert!(matches!(
+        wait_set.wait(Duration::new(0, 100_000_000)),
+        Err(DdsError::Timeout)
+    ));
+
+    let trigger_thread = std::thread::spawn({
+        let guard_condition = guard_condition.clone();
+        move || {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            guard_condition.set_trigger_value(true).unwrap();
+        }
+    });
+    let triggered = wait_set.wait(Duration::new(10, 0)).unwrap();
+    trigger_thread.join().unwrap();
+    assert!(triggered == vec![Condition::GuardCondition(guard_condition.clone())]);
+
+    guard_condition.set_trigger_value(false).unwrap();
+    assert!(matches!(
+        wait_set.wait(Duration::new(0, 100_000_000)),
+        Err(DdsError::Timeout)
+    ));
+}